@@ -0,0 +1,109 @@
+//! Benchmarks of `step()` throughput across grid sizes, soup densities, and
+//! world backends.
+//!
+//! Run with `cargo bench --features simd` to include the bit-packed backend.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use gameoflife_rs::{ChunkedWorld, ConwaySim, Grid, HashLifeSim, SparseWorld};
+
+/// Side lengths of the square grids benchmarked.
+const SIZES: &[u32] = &[64, 256, 1024];
+
+/// Densities of the random soups benchmarked.
+const DENSITIES: &[f64] = &[0.1, 0.3, 0.5];
+
+/// Seed of the random soups, fixed so runs are comparable.
+const SEED: u64 = 42;
+
+/// Benchmark a single step of each backend on soups of each size.
+fn bench_sizes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("step/size");
+
+    for &size in SIZES {
+        let soup = Grid::random(size, size, 0.3, SEED);
+        group.throughput(Throughput::Elements(size as u64 * size as u64));
+
+        group.bench_with_input(BenchmarkId::new("grid", size), &soup, |b, soup| {
+            let mut sim = ConwaySim::new_with_grid(soup.clone());
+            b.iter(|| sim.step());
+        });
+
+        #[cfg(feature = "simd")]
+        group.bench_with_input(BenchmarkId::new("bit-packed", size), &soup, |b, soup| {
+            let mut sim = ConwaySim::new_bit_packed(soup);
+            b.iter(|| sim.step());
+        });
+
+        group.bench_with_input(BenchmarkId::new("sparse", size), &soup, |b, soup| {
+            let mut sim = ConwaySim::new_with_world(SparseWorld::from_grid(soup));
+            b.iter(|| sim.step());
+        });
+
+        group.bench_with_input(BenchmarkId::new("chunked", size), &soup, |b, soup| {
+            let mut sim = ConwaySim::new_with_world(ChunkedWorld::from_grid(soup));
+            b.iter(|| sim.step());
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmark a single step of each backend on soups of each density.
+fn bench_densities(c: &mut Criterion) {
+    let mut group = c.benchmark_group("step/density");
+    let size = 256;
+
+    for &density in DENSITIES {
+        let soup = Grid::random(size, size, density, SEED);
+        group.throughput(Throughput::Elements(size as u64 * size as u64));
+
+        group.bench_with_input(BenchmarkId::new("grid", density), &soup, |b, soup| {
+            let mut sim = ConwaySim::new_with_grid(soup.clone());
+            b.iter(|| sim.step());
+        });
+
+        #[cfg(feature = "simd")]
+        group.bench_with_input(BenchmarkId::new("bit-packed", density), &soup, |b, soup| {
+            let mut sim = ConwaySim::new_bit_packed(soup);
+            b.iter(|| sim.step());
+        });
+
+        group.bench_with_input(BenchmarkId::new("sparse", density), &soup, |b, soup| {
+            let mut sim = ConwaySim::new_with_world(SparseWorld::from_grid(soup));
+            b.iter(|| sim.step());
+        });
+
+        group.bench_with_input(BenchmarkId::new("chunked", density), &soup, |b, soup| {
+            let mut sim = ConwaySim::new_with_world(ChunkedWorld::from_grid(soup));
+            b.iter(|| sim.step());
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmark HashLife advancing a soup by many generations at once, where it
+/// benefits from memoization.
+fn bench_hashlife(c: &mut Criterion) {
+    let mut group = c.benchmark_group("step_n/hashlife");
+    let soup = Grid::random(256, 256, 0.3, SEED);
+
+    for &generations in &[64u64, 1024] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(generations),
+            &generations,
+            |b, &generations| {
+                b.iter(|| {
+                    let mut sim = HashLifeSim::new_with_grid(&soup);
+                    sim.step_n(generations).unwrap();
+                    sim
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_sizes, bench_densities, bench_hashlife);
+criterion_main!(benches);
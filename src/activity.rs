@@ -0,0 +1,138 @@
+//! Tracking of how often each cell changes state, to tell active regions
+//! apart from frozen ash.
+//!
+//! A [Heatmap] keeps the [Diff]s of a sliding window of recent generations
+//! and the number of times each cell changed state within it. The counts can
+//! be read cell by cell or quantized into a [StateGrid] of heat levels to draw
+//! with a palette such as [state_palette](crate::render::state_palette).
+
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::vec::Vec;
+
+use crate::observer::Diff;
+use crate::states::StateGrid;
+
+/// Number of times each cell changed state over a window of recent
+/// generations.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Heatmap {
+    /// Number of generations the counts cover.
+    window: usize,
+
+    /// The [Diff]s of the generations in the window, oldest first.
+    diffs: VecDeque<Diff>,
+
+    /// Number of changes of each cell which changed within the window.
+    counts: BTreeMap<(i64, i64), u32>,
+}
+
+impl Heatmap {
+    /// Create a new, empty [Heatmap].
+    ///
+    /// # Arguments
+    /// * `window` - Number of recent generations the counts cover, at least
+    ///   one.
+    pub fn new(window: usize) -> Heatmap {
+        Heatmap {
+            window: window.max(1),
+            diffs: VecDeque::new(),
+            counts: BTreeMap::new(),
+        }
+    }
+
+    /// Get the number of recent generations the counts cover.
+    pub fn get_window(&self) -> usize {
+        self.window
+    }
+
+    /// Get the number of generations recorded in the window so far.
+    pub fn get_num_generations(&self) -> usize {
+        self.diffs.len()
+    }
+
+    /// Record the cells which changed in a generation, forgetting the oldest
+    /// generation once the window is full.
+    ///
+    /// # Arguments
+    /// * `diff` - The cells which changed since the previous generation.
+    pub fn record(&mut self, diff: &Diff) {
+        for &cell in diff.births.iter().chain(diff.deaths.iter()) {
+            *self.counts.entry(cell).or_insert(0) += 1;
+        }
+        self.diffs.push_back(diff.clone());
+
+        while self.diffs.len() > self.window {
+            if let Some(oldest) = self.diffs.pop_front() {
+                for cell in oldest.births.iter().chain(oldest.deaths.iter()) {
+                    if let Some(count) = self.counts.get_mut(cell) {
+                        *count -= 1;
+                        if *count == 0 {
+                            self.counts.remove(cell);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Forget every recorded generation.
+    pub fn clear(&mut self) {
+        self.diffs.clear();
+        self.counts.clear();
+    }
+
+    /// Get the number of times a cell changed state within the window.
+    pub fn get_count(&self, row: i64, col: i64) -> u32 {
+        self.counts.get(&(row, col)).copied().unwrap_or(0)
+    }
+
+    /// Get the greatest number of times any cell changed state within the
+    /// window.
+    pub fn get_max_count(&self) -> u32 {
+        self.counts.values().copied().max().unwrap_or(0)
+    }
+
+    /// Get the (row, col) and count of each cell which changed state within
+    /// the window, in no particular order.
+    pub fn get_active_cells(&self) -> Vec<((i64, i64), u32)> {
+        self.counts
+            .iter()
+            .map(|(&cell, &count)| (cell, count))
+            .collect()
+    }
+
+    /// Quantize the counts of a region into a [StateGrid] of heat levels:
+    /// level 0 for cells which never changed, up to `num_levels - 1` for
+    /// cells which changed every generation of the window.
+    ///
+    /// # Arguments
+    /// * `row` - Row of the top left corner of the region.
+    /// * `col` - Column of the top left corner of the region.
+    /// * `num_rows` - The number of rows (height) of the region.
+    /// * `num_cols` - The number of columns (width) of the region.
+    /// * `num_levels` - The number of heat levels, at least two.
+    pub fn to_state_grid(
+        &self,
+        row: i64,
+        col: i64,
+        num_rows: u32,
+        num_cols: u32,
+        num_levels: u8,
+    ) -> StateGrid {
+        let max_level = num_levels.max(2) as u64 - 1;
+        let mut grid = StateGrid::new(num_rows, num_cols);
+
+        for (&(cell_row, cell_col), &count) in self.counts.iter() {
+            let (grid_row, grid_col) = (cell_row - row, cell_col - col);
+
+            if (0..num_rows as i64).contains(&grid_row) && (0..num_cols as i64).contains(&grid_col)
+            {
+                // any activity at all is at least level 1
+                let level = (count as u64 * max_level).div_ceil(self.window as u64);
+                grid.set(grid_row as u32, grid_col as u32, level.min(max_level) as u8);
+            }
+        }
+
+        grid
+    }
+}
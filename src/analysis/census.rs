@@ -0,0 +1,296 @@
+//! Identification and counting of the small objects in a world, in the style
+//! of the census reports produced by apgsearch.
+//!
+//! The live cells of a world are first grouped into objects: cells within two
+//! cells of each other belong to the same object. Each object is reduced to a
+//! canonical form, the smallest of its eight rotations and reflections
+//! shifted to the origin, whose hash is looked up in a table of the hashes of
+//! every phase of the known objects, as with [Pattern::canonical_hash]. Groups
+//! which do not match are split into their connected components, which are
+//! matched in turn; anything still unknown is counted under its canonical RLE
+//! code.
+//!
+//! [Pattern::canonical_hash]: crate::Pattern::canonical_hash
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::game::{ConwaySim, Grid};
+use crate::pattern::{canonical_cells, canonical_hash};
+use crate::sparse::SparseWorld;
+use crate::world::World;
+
+/// The known objects, as (name, RLE pattern, period) in one of their phases.
+const KNOWN_OBJECTS: &[(&str, &str, u32)] = &[
+    // still lifes
+    ("block", "2o$2o!", 1),
+    ("beehive", "b2o$o2bo$b2o!", 1),
+    ("loaf", "b2o$o2bo$bobo$2bo!", 1),
+    ("boat", "2o$obo$bo!", 1),
+    ("ship", "2o$obo$b2o!", 1),
+    ("tub", "bo$obo$bo!", 1),
+    ("pond", "b2o$o2bo$o2bo$b2o!", 1),
+    ("long boat", "2o$obo$bobo$2bo!", 1),
+    ("barge", "bo$obo$bobo$2bo!", 1),
+    ("mango", "b2o$o2bo$bo2bo$2b2o!", 1),
+    ("eater 1", "2o$obo$2bo$2b2o!", 1),
+    ("aircraft carrier", "2o$o2bo$2b2o!", 1),
+    ("snake", "2obo$ob2o!", 1),
+    ("integral sign", "2o$obo$2bo$2bobo$3b2o!", 1),
+    ("shillelagh", "2o$o2b2o$b2obo!", 1),
+    // oscillators
+    ("blinker", "3o!", 2),
+    ("toad", "b3o$3o!", 2),
+    ("beacon", "2o$2o$2b2o$2b2o!", 2),
+    (
+        "pulsar",
+        "2b3o3b3o2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2$2b3o3b3o$o4bobo4bo$\
+         o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+        3,
+    ),
+    ("pentadecathlon", "2bo4bo$2ob4ob2o$2bo4bo!", 15),
+    // spaceships
+    ("glider", "bo$2bo$3o!", 4),
+    ("lwss", "bo2bo$o4b$o3bo$4o!", 4),
+    ("mwss", "3bo$bo3bo$o5b$o4bo$5o!", 4),
+    ("hwss", "3b2o$bo4bo$o6b$o5bo$6o!", 4),
+];
+
+/// Encode a canonical form, as found by [canonical_cells], as the body of an
+/// RLE pattern.
+fn rle_code(cells: &[(i64, i64)]) -> String {
+    let mut code = String::new();
+    let mut row = 0;
+    let mut col = 0;
+
+    // cells are sorted by row, then column
+    for &(cell_row, cell_col) in cells {
+        if cell_row > row {
+            let ends = cell_row - row;
+            if ends > 1 {
+                code.push_str(&ends.to_string());
+            }
+            code.push('$');
+            row = cell_row;
+            col = 0;
+        }
+
+        let gap = cell_col - col;
+        if gap > 1 {
+            code.push_str(&gap.to_string());
+        }
+        if gap > 0 {
+            code.push('b');
+        }
+
+        code.push('o');
+        col = cell_col + 1;
+    }
+
+    // merge runs of live cells, e.g. "ooo" into "3o"
+    let mut merged = String::new();
+    let mut chars = code.chars().peekable();
+    while let Some(tag) = chars.next() {
+        if tag == 'o' {
+            let mut run = 1;
+            while chars.peek() == Some(&'o') {
+                chars.next();
+                run += 1;
+            }
+            if run > 1 {
+                merged.push_str(&run.to_string());
+            }
+        }
+        merged.push(tag);
+    }
+
+    merged.push('!');
+    merged
+}
+
+/// Get the table of the canonical hashes of every phase of the known objects.
+fn known_objects() -> &'static HashMap<u64, &'static str> {
+    static KNOWN: OnceLock<HashMap<u64, &'static str>> = OnceLock::new();
+
+    KNOWN.get_or_init(|| {
+        let mut known = HashMap::new();
+
+        for &(name, rle, period) in KNOWN_OBJECTS {
+            // the patterns fit within a generously sized header
+            let grid = Grid::from_rle(&format!("x = 16, y = 16\n{}", rle))
+                .expect("known objects are valid RLE");
+
+            let mut sim = ConwaySim::new_with_world(SparseWorld::from_grid(&grid));
+
+            for _ in 0..period {
+                known.insert(canonical_hash(&sim.get_world().live_cells()), name);
+                sim.step();
+            }
+        }
+
+        known
+    })
+}
+
+/// Identify a single object by the live cells of one of its phases, in any
+/// position or orientation.
+///
+/// # Arguments
+/// * `cells` - The (row, col) of each live cell of the object.
+pub fn identify(cells: &[(i64, i64)]) -> Option<&'static str> {
+    known_objects().get(&canonical_hash(cells)).copied()
+}
+
+/// Group live cells into clusters whose cells are within `distance` cells of
+/// another cell of the same cluster.
+fn clusters(cells: &[(i64, i64)], distance: i64) -> Vec<Vec<(i64, i64)>> {
+    let index: HashMap<(i64, i64), usize> = cells
+        .iter()
+        .enumerate()
+        .map(|(index, &cell)| (cell, index))
+        .collect();
+
+    let mut visited = vec![false; cells.len()];
+    let mut clusters: Vec<Vec<(i64, i64)>> = Vec::new();
+
+    for start in 0..cells.len() {
+        if visited[start] {
+            continue;
+        }
+
+        let mut cluster = Vec::new();
+        let mut stack = vec![start];
+        visited[start] = true;
+
+        while let Some(current) = stack.pop() {
+            let (row, col) = cells[current];
+            cluster.push((row, col));
+
+            for row_offset in -distance..=distance {
+                for col_offset in -distance..=distance {
+                    let neighbor = (row + row_offset, col + col_offset);
+
+                    if let Some(&other) = index.get(&neighbor) {
+                        if !visited[other] {
+                            visited[other] = true;
+                            stack.push(other);
+                        }
+                    }
+                }
+            }
+        }
+
+        clusters.push(cluster);
+    }
+
+    clusters
+}
+
+/// Counts of the objects found in a world, by name.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Census {
+    /// Number of each object found, by name. Unknown objects are named by
+    /// their canonical RLE code.
+    counts: BTreeMap<String, u64>,
+}
+
+impl Census {
+    /// Create a new, empty [Census].
+    pub fn new() -> Census {
+        Census::default()
+    }
+
+    /// Count an object.
+    fn add(&mut self, name: &str, count: u64) {
+        *self.counts.entry(name.to_string()).or_insert(0) += count;
+    }
+
+    /// Get the number of an object found, by name or by the canonical RLE
+    /// code of an unknown object.
+    pub fn get_count(&self, name: &str) -> u64 {
+        self.counts.get(name).copied().unwrap_or(0)
+    }
+
+    /// Get the total number of objects found.
+    pub fn get_num_objects(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// Determine if no objects were found.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Get the (name, count) of each object found, most common first and
+    /// then by name.
+    pub fn get_counts(&self) -> Vec<(&str, u64)> {
+        let mut counts: Vec<(&str, u64)> = self
+            .counts
+            .iter()
+            .map(|(name, &count)| (name.as_str(), count))
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+        counts
+    }
+
+    /// Add the counts of another [Census] to this one, e.g. to total the
+    /// census of many soups.
+    pub fn merge(&mut self, other: &Census) {
+        for (name, &count) in other.counts.iter() {
+            self.add(name, count);
+        }
+    }
+}
+
+impl fmt::Display for Census {
+    /// Display one `name: count` line per object, most common first.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (name, count) in self.get_counts() {
+            writeln!(f, "{}: {}", name, count)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Take a [Census] of the objects in a [World].
+///
+/// # Arguments
+/// * `world` - The [World] to survey.
+pub fn census<W: World>(world: &W) -> Census {
+    let mut census = Census::new();
+
+    for group in clusters(&world.live_cells(), 2) {
+        if let Some(name) = identify(&group) {
+            census.add(name, 1);
+            continue;
+        }
+
+        // objects close together may still be separate objects
+        for object in clusters(&group, 1) {
+            match identify(&object) {
+                Some(name) => census.add(name, 1),
+                None => census.add(&rle_code(&canonical_cells(&object)), 1),
+            }
+        }
+    }
+
+    census
+}
+
+impl Grid {
+    /// Take a [Census] of the objects in the [Grid].
+    pub fn census(&self) -> Census {
+        census(self)
+    }
+}
+
+impl<W: World> ConwaySim<W> {
+    /// Take a [Census] of the objects in the simulation's current
+    /// generation.
+    pub fn census(&self) -> Census {
+        census(self.get_world())
+    }
+}
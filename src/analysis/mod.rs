@@ -0,0 +1,18 @@
+//! Analysis of simulations: identifying the objects in a world and counting
+//! them in a census, detecting how patterns repeat, measuring how fast they
+//! drift, searching random soups for the objects they produce, sweeping the
+//! density of random soups, and searching for predecessors.
+
+pub mod census;
+pub mod period;
+pub mod predecessor;
+pub mod search;
+pub mod sweep;
+pub mod velocity;
+
+pub use census::{census, identify, Census};
+pub use period::{detect_period, Periodicity};
+pub use predecessor::{predecessors, PredecessorBounds, PredecessorResult};
+pub use search::{SearchReport, SoupSearch};
+pub use sweep::{DensityResult, DensitySweep, SweepReport};
+pub use velocity::{measure_velocity, Drift, Velocity, VelocityTracker};
@@ -0,0 +1,125 @@
+//! Detection of still lifes, oscillators, and spaceships.
+//!
+//! A copy of the simulation's world is advanced one generation at a time and
+//! the shape of its live cells, shifted so that their bounding box starts at
+//! the origin, is compared with the shape of the starting generation. The
+//! first generation with the same shape gives the period, and the distance the
+//! bounding box moved gives the displacement.
+
+use std::fmt;
+
+use crate::game::ConwaySim;
+use crate::world::World;
+
+/// How a pattern repeats.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Periodicity {
+    /// The pattern never changes.
+    StillLife,
+
+    /// The pattern repeats in place every `period` generations.
+    Oscillator { period: u32 },
+
+    /// The pattern repeats every `period` generations, moved by
+    /// `displacement` (rows, cols).
+    Spaceship {
+        period: u32,
+        displacement: (i64, i64),
+    },
+}
+
+impl Periodicity {
+    /// Get the number of generations after which the pattern repeats.
+    pub fn get_period(self) -> u32 {
+        match self {
+            Periodicity::StillLife => 1,
+            Periodicity::Oscillator { period } | Periodicity::Spaceship { period, .. } => period,
+        }
+    }
+
+    /// Get the (rows, cols) the pattern moves by each period, (0, 0) for
+    /// patterns which stay in place.
+    pub fn get_displacement(self) -> (i64, i64) {
+        match self {
+            Periodicity::Spaceship { displacement, .. } => displacement,
+            _ => (0, 0),
+        }
+    }
+}
+
+impl fmt::Display for Periodicity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Periodicity::StillLife => write!(f, "still life"),
+            Periodicity::Oscillator { period } => write!(f, "oscillator (period {})", period),
+            Periodicity::Spaceship {
+                period,
+                displacement: (rows, cols),
+            } => write!(
+                f,
+                "spaceship (period {}, displacement ({}, {}))",
+                period, rows, cols
+            ),
+        }
+    }
+}
+
+/// Get the live cells of a world shifted so that their bounding box starts at
+/// the origin, along with the (row, col) the bounding box started at.
+fn shape<W: World>(world: &W) -> (Vec<(i64, i64)>, (i64, i64)) {
+    let mut cells = world.live_cells();
+
+    let min_row = cells.iter().map(|&(row, _)| row).min().unwrap_or(0);
+    let min_col = cells.iter().map(|&(_, col)| col).min().unwrap_or(0);
+    for cell in cells.iter_mut() {
+        *cell = (cell.0 - min_row, cell.1 - min_col);
+    }
+    cells.sort_unstable();
+
+    (cells, (min_row, min_col))
+}
+
+/// Determine how the pattern of a simulation repeats, by running a copy of it
+/// for up to `max_period` generations. The simulation itself is not advanced.
+///
+/// Returns [None] if there are no live cells, or if the pattern does not
+/// repeat within `max_period` generations, e.g. because it is still evolving
+/// or grows without bound.
+///
+/// # Arguments
+/// * `sim` - The simulation whose current generation is examined.
+/// * `max_period` - The longest period to look for.
+pub fn detect_period<W: World>(sim: &ConwaySim<W>, max_period: u32) -> Option<Periodicity> {
+    if sim.get_population() == 0 {
+        return None;
+    }
+
+    let rule = sim.get_rule();
+    let (start, origin) = shape(sim.get_world());
+
+    let mut world = sim.get_world().clone();
+    let mut next = world.clone();
+
+    for period in 1..=max_period {
+        world.step_into(&rule, &mut next);
+        std::mem::swap(&mut world, &mut next);
+
+        let (cells, position) = shape(&world);
+        if cells != start {
+            continue;
+        }
+
+        let displacement = (position.0 - origin.0, position.1 - origin.1);
+
+        return Some(match (period, displacement) {
+            (1, (0, 0)) => Periodicity::StillLife,
+            (_, (0, 0)) => Periodicity::Oscillator { period },
+            _ => Periodicity::Spaceship {
+                period,
+                displacement,
+            },
+        });
+    }
+
+    None
+}
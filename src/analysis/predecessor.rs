@@ -0,0 +1,234 @@
+//! Experimental search for a predecessor of a [Grid]: a generation which
+//! evolves into it in one step.
+//!
+//! The search assigns the cells of a candidate predecessor one at a time in
+//! row-major order, trying dead before alive, and backtracks as soon as some
+//! cell of the target can no longer evolve correctly whatever the unassigned
+//! cells around it turn out to be. Cells beyond the target are treated as an
+//! infinite plane of dead cells, so a predecessor must not give birth to any
+//! cell outside of the target either. A pattern with no predecessor at all is
+//! a Garden of Eden.
+
+use crate::game::{Cell, ConwaySim, Grid};
+use crate::rule::Rule;
+
+/// Limits of a predecessor search.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PredecessorBounds {
+    /// Number of cells beyond each edge of the target which may be alive in
+    /// a predecessor.
+    pub margin: u32,
+
+    /// Most cell assignments tried before the search gives up.
+    pub max_nodes: u64,
+}
+
+impl Default for PredecessorBounds {
+    /// A margin of one cell and ten million assignments.
+    fn default() -> PredecessorBounds {
+        PredecessorBounds {
+            margin: 1,
+            max_nodes: 10_000_000,
+        }
+    }
+}
+
+/// Outcome of a predecessor search.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PredecessorResult {
+    /// A predecessor, larger than the target by the margin on each side, so
+    /// that its cell (margin, margin) lies over the target's cell (0, 0).
+    Found(Grid),
+
+    /// The search was exhaustive: no predecessor has all of its live cells
+    /// within the margin of the target.
+    NoneWithinBounds,
+
+    /// The search tried the most assignments allowed without an answer.
+    NodeLimitReached,
+}
+
+/// State of a predecessor search.
+struct Search<'a> {
+    /// The generation a predecessor must evolve into.
+    target: &'a Grid,
+
+    /// The [Rule] the predecessor evolves under.
+    rule: Rule,
+
+    /// Cells beyond each edge of the target which may be alive.
+    margin: i64,
+
+    /// Number of rows of the predecessor.
+    num_rows: i64,
+
+    /// Number of columns of the predecessor.
+    num_cols: i64,
+
+    /// The cells of the predecessor assigned so far, in row-major order.
+    cells: Vec<Option<bool>>,
+}
+
+impl Search<'_> {
+    /// Get the assigned state of a predecessor cell, dead beyond its edges
+    /// and [None] if not yet assigned.
+    fn cell(&self, row: i64, col: i64) -> Option<bool> {
+        if (0..self.num_rows).contains(&row) && (0..self.num_cols).contains(&col) {
+            self.cells[(row * self.num_cols + col) as usize]
+        } else {
+            Some(false)
+        }
+    }
+
+    /// Determine if a cell is alive in the target, given in the coordinates
+    /// of the predecessor.
+    fn is_target_alive(&self, row: i64, col: i64) -> bool {
+        let (row, col) = (row - self.margin, col - self.margin);
+
+        (0..self.target.get_num_rows() as i64).contains(&row)
+            && (0..self.target.get_num_cols() as i64).contains(&col)
+            && self.target.get(row as u32, col as u32).is_alive()
+    }
+
+    /// Determine if the cell at (row, col) can still evolve into its target
+    /// state for some assignment of the unassigned cells around it.
+    fn is_feasible(&self, row: i64, col: i64) -> bool {
+        let mut alive = 0;
+        let mut unknown = 0;
+
+        for neighbor_row in row - 1..=row + 1 {
+            for neighbor_col in col - 1..=col + 1 {
+                if (neighbor_row, neighbor_col) == (row, col) {
+                    continue;
+                }
+
+                match self.cell(neighbor_row, neighbor_col) {
+                    Some(true) => alive += 1,
+                    Some(false) => {}
+                    None => unknown += 1,
+                }
+            }
+        }
+
+        let wanted = self.is_target_alive(row, col);
+        let centers: &[bool] = match self.cell(row, col) {
+            Some(center) => &[center][..],
+            None => &[false, true][..],
+        };
+
+        centers.iter().any(|&center| {
+            (alive..=alive + unknown).any(|count| {
+                let next = if center {
+                    self.rule.is_survival(count)
+                } else {
+                    self.rule.is_birth(count)
+                };
+
+                next == wanted
+            })
+        })
+    }
+
+    /// Determine if every target cell around the predecessor cell at `index`
+    /// can still evolve into its target state.
+    fn is_consistent(&self, index: usize) -> bool {
+        let (row, col) = (index as i64 / self.num_cols, index as i64 % self.num_cols);
+
+        (row - 1..=row + 1).all(|row| (col - 1..=col + 1).all(|col| self.is_feasible(row, col)))
+    }
+
+    /// Build the predecessor [Grid] from a complete assignment.
+    fn to_grid(&self) -> Grid {
+        let mut grid = Grid::new(self.num_rows as u32, self.num_cols as u32);
+
+        for row in 0..self.num_rows {
+            for col in 0..self.num_cols {
+                if self.cell(row, col) == Some(true) {
+                    grid.set(row as u32, col as u32, Cell::Alive);
+                }
+            }
+        }
+
+        grid
+    }
+
+    /// Run the search, backtracking through assignments in row-major order.
+    fn run(&mut self, max_nodes: u64) -> PredecessorResult {
+        let num_cells = self.cells.len();
+        let mut index = 0;
+        let mut nodes = 0;
+
+        loop {
+            if index == num_cells {
+                return PredecessorResult::Found(self.to_grid());
+            }
+
+            // try dead, then alive, then backtrack to the previous cell
+            self.cells[index] = match self.cells[index] {
+                None => Some(false),
+                Some(false) => Some(true),
+                Some(true) => {
+                    self.cells[index] = None;
+
+                    if index == 0 {
+                        return PredecessorResult::NoneWithinBounds;
+                    }
+
+                    index -= 1;
+                    continue;
+                }
+            };
+
+            nodes += 1;
+            if nodes > max_nodes {
+                return PredecessorResult::NodeLimitReached;
+            }
+
+            if self.is_consistent(index) {
+                index += 1;
+            }
+        }
+    }
+}
+
+/// Search for a predecessor of a [Grid] under a [Rule].
+fn search(target: &Grid, rule: Rule, bounds: &PredecessorBounds) -> PredecessorResult {
+    let margin = bounds.margin as i64;
+    let num_rows = target.get_num_rows() as i64 + 2 * margin;
+    let num_cols = target.get_num_cols() as i64 + 2 * margin;
+
+    let mut search = Search {
+        target,
+        rule,
+        margin,
+        num_rows,
+        num_cols,
+        cells: vec![None; (num_rows * num_cols) as usize],
+    };
+
+    search.run(bounds.max_nodes)
+}
+
+/// Search for a predecessor of a [Grid] under Conway's rule. Experimental:
+/// the search is exhaustive, so it is only practical for small grids.
+///
+/// The [Grid]'s topology is ignored; it is treated as a window onto an
+/// infinite plane of dead cells.
+///
+/// # Arguments
+/// * `grid` - The generation a predecessor must evolve into.
+/// * `bounds` - Limits of the search.
+pub fn predecessors(grid: &Grid, bounds: &PredecessorBounds) -> PredecessorResult {
+    search(grid, Rule::conway(), bounds)
+}
+
+impl ConwaySim {
+    /// Search for a predecessor of the simulation's current generation under
+    /// its [Rule], as with [predecessors].
+    ///
+    /// # Arguments
+    /// * `bounds` - Limits of the search.
+    pub fn find_predecessor(&self, bounds: &PredecessorBounds) -> PredecessorResult {
+        search(self.get_grid(), self.get_rule(), bounds)
+    }
+}
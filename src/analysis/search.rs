@@ -0,0 +1,316 @@
+//! Searching random soups for the objects they leave behind, in the style of
+//! apgsearch.
+//!
+//! Each soup is a square of random cells, optionally with a [Symmetry],
+//! placed in an unbounded [SparseWorld], so that escaping gliders and
+//! spaceships are not destroyed by the edges of a grid. A soup is run until
+//! its population repeats with a short period, at which point its ash, the
+//! objects left behind, is counted in a [Census]. Soups are shared between threads and their censuses merged
+//! into a [SearchReport], which also keeps a sample soup of each distinct
+//! object. Objects are told apart by their canonical form, so the same object
+//! left behind in different orientations by different soups is counted once
+//! under one name.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::thread;
+
+use crate::analysis::census::{census, Census};
+use crate::game::{ConwaySim, Grid};
+use crate::rule::Rule;
+use crate::soup::Symmetry;
+use crate::sparse::SparseWorld;
+
+/// Longest period of the population considered stable.
+const MAX_POPULATION_PERIOD: usize = 30;
+
+/// Number of generations the population must repeat for before a soup is
+/// considered stable.
+const STABLE_GENERATIONS: usize = 4 * MAX_POPULATION_PERIOD;
+
+/// Determine if the recent populations of a soup repeat with a short period.
+fn is_stable(populations: &[u64]) -> bool {
+    if populations.len() < STABLE_GENERATIONS + MAX_POPULATION_PERIOD {
+        return false;
+    }
+
+    let recent = &populations[populations.len() - STABLE_GENERATIONS - MAX_POPULATION_PERIOD..];
+
+    (1..=MAX_POPULATION_PERIOD).any(|period| {
+        (recent.len() - STABLE_GENERATIONS..recent.len())
+            .all(|index| recent[index] == recent[index - period])
+    })
+}
+
+/// The aggregated results of a [SoupSearch].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SearchReport {
+    /// Combined [Census] of the ash of every soup.
+    census: Census,
+
+    /// Number of soups searched.
+    num_soups: u64,
+
+    /// Number of soups which did not stabilize within the generation limit.
+    num_unstable: u64,
+
+    /// The lowest seed of the soups which left behind each object, by name.
+    samples: BTreeMap<String, u64>,
+}
+
+impl SearchReport {
+    /// Get the combined [Census] of the ash of every soup.
+    pub fn get_census(&self) -> &Census {
+        &self.census
+    }
+
+    /// Get the number of soups searched.
+    pub fn get_num_soups(&self) -> u64 {
+        self.num_soups
+    }
+
+    /// Get the number of soups which did not stabilize within the generation
+    /// limit. Their final generation is still counted in the [Census].
+    pub fn get_num_unstable(&self) -> u64 {
+        self.num_unstable
+    }
+
+    /// Get the seed of a soup which left behind an object, the lowest of
+    /// those searched, so that the object can be seen forming.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the object, or the canonical RLE code of an
+    ///   unknown object, as in the [Census].
+    pub fn get_sample_seed(&self, name: &str) -> Option<u64> {
+        self.samples.get(name).copied()
+    }
+
+    /// Add the ash of a soup to the report.
+    fn add_soup(&mut self, seed: u64, ash: &Census, stable: bool) {
+        self.census.merge(ash);
+        self.num_soups += 1;
+        if !stable {
+            self.num_unstable += 1;
+        }
+
+        for (name, _) in ash.get_counts() {
+            self.add_sample(name, seed);
+        }
+    }
+
+    /// Keep a soup as the sample of an object if its seed is the lowest.
+    fn add_sample(&mut self, name: &str, seed: u64) {
+        self.samples
+            .entry(name.to_string())
+            .and_modify(|sample| *sample = (*sample).min(seed))
+            .or_insert(seed);
+    }
+
+    /// Add the results of another [SearchReport] to this one.
+    pub fn merge(&mut self, other: &SearchReport) {
+        self.census.merge(&other.census);
+        self.num_soups += other.num_soups;
+        self.num_unstable += other.num_unstable;
+
+        for (name, &seed) in other.samples.iter() {
+            self.add_sample(name, seed);
+        }
+    }
+}
+
+impl fmt::Display for SearchReport {
+    /// Display the number of soups followed by one `name: count (soup seed)`
+    /// line per object, most common first.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} soups searched, {} unstable, {} objects",
+            self.num_soups,
+            self.num_unstable,
+            self.census.get_num_objects()
+        )?;
+
+        for (name, count) in self.census.get_counts() {
+            match self.get_sample_seed(name) {
+                Some(seed) => writeln!(f, "{}: {} (soup {})", name, count, seed)?,
+                None => writeln!(f, "{}: {}", name, count)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A search of many random soups, censusing the objects each leaves behind.
+#[derive(Clone, Debug)]
+pub struct SoupSearch {
+    /// Number of rows (height) of each soup.
+    num_rows: u32,
+
+    /// Number of columns (width) of each soup.
+    num_cols: u32,
+
+    /// Probability of each cell of a soup being alive.
+    density: f64,
+
+    /// The [Symmetry] of each soup.
+    symmetry: Symmetry,
+
+    /// The [Rule] the soups are run under.
+    rule: Rule,
+
+    /// Most generations a soup is run for before it is censused regardless.
+    max_generations: u32,
+
+    /// Number of threads the soups are shared between.
+    num_threads: usize,
+}
+
+impl SoupSearch {
+    /// Create a new search of 16x16 soups of 50% density under Conway's rule,
+    /// run for at most 10000 generations each on every available thread.
+    pub fn new() -> SoupSearch {
+        SoupSearch {
+            num_rows: 16,
+            num_cols: 16,
+            density: 0.5,
+            symmetry: Symmetry::C1,
+            rule: Rule::default(),
+            max_generations: 10_000,
+            num_threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+        }
+    }
+
+    /// Set the size of each soup.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of each soup.
+    /// * `num_cols` - The number of columns (width) of each soup.
+    pub fn set_size(&mut self, num_rows: u32, num_cols: u32) {
+        self.num_rows = num_rows;
+        self.num_cols = num_cols;
+    }
+
+    /// Set the probability of each cell of a soup being alive.
+    ///
+    /// # Panics
+    /// Panics if `density` is not within 0.0 to 1.0.
+    pub fn set_density(&mut self, density: f64) {
+        assert!(
+            (0.0..=1.0).contains(&density),
+            "density must be within 0.0 to 1.0"
+        );
+
+        self.density = density;
+    }
+
+    /// Set the [Symmetry] of each soup, to search for the objects which
+    /// symmetric soups leave behind more often.
+    pub fn set_symmetry(&mut self, symmetry: Symmetry) {
+        self.symmetry = symmetry;
+    }
+
+    /// Set the [Rule] the soups are run under.
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
+    /// Set the most generations a soup is run for before its ash is
+    /// censused even if it has not stabilized.
+    pub fn set_max_generations(&mut self, max_generations: u32) {
+        self.max_generations = max_generations;
+    }
+
+    /// Set the number of threads the soups are shared between, at least one.
+    pub fn set_num_threads(&mut self, num_threads: usize) {
+        self.num_threads = num_threads.max(1);
+    }
+
+    /// Get the number of threads the soups are shared between.
+    pub fn get_num_threads(&self) -> usize {
+        self.num_threads
+    }
+
+    /// Run a single soup to stabilization and add its ash to a report.
+    fn search_soup(&self, seed: u64, report: &mut SearchReport) {
+        let soup = Grid::random_symmetric(
+            self.num_rows,
+            self.num_cols,
+            self.density,
+            seed,
+            self.symmetry,
+        );
+        let mut sim = ConwaySim::new_with_world(SparseWorld::from_grid(&soup));
+        sim.set_rule(self.rule);
+
+        let mut populations = vec![sim.get_population()];
+        let mut stable = false;
+
+        while sim.get_generation() < self.max_generations {
+            sim.step();
+            populations.push(sim.get_population());
+
+            if is_stable(&populations) {
+                stable = true;
+                break;
+            }
+        }
+
+        report.add_soup(seed, &census(sim.get_world()), stable);
+    }
+
+    /// Search `num_soups` soups, seeded with consecutive seeds starting at
+    /// `seed`. The report is the same regardless of the number of threads.
+    ///
+    /// # Arguments
+    /// * `num_soups` - The number of soups to search.
+    /// * `seed` - Seed of the first soup.
+    ///
+    /// # Panics
+    /// Panics if the soups' [Symmetry] [only fits a square
+    /// grid](Symmetry::is_square_only) and the soups are not square.
+    pub fn run(&self, num_soups: u64, seed: u64) -> SearchReport {
+        assert!(
+            !self.symmetry.is_square_only() || self.num_rows == self.num_cols,
+            "{} symmetry requires square soups",
+            self.symmetry
+        );
+
+        let num_threads = self.num_threads as u64;
+
+        let reports: Vec<SearchReport> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..num_threads)
+                .map(|worker| {
+                    scope.spawn(move || {
+                        let mut report = SearchReport::default();
+
+                        // each worker takes every num_threads-th soup
+                        for index in (worker..num_soups).step_by(num_threads as usize) {
+                            self.search_soup(seed.wrapping_add(index), &mut report);
+                        }
+
+                        report
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .map(|worker| worker.join().expect("soup search thread panicked"))
+                .collect()
+        });
+
+        let mut total = SearchReport::default();
+        for report in reports.iter() {
+            total.merge(report);
+        }
+
+        total
+    }
+}
+
+impl Default for SoupSearch {
+    fn default() -> SoupSearch {
+        SoupSearch::new()
+    }
+}
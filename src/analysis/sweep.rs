@@ -0,0 +1,377 @@
+//! Sweeping the initial density of random soups to find how it decides what
+//! they settle to.
+//!
+//! A [DensitySweep] runs many soups at each of a range of densities, on a
+//! torus unless [DensitySweep::set_topology] chooses other edges, each until
+//! it enters a cycle or reaches a generation limit, and reports for each
+//! density the mean final population and the mean generation at which the
+//! soups entered their cycles. Plotted against the density, they show the
+//! transition between sparse soups which quickly die out and dense soups
+//! which burn for thousands of generations. Soups are shared between
+//! threads, and the report is the same however many there are.
+//!
+//! ```
+//! use gameoflife_rs::analysis::DensitySweep;
+//!
+//! let mut sweep = DensitySweep::new();
+//! sweep.set_size(16, 16);
+//! sweep.set_densities(&[0.0, 0.35]);
+//! sweep.set_num_trials(4);
+//!
+//! let report = sweep.run(1);
+//! let (empty, dense) = (&report.get_results()[0], &report.get_results()[1]);
+//!
+//! assert_eq!(empty.get_num_extinct(), 4);
+//! assert_eq!(empty.get_mean_stabilization(), Some(0.0));
+//! assert_eq!(dense.get_num_trials(), 4);
+//! assert!(dense.get_mean_population() > 0.0);
+//! ```
+
+use std::fmt;
+use std::thread;
+
+use crate::game::{ConwaySim, Grid};
+use crate::rule::Rule;
+use crate::topology::Topology;
+
+/// The aggregated results of the soups of one density of a [DensitySweep].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DensityResult {
+    /// Probability of each cell of the soups being alive.
+    density: f64,
+
+    /// Number of soups run.
+    num_trials: u64,
+
+    /// Number of soups which entered a cycle within the generation limit.
+    num_stabilized: u64,
+
+    /// Number of soups which died out.
+    num_extinct: u64,
+
+    /// Sum of the final populations of the soups.
+    total_population: u64,
+
+    /// Sum of the generations at which the stabilized soups entered their
+    /// cycles.
+    total_stabilization: u64,
+}
+
+impl DensityResult {
+    /// Create a new [DensityResult] of no soups.
+    fn new(density: f64) -> DensityResult {
+        DensityResult {
+            density,
+            num_trials: 0,
+            num_stabilized: 0,
+            num_extinct: 0,
+            total_population: 0,
+            total_stabilization: 0,
+        }
+    }
+
+    /// Get the probability of each cell of the soups being alive.
+    pub fn get_density(&self) -> f64 {
+        self.density
+    }
+
+    /// Get the number of soups run.
+    pub fn get_num_trials(&self) -> u64 {
+        self.num_trials
+    }
+
+    /// Get the number of soups which entered a cycle within the generation
+    /// limit, including those which died out.
+    pub fn get_num_stabilized(&self) -> u64 {
+        self.num_stabilized
+    }
+
+    /// Get the number of soups which died out.
+    pub fn get_num_extinct(&self) -> u64 {
+        self.num_extinct
+    }
+
+    /// Get the mean population of the soups' final generations, those they
+    /// entered their cycles at or reached the generation limit at.
+    pub fn get_mean_population(&self) -> f64 {
+        if self.num_trials == 0 {
+            return 0.0;
+        }
+
+        self.total_population as f64 / self.num_trials as f64
+    }
+
+    /// Get the mean generation at which the stabilized soups entered their
+    /// cycles, if any stabilized.
+    pub fn get_mean_stabilization(&self) -> Option<f64> {
+        if self.num_stabilized == 0 {
+            return None;
+        }
+
+        Some(self.total_stabilization as f64 / self.num_stabilized as f64)
+    }
+
+    /// Add the outcome of a soup to the result.
+    ///
+    /// # Arguments
+    /// * `population` - The population of the soup's final generation.
+    /// * `stabilized_at` - The generation the soup entered a cycle at, if it
+    ///   did within the generation limit.
+    fn add_trial(&mut self, population: u64, stabilized_at: Option<u32>) {
+        self.num_trials += 1;
+        self.total_population += population;
+
+        if population == 0 {
+            self.num_extinct += 1;
+        }
+        if let Some(generation) = stabilized_at {
+            self.num_stabilized += 1;
+            self.total_stabilization += generation as u64;
+        }
+    }
+}
+
+/// The results of a [DensitySweep], one per density.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SweepReport {
+    /// The results of each density, in the order they were swept.
+    results: Vec<DensityResult>,
+}
+
+impl SweepReport {
+    /// Get the results of each density, in the order they were swept.
+    pub fn get_results(&self) -> &[DensityResult] {
+        &self.results
+    }
+}
+
+impl fmt::Display for SweepReport {
+    /// Display a table of one row per density.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "density  trials  mean population  stabilized  mean generations  extinct"
+        )?;
+
+        for result in self.results.iter() {
+            let stabilization = result
+                .get_mean_stabilization()
+                .map_or("-".to_string(), |generations| format!("{:.1}", generations));
+
+            writeln!(
+                f,
+                "{:>7.3}  {:>6}  {:>15.1}  {:>10}  {:>16}  {:>7}",
+                result.density,
+                result.num_trials,
+                result.get_mean_population(),
+                result.num_stabilized,
+                stabilization,
+                result.num_extinct
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A sweep of random soups across a range of initial densities.
+#[derive(Clone, Debug)]
+pub struct DensitySweep {
+    /// Number of rows (height) of each soup.
+    num_rows: u32,
+
+    /// Number of columns (width) of each soup.
+    num_cols: u32,
+
+    /// The densities swept, in order.
+    densities: Vec<f64>,
+
+    /// Number of soups run at each density.
+    num_trials: u32,
+
+    /// The [Rule] the soups are run under.
+    rule: Rule,
+
+    /// How the cells on the edges of each soup find their neighbors.
+    topology: Topology,
+
+    /// Most generations a soup is run for before it is counted as
+    /// unstabilized.
+    max_generations: u32,
+
+    /// Number of threads the soups are shared between.
+    num_threads: usize,
+}
+
+impl DensitySweep {
+    /// Create a new sweep of 64x64 soups on a torus under Conway's rule, ten
+    /// at each density from 5% to 95% in steps of 5%, run for at most 5000
+    /// generations each on every available thread.
+    pub fn new() -> DensitySweep {
+        let mut sweep = DensitySweep {
+            num_rows: 64,
+            num_cols: 64,
+            densities: Vec::new(),
+            num_trials: 10,
+            rule: Rule::default(),
+            topology: Topology::Torus,
+            max_generations: 5000,
+            num_threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+        };
+        sweep.set_density_range(0.05, 0.95, 19);
+
+        sweep
+    }
+
+    /// Set the size of each soup.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of each soup.
+    /// * `num_cols` - The number of columns (width) of each soup.
+    pub fn set_size(&mut self, num_rows: u32, num_cols: u32) {
+        self.num_rows = num_rows;
+        self.num_cols = num_cols;
+    }
+
+    /// Get the densities swept, in order.
+    pub fn get_densities(&self) -> &[f64] {
+        &self.densities
+    }
+
+    /// Set the densities swept.
+    ///
+    /// # Panics
+    /// Panics if any density is not within 0.0 to 1.0.
+    pub fn set_densities(&mut self, densities: &[f64]) {
+        assert!(
+            densities
+                .iter()
+                .all(|density| (0.0..=1.0).contains(density)),
+            "densities must be within 0.0 to 1.0"
+        );
+
+        self.densities = densities.to_vec();
+    }
+
+    /// Sweep evenly spaced densities.
+    ///
+    /// # Arguments
+    /// * `from` - The first density.
+    /// * `to` - The last density.
+    /// * `steps` - The number of densities, only `from` if 1.
+    ///
+    /// # Panics
+    /// Panics if `from` or `to` is not within 0.0 to 1.0.
+    pub fn set_density_range(&mut self, from: f64, to: f64, steps: u32) {
+        let densities: Vec<f64> = (0..steps)
+            .map(|step| match steps {
+                1 => from,
+                _ => from + (to - from) * step as f64 / (steps - 1) as f64,
+            })
+            .collect();
+
+        self.set_densities(&densities);
+    }
+
+    /// Set the number of soups run at each density.
+    pub fn set_num_trials(&mut self, num_trials: u32) {
+        self.num_trials = num_trials;
+    }
+
+    /// Set the [Rule] the soups are run under.
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
+    /// Set how the cells on the edges of each soup find their neighbors.
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    /// Set the most generations a soup is run for before it is counted as
+    /// unstabilized.
+    pub fn set_max_generations(&mut self, max_generations: u32) {
+        self.max_generations = max_generations;
+    }
+
+    /// Set the number of threads the soups are shared between, at least one.
+    pub fn set_num_threads(&mut self, num_threads: usize) {
+        self.num_threads = num_threads.max(1);
+    }
+
+    /// Get the number of threads the soups are shared between.
+    pub fn get_num_threads(&self) -> usize {
+        self.num_threads
+    }
+
+    /// Run a single soup until it enters a cycle, returning its final
+    /// population and the generation it entered the cycle at, if it did.
+    fn run_trial(&self, density: f64, seed: u64) -> (u64, Option<u32>) {
+        let mut sim =
+            ConwaySim::new_with_grid(Grid::random(self.num_rows, self.num_cols, density, seed));
+        sim.set_rule(self.rule);
+        sim.set_topology(self.topology);
+
+        while sim.get_cycle().is_none() && sim.get_generation() < self.max_generations {
+            sim.step();
+        }
+
+        let stabilized_at = sim.get_cycle().map(|cycle| cycle.get_start());
+
+        (sim.get_population(), stabilized_at)
+    }
+
+    /// Run the sweep, seeding the soups with consecutive seeds starting at
+    /// `seed`, all the soups of one density before those of the next.
+    ///
+    /// # Arguments
+    /// * `seed` - Seed of the first soup.
+    pub fn run(&self, seed: u64) -> SweepReport {
+        let num_trials = self.num_trials as u64;
+        let num_soups = self.densities.len() as u64 * num_trials;
+        let num_threads = self.num_threads as u64;
+
+        let outcomes: Vec<Vec<(u64, u64, Option<u32>)>> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..num_threads)
+                .map(|worker| {
+                    scope.spawn(move || {
+                        // each worker takes every num_threads-th soup
+                        (worker..num_soups)
+                            .step_by(num_threads as usize)
+                            .map(|index| {
+                                let density = self.densities[(index / num_trials) as usize];
+                                let (population, stabilized_at) =
+                                    self.run_trial(density, seed.wrapping_add(index));
+
+                                (index, population, stabilized_at)
+                            })
+                            .collect()
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .map(|worker| worker.join().expect("density sweep thread panicked"))
+                .collect()
+        });
+
+        let mut results: Vec<DensityResult> = self
+            .densities
+            .iter()
+            .map(|&density| DensityResult::new(density))
+            .collect();
+
+        for &(index, population, stabilized_at) in outcomes.iter().flatten() {
+            results[(index / num_trials) as usize].add_trial(population, stabilized_at);
+        }
+
+        SweepReport { results }
+    }
+}
+
+impl Default for DensitySweep {
+    fn default() -> DensitySweep {
+        DensitySweep::new()
+    }
+}
@@ -0,0 +1,275 @@
+//! Estimation of how fast and in which direction a pattern drifts, telling
+//! spaceships from oscillators and still lifes.
+//!
+//! Each generation, the centroid (mean position) of the live cells is
+//! sampled. Along axes which wrap around, as on a torus, the centroid is the
+//! circular mean of the positions, and the centroid is assumed to have moved
+//! the short way around between samples, so a ship crossing the edge keeps
+//! its velocity. A least-squares line through the samples gives the velocity.
+//!
+//! ```
+//! use gameoflife_rs::analysis::{measure_velocity, Drift};
+//! use gameoflife_rs::{patterns, ConwaySim, Grid, Overlap, Pattern, Topology};
+//!
+//! let mut grid = Grid::new(16, 16);
+//! grid.set_topology(Topology::Torus);
+//! let glider = Pattern::new(patterns::by_name("glider").unwrap());
+//! grid.stamp(&glider, 12, 12, Overlap::Or).unwrap();
+//!
+//! let velocity = measure_velocity(&ConwaySim::new_with_grid(grid), 64).unwrap();
+//! assert!((velocity.get_speed() - 0.25).abs() < 0.01);
+//! assert!(matches!(velocity.get_drift(0.01), Drift::Moving(_)));
+//! ```
+
+use std::collections::VecDeque;
+use std::f64::consts::TAU;
+use std::fmt;
+
+use crate::game::ConwaySim;
+use crate::world::World;
+
+/// Velocity of a pattern in cells per generation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Velocity {
+    /// Rows moved per generation, positive downwards.
+    pub rows: f64,
+
+    /// Columns moved per generation, positive to the right.
+    pub cols: f64,
+}
+
+impl Velocity {
+    /// Get the speed as a fraction of the speed of light c, one cell per
+    /// generation along either axis or diagonally, e.g. 0.25 for a glider.
+    pub fn get_speed(self) -> f64 {
+        self.rows.abs().max(self.cols.abs())
+    }
+
+    /// Get the direction of travel in degrees clockwise from up (north),
+    /// from 0 to 360.
+    pub fn get_heading(self) -> f64 {
+        self.cols.atan2(-self.rows).to_degrees().rem_euclid(360.0)
+    }
+
+    /// Tell whether the pattern moves or stays in place.
+    ///
+    /// # Arguments
+    /// * `tolerance` - The largest speed, as a fraction of c, still counted
+    ///   as staying in place, allowing for the wobble of oscillators.
+    pub fn get_drift(self, tolerance: f64) -> Drift {
+        if self.get_speed() <= tolerance {
+            Drift::Stationary
+        } else {
+            Drift::Moving(self)
+        }
+    }
+}
+
+impl fmt::Display for Velocity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:.3}c heading {:.0}° ({:+.3} rows, {:+.3} cols per generation)",
+            self.get_speed(),
+            self.get_heading(),
+            self.rows,
+            self.cols
+        )
+    }
+}
+
+/// Whether a pattern moves, as with spaceships, or stays in place, as with
+/// oscillators and still lifes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Drift {
+    /// The pattern stays in place.
+    Stationary,
+
+    /// The pattern moves with a [Velocity].
+    Moving(Velocity),
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Drift::Stationary => write!(f, "stationary"),
+            Drift::Moving(velocity) => write!(f, "moving at {}", velocity),
+        }
+    }
+}
+
+/// Get the centroid of positions along one axis, as the circular mean if the
+/// axis wraps around after `len` cells.
+fn centroid(positions: impl Iterator<Item = i64>, len: Option<u32>) -> f64 {
+    match len {
+        Some(len) => {
+            let (mut sin, mut cos) = (0.0, 0.0);
+            for position in positions {
+                let angle = position as f64 * TAU / len as f64;
+                sin += angle.sin();
+                cos += angle.cos();
+            }
+
+            (sin.atan2(cos) * len as f64 / TAU).rem_euclid(len as f64)
+        }
+        None => {
+            let (mut sum, mut count) = (0.0, 0.0);
+            for position in positions {
+                sum += position as f64;
+                count += 1.0;
+            }
+
+            sum / count
+        }
+    }
+}
+
+/// Get the change from one position to another along an axis, the short way
+/// around if it wraps after `len` cells.
+fn shortest_delta(from: f64, to: f64, len: Option<u32>) -> f64 {
+    match len {
+        Some(len) => {
+            let len = len as f64;
+            (to - from + len / 2.0).rem_euclid(len) - len / 2.0
+        }
+        None => to - from,
+    }
+}
+
+/// Tracker of the centroid of a pattern's live cells over recent
+/// generations, estimating its [Velocity].
+#[derive(Clone, Debug)]
+pub struct VelocityTracker {
+    /// Maximum number of generations sampled, the oldest are discarded
+    /// first.
+    window: usize,
+
+    /// The generation and unwrapped (row, col) centroid of each sample,
+    /// oldest first.
+    samples: VecDeque<(u32, f64, f64)>,
+
+    /// The (row, col) centroid of the latest sample within the world, before
+    /// unwrapping.
+    last: Option<(f64, f64)>,
+}
+
+impl VelocityTracker {
+    /// Create a new [VelocityTracker] with no samples.
+    ///
+    /// # Arguments
+    /// * `window` - Number of recent generations the velocity is estimated
+    ///   over (at least 2). Windows spanning several periods of a spaceship
+    ///   smooth out its wobble.
+    pub fn new(window: usize) -> VelocityTracker {
+        VelocityTracker {
+            window: window.max(2),
+            samples: VecDeque::new(),
+            last: None,
+        }
+    }
+
+    /// Sample the centroid of the live cells of a generation, forgetting the
+    /// earlier samples if no cell is alive.
+    ///
+    /// # Arguments
+    /// * `generation` - The generation of the world.
+    /// * `world` - The [World] at the generation.
+    pub fn record<W: World>(&mut self, generation: u32, world: &W) {
+        let cells = world.live_cells();
+        if cells.is_empty() {
+            self.clear();
+            return;
+        }
+
+        let (wrap_rows, wrap_cols) = world.get_wrap();
+        let row = centroid(cells.iter().map(|&(row, _)| row), wrap_rows);
+        let col = centroid(cells.iter().map(|&(_, col)| col), wrap_cols);
+
+        let unwrapped = match (self.last, self.samples.back()) {
+            (Some((last_row, last_col)), Some(&(_, unwrapped_row, unwrapped_col))) => (
+                unwrapped_row + shortest_delta(last_row, row, wrap_rows),
+                unwrapped_col + shortest_delta(last_col, col, wrap_cols),
+            ),
+            _ => (row, col),
+        };
+
+        self.last = Some((row, col));
+        self.samples
+            .push_back((generation, unwrapped.0, unwrapped.1));
+        if self.samples.len() > self.window {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Forget every sample.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.last = None;
+    }
+
+    /// Get the number of generations sampled.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Determine if no generation was sampled.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Estimate the [Velocity] over the sampled generations, or [None] if
+    /// fewer than two different generations were sampled.
+    pub fn get_velocity(&self) -> Option<Velocity> {
+        let count = self.samples.len() as f64;
+        let mean = |value: fn(&(u32, f64, f64)) -> f64| -> f64 {
+            self.samples.iter().map(value).sum::<f64>() / count
+        };
+
+        let mean_generation = mean(|sample| sample.0 as f64);
+        let (mean_row, mean_col) = (mean(|sample| sample.1), mean(|sample| sample.2));
+
+        let (mut variance, mut rows, mut cols) = (0.0, 0.0, 0.0);
+        for &(generation, row, col) in self.samples.iter() {
+            let offset = generation as f64 - mean_generation;
+            variance += offset * offset;
+            rows += offset * (row - mean_row);
+            cols += offset * (col - mean_col);
+        }
+
+        if variance == 0.0 {
+            return None;
+        }
+
+        Some(Velocity {
+            rows: rows / variance,
+            cols: cols / variance,
+        })
+    }
+}
+
+/// Estimate the [Velocity] of the pattern of a simulation, by running a copy
+/// of it for a number of generations. The simulation itself is not advanced.
+///
+/// Returns [None] if the pattern dies out before two generations were
+/// sampled.
+///
+/// # Arguments
+/// * `sim` - The simulation whose pattern is measured from its current
+///   generation.
+/// * `generations` - Number of generations to sample after the current one.
+pub fn measure_velocity<W: World>(sim: &ConwaySim<W>, generations: u32) -> Option<Velocity> {
+    let rule = sim.get_rule();
+    let mut tracker = VelocityTracker::new(generations as usize + 1);
+
+    let mut world = sim.get_world().clone();
+    let mut next = world.clone();
+    tracker.record(0, &world);
+
+    for generation in 1..=generations {
+        world.step_into(&rule, &mut next);
+        std::mem::swap(&mut world, &mut next);
+        tracker.record(generation, &world);
+    }
+
+    tracker.get_velocity()
+}
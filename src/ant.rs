@@ -0,0 +1,344 @@
+//! Langton's Ant and its multi-color generalizations.
+//!
+//! Each ant stands on a cell facing one of four directions. Every generation
+//! each ant turns according to the state of its cell, advances that cell to
+//! the next state, and moves forward one cell. The turns are given by a rule
+//! such as `RL`, Langton's original ant, where the `n`th letter is the turn
+//! on cells in state `n`, so a rule of `k` letters uses `k` cell states.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::rule::RuleError;
+use crate::states::StateGrid;
+
+/// A turn made by an ant.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Turn {
+    /// Turn 90 degrees to the left, `L`.
+    Left,
+
+    /// Turn 90 degrees to the right, `R`.
+    Right,
+
+    /// Keep going straight ahead, `N`.
+    None,
+
+    /// Turn around, `U`.
+    UTurn,
+}
+
+/// The direction an ant is facing.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Direction {
+    /// Towards row 0.
+    Up,
+
+    /// Towards the last column.
+    Right,
+
+    /// Towards the last row.
+    Down,
+
+    /// Towards column 0.
+    Left,
+}
+
+impl Direction {
+    /// Get the direction faced after making a [Turn].
+    pub fn turned(self, turn: Turn) -> Direction {
+        const CLOCKWISE: [Direction; 4] = [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ];
+
+        let quarter_turns = match turn {
+            Turn::Left => 3,
+            Turn::Right => 1,
+            Turn::None => 0,
+            Turn::UTurn => 2,
+        };
+
+        CLOCKWISE[(self as usize + quarter_turns) % 4]
+    }
+}
+
+/// The turns of an ant by cell state, e.g. `RL` or `LLRR`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AntRule {
+    /// `turns[n]` is the turn on a cell in state `n`.
+    turns: Vec<Turn>,
+}
+
+impl AntRule {
+    /// Create a new [AntRule] from the turn on each cell state.
+    ///
+    /// # Arguments
+    /// * `turns` - The turn on cells in each state, in state order.
+    ///
+    /// # Panics
+    /// Panics if there are fewer than 2 or more than 255 turns.
+    pub fn new(turns: &[Turn]) -> AntRule {
+        assert!(
+            (2..=255).contains(&turns.len()),
+            "ant rules have 2 to 255 turns"
+        );
+
+        AntRule {
+            turns: turns.to_vec(),
+        }
+    }
+
+    /// Langton's original ant, `RL`.
+    pub fn langton() -> AntRule {
+        AntRule::new(&[Turn::Right, Turn::Left])
+    }
+
+    /// Get the number of cell states, one per turn.
+    pub fn get_num_states(&self) -> u8 {
+        self.turns.len() as u8
+    }
+
+    /// Get the turn on a cell in a state.
+    pub fn get_turn(&self, state: u8) -> Turn {
+        self.turns[state as usize]
+    }
+}
+
+impl Default for AntRule {
+    fn default() -> AntRule {
+        AntRule::langton()
+    }
+}
+
+impl FromStr for AntRule {
+    type Err = RuleError;
+
+    /// Parse a rule of `L`, `R`, `N`, and `U` turns (case insensitive).
+    fn from_str(rulestring: &str) -> Result<AntRule, RuleError> {
+        let turns = rulestring
+            .trim()
+            .chars()
+            .map(|turn| match turn.to_ascii_uppercase() {
+                'L' => Some(Turn::Left),
+                'R' => Some(Turn::Right),
+                'N' => Some(Turn::None),
+                'U' => Some(Turn::UTurn),
+                _ => None,
+            })
+            .collect::<Option<Vec<Turn>>>();
+
+        match turns {
+            Some(turns) if (2..=255).contains(&turns.len()) => Ok(AntRule { turns }),
+            _ => Err(RuleError::new(rulestring)),
+        }
+    }
+}
+
+impl fmt::Display for AntRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for turn in self.turns.iter() {
+            let letter = match turn {
+                Turn::Left => 'L',
+                Turn::Right => 'R',
+                Turn::None => 'N',
+                Turn::UTurn => 'U',
+            };
+            write!(f, "{}", letter)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An ant, standing on a cell and facing a [Direction].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Ant {
+    /// Row of the cell the ant stands on.
+    row: u32,
+
+    /// Column of the cell the ant stands on.
+    col: u32,
+
+    /// The direction the ant is facing.
+    direction: Direction,
+}
+
+impl Ant {
+    /// Create a new [Ant].
+    ///
+    /// # Arguments
+    /// * `row` - Row of the cell the ant stands on.
+    /// * `col` - Column of the cell the ant stands on.
+    /// * `direction` - The direction the ant is facing.
+    pub fn new(row: u32, col: u32, direction: Direction) -> Ant {
+        Ant {
+            row,
+            col,
+            direction,
+        }
+    }
+
+    /// Get the row of the cell the ant stands on.
+    pub fn get_row(&self) -> u32 {
+        self.row
+    }
+
+    /// Get the column of the cell the ant stands on.
+    pub fn get_col(&self) -> u32 {
+        self.col
+    }
+
+    /// Get the direction the ant is facing.
+    pub fn get_direction(&self) -> Direction {
+        self.direction
+    }
+}
+
+/// Simulation of ants walking a [StateGrid] which wraps around at its edges.
+#[derive(Clone, Debug)]
+pub struct LangtonsAnt {
+    /// The cell states.
+    grid: StateGrid,
+
+    /// The ants, moved in order each generation.
+    ants: Vec<Ant>,
+
+    /// The [AntRule] followed by every ant.
+    rule: AntRule,
+
+    /// The simulation's current generation.
+    generation: u32,
+}
+
+impl LangtonsAnt {
+    /// Create a new simulation of cells in state 0, without ants.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the grid.
+    /// * `num_cols` - The number of columns (width) of the grid.
+    /// * `rule` - The [AntRule] followed by every ant.
+    pub fn new(num_rows: u32, num_cols: u32, rule: AntRule) -> LangtonsAnt {
+        LangtonsAnt {
+            grid: StateGrid::new(num_rows, num_cols),
+            ants: Vec::new(),
+            rule,
+            generation: 0,
+        }
+    }
+
+    /// Create a new simulation with a single ant in the center of the grid,
+    /// facing up.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the grid.
+    /// * `num_cols` - The number of columns (width) of the grid.
+    /// * `rule` - The [AntRule] followed by the ant.
+    pub fn new_centered(num_rows: u32, num_cols: u32, rule: AntRule) -> LangtonsAnt {
+        let mut sim = LangtonsAnt::new(num_rows, num_cols, rule);
+        sim.add_ant(Ant::new(num_rows / 2, num_cols / 2, Direction::Up));
+        sim
+    }
+
+    /// Get the simulation's cell states.
+    pub fn get_grid(&self) -> &StateGrid {
+        &self.grid
+    }
+
+    /// Get the simulation's ants.
+    pub fn get_ants(&self) -> &[Ant] {
+        &self.ants
+    }
+
+    /// Add an ant, which moves after the ants already added.
+    ///
+    /// # Panics
+    /// Panics if the ant stands outside of the grid.
+    pub fn add_ant(&mut self, ant: Ant) {
+        assert!(
+            ant.row < self.grid.get_num_rows() && ant.col < self.grid.get_num_cols(),
+            "ant at ({}, {}) is outside of the grid",
+            ant.row,
+            ant.col
+        );
+
+        self.ants.push(ant);
+    }
+
+    /// Get the simulation's [AntRule].
+    pub fn get_rule(&self) -> &AntRule {
+        &self.rule
+    }
+
+    /// Get the simulation's current generation.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Set the state of a specified cell, wrapped to the rule's states.
+    pub fn set_cell(&mut self, row: u32, col: u32, state: u8) {
+        self.grid.set(row, col, state % self.rule.get_num_states());
+    }
+
+    /// Advance the simulation by one generation, moving each ant in turn.
+    pub fn step(&mut self) {
+        self.generation += 1;
+
+        let num_rows = self.grid.get_num_rows();
+        let num_cols = self.grid.get_num_cols();
+        let num_states = self.rule.get_num_states();
+
+        for ant in self.ants.iter_mut() {
+            let state = self.grid.get(ant.row, ant.col);
+
+            ant.direction = ant.direction.turned(self.rule.get_turn(state));
+            self.grid.set(ant.row, ant.col, (state + 1) % num_states);
+
+            match ant.direction {
+                Direction::Up => ant.row = (ant.row + num_rows - 1) % num_rows,
+                Direction::Right => ant.col = (ant.col + 1) % num_cols,
+                Direction::Down => ant.row = (ant.row + 1) % num_rows,
+                Direction::Left => ant.col = (ant.col + num_cols - 1) % num_cols,
+            }
+        }
+    }
+
+    /// Advance the simulation by `n` generations.
+    pub fn step_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+}
+
+impl fmt::Display for LangtonsAnt {
+    /// Display the cells as a [StateGrid] does, with ants drawn as arrows in
+    /// the direction they face.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in 0..self.grid.get_num_rows() {
+            for col in 0..self.grid.get_num_cols() {
+                let ant = self
+                    .ants
+                    .iter()
+                    .rev()
+                    .find(|ant| (ant.row, ant.col) == (row, col));
+
+                let symbol = match (ant.map(Ant::get_direction), self.grid.get(row, col)) {
+                    (Some(Direction::Up), _) => '▲',
+                    (Some(Direction::Right), _) => '▶',
+                    (Some(Direction::Down), _) => '▼',
+                    (Some(Direction::Left), _) => '◀',
+                    (None, 0) => '◻',
+                    (None, 1) => '◼',
+                    (None, state) => std::char::from_digit(state as u32, 36).unwrap_or('+'),
+                };
+                write!(f, "{}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
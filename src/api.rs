@@ -0,0 +1,402 @@
+//! REST API creating, editing, and stepping simulations over HTTP.
+//!
+//! Simulations are kept in memory by id under `/api/sims`:
+//!
+//! | Method and path                  | Action                                      |
+//! |----------------------------------|---------------------------------------------|
+//! | `POST /api/sims`                 | create a simulation from a [CreateSim] body |
+//! | `GET /api/sims`                  | list the simulations                        |
+//! | `GET /api/sims/{id}`             | get a simulation's summary                  |
+//! | `DELETE /api/sims/{id}`          | delete a simulation                         |
+//! | `POST /api/sims/{id}/patterns`   | stamp a pattern from a [PlacePattern] body  |
+//! | `POST /api/sims/{id}/step`       | advance by `{"generations": n}`             |
+//! | `GET /api/sims/{id}/grid`        | get the live cells as JSON, or `?format=png` |
+//!
+//! Patterns are given as a built-in pattern name or as RLE text. Errors are
+//! returned as `{"error": "..."}` with a 4xx status.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use gameoflife_rs::render::png::{self, PngOptions};
+use gameoflife_rs::{Anchor, ConwaySim, Grid, GridError, Overlap, Pattern, Rule, Topology, World};
+use serde::{Deserialize, Serialize};
+
+/// Most generations advanced by a single step request.
+const MAX_STEP: u32 = 10_000;
+
+/// Size of a new simulation without a pattern or size.
+const DEFAULT_SIZE: u32 = 64;
+
+/// Error returned by an endpoint.
+enum ApiError {
+    /// No simulation has the id.
+    NotFound(u64),
+
+    /// The request is invalid.
+    BadRequest(String),
+
+    /// The request conflicts with the simulation's cells.
+    Conflict(String),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::NotFound(id) => (StatusCode::NOT_FOUND, format!("no simulation {}", id)),
+            ApiError::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            ApiError::Conflict(message) => (StatusCode::CONFLICT, message),
+        };
+
+        (status, Json(ErrorBody { error: message })).into_response()
+    }
+}
+
+impl From<GridError> for ApiError {
+    fn from(err: GridError) -> ApiError {
+        match err {
+            GridError::Conflict { .. } => ApiError::Conflict(err.to_string()),
+            GridError::OutOfBounds { .. } => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
+/// Body of an error response.
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+/// Body of a request creating a simulation.
+#[derive(Deserialize)]
+struct CreateSim {
+    /// Number of rows [default: the pattern height, or 64].
+    rows: Option<u32>,
+
+    /// Number of columns [default: the pattern width, or 64].
+    cols: Option<u32>,
+
+    /// Built-in pattern name or RLE text, centered on the grid.
+    pattern: Option<String>,
+
+    /// Density of a random soup filling the grid instead of a pattern.
+    density: Option<f64>,
+
+    /// Seed of the random soup.
+    #[serde(default)]
+    seed: u64,
+
+    /// Rulestring in B/S notation [default: B3/S23].
+    rule: Option<String>,
+
+    /// Topology name, e.g. `torus` [default: bounded].
+    topology: Option<String>,
+}
+
+/// Body of a request stamping a pattern.
+#[derive(Deserialize)]
+struct PlacePattern {
+    /// Built-in pattern name or RLE text.
+    pattern: String,
+
+    /// Row of the pattern's top left cell.
+    row: u32,
+
+    /// Column of the pattern's top left cell.
+    col: u32,
+
+    /// How the pattern combines with live cells: `or`, `overwrite`, or
+    /// `error` [default: or].
+    overlap: Option<String>,
+}
+
+/// Body of a request stepping a simulation.
+#[derive(Deserialize)]
+struct StepRequest {
+    /// Number of generations to advance.
+    #[serde(default = "one")]
+    generations: u32,
+}
+
+/// Default number of generations of a [StepRequest].
+fn one() -> u32 {
+    1
+}
+
+/// Query of a request for the grid.
+#[derive(Deserialize)]
+struct GridQuery {
+    /// `json` or `png` [default: json].
+    format: Option<String>,
+
+    /// Size of a cell in pixels of a PNG [default: 8].
+    cell_size: Option<u32>,
+}
+
+/// Summary of a simulation.
+#[derive(Serialize)]
+struct SimSummary {
+    id: u64,
+    rows: u32,
+    cols: u32,
+    rule: String,
+    topology: String,
+    generation: u32,
+    population: u64,
+    status: String,
+}
+
+impl SimSummary {
+    /// Summarize a simulation.
+    fn new(id: u64, sim: &ConwaySim) -> SimSummary {
+        let grid = sim.get_grid();
+
+        SimSummary {
+            id,
+            rows: grid.get_num_rows(),
+            cols: grid.get_num_cols(),
+            rule: sim.get_rule().to_string(),
+            topology: grid.get_topology().to_string(),
+            generation: sim.get_generation(),
+            population: sim.get_population(),
+            status: sim.get_status().to_string(),
+        }
+    }
+}
+
+/// Live cells of a simulation's grid.
+#[derive(Serialize)]
+struct GridBody {
+    generation: u32,
+    rows: u32,
+    cols: u32,
+    cells: Vec<(i64, i64)>,
+}
+
+/// The simulations, by id.
+#[derive(Default)]
+struct Sims {
+    /// The simulations.
+    sims: BTreeMap<u64, ConwaySim>,
+
+    /// Id of the next simulation created.
+    next_id: u64,
+}
+
+/// Simulations shared by every request.
+type SharedSims = Arc<Mutex<Sims>>;
+
+/// Parse a built-in pattern name or RLE text.
+fn parse_pattern(pattern: &str) -> Result<Pattern, ApiError> {
+    if let Some(pattern) = Pattern::by_name(pattern) {
+        return Ok(pattern);
+    }
+
+    Grid::from_rle(pattern)
+        .map(Pattern::new)
+        .map_err(|err| ApiError::BadRequest(format!("invalid pattern: {}", err)))
+}
+
+/// Reject a grid size which a [Grid] cannot hold.
+fn check_size(num_rows: u32, num_cols: u32) -> Result<(), ApiError> {
+    Grid::check_size(num_rows, num_cols).map_err(|err| ApiError::BadRequest(err.to_string()))
+}
+
+/// Build a simulation from a [CreateSim] body.
+fn create_sim(body: &CreateSim) -> Result<ConwaySim, ApiError> {
+    let grid = match (&body.pattern, body.density) {
+        (Some(_), Some(_)) => {
+            return Err(ApiError::BadRequest(
+                "give either a pattern or a density".to_string(),
+            ))
+        }
+        (Some(pattern), None) => {
+            let pattern = parse_pattern(pattern)?;
+            let num_rows = body.rows.unwrap_or_else(|| pattern.get_num_rows());
+            let num_cols = body.cols.unwrap_or_else(|| pattern.get_num_cols());
+            check_size(num_rows, num_cols)?;
+
+            pattern
+                .get_grid()
+                .resized(num_rows, num_cols, Anchor::Center)
+        }
+        (None, density) => {
+            let num_rows = body.rows.unwrap_or(DEFAULT_SIZE);
+            let num_cols = body.cols.unwrap_or(DEFAULT_SIZE);
+            check_size(num_rows, num_cols)?;
+
+            match density {
+                Some(density) if !(0.0..=1.0).contains(&density) => {
+                    return Err(ApiError::BadRequest(
+                        "density must be within 0.0 to 1.0".to_string(),
+                    ))
+                }
+                Some(density) => Grid::random(num_rows, num_cols, density, body.seed),
+                None => Grid::new(num_rows, num_cols),
+            }
+        }
+    };
+
+    let mut sim = ConwaySim::new_with_grid(grid);
+
+    if let Some(rule) = &body.rule {
+        let rule: Rule = rule
+            .parse()
+            .map_err(|err| ApiError::BadRequest(format!("{}", err)))?;
+        sim.set_rule(rule);
+    }
+
+    if let Some(topology) = &body.topology {
+        let topology: Topology = topology
+            .parse()
+            .map_err(|err| ApiError::BadRequest(format!("{}", err)))?;
+        sim.set_topology(topology);
+    }
+
+    Ok(sim)
+}
+
+/// Create a simulation.
+async fn create(
+    State(sims): State<SharedSims>,
+    Json(body): Json<CreateSim>,
+) -> Result<(StatusCode, Json<SimSummary>), ApiError> {
+    let sim = create_sim(&body)?;
+
+    let mut sims = sims.lock().expect("simulations lock poisoned");
+    sims.next_id += 1;
+    let id = sims.next_id;
+
+    let summary = SimSummary::new(id, &sim);
+    sims.sims.insert(id, sim);
+
+    Ok((StatusCode::CREATED, Json(summary)))
+}
+
+/// List the simulations.
+async fn list(State(sims): State<SharedSims>) -> Json<Vec<SimSummary>> {
+    let sims = sims.lock().expect("simulations lock poisoned");
+
+    Json(
+        sims.sims
+            .iter()
+            .map(|(&id, sim)| SimSummary::new(id, sim))
+            .collect(),
+    )
+}
+
+/// Get a simulation's summary.
+async fn summary(
+    State(sims): State<SharedSims>,
+    Path(id): Path<u64>,
+) -> Result<Json<SimSummary>, ApiError> {
+    let sims = sims.lock().expect("simulations lock poisoned");
+    let sim = sims.sims.get(&id).ok_or(ApiError::NotFound(id))?;
+
+    Ok(Json(SimSummary::new(id, sim)))
+}
+
+/// Delete a simulation.
+async fn delete(
+    State(sims): State<SharedSims>,
+    Path(id): Path<u64>,
+) -> Result<StatusCode, ApiError> {
+    let mut sims = sims.lock().expect("simulations lock poisoned");
+    sims.sims.remove(&id).ok_or(ApiError::NotFound(id))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Stamp a pattern into a simulation.
+async fn place(
+    State(sims): State<SharedSims>,
+    Path(id): Path<u64>,
+    Json(body): Json<PlacePattern>,
+) -> Result<Json<SimSummary>, ApiError> {
+    let pattern = parse_pattern(&body.pattern)?;
+    let overlap = match body.overlap.as_deref().unwrap_or("or") {
+        "or" => Overlap::Or,
+        "overwrite" => Overlap::Overwrite,
+        "error" => Overlap::Error,
+        other => {
+            return Err(ApiError::BadRequest(format!(
+                "invalid overlap: '{}'",
+                other
+            )))
+        }
+    };
+
+    let mut sims = sims.lock().expect("simulations lock poisoned");
+    let sim = sims.sims.get_mut(&id).ok_or(ApiError::NotFound(id))?;
+    sim.stamp(&pattern, body.row, body.col, overlap)?;
+
+    Ok(Json(SimSummary::new(id, sim)))
+}
+
+/// Advance a simulation.
+async fn step(
+    State(sims): State<SharedSims>,
+    Path(id): Path<u64>,
+    Json(body): Json<StepRequest>,
+) -> Result<Json<SimSummary>, ApiError> {
+    if body.generations > MAX_STEP {
+        return Err(ApiError::BadRequest(format!(
+            "at most {} generations can be stepped at once",
+            MAX_STEP
+        )));
+    }
+
+    let mut sims = sims.lock().expect("simulations lock poisoned");
+    let sim = sims.sims.get_mut(&id).ok_or(ApiError::NotFound(id))?;
+    sim.step_n(body.generations);
+
+    Ok(Json(SimSummary::new(id, sim)))
+}
+
+/// Get a simulation's grid as JSON or as a PNG image.
+async fn grid(
+    State(sims): State<SharedSims>,
+    Path(id): Path<u64>,
+    Query(query): Query<GridQuery>,
+) -> Result<Response, ApiError> {
+    let sims = sims.lock().expect("simulations lock poisoned");
+    let sim = sims.sims.get(&id).ok_or(ApiError::NotFound(id))?;
+    let grid = sim.get_grid();
+
+    match query.format.as_deref().unwrap_or("json") {
+        "json" => Ok(Json(GridBody {
+            generation: sim.get_generation(),
+            rows: grid.get_num_rows(),
+            cols: grid.get_num_cols(),
+            cells: grid.live_cells(),
+        })
+        .into_response()),
+        "png" => {
+            let options = PngOptions {
+                cell_size: query.cell_size.unwrap_or(PngOptions::default().cell_size),
+                ..PngOptions::default()
+            };
+            let image =
+                png::encode(grid, &options).map_err(|err| ApiError::BadRequest(err.to_string()))?;
+
+            Ok(([(header::CONTENT_TYPE, "image/png")], image).into_response())
+        }
+        other => Err(ApiError::BadRequest(format!("invalid format: '{}'", other))),
+    }
+}
+
+/// Build the router of the REST API, with its own empty set of simulations.
+pub fn router() -> Router {
+    Router::new()
+        .route("/api/sims", post(create).get(list))
+        .route("/api/sims/{id}", get(summary).delete(delete))
+        .route("/api/sims/{id}/patterns", post(place))
+        .route("/api/sims/{id}/step", post(step))
+        .route("/api/sims/{id}/grid", get(grid))
+        .with_state(SharedSims::default())
+}
@@ -0,0 +1,291 @@
+//! Custom cellular automata on the crate's [Grid].
+//!
+//! A [Grid] holds cells of any [CellState], not only the [Cell]s of Conway's
+//! Game of Life. Implementing [StateRule] for a transition function, or
+//! passing a closure, lets a [Sim] step such a grid, finding the neighbors
+//! beyond its edges by its [Topology](crate::topology::Topology) and marking
+//! the chunks which changed as dirty for incremental rendering. The states
+//! are drawn by projecting them onto a two-state [Grid] with [Grid::to_grid],
+//! or onto a palette with [Grid::to_state_grid].
+//!
+//! ```
+//! use gameoflife_rs::automaton::{CellState, Sim, StateRule};
+//! use gameoflife_rs::Grid;
+//!
+//! #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+//! enum Brain {
+//!     #[default]
+//!     Off,
+//!     On,
+//!     Dying,
+//! }
+//!
+//! impl CellState for Brain {}
+//!
+//! /// Brian's Brain: cells turn on with exactly two neighbors on, and die
+//! /// over two generations.
+//! struct BriansBrain;
+//!
+//! impl StateRule<Brain> for BriansBrain {
+//!     fn next(&self, state: Brain, neighbors: &[Brain; 8]) -> Brain {
+//!         match state {
+//!             Brain::On => Brain::Dying,
+//!             Brain::Dying => Brain::Off,
+//!             Brain::Off if neighbors.iter().filter(|&&n| n == Brain::On).count() == 2 => {
+//!                 Brain::On
+//!             }
+//!             Brain::Off => Brain::Off,
+//!         }
+//!     }
+//! }
+//!
+//! let mut grid = Grid::filled(8, 8, Brain::Off);
+//! grid.set(3, 3, Brain::On);
+//! grid.set(3, 4, Brain::On);
+//!
+//! let mut sim = Sim::new(grid, BriansBrain);
+//! sim.step();
+//!
+//! assert_eq!(sim.get_grid().get(3, 3), Brain::Dying);
+//! assert_eq!(sim.get_grid().get(2, 3), Brain::On);
+//! assert_eq!(sim.get_population(), 6);
+//! ```
+
+use crate::game::{Cell, Grid};
+use crate::rule::Rule;
+use crate::states::StateGrid;
+
+/// (row, col) offsets of the eight neighbors of a cell, in the order they
+/// are passed to a [StateRule].
+const NEIGHBOR_OFFSETS: [(i64, i64); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A state of the cells of a [Grid].
+///
+/// The [Default] state fills the grid beyond [bounded](crate::topology::Edge)
+/// edges, and is the empty state of the automaton.
+pub trait CellState: Copy + Default + Eq {
+    /// Determine if a cell in this state is alive, counting towards the
+    /// population and drawn by [Grid::to_grid]. Any state other than the
+    /// default is alive unless overridden.
+    fn is_alive(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+impl CellState for Cell {}
+
+impl CellState for u8 {}
+
+/// The transition function of an automaton over cells of a [CellState].
+///
+/// It is implemented by the Life-like [Rule]s, and by closures of the same
+/// signature as [StateRule::next].
+pub trait StateRule<S: CellState> {
+    /// Get the next state of a cell.
+    ///
+    /// # Arguments
+    /// * `state` - The current state of the cell.
+    /// * `neighbors` - The states of its eight neighbors, row by row from the
+    ///   top left, skipping the cell itself.
+    fn next(&self, state: S, neighbors: &[S; 8]) -> S;
+}
+
+impl StateRule<Cell> for Rule {
+    fn next(&self, state: Cell, neighbors: &[Cell; 8]) -> Cell {
+        let neighbor_count = neighbors.iter().filter(|cell| cell.is_alive()).count() as u8;
+        let alive = if state.is_alive() {
+            self.is_survival(neighbor_count)
+        } else {
+            self.is_birth(neighbor_count)
+        };
+
+        if alive {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        }
+    }
+}
+
+impl<S: CellState, F: Fn(S, &[S; 8]) -> S> StateRule<S> for F {
+    fn next(&self, state: S, neighbors: &[S; 8]) -> S {
+        self(state, neighbors)
+    }
+}
+
+impl<S: CellState> Grid<S> {
+    /// Get the states of the eight neighbors of a cell, finding those beyond
+    /// the edges of the [Grid] by its topology.
+    ///
+    /// # Arguments
+    /// * `row` - Row of the cell.
+    /// * `col` - Column of the cell.
+    pub fn get_neighbor_states(&self, row: u32, col: u32) -> [S; 8] {
+        let (num_rows, num_cols) = (self.get_num_rows(), self.get_num_cols());
+        let topology = self.get_topology();
+        let mut states = [S::default(); 8];
+
+        for (state, &(row_offset, col_offset)) in states.iter_mut().zip(NEIGHBOR_OFFSETS.iter()) {
+            let neighbor = topology.resolve(
+                row as i64 + row_offset,
+                col as i64 + col_offset,
+                num_rows,
+                num_cols,
+            );
+
+            if let Some((neighbor_row, neighbor_col)) = neighbor {
+                *state = self.get(neighbor_row, neighbor_col);
+            }
+        }
+
+        states
+    }
+
+    /// Compute the next states of all cells into `next` by a [StateRule],
+    /// marking the chunks whose cells changed as dirty.
+    ///
+    /// # Arguments
+    /// * `rule` - The transition function of the automaton.
+    /// * `next` - The [Grid] to hold the next generation, overwritten.
+    pub fn step_states_into<R: StateRule<S>>(&self, rule: &R, next: &mut Grid<S>) {
+        next.clone_from(self);
+        next.clear_dirty();
+
+        for row in 0..self.get_num_rows() {
+            for col in 0..self.get_num_cols() {
+                let state = self.get(row, col);
+                let next_state = rule.next(state, &self.get_neighbor_states(row, col));
+
+                if next_state != state {
+                    next.set(row, col, next_state);
+                }
+            }
+        }
+    }
+
+    /// Get the number of cells of the [Grid] in a live state.
+    pub fn count_alive(&self) -> u64 {
+        self.get_cells()
+            .iter()
+            .filter(|state| state.is_alive())
+            .count() as u64
+    }
+
+    /// Project the states onto a two-state [Grid] of the same size and
+    /// topology, with live states as [Cell::Alive], so that any renderer can
+    /// draw it.
+    pub fn to_grid(&self) -> Grid {
+        let mut grid = Grid::new(self.get_num_rows(), self.get_num_cols());
+        grid.set_topology(self.get_topology());
+
+        for row in 0..self.get_num_rows() {
+            for col in 0..self.get_num_cols() {
+                if self.get(row, col).is_alive() {
+                    grid.set(row, col, Cell::Alive);
+                }
+            }
+        }
+
+        grid
+    }
+
+    /// Project the states onto a [StateGrid] of palette indices, to draw
+    /// them in colors, e.g. with
+    /// [AnsiRenderer::render_states](crate::render::ansi::AnsiRenderer::render_states).
+    ///
+    /// # Arguments
+    /// * `index` - The palette index of each state.
+    pub fn to_state_grid<F: Fn(S) -> u8>(&self, index: F) -> StateGrid {
+        let mut grid = StateGrid::new(self.get_num_rows(), self.get_num_cols());
+
+        for row in 0..self.get_num_rows() {
+            for col in 0..self.get_num_cols() {
+                grid.set(row, col, index(self.get(row, col)));
+            }
+        }
+
+        grid
+    }
+}
+
+/// Simulation of a custom automaton, stepping a [Grid] of a [CellState] by a
+/// [StateRule].
+#[derive(Clone, Debug)]
+pub struct Sim<S, R> {
+    /// The current generation of cells.
+    grid: Grid<S>,
+
+    /// Buffer the next generation is computed into before being swapped with
+    /// the current one.
+    next: Grid<S>,
+
+    /// The transition function applied to each cell every generation.
+    rule: R,
+
+    /// The simulation's current generation.
+    generation: u32,
+}
+
+impl<S: CellState, R: StateRule<S>> Sim<S, R> {
+    /// Create a new simulation of an automaton.
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cells of the simulation.
+    /// * `rule` - The transition function of the automaton.
+    pub fn new(grid: Grid<S>, rule: R) -> Sim<S, R> {
+        Sim {
+            next: grid.clone(),
+            grid,
+            rule,
+            generation: 0,
+        }
+    }
+
+    /// Get the simulation's [Grid].
+    pub fn get_grid(&self) -> &Grid<S> {
+        &self.grid
+    }
+
+    /// Get the simulation's [Grid] for modification.
+    pub fn get_grid_mut(&mut self) -> &mut Grid<S> {
+        &mut self.grid
+    }
+
+    /// Get the simulation's [StateRule].
+    pub fn get_rule(&self) -> &R {
+        &self.rule
+    }
+
+    /// Get the simulation's current generation.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Get the number of cells in a live state.
+    pub fn get_population(&self) -> u64 {
+        self.grid.count_alive()
+    }
+
+    /// Advance the simulation by one generation.
+    pub fn step(&mut self) {
+        self.grid.step_states_into(&self.rule, &mut self.next);
+        core::mem::swap(&mut self.grid, &mut self.next);
+        self.generation += 1;
+    }
+
+    /// Advance the simulation by `n` generations.
+    pub fn step_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+}
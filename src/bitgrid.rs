@@ -0,0 +1,282 @@
+//! A bounded [World] packing 64 cells into each machine word.
+//!
+//! Each row of the [BitGrid] is stored as `u64` words with the cell of column
+//! `col` in bit `col % 64` of word `col / 64`. A generation is computed for
+//! 64 cells at a time by evaluating the [Rule] on whole words with bitwise
+//! operations only.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use crate::bitops::{self, CountMasks, BITS};
+use crate::game::{Cell, ConwaySim, Grid};
+use crate::rule::Rule;
+use crate::world::{StateHasher, World};
+
+/// A bounded [World] of the same shape as a [Grid], storing each cell as a
+/// single bit to compute generations many times faster on large grids.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BitGrid {
+    /// Number of rows (height) of the [BitGrid].
+    num_rows: u32,
+
+    /// Number of columns (width) of the [BitGrid].
+    num_cols: u32,
+
+    /// Number of words storing each row.
+    words_per_row: usize,
+
+    /// The rows of cells, one bit per cell. Bits past the last column are
+    /// always clear.
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Create a new [BitGrid] of dead cells.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the [BitGrid].
+    /// * `num_cols` - The number of columns (width) of the [BitGrid].
+    pub fn new(num_rows: u32, num_cols: u32) -> BitGrid {
+        let words_per_row = (num_cols as usize).div_ceil(BITS);
+
+        BitGrid {
+            num_rows,
+            num_cols,
+            words_per_row,
+            words: vec![0; num_rows as usize * words_per_row],
+        }
+    }
+
+    /// Create a new [BitGrid] with the cells of a [Grid].
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cells of the [BitGrid].
+    pub fn from_grid(grid: &Grid) -> BitGrid {
+        let mut bit_grid = BitGrid::new(grid.get_num_rows(), grid.get_num_cols());
+
+        for row in 0..grid.get_num_rows() {
+            for col in 0..grid.get_num_cols() {
+                if grid.get(row, col).is_alive() {
+                    bit_grid.set(row, col, Cell::Alive);
+                }
+            }
+        }
+
+        bit_grid
+    }
+
+    /// Copy the cells into a new [Grid].
+    pub fn to_grid(&self) -> Grid {
+        let mut grid = Grid::new(self.num_rows, self.num_cols);
+
+        for row in 0..self.num_rows {
+            for col in 0..self.num_cols {
+                grid.set(row, col, self.get(row, col));
+            }
+        }
+
+        grid
+    }
+
+    /// Get the number of rows (height) of the [BitGrid].
+    pub fn get_num_rows(&self) -> u32 {
+        self.num_rows
+    }
+
+    /// Get the number of columns (width) of the [BitGrid].
+    pub fn get_num_cols(&self) -> u32 {
+        self.num_cols
+    }
+
+    /// Convert a (row, col) position to a word index and bit mask.
+    fn cell_to_bit(&self, row: u32, col: u32) -> (usize, u64) {
+        let col = col as usize;
+
+        (
+            row as usize * self.words_per_row + col / BITS,
+            1 << (col % BITS),
+        )
+    }
+
+    /// Get a specified [Cell] of the [BitGrid].
+    pub fn get(&self, row: u32, col: u32) -> Cell {
+        let (index, mask) = self.cell_to_bit(row, col);
+
+        if self.words[index] & mask != 0 {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        }
+    }
+
+    /// Set a specified [Cell] of the [BitGrid].
+    pub fn set(&mut self, row: u32, col: u32, state: Cell) {
+        let (index, mask) = self.cell_to_bit(row, col);
+
+        match state {
+            Cell::Alive => self.words[index] |= mask,
+            Cell::Dead => self.words[index] &= !mask,
+        }
+    }
+
+    /// Set the cells of the [BitGrid].
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (row, col) to set as [Cell::Alive].
+    pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
+        for &(row, col) in cells.iter() {
+            self.set(row, col, Cell::Alive);
+        }
+    }
+
+    /// Get the mask of the bits of the last word of a row which hold cells.
+    fn last_word_mask(&self) -> u64 {
+        match self.num_cols as usize % BITS {
+            0 => !0,
+            used => (1 << used) - 1,
+        }
+    }
+
+    /// Get the words of a row, or `empty` outside of the [BitGrid].
+    fn row_words<'a>(&'a self, row: i64, empty: &'a [u64]) -> &'a [u64] {
+        if row < 0 || row >= self.num_rows as i64 {
+            return empty;
+        }
+
+        let start = row as usize * self.words_per_row;
+        &self.words[start..start + self.words_per_row]
+    }
+}
+
+/// The cells of a word of a row together with their west and east
+/// neighbors, shifted into place.
+#[inline]
+fn word_neighbors(words: &[u64], index: usize) -> (u64, u64, u64) {
+    let previous = if index > 0 { words[index - 1] } else { 0 };
+    let following = words.get(index + 1).copied().unwrap_or(0);
+
+    bitops::neighbors(previous, words[index], following)
+}
+
+impl World for BitGrid {
+    fn is_alive(&self, row: i64, col: i64) -> bool {
+        row >= 0
+            && col >= 0
+            && row < self.num_rows as i64
+            && col < self.num_cols as i64
+            && self.get(row as u32, col as u32).is_alive()
+    }
+
+    fn get_population(&self) -> u64 {
+        self.words.iter().map(|word| word.count_ones() as u64).sum()
+    }
+
+    fn live_cells(&self) -> Vec<(i64, i64)> {
+        let mut cells: Vec<(i64, i64)> = Vec::new();
+
+        for row in 0..self.num_rows {
+            for col in 0..self.num_cols {
+                if self.get(row, col).is_alive() {
+                    cells.push((row as i64, col as i64));
+                }
+            }
+        }
+
+        cells
+    }
+
+    fn get_area(&self) -> u64 {
+        self.num_rows as u64 * self.num_cols as u64
+    }
+
+    fn count_changes(&self, previous: &BitGrid) -> (u64, u64) {
+        if previous.num_rows != self.num_rows || previous.num_cols != self.num_cols {
+            return (self.get_population(), previous.get_population());
+        }
+
+        self.words.iter().zip(previous.words.iter()).fold(
+            (0, 0),
+            |(births, deaths), (&after, &before)| {
+                (
+                    births + (after & !before).count_ones() as u64,
+                    deaths + (before & !after).count_ones() as u64,
+                )
+            },
+        )
+    }
+
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::default();
+        self.hash(&mut hasher);
+
+        hasher.finish()
+    }
+
+    fn step_into(&self, rule: &Rule, next: &mut BitGrid) {
+        if next.num_rows != self.num_rows || next.num_cols != self.num_cols {
+            *next = BitGrid::new(self.num_rows, self.num_cols);
+        }
+
+        // the neighbor counts leading to a live cell, for dead and live cells
+        let births = CountMasks::new(|count| rule.is_birth(count));
+        let survivals = CountMasks::new(|count| rule.is_survival(count));
+        let last_word_mask = self.last_word_mask();
+        let empty: Vec<u64> = vec![0; self.words_per_row];
+
+        for row in 0..self.num_rows as i64 {
+            let above = self.row_words(row - 1, &empty);
+            let current = self.row_words(row, &empty);
+            let below = self.row_words(row + 1, &empty);
+
+            for index in 0..self.words_per_row {
+                let mut word = bitops::next_cells(
+                    word_neighbors(above, index),
+                    word_neighbors(current, index),
+                    word_neighbors(below, index),
+                    &births,
+                    &survivals,
+                );
+
+                // keep the bits past the last column clear
+                if index + 1 == self.words_per_row {
+                    word &= last_word_mask;
+                }
+
+                next.words[row as usize * self.words_per_row + index] = word;
+            }
+        }
+    }
+}
+
+impl fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_grid())
+    }
+}
+
+impl ConwaySim<BitGrid> {
+    /// Create a new simulation of a [BitGrid] with the cells of a [Grid].
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cells of the simulation.
+    pub fn new_bit_packed(grid: &Grid) -> ConwaySim<BitGrid> {
+        ConwaySim::new_with_world(BitGrid::from_grid(grid))
+    }
+
+    /// Determine if the specified [Cell] is [Cell::Alive].
+    pub fn is_cell_alive(&self, row: u32, col: u32) -> bool {
+        self.get_world().get(row, col).is_alive()
+    }
+
+    /// Set the cells of the simulation's [BitGrid].
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (row, col) to set as [Cell::Alive].
+    pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
+        self.get_world_mut().set_cells(cells);
+        self.record_edit();
+    }
+}
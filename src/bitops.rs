@@ -0,0 +1,135 @@
+//! Bitwise evaluation of a [Rule](crate::Rule) on 64 cells at a time, shared
+//! by the bit-packed worlds.
+//!
+//! The cells of a row are packed into `u64` words with the cell of column
+//! `col` in bit `col % 64` of word `col / 64`. The eight neighbors of every
+//! cell in a word are gathered with shifts, summed by a bit-sliced adder into
+//! four bit planes, and the rule is evaluated on the planes with bitwise
+//! operations only.
+
+/// Number of cells packed into each word.
+pub(crate) const BITS: usize = 64;
+
+/// Add three bit planes, returning the (sum, carry) planes.
+#[inline]
+fn full_add(a: u64, b: u64, c: u64) -> (u64, u64) {
+    let partial = a ^ b;
+
+    (partial ^ c, (a & b) | (partial & c))
+}
+
+/// Add two bit planes, returning the (sum, carry) planes.
+#[inline]
+fn half_add(a: u64, b: u64) -> (u64, u64) {
+    (a ^ b, a & b)
+}
+
+/// Select the bits of `a` where `select` is set and of `b` elsewhere.
+#[inline]
+fn mux(select: u64, a: u64, b: u64) -> u64 {
+    b ^ ((a ^ b) & select)
+}
+
+/// The neighbor counts of 64 cells as four bit planes, from the least
+/// significant bit of the count to the most.
+struct CountPlanes {
+    ones: u64,
+    twos: u64,
+    fours: u64,
+    eights: u64,
+}
+
+/// For each neighbor count, a mask of all ones if the count leads to a live
+/// cell and of all zeros otherwise.
+pub(crate) struct CountMasks([u64; 9]);
+
+impl CountMasks {
+    /// Create the [CountMasks] of the neighbor counts accepted by a predicate.
+    pub(crate) fn new(accepts: impl Fn(u8) -> bool) -> CountMasks {
+        let mut masks = [0; 9];
+
+        for (count, mask) in masks.iter_mut().enumerate() {
+            if accepts(count as u8) {
+                *mask = !0;
+            }
+        }
+
+        CountMasks(masks)
+    }
+
+    /// Select the cells whose neighbor count is accepted, looking the count
+    /// up with a tree of multiplexers over its bit planes.
+    #[inline]
+    fn select(&self, counts: &CountPlanes) -> u64 {
+        let masks = &self.0;
+
+        let zero_one = mux(counts.ones, masks[1], masks[0]);
+        let two_three = mux(counts.ones, masks[3], masks[2]);
+        let four_five = mux(counts.ones, masks[5], masks[4]);
+        let six_seven = mux(counts.ones, masks[7], masks[6]);
+
+        let zero_three = mux(counts.twos, two_three, zero_one);
+        let four_seven = mux(counts.twos, six_seven, four_five);
+
+        let zero_seven = mux(counts.fours, four_seven, zero_three);
+
+        // a count of eight has its lower planes clear
+        mux(counts.eights, masks[8], zero_seven)
+    }
+}
+
+/// The cells of a word of a row together with their west and east
+/// neighbors, shifted into place.
+///
+/// # Arguments
+/// * `previous` - The word holding the columns before `center`, if any.
+/// * `center` - The word of cells.
+/// * `following` - The word holding the columns after `center`, if any.
+#[inline]
+pub(crate) fn neighbors(previous: u64, center: u64, following: u64) -> (u64, u64, u64) {
+    // the west neighbor of column c is column c - 1, found one bit lower
+    let west = (center << 1) | (previous >> (BITS - 1));
+    let east = (center >> 1) | (following << (BITS - 1));
+
+    (west, center, east)
+}
+
+/// Compute the next states of the cells of a word.
+///
+/// # Arguments
+/// * `above` - The (west, center, east) [neighbors] of the row above.
+/// * `current` - The (west, center, east) [neighbors] of the row of cells.
+/// * `below` - The (west, center, east) [neighbors] of the row below.
+/// * `births` - The neighbor counts leading dead cells to be born.
+/// * `survivals` - The neighbor counts leading live cells to survive.
+#[inline]
+pub(crate) fn next_cells(
+    above: (u64, u64, u64),
+    current: (u64, u64, u64),
+    below: (u64, u64, u64),
+    births: &CountMasks,
+    survivals: &CountMasks,
+) -> u64 {
+    let (above_west, above_center, above_east) = above;
+    let (west, alive, east) = current;
+    let (below_west, below_center, below_east) = below;
+
+    // sum the eight neighbors of each cell into four bit planes
+    let (sum_a, carry_a) = full_add(above_west, above_center, above_east);
+    let (sum_b, carry_b) = full_add(west, east, below_west);
+    let (sum_c, carry_c) = half_add(below_center, below_east);
+
+    let (ones, carry_d) = full_add(sum_a, sum_b, sum_c);
+    let (partial_twos, carry_e) = full_add(carry_a, carry_b, carry_c);
+    let (twos, carry_f) = half_add(partial_twos, carry_d);
+    let (fours, eights) = half_add(carry_e, carry_f);
+
+    let counts = CountPlanes {
+        ones,
+        twos,
+        fours,
+        eights,
+    };
+
+    mux(alive, survivals.select(&counts), births.select(&counts))
+}
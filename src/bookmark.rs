@@ -0,0 +1,70 @@
+//! Labeled snapshots of interesting moments in a simulation's run.
+//!
+//! [ConwaySim::bookmark] saves the current generation under a label, and
+//! [ConwaySim::goto_bookmark] returns to it later, however far the run has
+//! moved on and whether or not its [History](crate::History) still reaches
+//! it.
+//!
+//! ```
+//! use gameoflife_rs::ConwaySim;
+//!
+//! let mut sim = ConwaySim::new(12, 12);
+//! sim.set_cells(&[(1, 2), (2, 3), (3, 1), (3, 2), (3, 3)]);
+//!
+//! sim.step_n(4);
+//! sim.bookmark("one period in");
+//! sim.step_n(20);
+//!
+//! assert!(sim.goto_bookmark("one period in"));
+//! assert_eq!(sim.get_generation(), 4);
+//! ```
+//!
+//! [ConwaySim::bookmark]: crate::ConwaySim::bookmark
+//! [ConwaySim::goto_bookmark]: crate::ConwaySim::goto_bookmark
+
+use alloc::string::String;
+
+use crate::history::Snapshot;
+use crate::rule::Rule;
+
+/// A labeled snapshot of a simulation.
+#[derive(Clone, Debug)]
+pub struct Bookmark<W> {
+    /// The label the bookmark is found by.
+    label: String,
+
+    /// The generation, world, and rule bookmarked.
+    snapshot: Snapshot<W>,
+}
+
+impl<W> Bookmark<W> {
+    /// Create a new [Bookmark] of a snapshot.
+    pub(crate) fn new(label: String, snapshot: Snapshot<W>) -> Bookmark<W> {
+        Bookmark { label, snapshot }
+    }
+
+    /// Get the label of the bookmark.
+    pub fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the bookmarked generation.
+    pub fn get_generation(&self) -> u32 {
+        self.snapshot.generation
+    }
+
+    /// Get the world at the bookmarked generation.
+    pub fn get_world(&self) -> &W {
+        &self.snapshot.world
+    }
+
+    /// Get the rule in effect at the bookmarked generation.
+    pub fn get_rule(&self) -> Rule {
+        self.snapshot.rule
+    }
+
+    /// Get the bookmarked snapshot.
+    pub(crate) fn get_snapshot(&self) -> &Snapshot<W> {
+        &self.snapshot
+    }
+}
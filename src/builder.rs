@@ -0,0 +1,261 @@
+//! A [SimBuilder] composing the settings of a new simulation.
+//!
+//! The size, [Rule], [Topology], random soup, seeded patterns, and recorders
+//! of a simulation can all be set on a new [ConwaySim] one call at a time,
+//! but a builder gathers them into a single expression and reports an invalid
+//! rulestring, size, or pattern position as a [GolError] rather than
+//! panicking:
+//!
+//! ```
+//! use gameoflife_rs::patterns::glider;
+//! use gameoflife_rs::topology::Topology;
+//! use gameoflife_rs::ConwaySim;
+//!
+//! let mut sim = ConwaySim::builder()
+//!     .size(100, 100)
+//!     .rule("B3/S23")
+//!     .topology(Topology::Torus)
+//!     .seed_pattern(glider(), 10, 10)
+//!     .build()?;
+//!
+//! sim.step_n(4);
+//! assert_eq!(sim.get_population(), 5);
+//! # Ok::<(), gameoflife_rs::GolError>(())
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::error::GolError;
+use crate::game::{ConwaySim, Grid};
+use crate::pattern::{Overlap, Pattern};
+use crate::rule::Rule;
+use crate::topology::Topology;
+use crate::world::World;
+
+/// Builder of a [ConwaySim], created by [ConwaySim::builder].
+#[derive(Clone, Debug)]
+pub struct SimBuilder {
+    /// Number of (rows, cols) of the [Grid].
+    size: (u32, u32),
+
+    /// Rulestring of the simulation's [Rule], Conway's Game of Life if
+    /// [None].
+    rule: Option<String>,
+
+    /// [Topology] of the [Grid].
+    topology: Topology,
+
+    /// Probability of each cell of the initial soup being alive, or [None]
+    /// to start from dead cells.
+    density: Option<f64>,
+
+    /// Seed of the random number generator of the soup.
+    seed: u64,
+
+    /// Patterns to paste with their top left cells at (row, col).
+    patterns: Vec<(Pattern, u32, u32)>,
+
+    /// Interval and capacity of the [History](crate::History) to record, if
+    /// any.
+    history: Option<(u32, usize)>,
+
+    /// Capacity of the [UndoStack](crate::undo::UndoStack) to keep, if any.
+    undo: Option<usize>,
+
+    /// Whether to record the [Stats](crate::stats::Stats) of each
+    /// generation.
+    stats: bool,
+}
+
+impl Default for SimBuilder {
+    fn default() -> SimBuilder {
+        SimBuilder::new()
+    }
+}
+
+impl SimBuilder {
+    /// Create a new [SimBuilder] of a dead, bounded simulation of Conway's
+    /// Game of Life. Its size must be set before building it.
+    pub fn new() -> SimBuilder {
+        SimBuilder {
+            size: (0, 0),
+            rule: None,
+            topology: Topology::Bounded,
+            density: None,
+            seed: 0,
+            patterns: Vec::new(),
+            history: None,
+            undo: None,
+            stats: false,
+        }
+    }
+
+    /// Set the size of the [Grid].
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the [Grid].
+    /// * `num_cols` - The number of columns (width) of the [Grid].
+    pub fn size(mut self, num_rows: u32, num_cols: u32) -> SimBuilder {
+        self.size = (num_rows, num_cols);
+        self
+    }
+
+    /// Set the [Rule] of the simulation from a rulestring, parsed when
+    /// building it.
+    ///
+    /// # Arguments
+    /// * `rulestring` - The rulestring, e.g. `B36/S23`.
+    pub fn rule(mut self, rulestring: &str) -> SimBuilder {
+        self.rule = Some(rulestring.to_string());
+        self
+    }
+
+    /// Set the [Topology] deciding the neighbors of cells on the edges of the
+    /// [Grid].
+    ///
+    /// # Arguments
+    /// * `topology` - The [Topology] of the [Grid].
+    pub fn topology(mut self, topology: Topology) -> SimBuilder {
+        self.topology = topology;
+        self
+    }
+
+    /// Fill the [Grid] with a random soup of live cells, as with
+    /// [Grid::random], before the patterns are pasted.
+    ///
+    /// # Arguments
+    /// * `density` - Probability of each cell being alive, from 0.0 to 1.0.
+    ///
+    /// # Panics
+    /// Building panics if `density` is not within 0.0 to 1.0.
+    pub fn random(mut self, density: f64) -> SimBuilder {
+        self.density = Some(density);
+        self
+    }
+
+    /// Set the seed of the random number generator of the soup, 0 unless
+    /// set.
+    ///
+    /// # Arguments
+    /// * `seed` - Seed of the random number generator.
+    pub fn seed(mut self, seed: u64) -> SimBuilder {
+        self.seed = seed;
+        self
+    }
+
+    /// Paste a pattern into the [Grid] with its top left cell at (row, col),
+    /// merging its live cells with those already there. Patterns are pasted
+    /// in the order they are added.
+    ///
+    /// # Arguments
+    /// * `pattern` - The pattern, e.g. from [crate::patterns].
+    /// * `row` - Row of the pattern's top left cell.
+    /// * `col` - Column of the pattern's top left cell.
+    pub fn seed_pattern<P: Into<Pattern>>(mut self, pattern: P, row: u32, col: u32) -> SimBuilder {
+        self.patterns.push((pattern.into(), row, col));
+        self
+    }
+
+    /// Record the [History](crate::History) of the simulation, as with
+    /// [ConwaySim::enable_history].
+    ///
+    /// # Arguments
+    /// * `interval` - Number of generations between snapshots.
+    /// * `capacity` - Maximum number of snapshots kept.
+    pub fn history(mut self, interval: u32, capacity: usize) -> SimBuilder {
+        self.history = Some((interval, capacity));
+        self
+    }
+
+    /// Keep an [UndoStack](crate::undo::UndoStack) of edits, as with
+    /// [ConwaySim::enable_undo].
+    ///
+    /// # Arguments
+    /// * `capacity` - Maximum number of edits which can be undone.
+    pub fn undo(mut self, capacity: usize) -> SimBuilder {
+        self.undo = Some(capacity);
+        self
+    }
+
+    /// Record the [Stats](crate::stats::Stats) of each generation, as with
+    /// [ConwaySim::enable_stats].
+    pub fn stats(mut self) -> SimBuilder {
+        self.stats = true;
+        self
+    }
+
+    /// Build the simulation of a [Grid].
+    ///
+    /// Returns [GolError::InvalidDimensions] if the size was not set or is
+    /// too large, a [GolError::Parse] if the rulestring is invalid, or a
+    /// [GolError::OutOfBounds] if a pattern does not fit within the [Grid].
+    pub fn build(self) -> Result<ConwaySim, GolError> {
+        let (grid, rule) = self.seed_grid()?;
+
+        Ok(self.configure(ConwaySim::new_with_world(grid), rule))
+    }
+
+    /// Build the simulation of another [World] backend, converted from the
+    /// [Grid] the builder seeds, e.g. `build_with(ChunkedWorld::from_grid)`.
+    ///
+    /// Backends without edges, or without a [Topology] of their own, ignore
+    /// the topology of the [Grid].
+    ///
+    /// # Arguments
+    /// * `backend` - Conversion of the seeded [Grid] into the [World].
+    pub fn build_with<W, F>(self, backend: F) -> Result<ConwaySim<W>, GolError>
+    where
+        W: World,
+        F: FnOnce(&Grid) -> W,
+    {
+        let (grid, rule) = self.seed_grid()?;
+
+        Ok(self.configure(ConwaySim::new_with_world(backend(&grid)), rule))
+    }
+
+    /// Parse the [Rule] and create the [Grid] with its soup and patterns.
+    fn seed_grid(&self) -> Result<(Grid, Rule), GolError> {
+        let (num_rows, num_cols) = self.size;
+        let rule: Rule = match &self.rule {
+            Some(rulestring) => rulestring.parse()?,
+            None => Rule::default(),
+        };
+
+        let mut grid = Grid::try_new(num_rows, num_cols)?;
+        if let Some(density) = self.density {
+            grid = Grid::random(num_rows, num_cols, density, self.seed);
+        }
+        grid.set_topology(self.topology);
+
+        for (pattern, row, col) in &self.patterns {
+            grid.stamp(pattern, *row, *col, Overlap::Or)?;
+        }
+
+        Ok((grid, rule))
+    }
+
+    /// Apply the [Rule] and recorders to a new simulation.
+    fn configure<W: World>(self, mut sim: ConwaySim<W>, rule: Rule) -> ConwaySim<W> {
+        sim.set_rule(rule);
+
+        if let Some((interval, capacity)) = self.history {
+            sim.enable_history(interval, capacity);
+        }
+        if let Some(capacity) = self.undo {
+            sim.enable_undo(capacity);
+        }
+        if self.stats {
+            sim.enable_stats();
+        }
+
+        sim
+    }
+}
+
+impl ConwaySim {
+    /// Create a new [SimBuilder] composing the settings of a simulation.
+    pub fn builder() -> SimBuilder {
+        SimBuilder::new()
+    }
+}
@@ -0,0 +1,285 @@
+//! Compact binary checkpoints of simulations.
+//!
+//! A checkpoint stores everything needed to resume a simulation with
+//! identical results: the generation, the [Rule], the [Grid], and its
+//! [Topology]. All integers are little endian.
+//!
+//! | Bytes | Content                                                  |
+//! |-------|----------------------------------------------------------|
+//! | 4     | Magic `GOLC`                                             |
+//! | 1     | Format version, currently 2                              |
+//! | 2     | Birth neighbor counts, bit N set if N causes a birth     |
+//! | 2     | Survival neighbor counts, bit N set if N allows survival |
+//! | 4     | Generation                                               |
+//! | 4     | Number of rows                                           |
+//! | 4     | Number of columns                                        |
+//! | 1     | [Topology]: 0 bounded, 1 torus, 2 mirror, 3 klein,       |
+//! |       | 4 cross-surface, or 5 per-axis                           |
+//! | 1     | Per-axis [Edge]s, otherwise 0: 0 bounded, 1 wrap,        |
+//! |       | 2 mirror, or 3 twist, top/bottom in the low four bits    |
+//! |       | and left/right in the high four bits                     |
+//! | ...   | Cells row by row, one bit each, least significant first  |
+//!
+//! Version 1 checkpoints, which have no topology bytes, are still loaded
+//! and restore a [Topology::Bounded] grid.
+
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::game::{Cell, ConwaySim, Grid, MAX_CELLS};
+use crate::rule::Rule;
+use crate::topology::{Edge, Topology};
+
+/// Magic bytes at the start of every checkpoint.
+const MAGIC: &[u8; 4] = b"GOLC";
+
+/// Version of the checkpoint format written.
+const VERSION: u8 = 2;
+
+/// Length of the header preceding the cells.
+const HEADER_LEN: usize = 23;
+
+/// Length of the header of version 1 checkpoints, which have no topology.
+const V1_HEADER_LEN: usize = 21;
+
+/// Error raised while loading a checkpoint.
+#[derive(Debug)]
+pub enum CheckpointError {
+    /// The data does not start with the checkpoint magic bytes.
+    NotACheckpoint,
+
+    /// The checkpoint was written by an unsupported format version.
+    UnsupportedVersion(u8),
+
+    /// The checkpoint ends before all of its cells.
+    Truncated,
+
+    /// The checkpoint's topology bytes do not describe a [Topology].
+    InvalidTopology(u8, u8),
+
+    /// The checkpoint's grid has too many cells for a [Grid] to hold.
+    TooLarge { num_rows: u32, num_cols: u32 },
+
+    /// The checkpoint file could not be read or written.
+    Io(io::Error),
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckpointError::NotACheckpoint => write!(f, "not a simulation checkpoint"),
+            CheckpointError::UnsupportedVersion(version) => {
+                write!(f, "unsupported checkpoint version {}", version)
+            }
+            CheckpointError::Truncated => write!(f, "checkpoint is truncated"),
+            CheckpointError::InvalidTopology(topology, edges) => write!(
+                f,
+                "invalid checkpoint topology {} with edges {:#04x}",
+                topology, edges
+            ),
+            CheckpointError::TooLarge { num_rows, num_cols } => {
+                write!(
+                    f,
+                    "checkpoint grid of {}x{} cells is too large",
+                    num_rows, num_cols
+                )
+            }
+            CheckpointError::Io(err) => write!(f, "unable to access checkpoint file: {}", err),
+        }
+    }
+}
+
+impl error::Error for CheckpointError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            CheckpointError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CheckpointError {
+    fn from(err: io::Error) -> CheckpointError {
+        CheckpointError::Io(err)
+    }
+}
+
+/// Encode the neighbor counts accepted by a predicate as a bit mask.
+fn encode_counts(accepts: impl Fn(u8) -> bool) -> u16 {
+    (0..=8)
+        .filter(|&count| accepts(count))
+        .fold(0, |mask, count| mask | 1 << count)
+}
+
+/// Decode a bit mask of neighbor counts.
+fn decode_counts(mask: u16) -> Vec<u8> {
+    (0..=8).filter(|&count| mask & 1 << count != 0).collect()
+}
+
+/// [Edge]s in the order they are numbered.
+const EDGES: [Edge; 4] = [Edge::Bounded, Edge::Wrap, Edge::Mirror, Edge::Twist];
+
+/// Encode a [Topology] as its number and the numbers of its per-axis edges.
+fn encode_topology(topology: Topology) -> [u8; 2] {
+    let edge = |edge| EDGES.iter().position(|&other| other == edge).unwrap() as u8;
+
+    match topology {
+        Topology::Bounded => [0, 0],
+        Topology::Torus => [1, 0],
+        Topology::Mirror => [2, 0],
+        Topology::Klein => [3, 0],
+        Topology::CrossSurface => [4, 0],
+        Topology::PerAxis { rows, cols } => [5, edge(rows) | edge(cols) << 4],
+    }
+}
+
+/// Decode a [Topology] from its number and the numbers of its per-axis
+/// edges.
+fn decode_topology(topology: u8, edges: u8) -> Option<Topology> {
+    match (topology, edges) {
+        (0, 0) => Some(Topology::Bounded),
+        (1, 0) => Some(Topology::Torus),
+        (2, 0) => Some(Topology::Mirror),
+        (3, 0) => Some(Topology::Klein),
+        (4, 0) => Some(Topology::CrossSurface),
+        (5, _) => Some(Topology::PerAxis {
+            rows: *EDGES.get((edges & 0xf) as usize)?,
+            cols: *EDGES.get((edges >> 4) as usize)?,
+        }),
+        _ => None,
+    }
+}
+
+/// Read `N` bytes at an offset of the header.
+fn read_bytes<const N: usize>(bytes: &[u8], offset: usize) -> [u8; N] {
+    let mut value = [0; N];
+    value.copy_from_slice(&bytes[offset..offset + N]);
+
+    value
+}
+
+impl ConwaySim {
+    /// Encode the simulation as a checkpoint.
+    pub fn to_checkpoint(&self) -> Vec<u8> {
+        let grid = self.get_grid();
+        let rule = self.get_rule();
+        let num_cells = grid.get_num_rows() as usize * grid.get_num_cols() as usize;
+
+        let mut checkpoint: Vec<u8> = Vec::with_capacity(HEADER_LEN + num_cells.div_ceil(8));
+        checkpoint.extend_from_slice(MAGIC);
+        checkpoint.push(VERSION);
+        checkpoint.extend_from_slice(&encode_counts(|count| rule.is_birth(count)).to_le_bytes());
+        checkpoint.extend_from_slice(&encode_counts(|count| rule.is_survival(count)).to_le_bytes());
+        checkpoint.extend_from_slice(&self.get_generation().to_le_bytes());
+        checkpoint.extend_from_slice(&grid.get_num_rows().to_le_bytes());
+        checkpoint.extend_from_slice(&grid.get_num_cols().to_le_bytes());
+        checkpoint.extend_from_slice(&encode_topology(grid.get_topology()));
+
+        // pack the cells, eight to a byte
+        let mut byte: u8 = 0;
+        let mut bit: u32 = 0;
+
+        for row in 0..grid.get_num_rows() {
+            for col in 0..grid.get_num_cols() {
+                if grid.get(row, col).is_alive() {
+                    byte |= 1 << bit;
+                }
+
+                bit += 1;
+                if bit == 8 {
+                    checkpoint.push(byte);
+                    byte = 0;
+                    bit = 0;
+                }
+            }
+        }
+
+        if bit > 0 {
+            checkpoint.push(byte);
+        }
+
+        checkpoint
+    }
+
+    /// Restore a simulation from a checkpoint.
+    ///
+    /// # Arguments
+    /// * `checkpoint` - The checkpoint created by [ConwaySim::to_checkpoint].
+    pub fn from_checkpoint(checkpoint: &[u8]) -> Result<ConwaySim, CheckpointError> {
+        if checkpoint.len() < MAGIC.len() + 1 || &checkpoint[..MAGIC.len()] != MAGIC {
+            return Err(CheckpointError::NotACheckpoint);
+        }
+
+        let version = checkpoint[MAGIC.len()];
+        let header_len = match version {
+            1 => V1_HEADER_LEN,
+            VERSION => HEADER_LEN,
+            _ => return Err(CheckpointError::UnsupportedVersion(version)),
+        };
+
+        if checkpoint.len() < header_len {
+            return Err(CheckpointError::Truncated);
+        }
+
+        let birth = u16::from_le_bytes(read_bytes(checkpoint, 5));
+        let survival = u16::from_le_bytes(read_bytes(checkpoint, 7));
+        let generation = u32::from_le_bytes(read_bytes(checkpoint, 9));
+        let num_rows = u32::from_le_bytes(read_bytes(checkpoint, 13));
+        let num_cols = u32::from_le_bytes(read_bytes(checkpoint, 17));
+        let topology = match version {
+            1 => Topology::Bounded,
+            _ => decode_topology(checkpoint[21], checkpoint[22]).ok_or(
+                CheckpointError::InvalidTopology(checkpoint[21], checkpoint[22]),
+            )?,
+        };
+
+        if num_rows as u64 * num_cols as u64 > MAX_CELLS {
+            return Err(CheckpointError::TooLarge { num_rows, num_cols });
+        }
+
+        let num_cells = num_rows as usize * num_cols as usize;
+        let cells = &checkpoint[header_len..];
+        if cells.len() < num_cells.div_ceil(8) {
+            return Err(CheckpointError::Truncated);
+        }
+
+        let mut grid = Grid::new(num_rows, num_cols);
+        grid.set_topology(topology);
+
+        for index in 0..num_cells {
+            if cells[index / 8] & 1 << (index % 8) != 0 {
+                let row = (index / num_cols as usize) as u32;
+                let col = (index % num_cols as usize) as u32;
+                grid.set(row, col, Cell::Alive);
+            }
+        }
+
+        let rule = Rule::new(&decode_counts(birth), &decode_counts(survival));
+
+        Ok(ConwaySim::restore(grid, generation, rule))
+    }
+
+    /// Save the simulation to a checkpoint file so that it can be resumed
+    /// later with [ConwaySim::load_checkpoint].
+    ///
+    /// # Arguments
+    /// * `path` - Path of the checkpoint file.
+    pub fn save_checkpoint<P: AsRef<Path>>(&self, path: P) -> Result<(), CheckpointError> {
+        fs::write(path, self.to_checkpoint())?;
+
+        Ok(())
+    }
+
+    /// Resume a simulation from a checkpoint file.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the checkpoint file.
+    pub fn load_checkpoint<P: AsRef<Path>>(path: P) -> Result<ConwaySim, CheckpointError> {
+        let checkpoint = fs::read(path)?;
+
+        ConwaySim::from_checkpoint(&checkpoint)
+    }
+}
@@ -0,0 +1,511 @@
+//! An unbounded [World] storing its cells in lazily allocated chunks, which
+//! sleep while nothing around them changes.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::thread;
+
+use crate::bitops::{self, CountMasks, BITS};
+use crate::game::{ConwaySim, Grid};
+use crate::rule::Rule;
+use crate::scheduler::Pool;
+use crate::world::{StateHasher, World};
+
+/// Side of a chunk in cells, one word per row of the chunk.
+const CHUNK_SIZE: i64 = BITS as i64;
+
+/// The rows of a chunk of dead cells.
+const EMPTY: [u64; BITS] = [0; BITS];
+
+/// Fewest awake chunks worth handing to each thread, below which waking the
+/// thread costs more than it saves.
+const MIN_CHUNKS_PER_THREAD: usize = 32;
+
+/// A square chunk of [CHUNK_SIZE] cells, each row packed into a word with
+/// the cell of column `col` in bit `col`.
+#[derive(Clone, Debug)]
+struct Chunk {
+    /// The rows of cells, one bit per cell.
+    rows: [u64; BITS],
+
+    /// The number of live cells.
+    population: u32,
+
+    /// Hash of the chunk's position and cells, unless edited since it was
+    /// computed.
+    hash: Option<u64>,
+
+    /// Whether the chunk changed in the last generation or was edited since.
+    changed: bool,
+}
+
+impl Chunk {
+    /// Create a new [Chunk], hashing its cells.
+    ///
+    /// # Arguments
+    /// * `key` - The (row, col) of the chunk.
+    /// * `rows` - The rows of cells.
+    /// * `changed` - Whether the chunk changed in the last generation.
+    fn new(key: (i64, i64), rows: [u64; BITS], changed: bool) -> Chunk {
+        Chunk {
+            rows,
+            population: rows.iter().map(|word| word.count_ones()).sum(),
+            hash: Some(hash_chunk(key, &rows)),
+            changed,
+        }
+    }
+}
+
+/// Hash the position and cells of a chunk.
+fn hash_chunk(key: (i64, i64), rows: &[u64; BITS]) -> u64 {
+    let mut hasher = StateHasher::default();
+    key.hash(&mut hasher);
+    rows.hash(&mut hasher);
+
+    hasher.finish()
+}
+
+/// An unbounded [World] of chunks of 64x64 cells, allocated only where there
+/// are live cells, so huge mostly empty worlds cost nothing for their empty
+/// space.
+///
+/// A chunk only changes if it or one of its eight neighboring chunks changed
+/// in the previous generation, so chunks of still lifes and empty space
+/// sleep and are copied rather than computed until something approaches or
+/// the [Rule] changes.
+///
+/// The awake chunks of a generation are shared between threads, stealing
+/// work from each other once done with their own. Each chunk reads the
+/// borders of its neighbors from the previous generation, so the threads
+/// need no locks. The threads are kept in a pool shared by the world and
+/// its clones, spawned the first time a generation has enough awake chunks
+/// to share and then woken for each generation which does.
+///
+/// As in a [SparseWorld](crate::SparseWorld), rules with births on zero
+/// neighbors (`B0`) do not cause empty space to be born.
+#[derive(Clone, Debug)]
+pub struct ChunkedWorld {
+    /// The chunks holding live cells or which just changed, by (row, col) of
+    /// chunk, shared with the threads stepping them.
+    chunks: Arc<HashMap<(i64, i64), Chunk>>,
+
+    /// The [Rule] of the last generation, under which the sleeping chunks
+    /// are stable, if any.
+    rule: Option<Rule>,
+
+    /// Most threads the awake chunks are shared between.
+    num_threads: usize,
+
+    /// The threads stepping the awake chunks beside the calling thread.
+    pool: Arc<Pool>,
+}
+
+impl Default for ChunkedWorld {
+    fn default() -> ChunkedWorld {
+        ChunkedWorld::new()
+    }
+}
+
+/// Get the rows of a chunk, which are all dead if it is not allocated.
+fn chunk_rows(chunks: &HashMap<(i64, i64), Chunk>, key: (i64, i64)) -> &[u64; BITS] {
+    chunks.get(&key).map_or(&EMPTY, |chunk| &chunk.rows)
+}
+
+/// Get the (row, col) of the chunk holding a cell and the cell's (row, col)
+/// within it.
+fn chunk_of(row: i64, col: i64) -> ((i64, i64), (usize, usize)) {
+    (
+        (row.div_euclid(CHUNK_SIZE), col.div_euclid(CHUNK_SIZE)),
+        (
+            row.rem_euclid(CHUNK_SIZE) as usize,
+            col.rem_euclid(CHUNK_SIZE) as usize,
+        ),
+    )
+}
+
+impl ChunkedWorld {
+    /// Create a new, empty [ChunkedWorld], stepped on every available
+    /// thread.
+    pub fn new() -> ChunkedWorld {
+        ChunkedWorld {
+            chunks: Arc::new(HashMap::new()),
+            rule: None,
+            num_threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+            pool: Arc::new(Pool::new()),
+        }
+    }
+
+    /// Create a new [ChunkedWorld] from a [Grid], with the [Grid]'s (0, 0)
+    /// cell at the world's (0, 0) cell.
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cells of the world.
+    pub fn from_grid(grid: &Grid) -> ChunkedWorld {
+        let mut world = ChunkedWorld::new();
+        world.set_cells(&grid.live_cells());
+
+        world
+    }
+
+    /// Set the cells of the [ChunkedWorld] as alive.
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (row, col) to set as alive.
+    pub fn set_cells(&mut self, cells: &[(i64, i64)]) {
+        for &(row, col) in cells.iter() {
+            self.set(row, col, true);
+        }
+    }
+
+    /// Set the specified cell as alive or dead, waking its chunk and the
+    /// chunks around it.
+    pub fn set(&mut self, row: i64, col: i64, alive: bool) {
+        let (key, (row, col)) = chunk_of(row, col);
+        let chunk = Arc::make_mut(&mut self.chunks)
+            .entry(key)
+            .or_insert_with(|| Chunk::new(key, EMPTY, false));
+
+        let word = &mut chunk.rows[row];
+        let was_alive = *word & (1 << col) != 0;
+        if alive {
+            *word |= 1 << col;
+        } else {
+            *word &= !(1 << col);
+        }
+
+        chunk.population = chunk.population + alive as u32 - was_alive as u32;
+        chunk.hash = None;
+        chunk.changed = true;
+    }
+
+    /// Set the most threads the awake chunks are shared between, at least
+    /// one. Waking the threads costs each generation which shares its
+    /// chunks, so more threads only pay off for large, busy worlds.
+    pub fn set_num_threads(&mut self, num_threads: usize) {
+        self.num_threads = num_threads.max(1);
+    }
+
+    /// Get the most threads the awake chunks are shared between.
+    pub fn get_num_threads(&self) -> usize {
+        self.num_threads
+    }
+
+    /// Get the number of chunks allocated.
+    pub fn get_num_chunks(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Get the number of allocated chunks which will be skipped by the next
+    /// generation, as neither they nor their neighbors changed.
+    pub fn get_num_sleeping_chunks(&self) -> usize {
+        let awake = self.awake_chunks(self.rule);
+
+        self.chunks
+            .keys()
+            .filter(|key| !awake.contains(key))
+            .count()
+    }
+
+    /// Get the (row, col) of every chunk which changed or neighbors one that
+    /// did, whether allocated or not.
+    ///
+    /// # Arguments
+    /// * `rule` - The [Rule] of the next generation. Every chunk is awake
+    ///   if it differs from the rule of the last generation.
+    fn awake_chunks(&self, rule: Option<Rule>) -> HashSet<(i64, i64)> {
+        let same_rule = rule.is_some() && rule == self.rule;
+        let mut awake: HashSet<(i64, i64)> = HashSet::new();
+
+        for (&(chunk_row, chunk_col), chunk) in self.chunks.iter() {
+            if chunk.changed || !same_rule {
+                for row_offset in -1..=1 {
+                    for col_offset in -1..=1 {
+                        awake.insert((chunk_row + row_offset, chunk_col + col_offset));
+                    }
+                }
+            }
+        }
+
+        awake
+    }
+
+    /// Iterate over the live cells as (row, col) tuples, in no particular
+    /// order.
+    fn cells(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.chunks
+            .iter()
+            .flat_map(|(&(chunk_row, chunk_col), chunk)| {
+                chunk.rows.iter().enumerate().flat_map(move |(row, &word)| {
+                    (0..BITS)
+                        .filter(move |col| word & (1 << col) != 0)
+                        .map(move |col| {
+                            (
+                                chunk_row * CHUNK_SIZE + row as i64,
+                                chunk_col * CHUNK_SIZE + col as i64,
+                            )
+                        })
+                })
+            })
+    }
+
+    /// Get the (min_row, min_col, max_row, max_col) bounding box of the live
+    /// cells, or [None] if there are none.
+    pub fn bounding_box(&self) -> Option<(i64, i64, i64, i64)> {
+        self.cells().fold(None, |bounds, (row, col)| match bounds {
+            Some((min_row, min_col, max_row, max_col)) => Some((
+                row.min(min_row),
+                col.min(min_col),
+                row.max(max_row),
+                col.max(max_col),
+            )),
+            None => Some((row, col, row, col)),
+        })
+    }
+
+    /// Copy a rectangular region of the world into a new [Grid].
+    ///
+    /// # Arguments
+    /// * `row` - Row of the region's top left cell.
+    /// * `col` - Column of the region's top left cell.
+    /// * `num_rows` - The number of rows (height) of the region.
+    /// * `num_cols` - The number of columns (width) of the region.
+    pub fn to_grid(&self, row: i64, col: i64, num_rows: u32, num_cols: u32) -> Grid {
+        let mut grid = Grid::new(num_rows, num_cols);
+
+        let cells: Vec<(u32, u32)> = self
+            .cells()
+            .filter(|&(r, c)| {
+                r >= row && c >= col && r < row + num_rows as i64 && c < col + num_cols as i64
+            })
+            .map(|(r, c)| ((r - row) as u32, (c - col) as u32))
+            .collect();
+
+        grid.set_cells(&cells);
+
+        grid
+    }
+
+    /// Get the rows of a chunk, which are all dead if it is not allocated.
+    fn chunk_rows(&self, key: (i64, i64)) -> &[u64; BITS] {
+        chunk_rows(&self.chunks, key)
+    }
+}
+
+/// Compute the next generation of the cells of a chunk.
+///
+/// # Arguments
+/// * `chunks` - The chunks of the current generation.
+/// * `key` - The (row, col) of the chunk.
+/// * `births` - The counts of live neighbors dead cells are born on.
+/// * `survivals` - The counts of live neighbors live cells survive on.
+fn step_chunk(
+    chunks: &HashMap<(i64, i64), Chunk>,
+    (chunk_row, chunk_col): (i64, i64),
+    births: &CountMasks,
+    survivals: &CountMasks,
+) -> [u64; BITS] {
+    // the rows of the chunks above, through, and below the chunk, each
+    // from west to east
+    let bands = [-1, 0, 1].map(|row_offset| {
+        [-1, 0, 1]
+            .map(|col_offset| chunk_rows(chunks, (chunk_row + row_offset, chunk_col + col_offset)))
+    });
+
+    // the neighbors of a row from just above the chunk to just below it
+    let neighbors = |row: i64| {
+        let (band, row) = match row {
+            -1 => (&bands[0], BITS - 1),
+            CHUNK_SIZE => (&bands[2], 0),
+            _ => (&bands[1], row as usize),
+        };
+
+        bitops::neighbors(band[0][row], band[1][row], band[2][row])
+    };
+
+    let mut rows = EMPTY;
+    for (row, next) in rows.iter_mut().enumerate() {
+        let row = row as i64;
+        *next = bitops::next_cells(
+            neighbors(row - 1),
+            neighbors(row),
+            neighbors(row + 1),
+            births,
+            survivals,
+        );
+    }
+
+    rows
+}
+
+impl PartialEq for ChunkedWorld {
+    /// Compare the live cells of two worlds, regardless of which chunks are
+    /// allocated or asleep.
+    fn eq(&self, other: &ChunkedWorld) -> bool {
+        let within = |a: &ChunkedWorld, b: &ChunkedWorld| {
+            a.chunks
+                .iter()
+                .all(|(&key, chunk)| &chunk.rows == b.chunk_rows(key))
+        };
+
+        within(self, other) && within(other, self)
+    }
+}
+
+impl Eq for ChunkedWorld {}
+
+impl World for ChunkedWorld {
+    fn is_alive(&self, row: i64, col: i64) -> bool {
+        let (key, (row, col)) = chunk_of(row, col);
+
+        self.chunk_rows(key)[row] & (1 << col) != 0
+    }
+
+    fn get_population(&self) -> u64 {
+        self.chunks
+            .values()
+            .map(|chunk| chunk.population as u64)
+            .sum()
+    }
+
+    fn live_cells(&self) -> Vec<(i64, i64)> {
+        let mut cells: Vec<(i64, i64)> = self.cells().collect();
+        cells.sort_unstable();
+
+        cells
+    }
+
+    fn get_area(&self) -> u64 {
+        match self.bounding_box() {
+            Some((min_row, min_col, max_row, max_col)) => {
+                (max_row - min_row + 1) as u64 * (max_col - min_col + 1) as u64
+            }
+            None => 0,
+        }
+    }
+
+    /// Combine the hashes of the chunks holding live cells, which are only
+    /// computed again when a chunk changes.
+    fn state_hash(&self) -> u64 {
+        self.chunks
+            .iter()
+            .filter(|(_, chunk)| chunk.population > 0)
+            .map(|(&key, chunk)| chunk.hash.unwrap_or_else(|| hash_chunk(key, &chunk.rows)))
+            .fold(0, u64::wrapping_add)
+    }
+
+    fn count_changes(&self, previous: &ChunkedWorld) -> (u64, u64) {
+        let keys: HashSet<(i64, i64)> = self
+            .chunks
+            .keys()
+            .chain(previous.chunks.keys())
+            .cloned()
+            .collect();
+
+        keys.into_iter().fold((0, 0), |(births, deaths), key| {
+            let rows = self.chunk_rows(key).iter().zip(previous.chunk_rows(key));
+
+            rows.fold((births, deaths), |(births, deaths), (&after, &before)| {
+                (
+                    births + (after & !before).count_ones() as u64,
+                    deaths + (before & !after).count_ones() as u64,
+                )
+            })
+        })
+    }
+
+    fn step_into(&self, rule: &Rule, next: &mut ChunkedWorld) {
+        // empty space stays empty, as dead cells without live neighbors are
+        // never born
+        let births = CountMasks::new(|count| count > 0 && rule.is_birth(count));
+        let survivals = CountMasks::new(|count| rule.is_survival(count));
+
+        let awake = self.awake_chunks(Some(*rule));
+        next.rule = Some(*rule);
+        next.num_threads = self.num_threads;
+        next.pool = self.pool.clone();
+
+        // reuse the next generation's chunks unless a clone still shares them
+        let chunks = match Arc::get_mut(&mut next.chunks) {
+            Some(chunks) => chunks,
+            None => {
+                next.chunks = Arc::default();
+                Arc::get_mut(&mut next.chunks).unwrap()
+            }
+        };
+        chunks.clear();
+
+        // sleeping chunks are unchanged, and dropped once empty
+        for (&key, chunk) in self.chunks.iter() {
+            if !awake.contains(&key) && chunk.population > 0 {
+                chunks.insert(
+                    key,
+                    Chunk {
+                        changed: false,
+                        ..chunk.clone()
+                    },
+                );
+            }
+        }
+
+        // only threads with enough chunks to step are worth waking
+        let awake: Vec<(i64, i64)> = awake.into_iter().collect();
+        let num_threads = self
+            .num_threads
+            .min(awake.len() / MIN_CHUNKS_PER_THREAD)
+            .max(1);
+
+        let current = self.chunks.clone();
+        let stepped = self.pool.run(awake, num_threads, move |&key| {
+            let rows = step_chunk(&current, key, &births, &survivals);
+            let changed = &rows != chunk_rows(&current, key);
+
+            // chunks which just emptied are kept to wake their neighbors
+            (changed || rows != EMPTY).then(|| (key, Chunk::new(key, rows, changed)))
+        });
+        chunks.extend(stepped);
+    }
+}
+
+impl fmt::Display for ChunkedWorld {
+    /// Display the bounding box of the live cells.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.bounding_box() {
+            Some((min_row, min_col, max_row, max_col)) => {
+                let num_rows = (max_row - min_row + 1) as u32;
+                let num_cols = (max_col - min_col + 1) as u32;
+
+                self.to_grid(min_row, min_col, num_rows, num_cols).fmt(f)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl ConwaySim<ChunkedWorld> {
+    /// Create a new simulation of an empty, unbounded [ChunkedWorld].
+    pub fn new_chunked() -> ConwaySim<ChunkedWorld> {
+        ConwaySim::new_with_world(ChunkedWorld::new())
+    }
+
+    /// Determine if the specified cell is alive.
+    pub fn is_cell_alive(&self, row: i64, col: i64) -> bool {
+        self.get_world().is_alive(row, col)
+    }
+
+    /// Set the most threads the awake chunks of the simulation's
+    /// [ChunkedWorld] are shared between, at least one.
+    pub fn set_num_threads(&mut self, num_threads: usize) {
+        self.get_world_mut().set_num_threads(num_threads);
+    }
+
+    /// Set the cells of the simulation's [ChunkedWorld] as alive.
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (row, col) to set as alive.
+    pub fn set_cells(&mut self, cells: &[(i64, i64)]) {
+        self.get_world_mut().set_cells(cells);
+        self.record_edit();
+    }
+}
@@ -0,0 +1,423 @@
+#[cfg(feature = "serve")]
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+#[cfg(feature = "serve")]
+use gameoflife_rs::ColorVariant;
+use gameoflife_rs::{AntRule, HexRule, LeniaRule, Rule, Symmetry, Topology};
+
+/// How each generation is rendered to the terminal.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum RenderMode {
+    /// Print every generation as a grid of text symbols.
+    Text,
+
+    /// Print only a summary of the final generation.
+    Quiet,
+
+    /// Redraw every generation in place with colored cells fading as they age.
+    Ansi,
+
+    /// Print every generation in braille characters, each drawing 2x4 cells.
+    Braille,
+
+    /// Print the cells born (+row,col) and died (-row,col) each generation.
+    Diff,
+
+    /// Drive the simulation from typed commands such as `step 10` or `load glider 5 5`.
+    Repl,
+
+    /// Explore the simulation in an interactive terminal user interface.
+    #[cfg(feature = "tui")]
+    Tui,
+
+    /// Explore the simulation in a window with mouse editing.
+    #[cfg(feature = "gui")]
+    Gui,
+}
+
+/// Machine-readable format of the results of `simulate` and `sweep`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// One JSON object per line.
+    Json,
+
+    /// CSV with a header row.
+    Csv,
+}
+
+/// Arguments of the `simulate` subcommand.
+#[derive(Debug, Args)]
+pub struct SimulateArgs {
+    /// Built-in pattern names or pattern files in the RLE, plaintext (.cells) or Life 1.06
+    /// (.lif) format, each simulated separately.
+    #[arg(required = true, value_name = "PATTERN")]
+    pub patterns: Vec<PathBuf>,
+
+    /// Rulestring in B/S notation, e.g. B36/S23 [default: each pattern's rule, or B3/S23].
+    #[arg(long)]
+    pub rule: Option<Rule>,
+
+    /// Number of generations to simulate.
+    #[arg(short, long, default_value_t = 1000)]
+    pub generations: u32,
+
+    /// Number of rows (height) of a bounded grid [default: an unbounded plane, or the pattern
+    /// height if --cols is given].
+    #[arg(short, long)]
+    pub rows: Option<u32>,
+
+    /// Number of columns (width) of a bounded grid [default: an unbounded plane, or the pattern
+    /// width if --rows is given].
+    #[arg(short, long)]
+    pub cols: Option<u32>,
+
+    /// How cells on the edges of a bounded grid find their neighbors.
+    #[arg(long, default_value_t = Topology::Bounded)]
+    pub topology: Topology,
+
+    /// Format of the results.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+    pub format: OutputFormat,
+}
+
+/// Arguments of the `sweep` subcommand.
+#[derive(Debug, Args)]
+pub struct SweepArgs {
+    /// First density swept.
+    #[arg(long, default_value_t = 0.05)]
+    pub from: f64,
+
+    /// Last density swept.
+    #[arg(long, default_value_t = 0.95)]
+    pub to: f64,
+
+    /// Number of evenly spaced densities swept from --from to --to.
+    #[arg(long, default_value_t = 19)]
+    pub steps: u32,
+
+    /// Number of soups run at each density.
+    #[arg(long, default_value_t = 10)]
+    pub trials: u32,
+
+    /// Rulestring in B/S notation, e.g. B36/S23 [default: B3/S23].
+    #[arg(long)]
+    pub rule: Option<Rule>,
+
+    /// Most generations each soup runs for before it is counted as unstabilized.
+    #[arg(short, long, default_value_t = 5000)]
+    pub generations: u32,
+
+    /// Number of rows (height) of each soup.
+    #[arg(short, long, default_value_t = 64)]
+    pub rows: u32,
+
+    /// Number of columns (width) of each soup.
+    #[arg(short, long, default_value_t = 64)]
+    pub cols: u32,
+
+    /// How cells on the edges of each soup find their neighbors.
+    #[arg(long, default_value_t = Topology::Torus)]
+    pub topology: Topology,
+
+    /// Seed of the first soup [default: random].
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Number of threads running soups [default: the number of CPUs].
+    #[arg(long)]
+    pub threads: Option<usize>,
+
+    /// Format of the results [default: a table].
+    #[arg(long, value_enum)]
+    pub format: Option<OutputFormat>,
+}
+
+/// Encoding of the WebSocket messages sent by `serve`.
+#[cfg(feature = "serve")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum FrameFormat {
+    /// JSON text frames.
+    Json,
+
+    /// Compact little-endian binary frames.
+    Binary,
+}
+
+/// Multi-color variant of a shared world served by `serve --shared`.
+#[cfg(feature = "serve")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum SharedVariant {
+    /// Two players' colors, newborn cells taking the majority color of their parents.
+    Immigration,
+
+    /// Four players' colors, as in Immigration but with a fourth color breaking ties.
+    #[value(name = "quadlife")]
+    QuadLife,
+}
+
+#[cfg(feature = "serve")]
+impl From<SharedVariant> for ColorVariant {
+    fn from(variant: SharedVariant) -> ColorVariant {
+        match variant {
+            SharedVariant::Immigration => ColorVariant::Immigration,
+            SharedVariant::QuadLife => ColorVariant::QuadLife,
+        }
+    }
+}
+
+/// Arguments of the `serve` subcommand.
+#[cfg(feature = "serve")]
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    /// Built-in pattern name or pattern file in the RLE, plaintext (.cells) or Life 1.06 (.lif)
+    /// format, centered on the grid.
+    #[arg(short, long, default_value = "gosper-glider-gun")]
+    pub pattern: PathBuf,
+
+    /// Rulestring in B/S notation, e.g. B36/S23 [default: the pattern's rule, or B3/S23].
+    #[arg(long)]
+    pub rule: Option<Rule>,
+
+    /// Number of rows (height) of the grid.
+    #[arg(short, long, default_value_t = 64)]
+    pub rows: u32,
+
+    /// Number of columns (width) of the grid.
+    #[arg(short, long, default_value_t = 64)]
+    pub cols: u32,
+
+    /// How cells on the edges of the grid find their neighbors.
+    #[arg(long, default_value_t = Topology::Bounded)]
+    pub topology: Topology,
+
+    /// Address to listen on.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    pub bind: SocketAddr,
+
+    /// Time in milliseconds between generations.
+    #[arg(long, default_value_t = 100)]
+    pub interval: u64,
+
+    /// Encoding of the WebSocket messages.
+    #[arg(long, value_enum, default_value_t = FrameFormat::Json)]
+    pub format: FrameFormat,
+
+    /// Share the world with every client: each player is given a color and places cells of their
+    /// color by sending {"type":"place","cells":[[row,col],...]}. Shared worlds are bounded and
+    /// streamed as JSON.
+    #[arg(long, conflicts_with_all = ["topology", "format"])]
+    pub shared: bool,
+
+    /// Multi-color variant of the shared world.
+    #[arg(long, value_enum, default_value_t = SharedVariant::Immigration, requires = "shared")]
+    pub variant: SharedVariant,
+
+    /// Number of cells each player may place per second in the shared world.
+    #[arg(long, default_value_t = 20, requires = "shared")]
+    pub rate_limit: u32,
+}
+
+/// Arguments of the `fetch` subcommand.
+#[cfg(feature = "fetch")]
+#[derive(Debug, Args)]
+pub struct FetchArgs {
+    /// Name of the pattern in the collection, e.g. gosperglidergun or 52513m.
+    pub name: String,
+
+    /// URL of the pattern, with {name} replaced by the pattern name. URLs ending in .cells are
+    /// read as plaintext, any others as RLE.
+    #[arg(long, default_value = "https://conwaylife.com/patterns/{name}.rle")]
+    pub url: String,
+
+    /// Rulestring in B/S notation, e.g. B36/S23 [default: the pattern's rule, or B3/S23].
+    #[arg(long)]
+    pub rule: Option<Rule>,
+
+    /// Number of rows (height) of the grid [default: the pattern height].
+    #[arg(short, long)]
+    pub rows: Option<u32>,
+
+    /// Number of columns (width) of the grid [default: the pattern width].
+    #[arg(short, long)]
+    pub cols: Option<u32>,
+
+    /// Also save the downloaded pattern to this file.
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+}
+
+/// Arguments of the `resume` subcommand.
+#[derive(Debug, Args)]
+pub struct ResumeArgs {
+    /// Name of the session given to --session.
+    pub name: String,
+
+    /// Number of generations to simulate from where the session left off.
+    #[arg(short, long, default_value_t = 1000)]
+    pub generations: u32,
+
+    /// How each generation is rendered.
+    #[arg(long, value_enum, default_value_t = RenderMode::Quiet)]
+    pub render: RenderMode,
+
+    /// Number of generations between autosaves.
+    #[arg(long, value_name = "GENERATIONS", default_value_t = 1000)]
+    pub autosave: u32,
+}
+
+/// Subcommands run instead of the interactive simulation.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run patterns without rendering and print machine-readable results: the final population,
+    /// the generation at which each pattern stabilized, and a census of the objects left.
+    Simulate(SimulateArgs),
+
+    /// Run random soups across a range of initial densities and print, for each density, the mean
+    /// final population and the mean generation at which the soups entered a cycle.
+    Sweep(SweepArgs),
+
+    /// Continue a run autosaved with --session, autosaving it again as it runs.
+    Resume(ResumeArgs),
+
+    /// Run a simulation and stream each generation's births and deaths to WebSocket clients at
+    /// ws://<BIND>/ws, with a live viewer at http://<BIND>/.
+    #[cfg(feature = "serve")]
+    Serve(ServeArgs),
+
+    /// Download a pattern from LifeWiki or another pattern collection and drive it from typed
+    /// commands, as with --render repl.
+    #[cfg(feature = "fetch")]
+    Fetch(FetchArgs),
+}
+
+/// Conway's Game of Life implemented in Rust.
+#[derive(Debug, Parser)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
+#[command(group(ArgGroup::new("soup").args(["density", "search"]).multiple(true)))]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Number of rows (height) of the grid [default: 5, or the pattern height].
+    #[arg(short, long)]
+    pub rows: Option<u32>,
+
+    /// Number of columns (width) of the grid [default: 5, or the pattern width].
+    #[arg(short, long)]
+    pub cols: Option<u32>,
+
+    /// Maximum number of generations to simulate.
+    #[arg(short, long, default_value_t = 105)]
+    pub generations: u32,
+
+    /// Initial pattern: a built-in pattern name (glider, blinker, toad, beacon, pulsar, lwss,
+    /// gosper-glider-gun, r-pentomino) or a file in the RLE, plaintext (.cells) or Life 1.06
+    /// (.lif) format [default: blinker].
+    #[arg(short, long)]
+    pub pattern: Option<PathBuf>,
+
+    /// Fill the grid with a random soup of this density (0.0 to 1.0) instead of a pattern.
+    #[arg(long, conflicts_with = "pattern")]
+    pub density: Option<f64>,
+
+    /// Seed of the random soup, or of the first soup searched [default: random].
+    #[arg(long, requires = "soup")]
+    pub seed: Option<u64>,
+
+    /// Symmetry of the random soups, as in apgsearch: C1 (none), C2, C4, D2, D4, or D8. C4 and D8
+    /// require a square grid.
+    #[arg(long, requires = "soup", default_value_t = Symmetry::C1)]
+    pub symmetry: Symmetry,
+
+    /// Search this many random soups of --rows by --cols cells [default: 16x16] and print a
+    /// census of the objects they leave behind. Each soup runs until its population repeats, for
+    /// at most 10000 generations.
+    #[arg(long, value_name = "NUM_SOUPS", conflicts_with_all = ["pattern", "from_checkpoint", "ant", "hex", "lenia"])]
+    pub search: Option<u64>,
+
+    /// Number of threads searching soups [default: the number of CPUs].
+    #[arg(long, requires = "search")]
+    pub threads: Option<usize>,
+
+    /// Resume a simulation, with its rule and topology, from a checkpoint file instead of
+    /// starting from a pattern.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["pattern", "density", "rule", "topology"])]
+    pub from_checkpoint: Option<PathBuf>,
+
+    /// Save a checkpoint of the final generation to this file.
+    #[arg(long, value_name = "PATH")]
+    pub checkpoint: Option<PathBuf>,
+
+    /// Autosave the run as a named session under ~/.local/share/gameoflife/sessions, to be
+    /// continued with `resume <NAME>`, e.g. after a crash.
+    #[arg(long, value_name = "NAME")]
+    pub session: Option<String>,
+
+    /// Number of generations between autosaves of --session.
+    #[arg(
+        long,
+        value_name = "GENERATIONS",
+        default_value_t = 1000,
+        requires = "session"
+    )]
+    pub autosave: u32,
+
+    /// Rulestring in B/S notation, e.g. B36/S23 [default: the pattern's rule, or B3/S23].
+    #[arg(long)]
+    pub rule: Option<Rule>,
+
+    /// Run Langton's Ant with these turns by cell state, e.g. RL or LLRR, instead of Life.
+    #[arg(long, value_name = "TURNS", conflicts_with_all = ["pattern", "density", "from_checkpoint", "rule"])]
+    pub ant: Option<AntRule>,
+
+    /// Run Life on a hexagonal grid with this rulestring, e.g. B2/S34H, seeded from the pattern
+    /// or soup with each row shifted half a cell left of the row above.
+    #[arg(long, value_name = "RULE", conflicts_with_all = ["from_checkpoint", "rule", "ant"])]
+    pub hex: Option<HexRule>,
+
+    /// Run the experimental continuous Lenia automaton with these parameters, e.g.
+    /// R=13;T=10;b=1;m=0.15;s=0.015, seeded from the pattern or from a soup of random values.
+    #[arg(long, value_name = "PARAMS", conflicts_with_all = ["from_checkpoint", "rule", "ant", "hex"])]
+    pub lenia: Option<LeniaRule>,
+
+    /// How cells on the edges find their neighbors: bounded, torus, mirror, klein,
+    /// cross-surface, or a bounded/wrap/mirror/twist rule per axis such as wrap,mirror.
+    #[arg(long, default_value_t = Topology::Bounded)]
+    pub topology: Topology,
+
+    /// Run this Lua script on the initial simulation before running or exploring it, e.g. to
+    /// build a pattern (use --generations 0 to only run the script).
+    #[cfg(feature = "lua")]
+    #[arg(long, value_name = "PATH")]
+    pub script: Option<PathBuf>,
+
+    /// Delay between generations in milliseconds.
+    #[arg(short, long, default_value_t = 0)]
+    pub delay: u64,
+
+    /// Generations per second of --render tui and gui, such as 0.5 for one every two seconds,
+    /// instead of --delay; adjustable while exploring.
+    #[cfg(any(feature = "tui", feature = "gui"))]
+    #[arg(long, value_name = "GEN_PER_SEC", conflicts_with = "delay")]
+    pub speed: Option<f64>,
+
+    /// Stop as soon as the simulation is extinct, stable, or oscillating.
+    #[arg(long)]
+    pub until_settled: bool,
+
+    /// Write per-generation population statistics to this CSV file.
+    #[arg(long, value_name = "PATH")]
+    pub stats: Option<PathBuf>,
+
+    /// How each generation is rendered.
+    #[arg(long, value_enum, default_value_t = RenderMode::Text)]
+    pub render: RenderMode,
+
+    /// Read defaults for the flags above from this TOML file [default: gameoflife.toml in the
+    /// current directory, or in $XDG_CONFIG_HOME/gameoflife-rs or ~/.config/gameoflife-rs].
+    #[cfg(feature = "config")]
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+}
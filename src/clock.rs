@@ -0,0 +1,131 @@
+//! Scheduling of generations at a steady rate, independent of how often the
+//! interactive user interfaces draw a frame or poll for input.
+
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+/// Rate of generations per second when none was requested.
+pub const DEFAULT_RATE: f64 = 10.0;
+
+/// Range of the rate in generations per second.
+pub const RATE_RANGE: RangeInclusive<f64> = 0.1..=100.0;
+
+/// Most generations due at once, beyond which the clock falls behind rather
+/// than stepping ever more generations per frame to catch up.
+const MAX_CATCH_UP: u32 = 10;
+
+/// A clock telling when generations are due at a rate of generations per
+/// second, including fractional rates such as one every two seconds.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Clock {
+    /// Generations per second, within [RATE_RANGE].
+    rate: f64,
+
+    /// Time elapsed since the last generation was due, in seconds.
+    elapsed: f64,
+}
+
+impl Clock {
+    /// Create a new [Clock] with a rate clamped to [RATE_RANGE].
+    ///
+    /// # Arguments
+    /// * `rate` - Generations per second.
+    pub fn new(rate: f64) -> Clock {
+        Clock {
+            rate: clamp_rate(rate),
+            elapsed: 0.0,
+        }
+    }
+
+    /// Create a new [Clock] with the speed or delay of the command-line flags.
+    ///
+    /// # Arguments
+    /// * `speed` - Generations per second, if given.
+    /// * `delay_ms` - Delay between generations in milliseconds, or 0 for the
+    ///   [DEFAULT_RATE], used if no speed was given.
+    pub fn from_flags(speed: Option<f64>, delay_ms: u64) -> Clock {
+        Clock::new(match (speed, delay_ms) {
+            (Some(speed), _) => speed,
+            (None, 0) => DEFAULT_RATE,
+            (None, delay_ms) => 1000.0 / delay_ms as f64,
+        })
+    }
+
+    /// Get the rate in generations per second.
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    pub fn get_rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Set the rate, clamped to [RATE_RANGE], keeping the time elapsed
+    /// towards the next generation.
+    ///
+    /// # Arguments
+    /// * `rate` - Generations per second.
+    pub fn set_rate(&mut self, rate: f64) {
+        self.rate = clamp_rate(rate);
+    }
+
+    /// Double the rate.
+    pub fn faster(&mut self) {
+        self.set_rate(self.rate * 2.0);
+    }
+
+    /// Halve the rate.
+    pub fn slower(&mut self) {
+        self.set_rate(self.rate / 2.0);
+    }
+
+    /// Get the time between generations.
+    fn get_interval(&self) -> f64 {
+        1.0 / self.rate
+    }
+
+    /// Let time pass and get the number of generations now due, at most
+    /// [MAX_CATCH_UP].
+    ///
+    /// # Arguments
+    /// * `elapsed` - The time passed since the clock was last advanced.
+    pub fn advance(&mut self, elapsed: Duration) -> u32 {
+        let interval = self.get_interval();
+        self.elapsed += elapsed.as_secs_f64();
+
+        let due = (self.elapsed / interval).floor();
+        if due >= MAX_CATCH_UP as f64 {
+            self.elapsed = 0.0;
+            return MAX_CATCH_UP;
+        }
+
+        self.elapsed -= due * interval;
+        due as u32
+    }
+
+    /// Get the time left until the next generation is due.
+    #[cfg_attr(not(feature = "tui"), allow(dead_code))]
+    pub fn until_next(&self) -> Duration {
+        Duration::from_secs_f64((self.get_interval() - self.elapsed).max(0.0))
+    }
+
+    /// Forget the time elapsed towards the next generation, such as while
+    /// paused.
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+}
+
+impl fmt::Display for Clock {
+    /// Write the rate such as `2.5 gen/s`, rounded to hundredths.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} gen/s", (self.rate * 100.0).round() / 100.0)
+    }
+}
+
+/// Clamp a rate to [RATE_RANGE], taking the default for one not a number.
+fn clamp_rate(rate: f64) -> f64 {
+    if rate.is_nan() {
+        DEFAULT_RATE
+    } else {
+        rate.clamp(*RATE_RANGE.start(), *RATE_RANGE.end())
+    }
+}
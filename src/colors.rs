@@ -0,0 +1,281 @@
+//! Multi-color variants of Life, where newborn cells inherit the majority
+//! color of their parents.
+//!
+//! Cells evolve exactly as under the underlying Life-like [Rule] ignoring
+//! color; colors only add a resolution step at each birth. In Immigration
+//! there are two colors, so three parents always have a majority. In
+//! QuadLife there are four, and a cell born to three parents of different
+//! colors takes the fourth color.
+//!
+//! Colored cells live on a [StateGrid], where state 0 is dead and states 1
+//! to `n` are the `n` colors of live cells.
+
+use std::fmt;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::game::Grid;
+use crate::render::Rgb;
+use crate::rule::Rule;
+use crate::states::StateGrid;
+
+/// State of a dead cell.
+pub const DEAD: u8 = 0;
+
+/// A multi-color variant of Life.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ColorVariant {
+    /// Two colors, states 1 and 2.
+    Immigration,
+
+    /// Four colors, states 1 to 4.
+    QuadLife,
+}
+
+impl ColorVariant {
+    /// Get the number of colors of live cells.
+    pub fn get_num_colors(self) -> u8 {
+        match self {
+            ColorVariant::Immigration => 2,
+            ColorVariant::QuadLife => 4,
+        }
+    }
+
+    /// Resolve the color of a newborn cell from the colors of its parents.
+    ///
+    /// The most common color wins. If every color but one is tied, as with
+    /// three parents of different colors in QuadLife, the missing color wins;
+    /// any other tie goes to the lowest color.
+    ///
+    /// # Arguments
+    /// * `parent_counts` - The number of live neighbors of each color,
+    ///   indexed by state minus one.
+    pub fn resolve_birth(self, parent_counts: &[u8]) -> u8 {
+        let counts = &parent_counts[..self.get_num_colors() as usize];
+        let max = counts.iter().copied().max().unwrap_or(0);
+        let num_tied = counts.iter().filter(|&&count| count == max).count();
+
+        if num_tied > 1 && num_tied + 1 == counts.len() {
+            if let Some(missing) = counts.iter().position(|&count| count != max) {
+                if counts[missing] == 0 {
+                    return missing as u8 + 1;
+                }
+            }
+        }
+
+        counts
+            .iter()
+            .position(|&count| count == max)
+            .map_or(1, |color| color as u8 + 1)
+    }
+}
+
+impl fmt::Display for ColorVariant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColorVariant::Immigration => write!(f, "Immigration"),
+            ColorVariant::QuadLife => write!(f, "QuadLife"),
+        }
+    }
+}
+
+/// Build the palette of colors indexed by state for a variant, for
+/// [AnsiRenderer::render_states](crate::render::ansi::AnsiRenderer::render_states).
+pub fn palette(variant: ColorVariant) -> Vec<Rgb> {
+    let colors = [
+        Rgb(230, 60, 50),
+        Rgb(50, 120, 230),
+        Rgb(60, 200, 80),
+        Rgb(240, 200, 40),
+    ];
+
+    let mut palette = vec![Rgb::DARK_GREY];
+    palette.extend_from_slice(&colors[..variant.get_num_colors() as usize]);
+    palette
+}
+
+/// Simulation of a multi-color variant of a Life-like [Rule] on a bounded
+/// [StateGrid].
+#[derive(Clone, Debug)]
+pub struct ColorSim {
+    /// The cell states, 0 for dead cells and the color for live cells.
+    grid: StateGrid,
+
+    /// Buffer the next generation is computed into.
+    next: StateGrid,
+
+    /// The [Rule] deciding births and survivals.
+    rule: Rule,
+
+    /// The variant resolving the colors of newborn cells.
+    variant: ColorVariant,
+
+    /// The simulation's current generation.
+    generation: u32,
+}
+
+impl ColorSim {
+    /// Create a new simulation of dead cells under Conway's rule.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the grid.
+    /// * `num_cols` - The number of columns (width) of the grid.
+    /// * `variant` - The variant resolving the colors of newborn cells.
+    pub fn new(num_rows: u32, num_cols: u32, variant: ColorVariant) -> ColorSim {
+        ColorSim::new_with_grid(StateGrid::new(num_rows, num_cols), variant)
+    }
+
+    /// Create a new simulation under Conway's rule from existing cell states.
+    /// States above the variant's number of colors take its last color.
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cell states.
+    /// * `variant` - The variant resolving the colors of newborn cells.
+    pub fn new_with_grid(mut grid: StateGrid, variant: ColorVariant) -> ColorSim {
+        let num_colors = variant.get_num_colors();
+
+        for row in 0..grid.get_num_rows() {
+            for col in 0..grid.get_num_cols() {
+                if grid.get(row, col) > num_colors {
+                    grid.set(row, col, num_colors);
+                }
+            }
+        }
+
+        ColorSim {
+            next: grid.clone(),
+            grid,
+            rule: Rule::default(),
+            variant,
+            generation: 0,
+        }
+    }
+
+    /// Create a new simulation from the live cells of a [Grid], giving each
+    /// a random color.
+    ///
+    /// # Arguments
+    /// * `grid` - The initial live cells.
+    /// * `variant` - The variant resolving the colors of newborn cells.
+    /// * `seed` - Seed of the random number generator choosing the colors.
+    pub fn from_grid(grid: &Grid, variant: ColorVariant, seed: u64) -> ColorSim {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut states = StateGrid::new(grid.get_num_rows(), grid.get_num_cols());
+
+        for row in 0..grid.get_num_rows() {
+            for col in 0..grid.get_num_cols() {
+                if grid.get(row, col).is_alive() {
+                    states.set(row, col, rng.random_range(1..=variant.get_num_colors()));
+                }
+            }
+        }
+
+        ColorSim::new_with_grid(states, variant)
+    }
+
+    /// Get the simulation's cell states.
+    pub fn get_grid(&self) -> &StateGrid {
+        &self.grid
+    }
+
+    /// Get the simulation's [Rule].
+    pub fn get_rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Set the [Rule] deciding births and survivals.
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
+    /// Get the variant resolving the colors of newborn cells.
+    pub fn get_variant(&self) -> ColorVariant {
+        self.variant
+    }
+
+    /// Get the simulation's current generation.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Get the number of live cells of any color.
+    pub fn get_population(&self) -> u64 {
+        self.grid
+            .get_states()
+            .iter()
+            .filter(|&&state| state != DEAD)
+            .count() as u64
+    }
+
+    /// Get the number of live cells of a color.
+    pub fn get_color_population(&self, color: u8) -> u64 {
+        self.grid.count(color)
+    }
+
+    /// Determine if any cell is alive.
+    pub fn is_any_cell_alive(&self) -> bool {
+        self.grid.get_states().iter().any(|&state| state != DEAD)
+    }
+
+    /// Set the specified cells alive with a color.
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (row, col) to set as alive.
+    /// * `color` - The color of the cells, from 1 to the number of colors.
+    pub fn set_cells(&mut self, cells: &[(u32, u32)], color: u8) {
+        for &(row, col) in cells {
+            self.set_cell(row, col, color);
+        }
+    }
+
+    /// Set the state of a specified cell, 0 for dead or its color.
+    pub fn set_cell(&mut self, row: u32, col: u32, state: u8) {
+        self.grid
+            .set(row, col, state.min(self.variant.get_num_colors()));
+    }
+
+    /// Advance the simulation by one generation.
+    pub fn step(&mut self) {
+        self.generation += 1;
+
+        let (grid, rule, variant) = (&self.grid, &self.rule, self.variant);
+        let num_colors = variant.get_num_colors();
+
+        grid.map_into(&mut self.next, |row, col, state| {
+            let neighbor_count = grid.count_neighbors(row, col, |neighbor| neighbor != DEAD);
+
+            match state {
+                DEAD if rule.is_birth(neighbor_count) => {
+                    let mut parent_counts = [0; 4];
+                    for (color, count) in parent_counts.iter_mut().enumerate() {
+                        let color = color as u8 + 1;
+                        if color <= num_colors {
+                            *count = grid.count_neighbors(row, col, |neighbor| neighbor == color);
+                        }
+                    }
+
+                    variant.resolve_birth(&parent_counts)
+                }
+                DEAD => DEAD,
+                _ if rule.is_survival(neighbor_count) => state,
+                _ => DEAD,
+            }
+        });
+
+        std::mem::swap(&mut self.grid, &mut self.next);
+    }
+
+    /// Advance the simulation by `n` generations.
+    pub fn step_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+}
+
+impl fmt::Display for ColorSim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.grid.fmt(f)
+    }
+}
@@ -0,0 +1,356 @@
+//! Defaults for the command-line flags read from a `gameoflife.toml` file.
+//!
+//! Every key is optional, and a flag given on the command line always wins
+//! over the file:
+//!
+//! ```toml
+//! rows = 40
+//! cols = 80
+//! generations = 1000
+//! delay = 50
+//! rule = "B36/S23"
+//! topology = "torus"
+//! render = "ansi"
+//!
+//! [colors]
+//! alive = "#ffffff"
+//! dead = "#181818"
+//! background = "#000000"
+//! grid_lines = "#404040"
+//! old = "#285ac8"
+//! states = ["#181818", "#ffffff", "#ffc800"]
+//!
+//! [keys]
+//! randomize = "R"
+//! pause = ["space", "enter"]
+//! ```
+
+#[cfg(any(feature = "tui", feature = "gui"))]
+use std::collections::BTreeMap;
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use clap::parser::ValueSource;
+use clap::{ArgMatches, ValueEnum};
+use gameoflife_rs::render::{Rgb, Theme};
+use gameoflife_rs::{Rule, Topology};
+use serde::{Deserialize, Deserializer};
+
+use crate::cli::{Cli, RenderMode};
+#[cfg(any(feature = "tui", feature = "gui"))]
+use crate::keymap::{Action, Key, Keymap};
+
+/// Name of the configuration file.
+const FILE_NAME: &str = "gameoflife.toml";
+
+/// Error raised while loading a configuration file.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The configuration file could not be read.
+    Io { path: PathBuf, err: io::Error },
+
+    /// The configuration file is not valid TOML or holds an invalid value.
+    Invalid { path: PathBuf, err: toml::de::Error },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, err } => write!(f, "{}: {}", path.display(), err),
+            ConfigError::Invalid { path, err } => write!(f, "{}: {}", path.display(), err),
+        }
+    }
+}
+
+impl error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ConfigError::Io { err, .. } => Some(err),
+            ConfigError::Invalid { err, .. } => Some(err),
+        }
+    }
+}
+
+/// Deserialize a value from a string parsed with [FromStr].
+fn parse<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let value = String::deserialize(deserializer)?;
+
+    value.parse().map(Some).map_err(serde::de::Error::custom)
+}
+
+/// Deserialize a [RenderMode] by its command-line name.
+fn parse_render<'de, D>(deserializer: D) -> Result<Option<RenderMode>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+
+    RenderMode::from_str(&value, true)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+/// Parse an [Rgb] color written as `#rrggbb`.
+fn color_from_str<E: serde::de::Error>(value: &str) -> Result<Rgb, E> {
+    let invalid = || E::custom(format!("invalid color '{}'", value));
+
+    let hex = value.strip_prefix('#').ok_or_else(invalid)?;
+    if hex.len() != 6 {
+        return Err(invalid());
+    }
+
+    let channel = |index: usize| {
+        hex.get(index..index + 2)
+            .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+            .ok_or_else(invalid)
+    };
+
+    Ok(Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Deserialize an [Rgb] color written as `#rrggbb`.
+fn parse_color<'de, D>(deserializer: D) -> Result<Option<Rgb>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+
+    color_from_str(&value).map(Some)
+}
+
+/// Deserialize a list of [Rgb] colors written as `#rrggbb`.
+fn parse_colors<'de, D>(deserializer: D) -> Result<Vec<Rgb>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let values = Vec::<String>::deserialize(deserializer)?;
+
+    values.iter().map(|value| color_from_str(value)).collect()
+}
+
+/// One key, or a list of keys.
+#[cfg(any(feature = "tui", feature = "gui"))]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Deserialize the `[keys]` table of actions and the keys bound to them,
+/// refusing a key bound to two actions.
+#[cfg(any(feature = "tui", feature = "gui"))]
+fn parse_keys<'de, D>(deserializer: D) -> Result<Vec<(Action, Vec<Key>)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let table = BTreeMap::<String, KeyList>::deserialize(deserializer)?;
+    let mut bindings: Vec<(Action, Vec<Key>)> = Vec::new();
+
+    for (name, keys) in table {
+        let action: Action = name.parse().map_err(serde::de::Error::custom)?;
+        let keys = match keys {
+            KeyList::One(key) => vec![key],
+            KeyList::Many(keys) => keys,
+        };
+
+        let keys = keys
+            .iter()
+            .map(|key| key.parse::<Key>())
+            .collect::<Result<Vec<Key>, String>>()
+            .map_err(serde::de::Error::custom)?;
+
+        for (other, other_keys) in bindings.iter() {
+            if let Some(key) = keys.iter().find(|key| other_keys.contains(key)) {
+                return Err(serde::de::Error::custom(format!(
+                    "key '{}' is bound to both {} and {}",
+                    key,
+                    other.get_name(),
+                    action.get_name()
+                )));
+            }
+        }
+
+        bindings.push((action, keys));
+    }
+
+    Ok(bindings)
+}
+
+/// Colors of the [Theme] of every renderer.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Colors {
+    /// Color of newborn live cells.
+    #[serde(default, deserialize_with = "parse_color")]
+    alive: Option<Rgb>,
+
+    /// Color of dead cells.
+    #[serde(default, deserialize_with = "parse_color")]
+    dead: Option<Rgb>,
+
+    /// Color around the grid.
+    #[serde(default, deserialize_with = "parse_color")]
+    background: Option<Rgb>,
+
+    /// Color of the lines drawn between cells.
+    #[serde(default, deserialize_with = "parse_color")]
+    grid_lines: Option<Rgb>,
+
+    /// Color live cells fade to as they age.
+    #[serde(default, deserialize_with = "parse_color")]
+    old: Option<Rgb>,
+
+    /// Colors of the first cell states of multi-state rules.
+    #[serde(default, deserialize_with = "parse_colors")]
+    states: Vec<Rgb>,
+}
+
+/// Defaults for the command-line flags.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Number of rows (height) of the grid.
+    rows: Option<u32>,
+
+    /// Number of columns (width) of the grid.
+    cols: Option<u32>,
+
+    /// Maximum number of generations to simulate.
+    generations: Option<u32>,
+
+    /// Delay between generations in milliseconds.
+    delay: Option<u64>,
+
+    /// Rule of the simulation.
+    #[serde(default, deserialize_with = "parse")]
+    rule: Option<Rule>,
+
+    /// How cells on the edges find their neighbors.
+    #[serde(default, deserialize_with = "parse")]
+    topology: Option<Topology>,
+
+    /// How each generation is rendered.
+    #[serde(default, deserialize_with = "parse_render")]
+    render: Option<RenderMode>,
+
+    /// Colors of every renderer.
+    #[serde(default)]
+    colors: Colors,
+
+    /// Keys of the interactive user interfaces, by action.
+    #[cfg(any(feature = "tui", feature = "gui"))]
+    #[serde(default, deserialize_with = "parse_keys")]
+    keys: Vec<(Action, Vec<Key>)>,
+}
+
+impl Config {
+    /// Load the configuration from a file, or from `gameoflife.toml` in the
+    /// current directory or the user's configuration directory if no file is
+    /// given. Without any configuration file every flag keeps its default.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the configuration file, which must exist if given.
+    pub fn load(path: Option<&Path>) -> Result<Config, ConfigError> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match Config::default_paths()
+                .into_iter()
+                .find(|path| path.is_file())
+            {
+                Some(path) => path,
+                None => return Ok(Config::default()),
+            },
+        };
+
+        let toml = fs::read_to_string(&path).map_err(|err| ConfigError::Io {
+            path: path.clone(),
+            err,
+        })?;
+
+        toml::from_str(&toml).map_err(|err| ConfigError::Invalid { path, err })
+    }
+
+    /// Get the paths searched for a configuration file, in order.
+    fn default_paths() -> Vec<PathBuf> {
+        let mut paths = vec![PathBuf::from(FILE_NAME)];
+
+        let config_dir = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")));
+        if let Some(config_dir) = config_dir {
+            paths.push(config_dir.join("gameoflife-rs").join(FILE_NAME));
+        }
+
+        paths
+    }
+
+    /// Fill in the flags not given on the command line.
+    ///
+    /// # Arguments
+    /// * `cli` - The parsed command-line flags.
+    /// * `matches` - The matches `cli` was parsed from, telling which flags
+    ///   were given.
+    pub fn apply(&self, cli: &mut Cli, matches: &ArgMatches) {
+        let given = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+
+        cli.rows = cli.rows.or(self.rows);
+        cli.cols = cli.cols.or(self.cols);
+        cli.rule = cli.rule.or(self.rule);
+
+        if let (Some(generations), false) = (self.generations, given("generations")) {
+            cli.generations = generations;
+        }
+        if let (Some(delay), false) = (self.delay, given("delay")) {
+            cli.delay = delay;
+        }
+        if let (Some(topology), false) = (self.topology, given("topology")) {
+            cli.topology = topology;
+        }
+        if let (Some(render), false) = (self.render, given("render")) {
+            cli.render = render;
+        }
+    }
+
+    /// Override the colors of a theme with those of the configuration.
+    ///
+    /// # Arguments
+    /// * `theme` - The theme used without a configuration.
+    pub fn theme(&self, theme: Theme) -> Theme {
+        let colors = &self.colors;
+
+        Theme {
+            alive: colors.alive.unwrap_or(theme.alive),
+            dead: colors.dead.unwrap_or(theme.dead),
+            background: colors.background.unwrap_or(theme.background),
+            grid_lines: colors.grid_lines.unwrap_or(theme.grid_lines),
+            old: colors.old.or(theme.old),
+            states: if colors.states.is_empty() {
+                theme.states
+            } else {
+                colors.states.clone()
+            },
+        }
+    }
+
+    /// Get the default keys with the actions of the configuration rebound.
+    #[cfg(any(feature = "tui", feature = "gui"))]
+    pub fn keymap(&self) -> Keymap {
+        let mut keymap = Keymap::default();
+        for (action, keys) in self.keys.iter() {
+            keymap.bind(*action, keys);
+        }
+
+        keymap
+    }
+}
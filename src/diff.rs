@@ -0,0 +1,134 @@
+//! Comparison of the live cells of two grids.
+//!
+//! [Grid::diff] finds the cells alive in only one of two grids, such as a
+//! replayed run and the run it recorded, or two backends stepping the same
+//! soup, and measures how far apart they are: the Hamming distance counts
+//! the cells which differ, and the Jaccard similarity the fraction of live
+//! cells the grids share.
+//!
+//! ```
+//! use gameoflife_rs::Grid;
+//!
+//! let mut before = Grid::new(5, 5);
+//! before.set_cells(&[(2, 1), (2, 2), (2, 3)]);
+//!
+//! let mut after = Grid::new(5, 5);
+//! after.set_cells(&[(1, 2), (2, 2), (3, 2)]);
+//!
+//! let diff = before.diff(&after);
+//!
+//! assert_eq!(diff.get_added(), &[(1, 2), (3, 2)]);
+//! assert_eq!(diff.get_removed(), &[(2, 1), (2, 3)]);
+//! assert_eq!(diff.get_hamming_distance(), 4);
+//! assert_eq!(diff.get_jaccard_similarity(), 0.2);
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::automaton::CellState;
+use crate::game::Grid;
+
+/// The live cells which differ between two grids, from one to the other.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GridDiff {
+    /// The (row, col) of each cell alive only in the other grid, in
+    /// row-major order.
+    added: Vec<(u32, u32)>,
+
+    /// The (row, col) of each cell alive only in this grid, in row-major
+    /// order.
+    removed: Vec<(u32, u32)>,
+
+    /// Number of cells alive in both grids.
+    num_shared: u64,
+}
+
+impl GridDiff {
+    /// Get the cells alive only in the other grid, in row-major order.
+    pub fn get_added(&self) -> &[(u32, u32)] {
+        &self.added
+    }
+
+    /// Get the cells alive only in this grid, in row-major order.
+    pub fn get_removed(&self) -> &[(u32, u32)] {
+        &self.removed
+    }
+
+    /// Get the number of cells alive in both grids.
+    pub fn get_num_shared(&self) -> u64 {
+        self.num_shared
+    }
+
+    /// Determine if the grids have the same live cells.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Get the Hamming distance between the grids: the number of cells alive
+    /// in one but not the other.
+    pub fn get_hamming_distance(&self) -> u64 {
+        (self.added.len() + self.removed.len()) as u64
+    }
+
+    /// Get the Jaccard similarity of the live cells of the grids: the number
+    /// alive in both over the number alive in either, from 0 for no shared
+    /// cells to 1 for the same cells. Two empty grids are the same.
+    pub fn get_jaccard_similarity(&self) -> f64 {
+        let num_either = self.num_shared + self.get_hamming_distance();
+        if num_either == 0 {
+            return 1.0;
+        }
+
+        self.num_shared as f64 / num_either as f64
+    }
+}
+
+impl fmt::Display for GridDiff {
+    /// Display the number of cells added, removed, and shared.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} added, {} removed, {} shared",
+            self.added.len(),
+            self.removed.len(),
+            self.num_shared
+        )
+    }
+}
+
+impl<S: CellState> Grid<S> {
+    /// Compare the live cells of the [Grid] with those of another, finding
+    /// the cells alive in only one of them.
+    ///
+    /// Grids of different sizes are compared over the larger of their rows
+    /// and columns, the cells beyond the edges of the smaller being dead.
+    /// [World::diff](crate::World::diff) instead gives the births and deaths
+    /// from one generation to the next as a [Diff](crate::Diff); as this
+    /// method shadows it, call it on a [Grid] as `World::diff(&grid, &other)`.
+    ///
+    /// # Arguments
+    /// * `other` - The [Grid] to compare to.
+    pub fn diff(&self, other: &Grid<S>) -> GridDiff {
+        let num_rows = self.get_num_rows().max(other.get_num_rows());
+        let num_cols = self.get_num_cols().max(other.get_num_cols());
+        let is_alive = |grid: &Grid<S>, row: u32, col: u32| {
+            row < grid.get_num_rows() && col < grid.get_num_cols() && grid.get(row, col).is_alive()
+        };
+
+        let mut diff = GridDiff::default();
+
+        for row in 0..num_rows {
+            for col in 0..num_cols {
+                match (is_alive(self, row, col), is_alive(other, row, col)) {
+                    (true, true) => diff.num_shared += 1,
+                    (true, false) => diff.removed.push((row, col)),
+                    (false, true) => diff.added.push((row, col)),
+                    (false, false) => {}
+                }
+            }
+        }
+
+        diff
+    }
+}
@@ -0,0 +1,181 @@
+//! Drawing of grids on embedded displays with
+//! [embedded-graphics](https://docs.rs/embedded-graphics).
+//!
+//! A [Grid] is itself a [Drawable] in [BinaryColor], one pixel per cell from
+//! the display's origin, which suits monochrome displays such as the SSD1306.
+//! [Grid::drawing] positions and scales a grid in any [PixelColor], e.g. to
+//! draw it in color on an ST7735. Neither needs the standard library.
+
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::{OriginDimensions, Point, Size};
+use embedded_graphics_core::pixelcolor::{BinaryColor, PixelColor};
+use embedded_graphics_core::primitives::Rectangle;
+use embedded_graphics_core::Drawable;
+
+use crate::game::Grid;
+
+/// Options controlling how a [Grid] is drawn.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct GridStyle<C> {
+    /// Size of a cell in pixels.
+    pub cell_size: u32,
+
+    /// Color of live cells.
+    pub alive: C,
+
+    /// Color of dead cells, or [None] to leave the display beneath them
+    /// untouched.
+    pub dead: Option<C>,
+}
+
+impl<C> GridStyle<C> {
+    /// Create a new [GridStyle] drawing live cells in a color and leaving
+    /// dead cells untouched.
+    ///
+    /// # Arguments
+    /// * `cell_size` - Size of a cell in pixels.
+    /// * `alive` - Color of live cells.
+    pub fn new(cell_size: u32, alive: C) -> GridStyle<C> {
+        GridStyle {
+            cell_size,
+            alive,
+            dead: None,
+        }
+    }
+
+    /// Draw dead cells in a color, returning the updated [GridStyle].
+    ///
+    /// # Arguments
+    /// * `dead` - Color of dead cells.
+    pub fn with_dead(mut self, dead: C) -> GridStyle<C> {
+        self.dead = Some(dead);
+        self
+    }
+}
+
+/// A [Grid] positioned and styled to be drawn on a [DrawTarget].
+#[derive(Copy, Clone, Debug)]
+pub struct GridDrawing<'a, C> {
+    /// The [Grid] to draw.
+    grid: &'a Grid,
+
+    /// Position of the top left corner of the grid on the display.
+    position: Point,
+
+    /// The [GridStyle] of the grid.
+    style: GridStyle<C>,
+}
+
+impl<C: PixelColor> GridDrawing<'_, C> {
+    /// Get the area of the display covered by the grid.
+    pub fn bounding_box(&self) -> Rectangle {
+        let cell_size = self.style.cell_size;
+
+        Rectangle::new(
+            self.position,
+            Size::new(
+                self.grid.get_num_cols() * cell_size,
+                self.grid.get_num_rows() * cell_size,
+            ),
+        )
+    }
+
+    /// Get the area of the display covered by a cell.
+    fn cell_area(&self, row: u32, col: u32) -> Rectangle {
+        let cell_size = self.style.cell_size;
+        let offset = Point::new((col * cell_size) as i32, (row * cell_size) as i32);
+
+        Rectangle::new(self.position + offset, Size::new_equal(cell_size))
+    }
+}
+
+impl<C: PixelColor> Drawable for GridDrawing<'_, C> {
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let (grid, style) = (self.grid, &self.style);
+
+        if style.cell_size == 0 {
+            return Ok(());
+        }
+
+        match style.dead {
+            // every pixel is drawn, so stream them in one contiguous area
+            Some(dead) => {
+                let cell_size = style.cell_size;
+                let area = self.bounding_box();
+                let width = area.size.width;
+                let colors = (0..area.size.height).flat_map(move |y| {
+                    (0..width).map(move |x| {
+                        if grid.get(y / cell_size, x / cell_size).is_alive() {
+                            style.alive
+                        } else {
+                            dead
+                        }
+                    })
+                });
+
+                target.fill_contiguous(&area, colors)
+            }
+            None => {
+                for row in 0..grid.get_num_rows() {
+                    for col in 0..grid.get_num_cols() {
+                        if grid.get(row, col).is_alive() {
+                            target.fill_solid(&self.cell_area(row, col), style.alive)?;
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Grid {
+    /// Position and style the [Grid] to be drawn on a [DrawTarget].
+    ///
+    /// # Arguments
+    /// * `position` - Position of the top left corner of the grid on the
+    ///   display.
+    /// * `style` - The [GridStyle] of the grid.
+    pub fn drawing<C: PixelColor>(
+        &self,
+        position: Point,
+        style: GridStyle<C>,
+    ) -> GridDrawing<'_, C> {
+        GridDrawing {
+            grid: self,
+            position,
+            style,
+        }
+    }
+}
+
+impl OriginDimensions for Grid {
+    /// Get the size of the [Grid] drawn one pixel per cell.
+    fn size(&self) -> Size {
+        Size::new(self.get_num_cols(), self.get_num_rows())
+    }
+}
+
+impl Drawable for Grid {
+    type Color = BinaryColor;
+    type Output = ();
+
+    /// Draw the [Grid] one pixel per cell from the display's origin, with
+    /// live cells [On](BinaryColor::On) and dead cells
+    /// [Off](BinaryColor::Off).
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let style = GridStyle::new(1, BinaryColor::On).with_dead(BinaryColor::Off);
+
+        self.drawing(Point::zero(), style).draw(target)
+    }
+}
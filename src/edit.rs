@@ -0,0 +1,168 @@
+//! Editing tools setting whole shapes of cells at once: straight lines,
+//! rectangles, and a random spray brush.
+//!
+//! ```
+//! use gameoflife_rs::{Cell, Grid};
+//!
+//! let mut grid = Grid::new(5, 5);
+//! grid.draw_line((0, 0), (4, 4), Cell::Alive);
+//! grid.draw_rect((1, 3), (3, 4), Cell::Alive, false);
+//!
+//! assert_eq!(grid.get_population(), 10);
+//! ```
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::game::{Cell, ConwaySim, Grid};
+
+impl Grid {
+    /// Set a cell if it is within the [Grid], skipping it otherwise.
+    fn set_clipped(&mut self, row: i64, col: i64, state: Cell) {
+        if row >= 0
+            && col >= 0
+            && row < self.get_num_rows() as i64
+            && col < self.get_num_cols() as i64
+        {
+            self.set(row as u32, col as u32, state);
+        }
+    }
+
+    /// Set the cells of a straight line between two cells, both included,
+    /// as chosen by Bresenham's line algorithm. Cells outside the [Grid] are
+    /// skipped.
+    ///
+    /// # Arguments
+    /// * `from` - The (row, col) of the cell the line starts at.
+    /// * `to` - The (row, col) of the cell the line ends at.
+    /// * `state` - The state to set the cells of the line to.
+    pub fn draw_line(&mut self, from: (u32, u32), to: (u32, u32), state: Cell) {
+        let (mut row, mut col) = (from.0 as i64, from.1 as i64);
+        let (to_row, to_col) = (to.0 as i64, to.1 as i64);
+
+        let (d_row, d_col) = (-(to_row - row).abs(), (to_col - col).abs());
+        let (step_row, step_col) = ((to_row - row).signum(), (to_col - col).signum());
+        let mut error = d_col + d_row;
+
+        loop {
+            self.set_clipped(row, col, state);
+
+            if row == to_row && col == to_col {
+                break;
+            }
+
+            let doubled = 2 * error;
+            if doubled >= d_row {
+                error += d_row;
+                col += step_col;
+            }
+            if doubled <= d_col {
+                error += d_col;
+                row += step_row;
+            }
+        }
+    }
+
+    /// Set the cells of a rectangle between two opposite corner cells, both
+    /// included. Cells outside the [Grid] are skipped.
+    ///
+    /// # Arguments
+    /// * `from` - The (row, col) of a corner of the rectangle.
+    /// * `to` - The (row, col) of the opposite corner.
+    /// * `state` - The state to set the cells of the rectangle to.
+    /// * `filled` - Whether to set the cells inside the rectangle, or only
+    ///   its outline.
+    pub fn draw_rect(&mut self, from: (u32, u32), to: (u32, u32), state: Cell, filled: bool) {
+        let (first_row, last_row) = (from.0.min(to.0) as i64, from.0.max(to.0) as i64);
+        let (first_col, last_col) = (from.1.min(to.1) as i64, from.1.max(to.1) as i64);
+
+        for row in first_row..=last_row {
+            for col in first_col..=last_col {
+                let edge =
+                    row == first_row || row == last_row || col == first_col || col == last_col;
+
+                if filled || edge {
+                    self.set_clipped(row, col, state);
+                }
+            }
+        }
+    }
+
+    /// Bring random cells within a circle to life, like a spray can. Cells
+    /// outside the [Grid] are skipped, and live cells stay alive.
+    ///
+    /// # Arguments
+    /// * `center` - The (row, col) of the cell at the center of the circle.
+    /// * `radius` - Radius of the circle in cells, 0 spraying only the center.
+    /// * `density` - Probability of each cell in the circle being brought to
+    ///   life, from 0.0 to 1.0.
+    /// * `seed` - Seed of the random number generator.
+    ///
+    /// # Panics
+    /// Panics if `density` is not within 0.0 to 1.0.
+    pub fn spray(&mut self, center: (u32, u32), radius: u32, density: f64, seed: u64) {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let radius = radius as i64;
+
+        for d_row in -radius..=radius {
+            for d_col in -radius..=radius {
+                if d_row * d_row + d_col * d_col > radius * radius {
+                    continue;
+                }
+
+                if rng.random_bool(density) {
+                    self.set_clipped(
+                        center.0 as i64 + d_row,
+                        center.1 as i64 + d_col,
+                        Cell::Alive,
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl ConwaySim {
+    /// Set the cells of a straight line in the simulation's [Grid], as with
+    /// [Grid::draw_line].
+    ///
+    /// # Arguments
+    /// * `from` - The (row, col) of the cell the line starts at.
+    /// * `to` - The (row, col) of the cell the line ends at.
+    /// * `state` - The state to set the cells of the line to.
+    pub fn draw_line(&mut self, from: (u32, u32), to: (u32, u32), state: Cell) {
+        self.get_world_mut().draw_line(from, to, state);
+        self.record_edit();
+    }
+
+    /// Set the cells of a rectangle in the simulation's [Grid], as with
+    /// [Grid::draw_rect].
+    ///
+    /// # Arguments
+    /// * `from` - The (row, col) of a corner of the rectangle.
+    /// * `to` - The (row, col) of the opposite corner.
+    /// * `state` - The state to set the cells of the rectangle to.
+    /// * `filled` - Whether to set the cells inside the rectangle, or only
+    ///   its outline.
+    pub fn draw_rect(&mut self, from: (u32, u32), to: (u32, u32), state: Cell, filled: bool) {
+        self.get_world_mut().draw_rect(from, to, state, filled);
+        self.record_edit();
+    }
+
+    /// Bring random cells within a circle of the simulation's [Grid] to
+    /// life, as with [Grid::spray].
+    ///
+    /// # Arguments
+    /// * `center` - The (row, col) of the cell at the center of the circle.
+    /// * `radius` - Radius of the circle in cells, 0 spraying only the center.
+    /// * `density` - Probability of each cell in the circle being brought to
+    ///   life, from 0.0 to 1.0.
+    /// * `seed` - Seed of the random number generator.
+    ///
+    /// # Panics
+    /// Panics if `density` is not within 0.0 to 1.0.
+    pub fn spray(&mut self, center: (u32, u32), radius: u32, density: f64, seed: u64) {
+        self.get_world_mut().spray(center, radius, density, seed);
+        self.record_edit();
+    }
+}
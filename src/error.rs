@@ -0,0 +1,307 @@
+//! The crate-wide [GolError], which the errors of each module convert into.
+//!
+//! Each module reports its own error type, e.g. [RleError] or [GridError],
+//! so that callers can match on the ways a single operation fails. Code which
+//! loads, builds, and edits grids across several formats can instead use `?`
+//! to collect them all as a [GolError], where every parse error carries the
+//! line and column it was found at.
+//!
+//! ```
+//! use gameoflife_rs::{GolError, Grid};
+//!
+//! fn load(rle: &str) -> Result<Grid, GolError> {
+//!     let mut grid = Grid::from_rle(rle)?;
+//!     grid.try_set(0, 0, gameoflife_rs::Cell::Alive)?;
+//!     Ok(grid)
+//! }
+//!
+//! match load("x = 3, y = 1\no?o!") {
+//!     Err(GolError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 2)),
+//!     other => panic!("unexpected result: {:?}", other),
+//! }
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::error;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(feature = "std")]
+use crate::checkpoint::CheckpointError;
+use crate::game::GridError;
+#[cfg(feature = "std")]
+use crate::life106::Life106Error;
+#[cfg(feature = "std")]
+use crate::macrocell::MacrocellError;
+#[cfg(feature = "std")]
+use crate::plaintext::PlaintextError;
+use crate::rle::RleError;
+use crate::rule::RuleError;
+#[cfg(feature = "std")]
+use crate::ruletable::RuleTableError;
+
+/// Error raised by any fallible operation of the crate.
+#[derive(Debug)]
+pub enum GolError {
+    /// The (row, col) position is outside of the grid.
+    OutOfBounds {
+        row: u32,
+        col: u32,
+        num_rows: u32,
+        num_cols: u32,
+    },
+
+    /// A cell at the (row, col) position is already alive.
+    Conflict { row: u32, col: u32 },
+
+    /// A pattern or rulestring could not be parsed.
+    ///
+    /// The `line` and `column` are counted from 1. Errors concerning a whole
+    /// line, such as a missing header, are reported at its first column.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
+    /// The (num_rows, num_cols) size found does not match the one expected.
+    DimensionMismatch {
+        expected: (u32, u32),
+        found: (u32, u32),
+    },
+
+    /// A grid cannot have the requested number of rows and columns, either
+    /// because one is zero or because it would hold too many cells.
+    InvalidDimensions { num_rows: u32, num_cols: u32 },
+
+    /// A file could not be read or written.
+    #[cfg(feature = "std")]
+    Io(io::Error),
+}
+
+impl GolError {
+    /// Create a new [GolError::Parse] at a line and column.
+    fn parse(line: usize, column: usize, message: String) -> GolError {
+        GolError::Parse {
+            line,
+            column,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for GolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GolError::OutOfBounds {
+                row,
+                col,
+                num_rows,
+                num_cols,
+            } => write!(
+                f,
+                "cell ({}, {}) is outside of the {}x{} grid",
+                row, col, num_rows, num_cols
+            ),
+            GolError::Conflict { row, col } => {
+                write!(f, "cell ({}, {}) is already alive", row, col)
+            }
+            GolError::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            GolError::DimensionMismatch { expected, found } => write!(
+                f,
+                "expected a {}x{} grid but found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            GolError::InvalidDimensions { num_rows, num_cols } => {
+                write!(f, "a grid cannot be {}x{}", num_rows, num_cols)
+            }
+            #[cfg(feature = "std")]
+            GolError::Io(err) => write!(f, "unable to access file: {}", err),
+        }
+    }
+}
+
+impl error::Error for GolError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            GolError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for GolError {
+    fn from(err: io::Error) -> GolError {
+        GolError::Io(err)
+    }
+}
+
+impl From<GridError> for GolError {
+    fn from(err: GridError) -> GolError {
+        match err {
+            GridError::OutOfBounds {
+                row,
+                col,
+                num_rows,
+                num_cols,
+            } => GolError::OutOfBounds {
+                row,
+                col,
+                num_rows,
+                num_cols,
+            },
+            GridError::Conflict { row, col } => GolError::Conflict { row, col },
+        }
+    }
+}
+
+impl From<RuleError> for GolError {
+    /// A rulestring is parsed on its own, so its errors are reported at the
+    /// first line and column.
+    fn from(err: RuleError) -> GolError {
+        GolError::parse(1, 1, err.to_string())
+    }
+}
+
+impl From<RleError> for GolError {
+    fn from(err: RleError) -> GolError {
+        match err {
+            RleError::MissingHeader => GolError::parse(1, 1, "missing RLE header line".to_string()),
+            RleError::InvalidHeader { line, header } => {
+                GolError::parse(line, 1, format!("invalid RLE header: {}", header))
+            }
+            RleError::Empty { num_rows, num_cols } | RleError::TooLarge { num_rows, num_cols } => {
+                GolError::InvalidDimensions { num_rows, num_cols }
+            }
+            RleError::InvalidTag { line, column, tag } => {
+                GolError::parse(line, column, format!("invalid RLE tag: '{}'", tag))
+            }
+            RleError::Overflow { line, column } => {
+                GolError::parse(line, column, "RLE run count is too large".to_string())
+            }
+            RleError::OutOfBounds {
+                line,
+                column,
+                row,
+                col,
+            } => GolError::parse(
+                line,
+                column,
+                format!(
+                    "RLE cell ({}, {}) is outside of the pattern bounds",
+                    row, col
+                ),
+            ),
+            err @ RleError::UnsupportedRule { .. } => GolError::parse(1, 1, err.to_string()),
+            #[cfg(feature = "std")]
+            RleError::Io(err) => GolError::Io(err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<PlaintextError> for GolError {
+    fn from(err: PlaintextError) -> GolError {
+        match err {
+            PlaintextError::InvalidCharacter {
+                line,
+                column,
+                character,
+            } => GolError::parse(
+                line,
+                column,
+                format!("invalid plaintext character '{}'", character),
+            ),
+            PlaintextError::TooLarge { num_rows, num_cols } => {
+                GolError::InvalidDimensions { num_rows, num_cols }
+            }
+            PlaintextError::Io(err) => GolError::Io(err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Life106Error> for GolError {
+    fn from(err: Life106Error) -> GolError {
+        match err {
+            Life106Error::MissingHeader => {
+                GolError::parse(1, 1, "missing Life 1.06 header line".to_string())
+            }
+            Life106Error::InvalidCoordinate { line } => {
+                GolError::parse(line, 1, "invalid Life 1.06 coordinates".to_string())
+            }
+            Life106Error::TooLarge { num_rows, num_cols } => {
+                GolError::InvalidDimensions { num_rows, num_cols }
+            }
+            Life106Error::Io(err) => GolError::Io(err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<CheckpointError> for GolError {
+    /// A checkpoint is binary, so its format errors are reported at the first
+    /// line and column.
+    fn from(err: CheckpointError) -> GolError {
+        match err {
+            CheckpointError::TooLarge { num_rows, num_cols } => {
+                GolError::InvalidDimensions { num_rows, num_cols }
+            }
+            CheckpointError::Io(err) => GolError::Io(err),
+            err => GolError::parse(1, 1, err.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<MacrocellError> for GolError {
+    fn from(err: MacrocellError) -> GolError {
+        match err {
+            MacrocellError::MissingHeader => {
+                GolError::parse(1, 1, "missing macrocell header line".to_string())
+            }
+            MacrocellError::InvalidRule { line } => {
+                GolError::parse(line, 1, "invalid or unsupported rule".to_string())
+            }
+            MacrocellError::InvalidLine { line } => {
+                GolError::parse(line, 1, "invalid macrocell line".to_string())
+            }
+            MacrocellError::InvalidReference { line } => {
+                GolError::parse(line, 1, "invalid macrocell node reference".to_string())
+            }
+            MacrocellError::TooLarge { num_rows, num_cols } => {
+                GolError::InvalidDimensions { num_rows, num_cols }
+            }
+            err @ MacrocellError::Overflow { .. } => GolError::parse(1, 1, err.to_string()),
+            MacrocellError::Io(err) => GolError::Io(err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<RuleTableError> for GolError {
+    fn from(err: RuleTableError) -> GolError {
+        match err {
+            RuleTableError::MissingSection => {
+                GolError::parse(1, 1, "missing @TABLE or @TREE section".to_string())
+            }
+            RuleTableError::InvalidLine { line } => {
+                GolError::parse(line, 1, "invalid rule line".to_string())
+            }
+            RuleTableError::Unsupported { line } => GolError::parse(
+                line,
+                1,
+                "unsupported neighborhood or symmetries".to_string(),
+            ),
+            RuleTableError::Io(err) => GolError::Io(err),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Downloading of patterns from LifeWiki or another pattern collection.
+
+use std::error::Error;
+use std::fs;
+
+use gameoflife_rs::{Anchor, ConwaySim, Grid, Pattern, PatternMeta, Rule};
+
+use crate::cli::FetchArgs;
+use crate::repl;
+
+/// Download the text of a pattern.
+fn download(url: &str) -> Result<String, ureq::Error> {
+    ureq::get(url).call()?.body_mut().read_to_string()
+}
+
+/// Print the name, author, and comments of a pattern.
+fn print_meta(meta: &PatternMeta) {
+    if let Some(name) = &meta.name {
+        println!("{}", name);
+    }
+    if let Some(author) = &meta.author {
+        println!("by {}", author);
+    }
+    for comment in &meta.comments {
+        println!("{}", comment);
+    }
+}
+
+/// Download a pattern, centered in a grid of the requested size, and drive
+/// it from typed commands.
+pub fn run(args: &FetchArgs) -> Result<(), Box<dyn Error>> {
+    let url = args.url.replace("{name}", &args.name);
+    let text = download(&url).map_err(|err| format!("{}: {}", url, err))?;
+
+    let (pattern, rule) = if url.ends_with(".cells") {
+        (Pattern::from_plaintext(&text)?, Rule::default())
+    } else {
+        (
+            Pattern::from_rle(&text)?,
+            ConwaySim::from_rle(&text)?.get_rule(),
+        )
+    };
+
+    if let Some(path) = &args.output {
+        fs::write(path, &text).map_err(|err| format!("{}: {}", path.display(), err))?;
+    }
+
+    print_meta(pattern.get_meta());
+
+    let num_rows = args.rows.unwrap_or_else(|| pattern.get_num_rows());
+    let num_cols = args.cols.unwrap_or_else(|| pattern.get_num_cols());
+    Grid::check_size(num_rows, num_cols)?;
+
+    let grid = pattern
+        .get_grid()
+        .resized(num_rows, num_cols, Anchor::Center);
+
+    let mut sim = ConwaySim::new_with_grid(grid);
+    sim.set_rule(args.rule.unwrap_or(rule));
+
+    repl::run(sim, None)?;
+
+    Ok(())
+}
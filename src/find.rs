@@ -0,0 +1,81 @@
+//! Locating the occurrences of a pattern within a [Grid].
+
+use alloc::vec::Vec;
+
+use crate::game::Grid;
+use crate::transform::Transform;
+
+impl Grid {
+    /// Determine if a pattern matches the cells of the [Grid] with its top
+    /// left corner at (row, col).
+    fn matches_at(&self, pattern: &Grid, row: u32, col: u32) -> bool {
+        (0..pattern.get_num_rows()).all(|pattern_row| {
+            (0..pattern.get_num_cols()).all(|pattern_col| {
+                self.get(row + pattern_row, col + pattern_col)
+                    == pattern.get(pattern_row, pattern_col)
+            })
+        })
+    }
+
+    /// Find every occurrence of a pattern, in any rotation or reflection,
+    /// returned as the (row, col) of the top left corner of the transformed
+    /// pattern and the [Transform] applied to it.
+    ///
+    /// Every cell of the pattern must match, so dead cells around the live
+    /// cells of a pattern require the occurrence to be isolated. A pattern
+    /// with symmetries is reported once per location, under the first of
+    /// [Transform::ALL] producing that orientation. An empty pattern, with no
+    /// rows or no columns, is found nowhere.
+    ///
+    /// ```
+    /// use gameoflife_rs::{Grid, Transform};
+    ///
+    /// let mut grid = Grid::new(5, 5);
+    /// grid.set_cells(&[(2, 1), (2, 2), (2, 3)]);
+    ///
+    /// let blinker = Grid::from_rle("x = 3, y = 1\n3o!").unwrap();
+    /// assert_eq!(grid.find(&blinker), vec![(2, 1, Transform::Identity)]);
+    ///
+    /// assert!(grid.find(&Grid::new(0, 0)).is_empty());
+    /// assert!(grid.find(&Grid::new(0, 3)).is_empty());
+    /// ```
+    ///
+    /// # Arguments
+    /// * `pattern` - The pattern to look for, no larger than the [Grid].
+    pub fn find(&self, pattern: &Grid) -> Vec<(u32, u32, Transform)> {
+        if pattern.get_num_rows() == 0 || pattern.get_num_cols() == 0 {
+            return Vec::new();
+        }
+
+        let mut orientations: Vec<(Grid, Transform)> = Vec::new();
+
+        for &transform in Transform::ALL.iter() {
+            let oriented = pattern.transformed(transform);
+
+            if orientations.iter().all(|(other, _)| *other != oriented) {
+                orientations.push((oriented, transform));
+            }
+        }
+
+        let mut found = Vec::new();
+
+        for (oriented, transform) in orientations.iter() {
+            if oriented.get_num_rows() > self.get_num_rows()
+                || oriented.get_num_cols() > self.get_num_cols()
+            {
+                continue;
+            }
+
+            for row in 0..=self.get_num_rows() - oriented.get_num_rows() {
+                for col in 0..=self.get_num_cols() - oriented.get_num_cols() {
+                    if self.matches_at(oriented, row, col) {
+                        found.push((row, col, *transform));
+                    }
+                }
+            }
+        }
+
+        found.sort_unstable_by_key(|&(row, col, _)| (row, col));
+        found
+    }
+}
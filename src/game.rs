@@ -1,13 +1,54 @@
-use std::fmt;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::error;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Index, IndexMut};
+
+use crate::activity::Heatmap;
+use crate::automaton::CellState;
+use crate::bookmark::Bookmark;
+use crate::error::GolError;
+use crate::history::{History, HistoryError, Snapshot};
+use crate::observer::{Diff, Observers};
+use crate::rule::Rule;
+use crate::stats::{GenerationStats, Stats};
+use crate::status::{Cycle, SimStatus, StatusTracker};
+use crate::topology::{Edge, Topology};
+use crate::undo::UndoStack;
+use crate::view::GridView;
+use crate::world::{StateHasher, World};
+
+/// Side in cells of the square chunks of a [Grid] whose changes are tracked
+/// for incremental rendering.
+pub const CHUNK_SIZE: u32 = 32;
+
+/// Largest number of cells a [Grid] can hold, so that the row-major index of
+/// every cell fits in 32 bits.
+pub(crate) const MAX_CELLS: u64 = u32::MAX as u64;
 
 /// Representation of a Conway's Game of Life [Cell].
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+///
+/// A [Cell] is stored as a single byte: 0 when dead and 1 when alive.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
 pub enum Cell {
     /// [Cell] is dead.
-    Dead,
+    #[default]
+    Dead = 0,
 
     /// [Cell] is alive.
-    Alive,
+    Alive = 1,
+}
+
+impl Cell {
+    /// Determine if the [Cell] is [Cell::Alive].
+    pub fn is_alive(self) -> bool {
+        self == Cell::Alive
+    }
 }
 
 impl fmt::Display for Cell {
@@ -19,8 +60,89 @@ impl fmt::Display for Cell {
     }
 }
 
+/// (row, col) offsets of the eight neighbors of a cell.
+const NEIGHBOR_OFFSETS: [(i64, i64); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Next states of a [Cell] under a [Rule], indexed by its current state and
+/// neighbor count.
+struct Transitions([Cell; 18]);
+
+impl Transitions {
+    /// Tabulate the next states of a [Cell] under a [Rule].
+    fn new(rule: &Rule) -> Transitions {
+        let mut table = [Cell::Dead; 18];
+
+        for (index, next) in table.iter_mut().enumerate() {
+            let state = if index < 9 { Cell::Dead } else { Cell::Alive };
+            *next = Grid::apply_rules(rule, state, (index % 9) as u8);
+        }
+
+        Transitions(table)
+    }
+
+    /// Get the next state of a [Cell].
+    #[inline]
+    fn next_state(&self, state: Cell, neighbor_count: u8) -> Cell {
+        self.0[state as usize * 9 + neighbor_count as usize]
+    }
+}
+
+/// Error raised when accessing or editing a [Grid].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GridError {
+    /// The (row, col) position is outside of the [Grid].
+    OutOfBounds {
+        row: u32,
+        col: u32,
+        num_rows: u32,
+        num_cols: u32,
+    },
+
+    /// A [Cell] at the (row, col) position is already alive.
+    Conflict { row: u32, col: u32 },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::OutOfBounds {
+                row,
+                col,
+                num_rows,
+                num_cols,
+            } => write!(
+                f,
+                "cell ({}, {}) is outside of the {}x{} grid",
+                row, col, num_rows, num_cols
+            ),
+            GridError::Conflict { row, col } => {
+                write!(f, "cell ({}, {}) is already alive", row, col)
+            }
+        }
+    }
+}
+
+impl error::Error for GridError {}
+
 /// Conway's Game of Life game [Grid].
-pub struct Grid {
+///
+/// The [Grid] tracks which chunks of [CHUNK_SIZE] cells square changed,
+/// whether by stepping or by editing, so that renderers can redraw only the
+/// [dirty regions](Grid::dirty_regions) rather than the whole board.
+///
+/// Its cells are [Cell]s unless another [CellState] is given, for the custom
+/// automata of [crate::automaton].
+#[derive(Debug)]
+pub struct Grid<S = Cell> {
     /// Number of rows (height) of the [Grid].
     num_rows: u32,
 
@@ -28,7 +150,189 @@ pub struct Grid {
     num_cols: u32,
 
     /// The game [Grid] represented as a 2D [Vec] in row-major order.
-    grid: Vec<Cell>,
+    grid: Vec<S>,
+
+    /// How the cells on the edges of the [Grid] find their neighbors.
+    topology: Topology,
+
+    /// Whether each chunk changed since the dirty regions were last cleared,
+    /// in row-major order.
+    dirty: Vec<bool>,
+}
+
+impl<S: Clone> Clone for Grid<S> {
+    fn clone(&self) -> Grid<S> {
+        Grid {
+            num_rows: self.num_rows,
+            num_cols: self.num_cols,
+            grid: self.grid.clone(),
+            topology: self.topology,
+            dirty: self.dirty.clone(),
+        }
+    }
+
+    /// Copy another [Grid] into this one, reusing its storage.
+    fn clone_from(&mut self, source: &Grid<S>) {
+        self.num_rows = source.num_rows;
+        self.num_cols = source.num_cols;
+        self.grid.clone_from(&source.grid);
+        self.topology = source.topology;
+        self.dirty.clone_from(&source.dirty);
+    }
+}
+
+impl<S: PartialEq> PartialEq for Grid<S> {
+    /// Compare the size, cells, and topology of two grids, but not their
+    /// dirty regions.
+    fn eq(&self, other: &Grid<S>) -> bool {
+        self.num_rows == other.num_rows
+            && self.num_cols == other.num_cols
+            && self.grid == other.grid
+            && self.topology == other.topology
+    }
+}
+
+impl<S: Eq> Eq for Grid<S> {}
+
+impl<S: Hash> Hash for Grid<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.num_rows.hash(state);
+        self.num_cols.hash(state);
+        self.grid.hash(state);
+        self.topology.hash(state);
+    }
+}
+
+impl<S: CellState> Grid<S> {
+    /// Create a new [Grid] of cells in a [CellState], for automata other than
+    /// Conway's Game of Life.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the [Grid].
+    /// * `num_cols` - The number of columns (width) of the [Grid].
+    /// * `state` - The state of every cell.
+    ///
+    /// # Panics
+    /// Panics if the [Grid] would hold more than `u32::MAX` cells.
+    pub fn filled(num_rows: u32, num_cols: u32, state: S) -> Grid<S> {
+        assert!(
+            num_rows as u64 * num_cols as u64 <= MAX_CELLS,
+            "a {}x{} grid holds too many cells",
+            num_rows,
+            num_cols
+        );
+
+        Grid {
+            num_rows,
+            num_cols,
+            grid: vec![state; num_rows as usize * num_cols as usize],
+            topology: Topology::Bounded,
+            // nothing has been drawn yet
+            dirty: vec![
+                true;
+                (num_rows.div_ceil(CHUNK_SIZE) * num_cols.div_ceil(CHUNK_SIZE)) as usize
+            ],
+        }
+    }
+
+    /// Get the number of rows (height) of the [Grid].
+    pub fn get_num_rows(&self) -> u32 {
+        self.num_rows
+    }
+
+    /// Get the number of columns (width) of the [Grid].
+    pub fn get_num_cols(&self) -> u32 {
+        self.num_cols
+    }
+
+    /// Get the [Topology] deciding the neighbors of cells on the edges of the
+    /// [Grid].
+    pub fn get_topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Set the [Topology] deciding the neighbors of cells on the edges of the
+    /// [Grid]. New grids are [Topology::Bounded].
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    /// Get the number of chunks along each row of the [Grid].
+    fn num_chunk_cols(&self) -> usize {
+        self.num_cols.div_ceil(CHUNK_SIZE) as usize
+    }
+
+    /// Mark the chunk holding a cell as changed.
+    fn mark_cell_dirty(&mut self, row: u32, col: u32) {
+        let chunk = (row / CHUNK_SIZE) as usize * self.num_chunk_cols() + (col / CHUNK_SIZE) as usize;
+        self.dirty[chunk] = true;
+    }
+
+    /// Determine if any region of the [Grid] changed since the dirty regions
+    /// were last cleared.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.contains(&true)
+    }
+
+    /// Forget the regions which changed, once they have been redrawn.
+    pub fn clear_dirty(&mut self) {
+        self.dirty.fill(false);
+    }
+
+    /// Convert a (row, col) position to a row-major index.
+    fn cell_to_index(&self, row: u32, col: u32) -> usize {
+        ((row * self.num_cols) + col) as usize
+    }
+
+    /// Convert a (row, col) position to a row-major index, checking that it
+    /// is within the [Grid].
+    fn checked_index(&self, row: u32, col: u32) -> Result<usize, GridError> {
+        if row < self.num_rows && col < self.num_cols {
+            Ok(self.cell_to_index(row, col))
+        } else {
+            Err(GridError::OutOfBounds {
+                row,
+                col,
+                num_rows: self.num_rows,
+                num_cols: self.num_cols,
+            })
+        }
+    }
+
+    /// Get the states of all cells of the [Grid] in row-major order.
+    pub fn get_cells(&self) -> &[S] {
+        &self.grid
+    }
+
+    /// Get the state of a specified cell of the [Grid].
+    pub fn get(&self, row: u32, col: u32) -> S {
+        let index = self.cell_to_index(row, col);
+        self.grid[index]
+    }
+
+    /// Get the state of a specified cell of the [Grid], or a [GridError] if
+    /// it is out of bounds.
+    pub fn try_get(&self, row: u32, col: u32) -> Result<S, GridError> {
+        let index = self.checked_index(row, col)?;
+        Ok(self.grid[index])
+    }
+
+    /// Set the state of a specified cell of the [Grid], or return a
+    /// [GridError] if it is out of bounds.
+    pub fn try_set(&mut self, row: u32, col: u32, state: S) -> Result<(), GridError> {
+        let index = self.checked_index(row, col)?;
+        self.grid[index] = state;
+        self.mark_cell_dirty(row, col);
+
+        Ok(())
+    }
+
+    /// Set the state of a specified cell of the [Grid].
+    pub fn set(&mut self, row: u32, col: u32, state: S) {
+        let index = self.cell_to_index(row, col);
+        self.grid[index] = state;
+        self.mark_cell_dirty(row, col);
+    }
 }
 
 impl Grid {
@@ -37,12 +341,70 @@ impl Grid {
     /// # Arguments
     /// * `num_rows` - The number of rows (height) of the [Grid].
     /// * `num_cols` - The number of columns (width) of the [Grid].
+    ///
+    /// # Panics
+    /// Panics if the [Grid] would hold more than `u32::MAX` cells. Use
+    /// [Grid::try_new] to handle sizes which are not known to be valid.
     pub fn new(num_rows: u32, num_cols: u32) -> Grid {
-        Grid {
-            num_rows: num_rows,
-            num_cols: num_cols,
-            grid: vec![Cell::Dead; (num_rows * num_cols) as usize],
+        Grid::filled(num_rows, num_cols, Cell::Dead)
+    }
+
+    /// Create a new [Grid] instance, or return
+    /// [GolError::InvalidDimensions] if either dimension is zero or the
+    /// [Grid] would hold more than `u32::MAX` cells.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the [Grid].
+    /// * `num_cols` - The number of columns (width) of the [Grid].
+    pub fn try_new(num_rows: u32, num_cols: u32) -> Result<Grid, GolError> {
+        Grid::check_size(num_rows, num_cols)?;
+
+        Ok(Grid::new(num_rows, num_cols))
+    }
+
+    /// Check that [Grid::try_new] can create a [Grid] of a size, without
+    /// creating it, returning [GolError::InvalidDimensions] if not.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the [Grid].
+    /// * `num_cols` - The number of columns (width) of the [Grid].
+    pub fn check_size(num_rows: u32, num_cols: u32) -> Result<(), GolError> {
+        if num_rows == 0 || num_cols == 0 || num_rows as u64 * num_cols as u64 > MAX_CELLS {
+            return Err(GolError::InvalidDimensions { num_rows, num_cols });
         }
+
+        Ok(())
+    }
+
+    /// Create a new [Grid] from rows of cells, as wide as the first row.
+    ///
+    /// Returns [GolError::DimensionMismatch] if a later row is of another
+    /// width, or [GolError::InvalidDimensions] if there are no cells or too
+    /// many.
+    ///
+    /// # Arguments
+    /// * `rows` - The cells of each row, from top to bottom.
+    pub fn from_rows<R: AsRef<[Cell]>>(rows: &[R]) -> Result<Grid, GolError> {
+        let dimension = |len: usize| u32::try_from(len).unwrap_or(u32::MAX);
+        let num_rows = dimension(rows.len());
+        let num_cols = dimension(rows.first().map_or(0, |row| row.as_ref().len()));
+
+        let mut grid = Grid::try_new(num_rows, num_cols)?;
+        grid.grid.clear();
+
+        for row in rows {
+            let row = row.as_ref();
+            if row.len() != num_cols as usize {
+                return Err(GolError::DimensionMismatch {
+                    expected: (num_rows, num_cols),
+                    found: (num_rows, dimension(row.len())),
+                });
+            }
+
+            grid.grid.extend_from_slice(row);
+        }
+
+        Ok(grid)
     }
 
     /// Set the cells of the [Grid].
@@ -53,264 +415,1056 @@ impl Grid {
         for (row, col) in cells.iter().cloned() {
             let idx = self.cell_to_index(row, col);
             self.grid[idx] = Cell::Alive;
+            self.mark_cell_dirty(row, col);
         }
     }
 
-    /// Convert a (row, col) position to a row-major index.
-    fn cell_to_index(&self, row: u32, col: u32) -> usize {
-        ((row * self.num_cols) + col) as usize
+    /// Get views of the regions of the [Grid] which changed since the dirty
+    /// regions were last cleared, each one or more chunks of [CHUNK_SIZE]
+    /// cells square along a row of chunks.
+    ///
+    /// A new [Grid] is dirty all over, as none of it has been drawn yet.
+    pub fn dirty_regions(&self) -> Vec<GridView<'_>> {
+        let num_chunk_cols = self.num_chunk_cols();
+        let mut regions: Vec<GridView<'_>> = Vec::new();
+
+        for (chunk_row, chunks) in self.dirty.chunks(num_chunk_cols.max(1)).enumerate() {
+            let mut chunk_col = 0;
+
+            while chunk_col < chunks.len() {
+                if !chunks[chunk_col] {
+                    chunk_col += 1;
+                    continue;
+                }
+
+                // merge the run of dirty chunks along the row
+                let start = chunk_col;
+                while chunk_col < chunks.len() && chunks[chunk_col] {
+                    chunk_col += 1;
+                }
+
+                regions.push(self.region(
+                    chunk_row as u32 * CHUNK_SIZE,
+                    start as u32 * CHUNK_SIZE,
+                    CHUNK_SIZE,
+                    (chunk_col - start) as u32 * CHUNK_SIZE,
+                ));
+            }
+        }
+
+        regions
     }
 
-    /// Get a specified [Cell] of the [Grid].
-    pub fn get(&self, row: u32, col: u32) -> Cell {
-        let index = self.cell_to_index(row, col);
-        self.grid[index]
+    /// Toggle a specified [Cell] of the [Grid] between [Cell::Dead] and
+    /// [Cell::Alive].
+    pub fn toggle(&mut self, row: u32, col: u32) {
+        let state = if self.get(row, col).is_alive() { Cell::Dead } else { Cell::Alive };
+        self.set(row, col, state);
+    }
+
+    /// Get the number of [Cell::Alive] cells of the [Grid].
+    pub fn get_population(&self) -> u64 {
+        self.grid.iter().filter(|cell| cell.is_alive()).count() as u64
+    }
+
+    /// Get the number of live neighbors of the specified [Cell].
+    pub fn get_neighbor_count(&self, row: u32, col: u32) -> u8 {
+        if row > 0 && col > 0 && row + 1 < self.num_rows && col + 1 < self.num_cols {
+            self.interior_neighbor_count(self.cell_to_index(row, col))
+        } else {
+            self.edge_neighbor_count(row, col)
+        }
     }
 
-    /// Set a specified [Cell] of the [Grid].
-    fn set(&mut self, row: u32, col: u32, state: Cell) {
+    /// Get the number of live neighbors of a [Cell] which is not on the edge
+    /// of the [Grid], by its row-major index.
+    ///
+    /// All eight neighbors are known to exist, so their states are summed
+    /// without any branches.
+    #[inline]
+    fn interior_neighbor_count(&self, index: usize) -> u8 {
+        let width = self.num_cols as usize;
+        let above = &self.grid[index - width - 1..index - width + 2];
+        let current = &self.grid[index - 1..index + 2];
+        let below = &self.grid[index + width - 1..index + width + 2];
+
+        // 0 1 2
+        // 3 X 4
+        // 5 6 7
+        above[0] as u8
+            + above[1] as u8
+            + above[2] as u8
+            + current[0] as u8
+            + current[2] as u8
+            + below[0] as u8
+            + below[1] as u8
+            + below[2] as u8
+    }
+
+    /// Get the number of live neighbors of a [Cell] on the edge of the
+    /// [Grid], finding the neighbors beyond its edges by its [Topology].
+    fn edge_neighbor_count(&self, row: u32, col: u32) -> u8 {
+        let mut count: u8 = 0;
+
+        if self.topology.get_edges() != (Edge::Bounded, Edge::Bounded) {
+            for &(row_offset, col_offset) in NEIGHBOR_OFFSETS.iter() {
+                let neighbor = self.topology.resolve(
+                    row as i64 + row_offset,
+                    col as i64 + col_offset,
+                    self.num_rows,
+                    self.num_cols,
+                );
+
+                if let Some((new_row, new_col)) = neighbor {
+                    count += self.get(new_row, new_col) as u8;
+                }
+            }
+
+            return count;
+        }
+
+        for &(row_offset, col_offset) in NEIGHBOR_OFFSETS.iter() {
+            let new_row = row as i64 + row_offset;
+            let new_col = col as i64 + col_offset;
+
+            if new_row >= 0
+                && new_col >= 0
+                && new_row < self.num_rows as i64
+                && new_col < self.num_cols as i64
+            {
+                count += self.get(new_row as u32, new_col as u32) as u8;
+            }
+        }
+
+        count
+    }
+
+    /// Compute the next state of a [Cell] on the edge of the [Grid] into
+    /// `next`.
+    fn step_edge_cell(&self, transitions: &Transitions, row: u32, col: u32, next: &mut Grid) {
         let index = self.cell_to_index(row, col);
-        self.grid[index] = state
+        let neighbor_count = self.edge_neighbor_count(row, col);
+
+        next.grid[index] = transitions.next_state(self.grid[index], neighbor_count);
+    }
+
+    /// Determine the next state of a [Cell] by applying a [Rule].
+    ///
+    /// # Arguments
+    /// * `rule` - The [Rule] to apply.
+    /// * `state` - The current state of the [Cell].
+    /// * `neighbor_count` - The number of live neighbors of the [Cell].
+    fn apply_rules(rule: &Rule, state: Cell, neighbor_count: u8) -> Cell {
+        // determine if the current cell is alive
+        let alive = state.is_alive();
+
+        // RULES FOR LIVE CELLS ///////////////////////////////////////////////
+        if alive {
+            // any live cell without a survival neighbor count dies (for
+            //  Conway's rule: fewer than two or more than three neighbors).
+            if !rule.is_survival(neighbor_count) {
+                return Cell::Dead;
+            }
+        }
+
+        // RULES FOR DEAD CELLS ///////////////////////////////////////////////
+        else {
+            // any dead cell with a birth neighbor count becomes a live cell
+            //  (for Conway's rule: exactly three neighbors).
+            if rule.is_birth(neighbor_count) {
+                return Cell::Alive;
+            }
+        }
+
+        // otherwise the cell keeps its current state
+        state
     }
 }
 
 impl fmt::Display for Grid {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for line in self.grid.as_slice().chunks(self.num_cols as usize) {
+        for line in self.grid.as_slice().chunks(self.num_cols.max(1) as usize) {
             for &cell in line {
                 let smybol = if cell == Cell::Dead { '◻' } else { '◼' };
                 write!(f, "{}", smybol)?;
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
 
         Ok(())
     }
 }
 
-/// Represents an [Operation] to be applied to a cell of a [Grid]. 
-struct Operation {
-    /// Row of the operation.
-    row: u32,
+impl<S: CellState> Index<(u32, u32)> for Grid<S> {
+    type Output = S;
 
-    /// Column of the operation.
-    col: u32,
-
-    /// The [Cell] state to apply.
-    state: Cell
+    /// Get the state of a specified cell of the [Grid] by (row, col).
+    ///
+    /// # Panics
+    /// Panics if the (row, col) position is outside of the [Grid].
+    fn index(&self, (row, col): (u32, u32)) -> &S {
+        match self.checked_index(row, col) {
+            Ok(index) => &self.grid[index],
+            Err(err) => panic!("{}", err),
+        }
+    }
 }
 
-impl Operation {
-    /// Create a new [Operation].
-    fn new(row: u32, col: u32, state: Cell) -> Operation {
-        Operation { row, col, state }
+impl<S: CellState> IndexMut<(u32, u32)> for Grid<S> {
+    /// Get the state of a specified cell of the [Grid] by (row, col) for
+    /// modification, marking its chunk as changed.
+    ///
+    /// # Panics
+    /// Panics if the (row, col) position is outside of the [Grid].
+    fn index_mut(&mut self, (row, col): (u32, u32)) -> &mut S {
+        match self.checked_index(row, col) {
+            Ok(index) => {
+                self.mark_cell_dirty(row, col);
+                &mut self.grid[index]
+            }
+            Err(err) => panic!("{}", err),
+        }
     }
 }
 
-impl fmt::Display for Operation {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Operation[row: {}, col: {}, state: {}]", self.row, self.col, self.state)
+impl World for Grid {
+    fn is_alive(&self, row: i64, col: i64) -> bool {
+        row >= 0
+            && col >= 0
+            && row < self.num_rows as i64
+            && col < self.num_cols as i64
+            && self.get(row as u32, col as u32).is_alive()
+    }
+
+    fn get_population(&self) -> u64 {
+        Grid::get_population(self)
+    }
+
+    fn live_cells(&self) -> Vec<(i64, i64)> {
+        let mut cells: Vec<(i64, i64)> = Vec::new();
+
+        for row in 0..self.num_rows {
+            for col in 0..self.num_cols {
+                if self.get(row, col).is_alive() {
+                    cells.push((row as i64, col as i64));
+                }
+            }
+        }
+
+        cells
+    }
+
+    fn get_area(&self) -> u64 {
+        self.num_rows as u64 * self.num_cols as u64
+    }
+
+    fn count_changes(&self, previous: &Grid) -> (u64, u64) {
+        if previous.num_rows != self.num_rows || previous.num_cols != self.num_cols {
+            return (self.get_population(), previous.get_population());
+        }
+
+        let mut births: u64 = 0;
+        let mut deaths: u64 = 0;
+
+        for (&before, &after) in previous.grid.iter().zip(self.grid.iter()) {
+            match (before, after) {
+                (Cell::Dead, Cell::Alive) => births += 1,
+                (Cell::Alive, Cell::Dead) => deaths += 1,
+                _ => {}
+            }
+        }
+
+        (births, deaths)
+    }
+
+    fn get_wrap(&self) -> (Option<u32>, Option<u32>) {
+        // twisted edges also flip the other axis, so only plain wrapping
+        // keeps positions continuous
+        let (rows, cols) = self.topology.get_edges();
+        let wrap = |edge: Edge, len: u32| if edge == Edge::Wrap { Some(len) } else { None };
+
+        (wrap(rows, self.num_rows), wrap(cols, self.num_cols))
+    }
+
+    fn mark_dirty(&mut self) {
+        self.dirty.fill(true);
+    }
+
+    fn diff(&self, previous: &Grid) -> Diff {
+        if previous.num_rows != self.num_rows || previous.num_cols != self.num_cols {
+            return Diff {
+                births: self.live_cells(),
+                deaths: previous.live_cells(),
+            };
+        }
+
+        let mut diff = Diff::default();
+
+        for (index, (&before, &after)) in previous.grid.iter().zip(self.grid.iter()).enumerate() {
+            let cell = (
+                (index / self.num_cols as usize) as i64,
+                (index % self.num_cols as usize) as i64,
+            );
+
+            match (before, after) {
+                (Cell::Dead, Cell::Alive) => diff.births.push(cell),
+                (Cell::Alive, Cell::Dead) => diff.deaths.push(cell),
+                _ => {}
+            }
+        }
+
+        diff
+    }
+
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::default();
+        self.hash(&mut hasher);
+
+        hasher.finish()
+    }
+
+    fn step_into(&self, rule: &Rule, next: &mut Grid) {
+        if next.num_rows != self.num_rows || next.num_cols != self.num_cols {
+            *next = Grid::new(self.num_rows, self.num_cols);
+        }
+        next.topology = self.topology;
+
+        let width = self.num_cols as usize;
+
+        // tabulate the rules for every state and neighbor count, avoiding
+        // unpredictable branches in the loop below
+        let transitions = Transitions::new(rule);
+
+        // loop over each row in the grid
+        for row in 0..self.num_rows {
+            // cells on the edges may be missing neighbors, check each one
+            if row == 0 || row + 1 == self.num_rows || width < 3 {
+                for col in 0..self.num_cols {
+                    self.step_edge_cell(&transitions, row, col, next);
+                }
+
+                continue;
+            }
+
+            self.step_edge_cell(&transitions, row, 0, next);
+
+            // interior cells sum the three-cell windows of the rows above,
+            // through, and below them without any bounds checks
+            let start = row as usize * width;
+            let above = &self.grid[start - width..start];
+            let current = &self.grid[start..start + width];
+            let below = &self.grid[start + width..start + 2 * width];
+            let next_row = &mut next.grid[start + 1..start + width - 1];
+
+            let windows = above
+                .windows(3)
+                .zip(current.windows(3))
+                .zip(below.windows(3));
+
+            for (next_cell, ((above, current), below)) in next_row.iter_mut().zip(windows) {
+                let neighbor_count = above[0] as u8
+                    + above[1] as u8
+                    + above[2] as u8
+                    + current[0] as u8
+                    + current[2] as u8
+                    + below[0] as u8
+                    + below[1] as u8
+                    + below[2] as u8;
+
+                // apply rules to the cell and write the resultant state into
+                // the next generation
+                *next_cell = transitions.next_state(current[1], neighbor_count);
+            }
+
+            self.step_edge_cell(&transitions, row, self.num_cols - 1, next);
+        }
+
+        // mark the chunks which changed, keeping those not yet cleared
+        let num_chunk_cols = self.num_chunk_cols();
+        next.dirty.clone_from(&self.dirty);
+
+        for (row, (before, after)) in self
+            .grid
+            .chunks(width.max(1))
+            .zip(next.grid.chunks(width.max(1)))
+            .enumerate()
+        {
+            if before == after {
+                continue;
+            }
+
+            let first_chunk = row / CHUNK_SIZE as usize * num_chunk_cols;
+            let chunks = before
+                .chunks(CHUNK_SIZE as usize)
+                .zip(after.chunks(CHUNK_SIZE as usize));
+
+            for (dirty, (before, after)) in next.dirty[first_chunk..].iter_mut().zip(chunks) {
+                *dirty |= before != after;
+            }
+        }
     }
 }
 
 /// Conway's Game of Life Simulation.
-pub struct ConwaySim {
-    /// Simulation [Grid].
-    grid: Grid,
+///
+/// The simulation is generic over the [World] the game is played in: a
+/// bounded [Grid] (the default) or an unbounded
+/// [SparseWorld](crate::SparseWorld).
+pub struct ConwaySim<W: World = Grid> {
+    /// Simulation [World].
+    world: W,
+
+    /// Buffer the next generation is computed into before being swapped with
+    /// the simulation [World].
+    next: W,
 
     /// The simulation's current generation.
     generation: u32,
+
+    /// The [Rule] applied to each cell every generation.
+    rule: Rule,
+
+    /// Recorded [History] of the simulation, if enabled.
+    history: Option<History<W>>,
+
+    /// [UndoStack] of the states before recent edits, if enabled.
+    undo: Option<UndoStack<W>>,
+
+    /// [Bookmark]s of the run, ordered by generation.
+    bookmarks: Vec<Bookmark<W>>,
+
+    /// Tracker of the simulation's [SimStatus].
+    status: StatusTracker,
+
+    /// Recorded per-generation [Stats] of the simulation, if enabled.
+    stats: Option<Stats>,
+
+    /// [Heatmap] of recent cell changes, if enabled.
+    activity: Option<Heatmap>,
+
+    /// Callbacks notified of each generation.
+    observers: Observers<W>,
 }
 
-impl ConwaySim {
-    /// Create a new simulation.
+impl<W: World> ConwaySim<W> {
+    /// Create a new simulation from an existing [World].
     ///
     /// # Arguments
-    /// * `num_rows` - The number of rows (height) of the [Grid].
-    /// * `num_cols` - The number of columns (width) of the [Grid].
-    pub fn new(num_rows: u32, num_cols: u32) -> ConwaySim {
+    /// * `world` - The initial [World] of the simulation.
+    pub fn new_with_world(world: W) -> ConwaySim<W> {
+        let next = world.clone();
+
         ConwaySim {
-            grid: Grid::new(num_rows, num_cols),
+            world,
+            next,
             generation: 0,
+            rule: Rule::default(),
+            history: None,
+            undo: None,
+            bookmarks: Vec::new(),
+            status: StatusTracker::new(),
+            stats: None,
+            activity: None,
+            observers: Observers::new(),
         }
     }
 
-    #[allow(dead_code)]
-    pub fn new_with_grid(grid: Grid) -> ConwaySim {
-        ConwaySim { grid, generation: 0 }
+    /// Restore a simulation at a generation, without any [History] or
+    /// [Stats] recorded.
+    ///
+    /// # Arguments
+    /// * `world` - The [World] at the generation.
+    /// * `generation` - The generation of the simulation.
+    /// * `rule` - The [Rule] of the simulation.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn restore(world: W, generation: u32, rule: Rule) -> ConwaySim<W> {
+        let mut sim = ConwaySim::new_with_world(world);
+        sim.generation = generation;
+        sim.rule = rule;
+        sim.status.reset(sim.world.get_population());
+
+        sim
     }
 
-    pub fn get_generation(&self) -> u32 {
-        self.generation
+    /// Get the simulation's [World].
+    pub fn get_world(&self) -> &W {
+        &self.world
     }
 
-    pub fn is_cell_alive(&self, row: u32, col: u32) -> bool {
-        self.grid.get(row, col) == Cell::Alive
+    /// Get the simulation's [World] for modification.
+    pub(crate) fn get_world_mut(&mut self) -> &mut W {
+        &mut self.world
     }
 
-    pub fn is_any_cell_alive(&self) -> bool {
-        let mut alive = false;
+    /// Get the simulation's [Rule].
+    pub fn get_rule(&self) -> Rule {
+        self.rule
+    }
 
-        for &cell in self.grid.grid.iter() {
-            if cell == Cell::Alive {
-                alive = true;
-                break;
-            }
-        }
-        
-        return alive;
+    /// Set the [Rule] applied to each cell every generation.
+    ///
+    /// # Arguments
+    /// * `rule` - The new [Rule] of the simulation.
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+        self.record_edit();
     }
 
-    pub fn get_neighbor_count(&self, row: u32, col: u32) -> u8 {
-        let mut count: u8 = 0;
+    /// Start recording the [History] of the simulation so that it can be
+    /// rewound, beginning with the current generation.
+    ///
+    /// # Arguments
+    /// * `interval` - Number of generations between snapshots. Smaller
+    ///   intervals use more memory but rewind faster.
+    /// * `capacity` - Maximum number of snapshots kept, the oldest are
+    ///   discarded first.
+    pub fn enable_history(&mut self, interval: u32, capacity: usize) {
+        let mut history = History::new(interval, capacity);
+        history.record(self.generation, &self.world, self.rule);
 
-        let mut new_row: u32;
-        let mut new_col: u32;
+        self.history = Some(history);
+    }
 
-        // 0 1 2
-        // 3 X 4
-        // 5 6 7
+    /// Stop recording the [History] of the simulation and discard it.
+    pub fn disable_history(&mut self) {
+        self.history = None;
+    }
 
-        // check the top left neighbor
-        if (row > 0) && (col > 0) {
-            new_row = row - 1;
-            new_col = col - 1;
+    /// Get the recorded [History] of the simulation, if enabled.
+    pub fn get_history(&self) -> Option<&History<W>> {
+        self.history.as_ref()
+    }
 
-            if self.is_cell_alive(new_row, new_col) { count += 1; }
-        }
+    /// Start keeping an [UndoStack] of the states before edits, saved by
+    /// [ConwaySim::begin_edit], so that the edits can be undone.
+    ///
+    /// # Arguments
+    /// * `capacity` - Maximum number of edits which can be undone, the
+    ///   oldest are discarded first.
+    pub fn enable_undo(&mut self, capacity: usize) {
+        self.undo = Some(UndoStack::new(capacity));
+    }
 
-        // check the top center neighbor
-        if row > 0 {
-            new_row = row - 1;
-            new_col = col;
+    /// Stop keeping the [UndoStack] of the simulation and discard it.
+    pub fn disable_undo(&mut self) {
+        self.undo = None;
+    }
 
-            if self.is_cell_alive(new_row, new_col) { count += 1; }
-        }
+    /// Get the [UndoStack] of the simulation, if enabled.
+    pub fn get_undo(&self) -> Option<&UndoStack<W>> {
+        self.undo.as_ref()
+    }
 
-        // check the top right neighbor
-        if (row > 0) && ((col + 1) < self.grid.num_cols) {
-            new_row = row - 1;
-            new_col = col + 1;
+    /// Save the current state to undo back to, before an edit such as
+    /// toggling cells or stamping a pattern. Edits made until the next call
+    /// are undone together, so a whole stroke of painted cells can be saved
+    /// once. Does nothing unless undo is enabled.
+    pub fn begin_edit(&mut self) {
+        let snapshot = self.snapshot();
 
-            if self.is_cell_alive(new_row, new_col) { count += 1; }
+        if let Some(undo) = &mut self.undo {
+            undo.push(snapshot);
         }
+    }
 
-        // check left neighbor
-        if col > 0 {
-            new_row = row;
-            new_col = col - 1;
+    /// Undo the latest edit, restoring the state saved before it by
+    /// [ConwaySim::begin_edit]. Returns whether there was an edit to undo.
+    pub fn undo(&mut self) -> bool {
+        let snapshot = self.snapshot();
 
-            if self.is_cell_alive(new_row, new_col) { count += 1; }
+        match self.undo.as_mut().and_then(|undo| undo.undo(snapshot)) {
+            Some(snapshot) => {
+                self.restore_edit(snapshot);
+                true
+            }
+            None => false,
         }
+    }
 
-        // check right neighbor
-        if (col + 1) < self.grid.num_cols {
-            new_row = row;
-            new_col = col + 1;
+    /// Redo the latest undone edit. Returns whether there was an undone edit
+    /// to redo.
+    pub fn redo(&mut self) -> bool {
+        let snapshot = self.snapshot();
 
-            if self.is_cell_alive(new_row, new_col) { count += 1; }
+        match self.undo.as_mut().and_then(|undo| undo.redo(snapshot)) {
+            Some(snapshot) => {
+                self.restore_edit(snapshot);
+                true
+            }
+            None => false,
         }
+    }
 
-        // check bottom left neighbor
-        if ((row + 1) < self.grid.num_rows) && (col > 0) {
-            new_row = row + 1;
-            new_col = col - 1;
-
-            if self.is_cell_alive(new_row, new_col) { count += 1; }
+    /// Take a [Snapshot] of the current generation, world, and rule.
+    fn snapshot(&self) -> Snapshot<W> {
+        Snapshot {
+            generation: self.generation,
+            world: self.world.clone(),
+            rule: self.rule,
         }
+    }
+
+    /// Restore a [Snapshot] saved for undo, recording it as an edit.
+    fn restore_edit(&mut self, snapshot: Snapshot<W>) {
+        self.generation = snapshot.generation;
+        self.world = snapshot.world;
+        self.world.mark_dirty();
+        self.rule = snapshot.rule;
+        self.record_edit();
+    }
+
+    /// Save the current generation as a [Bookmark] to return to with
+    /// [ConwaySim::goto_bookmark], replacing any bookmark with the same
+    /// label.
+    ///
+    /// # Arguments
+    /// * `label` - The label the bookmark is found by.
+    pub fn bookmark(&mut self, label: &str) {
+        self.remove_bookmark(label);
+
+        let bookmark = Bookmark::new(label.into(), self.snapshot());
+        let index = self
+            .bookmarks
+            .partition_point(|bookmark| bookmark.get_generation() <= self.generation);
+        self.bookmarks.insert(index, bookmark);
+    }
 
-        // check bottom center neighbor
-        if (row + 1) < self.grid.num_rows {
-            new_row = row + 1;
-            new_col = col;
+    /// Get the [Bookmark]s of the simulation, ordered by generation.
+    pub fn get_bookmarks(&self) -> &[Bookmark<W>] {
+        &self.bookmarks
+    }
+
+    /// Return to a [Bookmark], restoring its generation, world, and rule as
+    /// an edit. Returns whether there was a bookmark with the label.
+    ///
+    /// # Arguments
+    /// * `label` - The label of the bookmark.
+    pub fn goto_bookmark(&mut self, label: &str) -> bool {
+        let snapshot = match self
+            .bookmarks
+            .iter()
+            .find(|bookmark| bookmark.get_label() == label)
+        {
+            Some(bookmark) => bookmark.get_snapshot().clone(),
+            None => return false,
+        };
+
+        self.restore_edit(snapshot);
+        true
+    }
+
+    /// Remove a [Bookmark]. Returns whether there was a bookmark with the
+    /// label.
+    ///
+    /// # Arguments
+    /// * `label` - The label of the bookmark.
+    pub fn remove_bookmark(&mut self, label: &str) -> bool {
+        let count = self.bookmarks.len();
+        self.bookmarks
+            .retain(|bookmark| bookmark.get_label() != label);
+
+        self.bookmarks.len() != count
+    }
+
+    /// Start recording per-generation [Stats] of the simulation, beginning
+    /// with the current generation.
+    pub fn enable_stats(&mut self) {
+        let mut stats = Stats::new();
+        stats.record(self.sample_stats(0, 0));
+
+        self.stats = Some(stats);
+    }
 
-            if self.is_cell_alive(new_row, new_col) { count += 1; }
+    /// Stop recording [Stats] of the simulation and discard them.
+    pub fn disable_stats(&mut self) {
+        self.stats = None;
+    }
+
+    /// Get the recorded [Stats] of the simulation, if enabled.
+    pub fn get_stats(&self) -> Option<&Stats> {
+        self.stats.as_ref()
+    }
+
+    /// Start tracking a [Heatmap] of how often each cell changes state,
+    /// beginning with the next generation.
+    ///
+    /// # Arguments
+    /// * `window` - Number of recent generations the [Heatmap] covers.
+    pub fn enable_activity(&mut self, window: usize) {
+        self.activity = Some(Heatmap::new(window));
+    }
+
+    /// Stop tracking the [Heatmap] of cell changes and discard it.
+    pub fn disable_activity(&mut self) {
+        self.activity = None;
+    }
+
+    /// Get the [Heatmap] of recent cell changes, if enabled.
+    pub fn get_activity(&self) -> Option<&Heatmap> {
+        self.activity.as_ref()
+    }
+
+    /// Get the density of the simulation's [World].
+    fn get_density(&self) -> f64 {
+        match self.world.get_area() {
+            0 => 0.0,
+            area => self.world.get_population() as f64 / area as f64,
+        }
+    }
+
+    /// Sample the [GenerationStats] of the current generation.
+    fn sample_stats(&self, births: u64, deaths: u64) -> GenerationStats {
+        GenerationStats {
+            generation: self.generation,
+            population: self.world.get_population(),
+            births,
+            deaths,
+            density: self.get_density(),
         }
+    }
 
-        // check bottom left neighbor
-        if ((row + 1) < self.grid.num_rows)
-                && ((col + 1) < self.grid.num_cols) {
-            new_row = row + 1;
-            new_col = col + 1;
+    /// Record an edit of the world or rule in the [History] and [Stats], if
+    /// enabled, and restart detection of the [SimStatus].
+    pub(crate) fn record_edit(&mut self) {
+        if let Some(history) = &mut self.history {
+            history.record_edit(self.generation, &self.world, self.rule);
+        }
 
-            if self.is_cell_alive(new_row, new_col) { count += 1; }
+        let (population, density) = (self.world.get_population(), self.get_density());
+        if let Some(stats) = &mut self.stats {
+            stats.record_edit(self.generation, population, density);
         }
 
-        return count;
+        self.status.reset(self.world.get_population());
     }
 
-    pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
-        self.grid.set_cells(cells);
+    /// Get the [SimStatus] of the simulation as of the current generation.
+    ///
+    /// Oscillators are detected once they have completed a full period, for
+    /// periods of up to 4096 generations.
+    pub fn get_status(&self) -> SimStatus {
+        self.status.get_status()
     }
 
-    fn apply_rules(&self, row: u32, col: u32) -> Vec<Operation> {
-        let mut operations: Vec<Operation> = Vec::new();
+    /// Get the [Cycle] the simulation entered, once it has completed a full
+    /// period of it: the generations it repeats forever from the first one
+    /// repeated, for periods of up to 4096 generations.
+    ///
+    /// Generations are compared by hash since the last edit, [ConwaySim::seek]
+    /// backwards, or [ConwaySim::step_n], so a cycle which began before then
+    /// is reported as starting at the first generation compared.
+    pub fn get_cycle(&self) -> Option<Cycle> {
+        self.status.get_cycle()
+    }
 
-        // determine the number of live neighbors to the current cell
-        let neighbor_count = self.get_neighbor_count(row, col);
+    /// Move the simulation to a generation, either by stepping forwards or by
+    /// restoring the recorded [History] and re-simulating.
+    ///
+    /// # Arguments
+    /// * `generation` - The generation to move to.
+    pub fn seek(&mut self, generation: u32) -> Result<(), HistoryError> {
+        if generation < self.generation {
+            let history = self.history.as_ref().ok_or(HistoryError::Disabled)?;
+            let snapshot = history
+                .latest_at_or_before(generation)
+                .ok_or(HistoryError::Unavailable { generation })?;
 
-        // determine if the current cell is alive
-        let alive = self.is_cell_alive(row, col);
+            self.world = snapshot.world.clone();
+            self.world.mark_dirty();
+            self.rule = snapshot.rule;
+            self.generation = snapshot.generation;
+            self.status.reset(self.world.get_population());
 
-        // RULES FOR LIVE CELLS ///////////////////////////////////////////////
-        if alive {
-            // rule 1: any live cell with fewer than two live neighbors dies,
-            //          as if caused by under-population.
-            if neighbor_count < 2 {
-                operations.push(Operation::new(row, col, Cell::Dead));
+            if let Some(stats) = &mut self.stats {
+                stats.truncate_after(self.generation);
             }
+        }
 
-            // rule 2: any live cell with two or three live neigbors lives on
-            //          to the next generation.
-            else if neighbor_count <= 3 {
-                // do nothing, cell lives
-            }
+        while self.generation < generation {
+            self.step();
+        }
 
-            // rule 3: any live cell with more than three neigborns dies, as if
-            //          caused by overcrowding.
-            else {
-                operations.push(Operation::new(row, col, Cell::Dead));
-            }
+        Ok(())
+    }
+
+    /// Move the simulation back by a number of generations.
+    ///
+    /// # Arguments
+    /// * `generations` - The number of generations to move back.
+    pub fn rewind(&mut self, generations: u32) -> Result<(), HistoryError> {
+        self.seek(self.generation.saturating_sub(generations))
+    }
+
+    /// Move the simulation back by one generation, restoring the recorded
+    /// [History] and re-simulating, which is exact for any rule.
+    ///
+    /// Reversible automata step backward without a [History]: see
+    /// [MargolusSim](crate::margolus::MargolusSim) and
+    /// [SecondOrderSim](crate::reversible::SecondOrderSim).
+    pub fn step_backward(&mut self) -> Result<(), HistoryError> {
+        self.rewind(1)
+    }
+
+    /// Get the simulation's current generation.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Get the number of live cells.
+    pub fn get_population(&self) -> u64 {
+        self.world.get_population()
+    }
+
+    /// Determine if any cell of the [World] is alive.
+    pub fn is_any_cell_alive(&self) -> bool {
+        self.world.get_population() > 0
+    }
+
+    /// Advance the simulation by one generation.
+    pub fn step(&mut self) {
+        // increment the sim's generation
+        self.generation += 1;
+
+        // compute the next generation and swap it in as the current one
+        self.world.step_into(&self.rule, &mut self.next);
+        core::mem::swap(&mut self.world, &mut self.next);
+
+        if let Some(history) = &mut self.history {
+            history.record_step(self.generation, &self.world, self.rule);
         }
 
-        // RULES FOR DEAD CELLS ///////////////////////////////////////////////
-        else {
-            // rule 4: any dead cell with exactly three live neighbors becomes
-            //          a live cell, as if by reproduction.
-            if neighbor_count == 3 {
-                operations.push(Operation::new(row, col, Cell::Alive));
+        if self.stats.is_some() {
+            // the buffer now holds the previous generation
+            let (births, deaths) = self.world.count_changes(&self.next);
+            let sample = self.sample_stats(births, deaths);
+
+            if let Some(stats) = &mut self.stats {
+                stats.record(sample);
             }
         }
 
-        return operations;
+        if let Some(activity) = &mut self.activity {
+            activity.record(&self.world.diff(&self.next));
+        }
+
+        // after a reset, hash the previous generation too so that a cycle
+        // through it is found from its first generation
+        if self.status.is_empty() {
+            let population = self.next.get_population();
+            self.status
+                .update(self.generation - 1, self.next.state_hash(), population);
+        }
+
+        self.status.update(
+            self.generation,
+            self.world.state_hash(),
+            self.world.get_population(),
+        );
+
+        if !self.observers.is_empty() {
+            self.observers.notify(self.generation, &self.world, &self.next);
+        }
     }
 
-    pub fn step(&mut self) {
-        let mut operations: Vec<Operation> = Vec::new();
+    /// Advance the simulation by one generation, as with [ConwaySim::step],
+    /// and get the [Diff] of the cells born and died.
+    pub fn step_diff(&mut self) -> Diff {
+        self.step();
 
-        // increment the sim's generation
-        self.generation += 1;
+        // the buffer now holds the previous generation
+        self.world.diff(&self.next)
+    }
+
+    /// Advance the simulation by `n` generations.
+    ///
+    /// Only the last generation is sampled into the [Stats] and [Heatmap]
+    /// and reported to observers, and detection of the [SimStatus] restarts
+    /// from it, so jumping many generations ahead is cheaper than calling
+    /// [ConwaySim::step] repeatedly.
+    ///
+    /// # Arguments
+    /// * `n` - The number of generations to advance by.
+    pub fn step_n(&mut self, n: u32) {
+        if n == 0 {
+            return;
+        }
+
+        for _ in 1..n {
+            self.generation += 1;
 
-        // loop over each cell in the grid
-        for row in 0..self.grid.num_rows {
-            for col in 0..self.grid.num_cols {
-                // apply rules to the cell
-                let results = self.apply_rules(row, col);
+            self.world.step_into(&self.rule, &mut self.next);
+            core::mem::swap(&mut self.world, &mut self.next);
 
-                // add any resultant operations to the step's operations list
-                operations.extend(results);
+            // periodic snapshots are cheap and keep seeking backwards fast
+            if let Some(history) = &mut self.history {
+                history.record_step(self.generation, &self.world, self.rule);
             }
         }
 
-        // apply any operations for this step to the grid
-        for operation in operations {
-            self.grid.set(operation.row, operation.col, operation.state)
+        // the skipped generations were not hashed, so periods spanning them
+        // cannot be detected
+        self.status.reset(self.world.get_population());
+
+        self.step();
+    }
+
+    /// Advance the simulation to a later generation, as with
+    /// [ConwaySim::step_n]. Does nothing if the simulation is already at or
+    /// past the generation; use [ConwaySim::seek] to move backwards.
+    ///
+    /// # Arguments
+    /// * `generation` - The generation to advance to.
+    pub fn advance_to(&mut self, generation: u32) {
+        self.step_n(generation.saturating_sub(self.generation));
+    }
+
+    /// Register a callback notified after each step with the new generation,
+    /// the [World], and the [Diff] of the cells which changed.
+    ///
+    /// Edits and moving backwards through the [History] do not notify
+    /// observers.
+    ///
+    /// # Arguments
+    /// * `observer` - The callback.
+    pub fn on_generation<F>(&mut self, observer: F)
+    where
+        F: FnMut(u32, &W, &Diff) + Send + 'static,
+    {
+        self.observers.add_generation(Box::new(observer));
+    }
+
+    /// Register a callback notified after each step of each cell which
+    /// changed, with the new generation, the cell's (row, col), and whether
+    /// it is now alive.
+    ///
+    /// Edits and moving backwards through the [History] do not notify
+    /// observers.
+    ///
+    /// # Arguments
+    /// * `observer` - The callback.
+    pub fn on_cell_change<F>(&mut self, observer: F)
+    where
+        F: FnMut(u32, i64, i64, bool) + Send + 'static,
+    {
+        self.observers.add_cell_change(Box::new(observer));
+    }
+
+    /// Remove all callbacks registered with [ConwaySim::on_generation] and
+    /// [ConwaySim::on_cell_change].
+    pub fn clear_observers(&mut self) {
+        self.observers.clear();
+    }
+}
+
+impl ConwaySim {
+    /// Create a new simulation.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the [Grid].
+    /// * `num_cols` - The number of columns (width) of the [Grid].
+    ///
+    /// # Panics
+    /// Panics if the [Grid] would hold more than `u32::MAX` cells.
+    pub fn new(num_rows: u32, num_cols: u32) -> ConwaySim {
+        ConwaySim::new_with_grid(Grid::new(num_rows, num_cols))
+    }
+
+    /// Create a new simulation, or return [GolError::InvalidDimensions] if
+    /// either dimension is zero or the [Grid] would be too large.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the [Grid].
+    /// * `num_cols` - The number of columns (width) of the [Grid].
+    pub fn try_new(num_rows: u32, num_cols: u32) -> Result<ConwaySim, GolError> {
+        Ok(ConwaySim::new_with_grid(Grid::try_new(num_rows, num_cols)?))
+    }
+
+    /// Create a new simulation from an existing [Grid].
+    ///
+    /// # Arguments
+    /// * `grid` - The initial [Grid] of the simulation.
+    pub fn new_with_grid(grid: Grid) -> ConwaySim {
+        ConwaySim::new_with_world(grid)
+    }
+
+    /// Get the simulation's [Grid].
+    pub fn get_grid(&self) -> &Grid {
+        &self.world
+    }
+
+    /// Determine if the specified [Cell] is [Cell::Alive].
+    pub fn is_cell_alive(&self, row: u32, col: u32) -> bool {
+        self.world.get(row, col) == Cell::Alive
+    }
+
+    /// Get the number of live neighbors of the specified [Cell].
+    pub fn get_neighbor_count(&self, row: u32, col: u32) -> u8 {
+        self.world.get_neighbor_count(row, col)
+    }
+
+    /// Set the cells of the simulation's [Grid].
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (row, col) to set as [Cell::Alive].
+    pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
+        self.world.set_cells(cells);
+        self.record_edit();
+    }
+
+    /// Set the specified [Cell] of the simulation's [Grid].
+    pub fn set_cell(&mut self, row: u32, col: u32, state: Cell) {
+        self.world.set(row, col, state);
+        self.record_edit();
+    }
+
+    /// Toggle the specified [Cell] of the simulation's [Grid] between
+    /// [Cell::Dead] and [Cell::Alive].
+    pub fn toggle_cell(&mut self, row: u32, col: u32) {
+        self.world.toggle(row, col);
+        self.record_edit();
+    }
+
+    /// Forget the regions of the simulation's [Grid] which changed, once
+    /// they have been redrawn.
+    pub fn clear_dirty(&mut self) {
+        self.world.clear_dirty();
+    }
+
+    /// Set every [Cell] of the simulation's [Grid] as [Cell::Dead].
+    pub fn clear(&mut self) {
+        let topology = self.world.topology;
+        self.world = Grid::new(self.world.num_rows, self.world.num_cols);
+        self.world.topology = topology;
+        self.record_edit();
+    }
+
+    /// Advance the simulation by one generation, reporting the cells which
+    /// changed state.
+    ///
+    /// Returns a tuple (row, col, state) with the new state of each [Cell]
+    /// which changed.
+    pub fn step_with_changes(&mut self) -> Vec<(u32, u32, Cell)> {
+        self.step();
+
+        // the previous generation is now in the next buffer
+        let mut changes: Vec<(u32, u32, Cell)> = Vec::new();
+
+        for row in 0..self.world.num_rows {
+            for col in 0..self.world.num_cols {
+                let state = self.world.get(row, col);
+
+                if state != self.next.get(row, col) {
+                    changes.push((row, col, state));
+                }
+            }
         }
+
+        changes
     }
 }
 
-impl fmt::Display for ConwaySim {
+impl<W: World + fmt::Display> fmt::Display for ConwaySim<W> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.grid.fmt(f)
+        self.world.fmt(f)
     }
 }
@@ -0,0 +1,311 @@
+//! Generations rules, where dying cells decay through extra states.
+//!
+//! A Generations rule extends a Life-like rule with a number of states `C`.
+//! State 0 is dead and state 1 is alive; a live cell which does not survive
+//! moves to state 2 and then decays through each state up to `C - 1` before
+//! dying. Only live cells count as neighbors, and decaying cells can neither
+//! survive nor be born.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::game::Grid;
+use crate::rule::{parse_counts, Rule, RuleError};
+use crate::states::StateGrid;
+
+/// State of a dead cell.
+pub const DEAD: u8 = 0;
+
+/// State of a live cell.
+pub const ALIVE: u8 = 1;
+
+/// A Generations [Rule] with birth and survival neighbor counts and a number
+/// of cell states.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GenerationsRule {
+    /// `birth[n]` is set if a dead cell with `n` live neighbors is born.
+    birth: [bool; 9],
+
+    /// `survival[n]` is set if a live cell with `n` live neighbors survives.
+    survival: [bool; 9],
+
+    /// Number of cell states, including the dead and live states.
+    num_states: u8,
+}
+
+impl GenerationsRule {
+    /// Create a new [GenerationsRule] from lists of neighbor counts.
+    ///
+    /// # Arguments
+    /// * `birth` - Neighbor counts which cause a dead cell to be born.
+    /// * `survival` - Neighbor counts which allow a live cell to survive.
+    /// * `num_states` - Number of cell states, at least 2.
+    ///
+    /// # Panics
+    /// Panics if any neighbor count is greater than 8 or there are fewer
+    /// than 2 states.
+    pub fn new(birth: &[u8], survival: &[u8], num_states: u8) -> GenerationsRule {
+        GenerationsRule::from_rule(Rule::new(birth, survival), num_states)
+    }
+
+    /// Create a new [GenerationsRule] with the neighbor counts of a Life-like
+    /// [Rule].
+    ///
+    /// # Arguments
+    /// * `rule` - The birth and survival neighbor counts.
+    /// * `num_states` - Number of cell states, at least 2.
+    ///
+    /// # Panics
+    /// Panics if there are fewer than 2 states.
+    pub fn from_rule(rule: Rule, num_states: u8) -> GenerationsRule {
+        assert!(num_states >= 2, "Generations rules have at least 2 states");
+
+        let mut birth = [false; 9];
+        let mut survival = [false; 9];
+
+        for count in 0..9 {
+            birth[count as usize] = rule.is_birth(count);
+            survival[count as usize] = rule.is_survival(count);
+        }
+
+        GenerationsRule {
+            birth,
+            survival,
+            num_states,
+        }
+    }
+
+    /// Brian's Brain, `/2/3`.
+    pub fn brians_brain() -> GenerationsRule {
+        GenerationsRule::new(&[2], &[], 3)
+    }
+
+    /// Star Wars, `345/2/4`.
+    pub fn star_wars() -> GenerationsRule {
+        GenerationsRule::new(&[2], &[3, 4, 5], 4)
+    }
+
+    /// Get the number of cell states, including the dead and live states.
+    pub fn get_num_states(&self) -> u8 {
+        self.num_states
+    }
+
+    /// Get the next state of a cell.
+    ///
+    /// # Arguments
+    /// * `state` - The current state of the cell.
+    /// * `neighbor_count` - The number of live neighbors of the cell.
+    pub fn next_state(&self, state: u8, neighbor_count: u8) -> u8 {
+        match state {
+            DEAD if self.birth[neighbor_count as usize] => ALIVE,
+            DEAD => DEAD,
+            ALIVE if self.survival[neighbor_count as usize] => ALIVE,
+            _ if state + 1 < self.num_states => state + 1,
+            _ => DEAD,
+        }
+    }
+}
+
+impl FromStr for GenerationsRule {
+    type Err = RuleError;
+
+    /// Parse a rulestring in `S/B/C` notation, e.g. `345/2/4`, or in
+    /// `B2/S345/C4` notation (case insensitive, in any order).
+    fn from_str(rulestring: &str) -> Result<GenerationsRule, RuleError> {
+        let error = || RuleError::new(rulestring);
+
+        let parts: Vec<&str> = rulestring.trim().split('/').collect();
+        if parts.len() != 3 {
+            return Err(error());
+        }
+
+        let (mut birth, mut survival, mut num_states) = (None, None, None);
+
+        for part in parts.iter() {
+            let mut chars = part.chars();
+
+            match chars.next().map(|prefix| prefix.to_ascii_uppercase()) {
+                Some('B') if birth.is_none() => birth = parse_counts(chars.as_str()),
+                Some('S') if survival.is_none() => survival = parse_counts(chars.as_str()),
+                Some('C') | Some('G') if num_states.is_none() => {
+                    num_states = chars.as_str().parse::<u8>().ok()
+                }
+                _ => {}
+            }
+        }
+
+        // without prefixes, the parts list the survival and birth counts
+        // followed by the number of states
+        if (birth, survival, num_states) == (None, None, None) {
+            survival = parse_counts(parts[0]);
+            birth = parse_counts(parts[1]);
+            num_states = parts[2].parse::<u8>().ok();
+        }
+
+        match (birth, survival, num_states) {
+            (Some(birth), Some(survival), Some(num_states)) if num_states >= 2 => {
+                Ok(GenerationsRule {
+                    birth,
+                    survival,
+                    num_states,
+                })
+            }
+            _ => Err(error()),
+        }
+    }
+}
+
+impl fmt::Display for GenerationsRule {
+    /// Display the rule in `S/B/C` notation.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for count in (0..9).filter(|&count| self.survival[count]) {
+            write!(f, "{}", count)?;
+        }
+
+        write!(f, "/")?;
+        for count in (0..9).filter(|&count| self.birth[count]) {
+            write!(f, "{}", count)?;
+        }
+
+        write!(f, "/{}", self.num_states)
+    }
+}
+
+/// Simulation of a [GenerationsRule] on a bounded [StateGrid].
+#[derive(Clone, Debug)]
+pub struct GenerationsSim {
+    /// The cell states.
+    grid: StateGrid,
+
+    /// Buffer the next generation is computed into.
+    next: StateGrid,
+
+    /// The [GenerationsRule] applied to each cell every generation.
+    rule: GenerationsRule,
+
+    /// The simulation's current generation.
+    generation: u32,
+}
+
+impl GenerationsSim {
+    /// Create a new simulation of dead cells.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the grid.
+    /// * `num_cols` - The number of columns (width) of the grid.
+    /// * `rule` - The [GenerationsRule] applied to each cell.
+    pub fn new(num_rows: u32, num_cols: u32, rule: GenerationsRule) -> GenerationsSim {
+        GenerationsSim::new_with_grid(StateGrid::new(num_rows, num_cols), rule)
+    }
+
+    /// Create a new simulation from existing cell states.
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cell states.
+    /// * `rule` - The [GenerationsRule] applied to each cell.
+    pub fn new_with_grid(grid: StateGrid, rule: GenerationsRule) -> GenerationsSim {
+        GenerationsSim {
+            next: grid.clone(),
+            grid,
+            rule,
+            generation: 0,
+        }
+    }
+
+    /// Create a new simulation with the live cells of a [Grid].
+    ///
+    /// # Arguments
+    /// * `grid` - The initial live cells.
+    /// * `rule` - The [GenerationsRule] applied to each cell.
+    pub fn from_grid(grid: &Grid, rule: GenerationsRule) -> GenerationsSim {
+        GenerationsSim::new_with_grid(StateGrid::from_grid(grid), rule)
+    }
+
+    /// Get the simulation's cell states.
+    pub fn get_grid(&self) -> &StateGrid {
+        &self.grid
+    }
+
+    /// Get the simulation's [GenerationsRule].
+    pub fn get_rule(&self) -> GenerationsRule {
+        self.rule
+    }
+
+    /// Set the [GenerationsRule] applied to each cell every generation.
+    /// Cells in states the new rule does not have die.
+    pub fn set_rule(&mut self, rule: GenerationsRule) {
+        self.rule = rule;
+
+        let num_states = rule.get_num_states();
+        let (num_rows, num_cols) = (self.grid.get_num_rows(), self.grid.get_num_cols());
+
+        for row in 0..num_rows {
+            for col in 0..num_cols {
+                if self.grid.get(row, col) >= num_states {
+                    self.grid.set(row, col, DEAD);
+                }
+            }
+        }
+    }
+
+    /// Get the simulation's current generation.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Get the number of live cells, excluding decaying cells.
+    pub fn get_population(&self) -> u64 {
+        self.grid.count(ALIVE)
+    }
+
+    /// Determine if any cell is alive or decaying.
+    pub fn is_any_cell_alive(&self) -> bool {
+        self.grid.get_states().iter().any(|&state| state != DEAD)
+    }
+
+    /// Set the specified cells as alive.
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (row, col) to set as alive.
+    pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
+        self.grid.set_cells(cells, ALIVE);
+    }
+
+    /// Set the state of a specified cell.
+    pub fn set_cell(&mut self, row: u32, col: u32, state: u8) {
+        self.grid
+            .set(row, col, state.min(self.rule.get_num_states() - 1));
+    }
+
+    /// Advance the simulation by one generation.
+    pub fn step(&mut self) {
+        self.generation += 1;
+
+        let (grid, rule) = (&self.grid, &self.rule);
+        grid.map_into(&mut self.next, |row, col, state| {
+            let neighbor_count = if state <= ALIVE {
+                grid.count_neighbors(row, col, |neighbor| neighbor == ALIVE)
+            } else {
+                // decaying cells ignore their neighbors
+                0
+            };
+
+            rule.next_state(state, neighbor_count)
+        });
+
+        std::mem::swap(&mut self.grid, &mut self.next);
+    }
+
+    /// Advance the simulation by `n` generations.
+    pub fn step_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+}
+
+impl fmt::Display for GenerationsSim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.grid.fmt(f)
+    }
+}
@@ -0,0 +1,1918 @@
+//! Graphical user interface with mouse editing.
+
+use std::ops::Range;
+use std::time::Duration;
+
+use gameoflife_rs::render::{Rgb, Theme};
+use gameoflife_rs::{Cell, ConwaySim, Grid, HexSim, LeniaSim, Pattern, Transform};
+use macroquad::miniquad::window::{clipboard_get, clipboard_set};
+use macroquad::prelude::*;
+use macroquad::ui::{hash, root_ui, widgets};
+
+use crate::clock::{Clock, RATE_RANGE};
+use crate::keymap::{self, Action, Key, Keymap};
+use crate::selection::{self, Selection};
+use crate::session::Session;
+use crate::timeline::{Entry, Timeline};
+
+/// Initial size of a cell in pixels.
+const DEFAULT_CELL_SIZE: f32 = 12.0;
+
+/// Smallest size of a cell in pixels when zoomed out.
+const MIN_CELL_SIZE: f32 = 1.0;
+
+/// Largest size of a cell in pixels when zoomed in.
+const MAX_CELL_SIZE: f32 = 64.0;
+
+/// Smallest cell size at which grid lines are drawn.
+const GRID_LINE_CELL_SIZE: f32 = 6.0;
+
+/// Cell size below which cells are sized in whole pixels and drawn as the
+/// pixels of a texture rather than one rectangle each.
+const PIXEL_CELL_SIZE: f32 = 4.0;
+
+/// Fraction of the window filled by the pattern when fitting it in view.
+const FIT_FILL: f32 = 0.9;
+
+/// Length in pixels of the longer side of the minimap.
+const MINIMAP_SIZE: f32 = 200.0;
+
+/// Time in seconds between redraws of the minimap, which scans every cell.
+const MINIMAP_REFRESH: f64 = 0.1;
+
+/// Size of the population graph in pixels.
+const GRAPH_SIZE: Vec2 = vec2(300.0, 100.0);
+
+/// Height of the timeline in pixels.
+const TIMELINE_HEIGHT: f32 = 28.0;
+
+/// Width in pixels of the labels of the first and last generations at
+/// either end of the timeline.
+const TIMELINE_LABEL_WIDTH: f32 = 80.0;
+
+/// Range of the speed slider in generations per second.
+const RATE_SLIDER_RANGE: Range<f32> = *RATE_RANGE.start() as f32..*RATE_RANGE.end() as f32;
+
+/// Range of the spray size slider, as the radius of the spray in cells.
+const SPRAY_RADIUS_RANGE: std::ops::Range<f32> = 0.0..20.0;
+
+/// Range of the spray density slider, as the probability of each cell under
+/// the spray being brought to life per frame.
+const SPRAY_DENSITY_RANGE: std::ops::Range<f32> = 0.01..1.0;
+
+/// Named keys which do not type a character, polled alongside the typed
+/// characters.
+const NAMED_KEYS: [(KeyCode, Key); 9] = [
+    (KeyCode::Space, Key::Space),
+    (KeyCode::Enter, Key::Enter),
+    (KeyCode::Escape, Key::Esc),
+    (KeyCode::Tab, Key::Tab),
+    (KeyCode::Backspace, Key::Backspace),
+    (KeyCode::Up, Key::Up),
+    (KeyCode::Down, Key::Down),
+    (KeyCode::Left, Key::Left),
+    (KeyCode::Right, Key::Right),
+];
+
+/// Mouse controls, which cannot be rebound, for the help overlay.
+const MOUSE_HELP: [(&str, &str); 6] = [
+    ("left drag", "draw with the tool"),
+    ("shift drag", "select cells"),
+    ("right drag", "pan"),
+    ("scroll", "zoom"),
+    ("minimap click", "jump there"),
+    ("timeline drag", "move through the generations"),
+];
+
+/// Convert a color of a [Theme] to an opaque macroquad [Color].
+fn color(Rgb(red, green, blue): Rgb) -> Color {
+    Color::from_rgba(red, green, blue, 255)
+}
+
+/// Get the keys pressed since the last frame.
+fn pressed_keys() -> Vec<Key> {
+    let mut keys: Vec<Key> = Vec::new();
+
+    while let Some(c) = get_char_pressed() {
+        keys.push(Key::from(c));
+    }
+
+    // some platforms also type a character for these keys
+    for &(code, key) in NAMED_KEYS.iter() {
+        if is_key_pressed(code) && !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    keys
+}
+
+/// Position and zoom of the view onto the grid.
+///
+/// Below [PIXEL_CELL_SIZE] the cell size only takes whole pixel values and
+/// the offset is kept on whole pixels, so zoomed out cells are drawn pixel
+/// for pixel, down to one cell per pixel.
+#[derive(Copy, Clone, Debug)]
+struct Camera {
+    /// Size of a cell in pixels.
+    cell_size: f32,
+
+    /// Screen position of the grid's top left corner.
+    offset: Vec2,
+}
+
+impl Camera {
+    /// Create a new [Camera] showing an area of the grid in the middle of
+    /// the window, as large as fits within [FIT_FILL] of it.
+    ///
+    /// # Arguments
+    /// * `area` - The area to show, in cells.
+    /// * `max_cell_size` - The largest size of a cell in pixels.
+    fn fitting(area: Rect, max_cell_size: f32) -> Camera {
+        let mut camera = Camera {
+            cell_size: max_cell_size,
+            offset: Vec2::ZERO,
+        };
+        camera.fit(area, max_cell_size);
+
+        camera
+    }
+
+    /// Pan by a distance in pixels.
+    fn pan(&mut self, delta: Vec2) {
+        self.offset = (self.offset + delta).round();
+    }
+
+    /// Zoom by `factor` while keeping the point under `anchor` in place.
+    fn zoom(&mut self, factor: f32, anchor: Vec2) {
+        let mut cell_size = self.cell_size * factor;
+
+        // step through the small sizes a whole pixel at a time, or the same
+        // size would be rounded back every time
+        if cell_size < PIXEL_CELL_SIZE {
+            cell_size = if factor < 1.0 {
+                self.cell_size.ceil() - 1.0
+            } else {
+                self.cell_size.floor() + 1.0
+            };
+        }
+
+        self.zoom_to(cell_size, anchor);
+    }
+
+    /// Set the cell size while keeping the point under `anchor` in place.
+    fn zoom_to(&mut self, cell_size: f32, anchor: Vec2) {
+        let mut cell_size = cell_size.clamp(MIN_CELL_SIZE, MAX_CELL_SIZE);
+        if cell_size < PIXEL_CELL_SIZE {
+            cell_size = cell_size.round();
+        }
+
+        let scale = cell_size / self.cell_size;
+
+        self.offset = (anchor - (anchor - self.offset) * scale).round();
+        self.cell_size = cell_size;
+    }
+
+    /// Zoom and pan so an area of the grid fills the middle of the window.
+    ///
+    /// # Arguments
+    /// * `area` - The area to show, in cells.
+    /// * `max_cell_size` - The largest size of a cell in pixels.
+    fn fit(&mut self, area: Rect, max_cell_size: f32) {
+        let screen = vec2(screen_width(), screen_height());
+        let fill = screen * FIT_FILL / vec2(area.w, area.h).max(Vec2::ONE);
+
+        let mut cell_size = fill.min_element().clamp(MIN_CELL_SIZE, max_cell_size);
+        if cell_size < PIXEL_CELL_SIZE {
+            cell_size = cell_size.floor();
+        }
+
+        self.cell_size = cell_size;
+        self.center_on(area.center());
+    }
+
+    /// Pan so a point of the grid, in cells, is in the middle of the window.
+    fn center_on(&mut self, point: Vec2) {
+        let screen = vec2(screen_width(), screen_height());
+
+        self.offset = (screen / 2.0 - point * self.cell_size).round();
+    }
+
+    /// Get the area of the grid visible in the window, in cells.
+    fn viewport(&self) -> Rect {
+        let size = vec2(screen_width(), screen_height()) / self.cell_size;
+        let origin = -self.offset / self.cell_size;
+
+        Rect::new(origin.x, origin.y, size.x, size.y)
+    }
+
+    /// Get the (row, col) of the cell under a screen position, if any.
+    fn cell_at(&self, grid: &Grid, position: Vec2) -> Option<(u32, u32)> {
+        let cell = (position - self.offset) / self.cell_size;
+
+        if cell.x < 0.0 || cell.y < 0.0 {
+            return None;
+        }
+
+        let (row, col) = (cell.y as u32, cell.x as u32);
+
+        if row < grid.get_num_rows() && col < grid.get_num_cols() {
+            Some((row, col))
+        } else {
+            None
+        }
+    }
+
+    /// Get the screen position of the top left corner of the cell at (row,
+    /// col).
+    fn position(&self, row: u32, col: u32) -> Vec2 {
+        self.offset + vec2(col as f32, row as f32) * self.cell_size
+    }
+
+    /// Get the ranges of the rows and columns of the grid visible in the
+    /// window.
+    fn visible(&self, grid: &Grid) -> (Range<u32>, Range<u32>) {
+        let first = (-self.offset / self.cell_size).floor().max(Vec2::ZERO);
+        let last = ((vec2(screen_width(), screen_height()) - self.offset) / self.cell_size)
+            .ceil()
+            .max(Vec2::ZERO);
+
+        let rows = first.y as u32..(last.y as u32).min(grid.get_num_rows());
+        let cols = first.x as u32..(last.x as u32).min(grid.get_num_cols());
+
+        (rows, cols)
+    }
+}
+
+/// Get the area of the grid in cells, as a rectangle.
+fn grid_area(grid: &Grid) -> Rect {
+    Rect::new(
+        0.0,
+        0.0,
+        grid.get_num_cols() as f32,
+        grid.get_num_rows() as f32,
+    )
+}
+
+/// Get the screen area of the timeline's track along the bottom of the
+/// window, between the labels of its first and last generations.
+fn timeline_track() -> Rect {
+    Rect::new(
+        10.0 + TIMELINE_LABEL_WIDTH,
+        screen_height() - TIMELINE_HEIGHT - 10.0,
+        (screen_width() - 20.0 - 2.0 * TIMELINE_LABEL_WIDTH).max(1.0),
+        TIMELINE_HEIGHT,
+    )
+}
+
+/// Downsampled view of the whole grid in the top right corner of the window,
+/// outlining the area visible in the window.
+struct Minimap {
+    /// Texture of one pixel per block of cells, alive if any cell of the
+    /// block is.
+    texture: Option<Texture2D>,
+
+    /// Time the texture was last redrawn, from [get_time].
+    refreshed: f64,
+
+    /// Whether the left mouse button was pressed on the minimap and is still
+    /// down, moving the view along.
+    dragging: bool,
+}
+
+impl Minimap {
+    /// Get the screen area of the minimap for a grid, keeping its aspect
+    /// ratio.
+    fn area(grid: &Grid) -> Rect {
+        let size = vec2(grid.get_num_cols() as f32, grid.get_num_rows() as f32).max(Vec2::ONE);
+        let size = size * MINIMAP_SIZE / size.max_element();
+
+        Rect::new(screen_width() - size.x - 10.0, 10.0, size.x, size.y)
+    }
+
+    /// Get the point of the grid, in cells, under a screen position on the
+    /// minimap.
+    fn point_at(grid: &Grid, position: Vec2) -> Vec2 {
+        let area = Minimap::area(grid);
+
+        (position - area.point()) / area.size() * grid_area(grid).size()
+    }
+
+    /// Redraw the texture of the grid if it is older than
+    /// [MINIMAP_REFRESH].
+    fn refresh(&mut self, grid: &Grid, theme: &Theme) {
+        if self.texture.is_some() && get_time() - self.refreshed < MINIMAP_REFRESH {
+            return;
+        }
+
+        // one pixel per block of cells, so the texture is no larger than the
+        // minimap
+        let (num_rows, num_cols) = (grid.get_num_rows(), grid.get_num_cols());
+        let block = (num_rows.max(num_cols) as f32 / MINIMAP_SIZE)
+            .ceil()
+            .max(1.0) as u32;
+        let (width, height) = (
+            num_cols.div_ceil(block).max(1) as u16,
+            num_rows.div_ceil(block).max(1) as u16,
+        );
+
+        let mut image = Image::gen_image_color(width, height, color(theme.dead));
+        if num_cols > 0 {
+            for (row, cells) in grid.get_cells().chunks(num_cols as usize).enumerate() {
+                for (col, cell) in cells.iter().enumerate() {
+                    if cell.is_alive() {
+                        image.set_pixel(col as u32 / block, row as u32 / block, color(theme.alive));
+                    }
+                }
+            }
+        }
+
+        match &self.texture {
+            Some(texture) if texture.size() == vec2(width as f32, height as f32) => {
+                texture.update(&image);
+            }
+            _ => {
+                let texture = Texture2D::from_image(&image);
+                texture.set_filter(FilterMode::Nearest);
+                self.texture = Some(texture);
+            }
+        }
+
+        self.refreshed = get_time();
+    }
+
+    /// Draw the minimap of a grid in the colors of a theme, with the area
+    /// seen by a camera outlined.
+    fn draw(&mut self, grid: &Grid, camera: &Camera, theme: &Theme) {
+        self.refresh(grid, theme);
+
+        let area = Minimap::area(grid);
+        if let Some(texture) = &self.texture {
+            draw_texture_ex(
+                texture,
+                area.x,
+                area.y,
+                WHITE,
+                DrawTextureParams {
+                    dest_size: Some(area.size()),
+                    ..Default::default()
+                },
+            );
+        }
+        draw_rectangle_lines(area.x, area.y, area.w, area.h, 1.0, GRAY);
+
+        // outline the viewport, clipped to the minimap
+        let scale = area.size() / grid_area(grid).size().max(Vec2::ONE);
+        let viewport = camera.viewport();
+        let from = (area.point() + viewport.point() * scale)
+            .clamp(area.point(), area.point() + area.size());
+        let to = (area.point() + (viewport.point() + viewport.size()) * scale)
+            .clamp(area.point(), area.point() + area.size());
+
+        if to.x > from.x && to.y > from.y {
+            draw_rectangle_lines(from.x, from.y, to.x - from.x, to.y - from.y, 2.0, YELLOW);
+        }
+    }
+}
+
+/// Tool used on the grid with the left mouse button.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Tool {
+    /// Toggle the clicked cell, then paint its new state while dragging.
+    Pencil,
+
+    /// Draw a line of live cells from where the drag starts to where it
+    /// ends.
+    Line,
+
+    /// Draw a rectangle of live cells between the corners of the drag.
+    Rectangle,
+
+    /// Spray random live cells around the mouse while the button is down.
+    Spray,
+
+    /// Select the rectangle of cells between the corners of the drag.
+    Select,
+}
+
+impl Tool {
+    /// Every tool, in the order of the toolbar.
+    const ALL: [Tool; 5] = [
+        Tool::Pencil,
+        Tool::Line,
+        Tool::Rectangle,
+        Tool::Spray,
+        Tool::Select,
+    ];
+
+    /// Get the name of the tool on the toolbar.
+    fn get_name(self) -> &'static str {
+        match self {
+            Tool::Pencil => "Pencil",
+            Tool::Line => "Line",
+            Tool::Rectangle => "Rect",
+            Tool::Spray => "Spray",
+            Tool::Select => "Select",
+        }
+    }
+}
+
+/// Edit in progress while the left mouse button is down.
+#[derive(Copy, Clone, Debug)]
+enum Stroke {
+    /// Painting cells with a state.
+    Paint(Cell),
+
+    /// Drawing the line or rectangle of a tool between two cells, drawn
+    /// once the button is released.
+    Shape {
+        tool: Tool,
+        from: (u32, u32),
+        to: (u32, u32),
+    },
+
+    /// Spraying live cells.
+    Spray,
+
+    /// Extending the selection.
+    Select,
+}
+
+/// State of the graphical user interface.
+struct App {
+    /// The simulation being explored.
+    sim: ConwaySim,
+
+    /// The session autosaving the simulation, if any.
+    session: Option<Session>,
+
+    /// Whether the simulation is paused.
+    paused: bool,
+
+    /// Schedule of the generations while playing.
+    clock: Clock,
+
+    /// The view onto the grid.
+    camera: Camera,
+
+    /// Texture of one pixel per visible cell and the rows and columns it
+    /// shows, reused from frame to frame while the number of visible cells
+    /// stays the same.
+    pixels: Option<(Texture2D, Range<u32>, Range<u32>)>,
+
+    /// Tool used on the grid with the left mouse button.
+    tool: Tool,
+
+    /// Whether the rectangle tool fills the rectangles it draws.
+    fill: bool,
+
+    /// Radius of the spray tool in cells.
+    spray_radius: f32,
+
+    /// Probability of each cell under the spray tool being brought to life
+    /// per frame.
+    spray_density: f32,
+
+    /// Edit in progress with the left mouse button, if any.
+    stroke: Option<Stroke>,
+
+    /// The selected cells, if any.
+    selection: Option<Selection>,
+
+    /// The cells last copied or cut.
+    clipboard: Option<Pattern>,
+
+    /// Whether the population graph is shown.
+    show_graph: bool,
+
+    /// The minimap of the whole grid.
+    minimap: Minimap,
+
+    /// Whether the minimap is shown.
+    show_minimap: bool,
+
+    /// The generations which can be returned to, and their bookmarks.
+    timeline: Timeline,
+
+    /// Whether the left mouse button was pressed on the timeline and is
+    /// still down, moving the scrubber along.
+    scrubbing: bool,
+
+    /// The keys bound to each action.
+    keymap: Keymap,
+
+    /// Colors of the cells, grid lines, and background.
+    theme: Theme,
+
+    /// Whether the key bindings are shown over the grid.
+    show_help: bool,
+
+    /// Outcome of the last action to report in the control panel, if any.
+    message: Option<String>,
+
+    /// Whether the user has requested to quit.
+    quit: bool,
+
+    /// Mouse position of the previous frame, used for panning.
+    last_mouse: Vec2,
+}
+
+impl App {
+    /// Create a new, paused [App] with the grid centered in the window and
+    /// zoomed out to fit if needed, recording the simulation's statistics
+    /// for the population graph.
+    fn new(
+        mut sim: ConwaySim,
+        session: Option<Session>,
+        clock: Clock,
+        keymap: Keymap,
+        theme: Theme,
+    ) -> App {
+        if sim.get_stats().is_none() {
+            sim.enable_stats();
+        }
+        sim.enable_undo(keymap::UNDO_CAPACITY);
+        if sim.get_history().is_none() {
+            sim.enable_history(keymap::HISTORY_INTERVAL, keymap::HISTORY_CAPACITY);
+        }
+
+        let camera = Camera::fitting(grid_area(sim.get_grid()), DEFAULT_CELL_SIZE);
+        let timeline = Timeline::new(&sim);
+
+        App {
+            sim,
+            session,
+            paused: true,
+            clock,
+            camera,
+            pixels: None,
+            tool: Tool::Pencil,
+            fill: false,
+            spray_radius: 4.0,
+            spray_density: 0.1,
+            stroke: None,
+            selection: None,
+            clipboard: None,
+            show_graph: true,
+            minimap: Minimap {
+                texture: None,
+                refreshed: 0.0,
+                dragging: false,
+            },
+            show_minimap: true,
+            timeline,
+            scrubbing: false,
+            keymap,
+            theme,
+            show_help: false,
+            message: None,
+            quit: false,
+            last_mouse: Vec2::from(mouse_position()),
+        }
+    }
+
+    /// Perform an action bound to a key.
+    fn perform(&mut self, action: Action) {
+        match action {
+            Action::Pause => self.paused = !self.paused,
+            Action::Step => {
+                self.paused = true;
+                self.sim.step();
+            }
+            Action::StepBack => {
+                self.paused = true;
+
+                if self.sim.get_generation() == 0 {
+                    self.message = Some("At the first generation".to_string());
+                } else if let Err(err) = self.sim.step_backward() {
+                    self.message = Some(format!("Unable to step back: {}", err));
+                }
+            }
+            Action::ScrubBack | Action::ScrubForward => {
+                self.paused = true;
+
+                let forward = action == Action::ScrubForward;
+                if let Err(err) = self.timeline.scrub(&mut self.sim, forward) {
+                    self.message = Some(format!("Unable to scrub: {}", err));
+                }
+            }
+            Action::GoTo => self.timeline.start_entry(),
+            Action::Bookmark => {
+                let generation = self.sim.get_generation();
+
+                self.message = Some(if self.timeline.toggle_bookmark(generation) {
+                    format!("Bookmarked generation {}", generation)
+                } else {
+                    format!("Removed the bookmark of generation {}", generation)
+                });
+            }
+            Action::PreviousBookmark => {
+                match self.timeline.previous_bookmark(self.sim.get_generation()) {
+                    Some(generation) => self.go_to(generation),
+                    None => self.message = Some("No earlier bookmark".to_string()),
+                }
+            }
+            Action::NextBookmark => match self.timeline.next_bookmark(self.sim.get_generation()) {
+                Some(generation) => self.go_to(generation),
+                None => self.message = Some("No later bookmark".to_string()),
+            },
+            Action::Faster => self.clock.faster(),
+            Action::Slower => self.clock.slower(),
+            Action::Clear => {
+                self.sim.begin_edit();
+                self.sim.clear();
+            }
+            Action::Randomize => {
+                self.sim.begin_edit();
+                keymap::randomize(&mut self.sim);
+            }
+            Action::Undo => {
+                if !self.sim.undo() {
+                    self.message = Some("Nothing to undo".to_string());
+                }
+            }
+            Action::Redo => {
+                if !self.sim.redo() {
+                    self.message = Some("Nothing to redo".to_string());
+                }
+            }
+            Action::Select => {
+                let had_selection = self.selection.take().is_some();
+
+                if self.tool == Tool::Select {
+                    self.tool = Tool::Pencil;
+                } else if !had_selection {
+                    self.tool = Tool::Select;
+                }
+            }
+            Action::Copy | Action::Cut | Action::Rotate | Action::Flip => match self.selection {
+                Some(selected) => self.edit_selection(action, &selected),
+                None => self.message = Some("Nothing selected".to_string()),
+            },
+            Action::Paste => {
+                // prefer RLE copied from another program, such as Golly
+                if let Some(pattern) =
+                    clipboard_get().and_then(|text| Pattern::from_rle(&text).ok())
+                {
+                    self.clipboard = Some(pattern);
+                }
+
+                let mouse = Vec2::from(mouse_position());
+                let target = self
+                    .camera
+                    .cell_at(self.sim.get_grid(), mouse)
+                    .or_else(|| self.selection.map(|selection| selection.get_origin()));
+
+                match (&self.clipboard, target) {
+                    (Some(pattern), Some((row, col))) => {
+                        self.sim.begin_edit();
+                        self.selection = Some(selection::paste(&mut self.sim, pattern, row, col));
+                    }
+                    (Some(_), None) => self.message = Some("Point at a cell to paste".to_string()),
+                    (None, _) => self.message = Some("Nothing to paste".to_string()),
+                }
+            }
+            Action::Save => {
+                self.message = Some(match keymap::save(&self.sim) {
+                    Ok(path) => format!("Saved {}", path.display()),
+                    Err(err) => format!("Unable to save: {}", err),
+                });
+            }
+            Action::Graph => self.show_graph = !self.show_graph,
+            Action::Fit => {
+                let grid = self.sim.get_grid();
+                let area = match grid.bounding_box() {
+                    Some((min_row, min_col, max_row, max_col)) => Rect::new(
+                        min_col as f32,
+                        min_row as f32,
+                        (max_col - min_col + 1) as f32,
+                        (max_row - min_row + 1) as f32,
+                    ),
+                    None => grid_area(grid),
+                };
+
+                self.camera.fit(area, MAX_CELL_SIZE);
+            }
+            Action::Minimap => self.show_minimap = !self.show_minimap,
+            Action::Help => self.show_help = !self.show_help,
+            Action::Quit => self.quit = true,
+        }
+    }
+
+    /// Pause and move to a generation of the timeline, reporting if it
+    /// cannot be reached.
+    fn go_to(&mut self, generation: u32) {
+        self.paused = true;
+
+        if let Err(err) = self.timeline.seek(&mut self.sim, generation) {
+            self.message = Some(format!(
+                "Unable to go to generation {}: {}",
+                generation, err
+            ));
+        }
+    }
+
+    /// Copy, cut, rotate, or flip the selected cells.
+    fn edit_selection(&mut self, action: Action, selected: &Selection) {
+        match action {
+            Action::Copy | Action::Cut => {
+                let pattern = if action == Action::Cut {
+                    self.sim.begin_edit();
+                    selection::cut(&mut self.sim, selected)
+                } else {
+                    selection::copy(&self.sim, selected)
+                };
+
+                clipboard_set(&pattern.to_rle());
+                self.message = Some(format!(
+                    "Copied {}x{} as RLE",
+                    pattern.get_num_cols(),
+                    pattern.get_num_rows()
+                ));
+                self.clipboard = Some(pattern);
+            }
+            Action::Rotate => {
+                self.sim.begin_edit();
+                self.selection = Some(selection::transform(
+                    &mut self.sim,
+                    selected,
+                    Transform::Rotate90,
+                ));
+            }
+            Action::Flip => {
+                self.sim.begin_edit();
+                self.selection = Some(selection::transform(
+                    &mut self.sim,
+                    selected,
+                    Transform::FlipHorizontal,
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle keyboard and mouse input.
+    fn handle_input(&mut self) {
+        let mouse = Vec2::from(mouse_position());
+        let over_ui = root_ui().is_mouse_over(mouse);
+
+        let keys = pressed_keys();
+        if !keys.is_empty() {
+            self.message = None;
+        }
+
+        // keys are typed into the generation entry while it is open
+        for key in keys {
+            if self.timeline.get_entry().is_some() {
+                if let Entry::Submitted(generation) = self.timeline.type_key(key) {
+                    self.go_to(generation);
+                }
+            } else if let Some(action) = self.keymap.get_action(key) {
+                self.perform(action);
+            }
+        }
+
+        // zoom around the mouse with the scroll wheel
+        let (_, wheel) = mouse_wheel();
+        if wheel != 0.0 && !over_ui {
+            self.camera
+                .zoom(if wheel > 0.0 { 1.1 } else { 1.0 / 1.1 }, mouse);
+        }
+
+        // pan with the right or middle mouse button
+        if (is_mouse_button_down(MouseButton::Right) || is_mouse_button_down(MouseButton::Middle))
+            && !over_ui
+        {
+            self.camera.pan(mouse - self.last_mouse);
+        }
+
+        // jump to the point clicked on the minimap, following the mouse
+        // while dragging
+        let grid = self.sim.get_grid();
+        if is_mouse_button_pressed(MouseButton::Left)
+            && self.show_minimap
+            && Minimap::area(grid).contains(mouse)
+            && !over_ui
+        {
+            self.minimap.dragging = true;
+        } else if !is_mouse_button_down(MouseButton::Left) {
+            self.minimap.dragging = false;
+        }
+
+        // move to the generation clicked on the timeline, following the
+        // mouse while dragging
+        let track = timeline_track();
+        if is_mouse_button_pressed(MouseButton::Left)
+            && !self.minimap.dragging
+            && track.contains(mouse)
+            && !over_ui
+        {
+            self.scrubbing = true;
+        } else if !is_mouse_button_down(MouseButton::Left) {
+            self.scrubbing = false;
+        }
+
+        // edit with the tool from the clicked cell while dragging, or select
+        // with shift held
+        let cell = self.camera.cell_at(grid, mouse);
+        if self.minimap.dragging {
+            self.camera.center_on(Minimap::point_at(grid, mouse));
+        } else if self.scrubbing {
+            let generation = self
+                .timeline
+                .generation_at(&self.sim, (mouse.x - track.x) / track.w);
+            self.go_to(generation);
+        } else if is_mouse_button_pressed(MouseButton::Left) && !over_ui {
+            let tool = if is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift) {
+                Tool::Select
+            } else {
+                self.tool
+            };
+
+            self.stroke = cell.map(|cell| self.begin_stroke(tool, cell));
+        } else if is_mouse_button_down(MouseButton::Left) {
+            if let (Some(stroke), Some(cell)) = (self.stroke, cell) {
+                self.stroke = Some(self.continue_stroke(stroke, cell));
+            }
+        } else if let Some(stroke) = self.stroke.take() {
+            self.end_stroke(stroke);
+        }
+
+        self.last_mouse = mouse;
+    }
+
+    /// Start editing with a tool from the (row, col) of the clicked cell,
+    /// saving the cells to undo a painting or spraying stroke as a whole.
+    fn begin_stroke(&mut self, tool: Tool, (row, col): (u32, u32)) -> Stroke {
+        match tool {
+            Tool::Pencil => {
+                self.sim.begin_edit();
+                self.sim.toggle_cell(row, col);
+                Stroke::Paint(self.sim.get_grid().get(row, col))
+            }
+            Tool::Line | Tool::Rectangle => Stroke::Shape {
+                tool,
+                from: (row, col),
+                to: (row, col),
+            },
+            Tool::Spray => {
+                self.sim.begin_edit();
+                self.spray((row, col));
+                Stroke::Spray
+            }
+            Tool::Select => {
+                self.selection = Some(Selection::new(row, col));
+                Stroke::Select
+            }
+        }
+    }
+
+    /// Continue an edit with the (row, col) of the cell under the mouse.
+    fn continue_stroke(&mut self, stroke: Stroke, (row, col): (u32, u32)) -> Stroke {
+        match stroke {
+            Stroke::Paint(state) => self.sim.set_cell(row, col, state),
+            Stroke::Shape { tool, from, .. } => {
+                return Stroke::Shape {
+                    tool,
+                    from,
+                    to: (row, col),
+                };
+            }
+            Stroke::Spray => self.spray((row, col)),
+            Stroke::Select => {
+                if let Some(selection) = self.selection.as_mut() {
+                    selection.extend_to(row, col);
+                }
+            }
+        }
+
+        stroke
+    }
+
+    /// Finish an edit once the left mouse button is released, drawing its
+    /// line or rectangle.
+    fn end_stroke(&mut self, stroke: Stroke) {
+        if let Stroke::Shape { .. } = stroke {
+            self.sim.begin_edit();
+        }
+
+        match stroke {
+            Stroke::Shape {
+                tool: Tool::Line,
+                from,
+                to,
+            } => self.sim.draw_line(from, to, Cell::Alive),
+            Stroke::Shape { from, to, .. } => self.sim.draw_rect(from, to, Cell::Alive, self.fill),
+            _ => {}
+        }
+    }
+
+    /// Spray random live cells around a cell with the spray tool's size and
+    /// density.
+    fn spray(&mut self, center: (u32, u32)) {
+        self.sim.spray(
+            center,
+            self.spray_radius.round() as u32,
+            self.spray_density as f64,
+            crate::random_seed(),
+        );
+    }
+
+    /// Advance the simulation by the generations due since the last frame.
+    fn update(&mut self) {
+        if self.paused {
+            self.clock.reset();
+        } else {
+            for _ in 0..self
+                .clock
+                .advance(Duration::from_secs_f32(get_frame_time()))
+            {
+                self.sim.step();
+            }
+        }
+
+        self.timeline.observe(&self.sim);
+
+        if let Some(session) = &mut self.session {
+            if let Err(err) = session.autosave(&self.sim) {
+                self.message = Some(format!("Unable to autosave: {}", err));
+            }
+        }
+    }
+
+    /// Draw the visible cells and grid lines.
+    fn draw_grid(&mut self) {
+        let Camera { cell_size, offset } = self.camera;
+        let (rows, cols) = self.camera.visible(self.sim.get_grid());
+
+        let grid = self.sim.get_grid();
+        let size = vec2(grid.get_num_cols() as f32, grid.get_num_rows() as f32) * cell_size;
+        draw_rectangle(offset.x, offset.y, size.x, size.y, color(self.theme.dead));
+
+        if cell_size < PIXEL_CELL_SIZE {
+            self.draw_pixels(rows, cols);
+            return;
+        }
+
+        for row in rows.clone() {
+            for col in cols.clone() {
+                if grid.get(row, col).is_alive() {
+                    let position = self.camera.position(row, col);
+                    draw_rectangle(
+                        position.x,
+                        position.y,
+                        cell_size,
+                        cell_size,
+                        color(self.theme.alive),
+                    );
+                }
+            }
+        }
+
+        if cell_size >= GRID_LINE_CELL_SIZE {
+            let line_color = color(self.theme.grid_lines);
+
+            for row in rows.start..=rows.end {
+                let y = offset.y + row as f32 * cell_size;
+                draw_line(offset.x, y, offset.x + size.x, y, 1.0, line_color);
+            }
+
+            for col in cols.start..=cols.end {
+                let x = offset.x + col as f32 * cell_size;
+                draw_line(x, offset.y, x, offset.y + size.y, 1.0, line_color);
+            }
+        }
+    }
+
+    /// Draw the visible cells as the pixels of a texture scaled by the whole
+    /// pixel cell size, without filtering, so each cell covers exactly the
+    /// same number of pixels.
+    ///
+    /// While the same cells stay visible, only the grid's dirty regions are
+    /// uploaded to the texture.
+    fn draw_pixels(&mut self, rows: Range<u32>, cols: Range<u32>) {
+        if rows.is_empty() || cols.is_empty() {
+            return;
+        }
+
+        let (width, height) = (cols.len() as u16, rows.len() as u16);
+
+        match &self.pixels {
+            Some((texture, shown_rows, shown_cols))
+                if (shown_rows, shown_cols) == (&rows, &cols) =>
+            {
+                for region in self.sim.get_grid().dirty_regions() {
+                    let (row, col) = region.get_origin();
+                    let dirty_rows =
+                        row.max(rows.start)..(row + region.get_num_rows()).min(rows.end);
+                    let dirty_cols =
+                        col.max(cols.start)..(col + region.get_num_cols()).min(cols.end);
+                    if dirty_rows.is_empty() || dirty_cols.is_empty() {
+                        continue;
+                    }
+
+                    texture.update_part(
+                        &self.cells_image(dirty_rows.clone(), dirty_cols.clone()),
+                        (dirty_cols.start - cols.start) as i32,
+                        (dirty_rows.start - rows.start) as i32,
+                        dirty_cols.len() as i32,
+                        dirty_rows.len() as i32,
+                    );
+                }
+            }
+            Some((texture, _, _)) if texture.size() == vec2(width as f32, height as f32) => {
+                texture.update(&self.cells_image(rows.clone(), cols.clone()));
+                self.pixels = Some((texture.clone(), rows.clone(), cols.clone()));
+            }
+            _ => {
+                let texture = Texture2D::from_image(&self.cells_image(rows.clone(), cols.clone()));
+                texture.set_filter(FilterMode::Nearest);
+                self.pixels = Some((texture, rows.clone(), cols.clone()));
+            }
+        }
+        self.sim.clear_dirty();
+
+        let texture = match &self.pixels {
+            Some((texture, _, _)) => texture,
+            None => return,
+        };
+
+        let position = self.camera.position(rows.start, cols.start);
+        draw_texture_ex(
+            texture,
+            position.x,
+            position.y,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(texture.size() * self.camera.cell_size),
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Build an image of one pixel per cell within some rows and columns,
+    /// transparent where the cells are dead.
+    fn cells_image(&self, rows: Range<u32>, cols: Range<u32>) -> Image {
+        let grid = self.sim.get_grid();
+
+        let mut image = Image::gen_image_color(cols.len() as u16, rows.len() as u16, BLANK);
+        for (y, row) in rows.enumerate() {
+            for (x, col) in cols.clone().enumerate() {
+                if grid.get(row, col).is_alive() {
+                    image.set_pixel(x as u32, y as u32, color(self.theme.alive));
+                }
+            }
+        }
+
+        image
+    }
+
+    /// Shade and outline the selected cells.
+    fn draw_selection(&self) {
+        let selection = match &self.selection {
+            Some(selection) => selection,
+            None => return,
+        };
+
+        let (row, col) = selection.get_origin();
+        let position = self.camera.position(row, col);
+        let size = vec2(
+            selection.get_num_cols() as f32,
+            selection.get_num_rows() as f32,
+        ) * self.camera.cell_size;
+
+        draw_rectangle(
+            position.x,
+            position.y,
+            size.x,
+            size.y,
+            Color::from_rgba(255, 220, 0, 60),
+        );
+        draw_rectangle_lines(position.x, position.y, size.x, size.y, 2.0, YELLOW);
+    }
+
+    /// Draw the line or rectangle being drawn, or the outline of the spray.
+    fn draw_stroke(&self) {
+        let preview = Color::from_rgba(255, 255, 255, 120);
+        let cell_size = self.camera.cell_size;
+
+        match self.stroke {
+            Some(Stroke::Shape {
+                tool: Tool::Line,
+                from,
+                to,
+            }) => {
+                let from = self.camera.position(from.0, from.1) + Vec2::splat(cell_size / 2.0);
+                let to = self.camera.position(to.0, to.1) + Vec2::splat(cell_size / 2.0);
+                draw_line(from.x, from.y, to.x, to.y, cell_size, preview);
+            }
+            Some(Stroke::Shape { from, to, .. }) => {
+                let first = self.camera.position(from.0.min(to.0), from.1.min(to.1));
+                let last = self.camera.position(from.0.max(to.0), from.1.max(to.1))
+                    + Vec2::splat(cell_size);
+                let size = last - first;
+
+                if self.fill {
+                    draw_rectangle(first.x, first.y, size.x, size.y, preview);
+                } else {
+                    draw_rectangle_lines(
+                        first.x,
+                        first.y,
+                        size.x,
+                        size.y,
+                        cell_size * 2.0,
+                        preview,
+                    );
+                }
+            }
+            _ if self.tool == Tool::Spray => {
+                let (x, y) = mouse_position();
+                let radius = (self.spray_radius.round() + 0.5) * cell_size;
+                draw_circle_lines(x, y, radius, 1.0, preview);
+            }
+            _ => {}
+        }
+    }
+
+    /// Draw a line graph of the population of the most recent generations
+    /// in the bottom left corner of the window.
+    fn draw_graph(&self) {
+        let stats = match self.sim.get_stats() {
+            Some(stats) => stats,
+            None => return,
+        };
+
+        let origin = vec2(
+            10.0,
+            screen_height() - GRAPH_SIZE.y - TIMELINE_HEIGHT - 20.0,
+        );
+        draw_rectangle(
+            origin.x,
+            origin.y,
+            GRAPH_SIZE.x,
+            GRAPH_SIZE.y,
+            Color::from_rgba(0, 0, 0, 200),
+        );
+        draw_rectangle_lines(origin.x, origin.y, GRAPH_SIZE.x, GRAPH_SIZE.y, 1.0, GRAY);
+
+        // one generation per pixel, scaled to the highest population shown
+        let populations = stats.recent_populations(GRAPH_SIZE.x as usize);
+        let max = populations.iter().copied().max().unwrap_or(0).max(1) as f32;
+        let point = |index: usize, population: u64| {
+            origin + vec2(index as f32, GRAPH_SIZE.y * (1.0 - population as f32 / max))
+        };
+
+        for (index, pair) in populations.windows(2).enumerate() {
+            let (from, to) = (point(index, pair[0]), point(index + 1, pair[1]));
+            draw_line(from.x, from.y, to.x, to.y, 1.0, GREEN);
+        }
+
+        draw_text(
+            &format!("Population (peak {})", stats.peak_population()),
+            origin.x + 4.0,
+            origin.y + 14.0,
+            16.0,
+            LIGHTGRAY,
+        );
+    }
+
+    /// Draw the timeline of the generations which can be returned to along
+    /// the bottom of the window, with ticks at the recorded snapshots, its
+    /// bookmarks, and a handle at the current generation.
+    fn draw_timeline(&self) {
+        let track = timeline_track();
+        let range = self.timeline.get_range(&self.sim);
+        let middle = track.y + track.h / 2.0;
+
+        draw_rectangle(
+            10.0,
+            track.y,
+            screen_width() - 20.0,
+            track.h,
+            Color::from_rgba(0, 0, 0, 200),
+        );
+        draw_rectangle_lines(10.0, track.y, screen_width() - 20.0, track.h, 1.0, GRAY);
+        draw_line(track.x, middle, track.x + track.w, middle, 2.0, GRAY);
+
+        let x =
+            |generation: u32| track.x + self.timeline.fraction_of(&self.sim, generation) * track.w;
+
+        // snapshots are restored without re-simulating
+        if let Some(history) = self.sim.get_history() {
+            for generation in history.generations() {
+                if range.contains(&generation) {
+                    draw_line(
+                        x(generation),
+                        middle - 4.0,
+                        x(generation),
+                        middle + 4.0,
+                        1.0,
+                        LIGHTGRAY,
+                    );
+                }
+            }
+        }
+
+        for &bookmark in self.timeline.get_bookmarks() {
+            if range.contains(&bookmark) {
+                let x = x(bookmark);
+                draw_line(x, track.y + 3.0, x, track.y + track.h - 3.0, 2.0, YELLOW);
+            }
+        }
+
+        let handle = x(self.sim.get_generation());
+        draw_rectangle(handle - 4.0, track.y + 4.0, 8.0, track.h - 8.0, WHITE);
+
+        let first = range.start().to_string();
+        let last = range.end().to_string();
+        let last_width = measure_text(&last, None, 16, 1.0).width;
+        draw_text(&first, 16.0, middle + 5.0, 16.0, LIGHTGRAY);
+        draw_text(
+            &last,
+            screen_width() - 16.0 - last_width,
+            middle + 5.0,
+            16.0,
+            LIGHTGRAY,
+        );
+    }
+
+    /// Draw the key bound to each action in a box over the grid.
+    fn draw_help(&self) {
+        const LINE_HEIGHT: f32 = 24.0;
+        const KEYS_WIDTH: f32 = 150.0;
+
+        let entries = self.keymap.help_entries();
+        let entries: Vec<(&str, &str)> = entries
+            .iter()
+            .map(|(keys, description)| (keys.as_str(), *description))
+            .chain(MOUSE_HELP.iter().copied())
+            .collect();
+
+        let size = vec2(460.0, LINE_HEIGHT * (entries.len() + 2) as f32);
+        let origin = (vec2(screen_width(), screen_height()) - size) / 2.0;
+        draw_rectangle(
+            origin.x,
+            origin.y,
+            size.x,
+            size.y,
+            Color::from_rgba(0, 0, 0, 220),
+        );
+        draw_rectangle_lines(origin.x, origin.y, size.x, size.y, 1.0, GRAY);
+        draw_text("Keys", origin.x + 10.0, origin.y + LINE_HEIGHT, 20.0, WHITE);
+
+        for (index, (keys, description)) in entries.into_iter().enumerate() {
+            let y = origin.y + LINE_HEIGHT * (index + 2) as f32;
+            draw_text(keys, origin.x + 10.0, y, 20.0, LIGHTGRAY);
+            draw_text(
+                description,
+                origin.x + 10.0 + KEYS_WIDTH,
+                y,
+                20.0,
+                LIGHTGRAY,
+            );
+        }
+    }
+
+    /// Draw the toolbar choosing the tool of the left mouse button, and the
+    /// settings of the tools.
+    fn draw_tools(&mut self) {
+        let current = &mut self.tool;
+        let fill = &mut self.fill;
+        let spray_radius = &mut self.spray_radius;
+        let spray_density = &mut self.spray_density;
+
+        widgets::Window::new(hash!(), vec2(10.0, 170.0), vec2(300.0, 130.0))
+            .label("Tools")
+            .ui(&mut root_ui(), |ui| {
+                for (index, &tool) in Tool::ALL.iter().enumerate() {
+                    if index > 0 {
+                        ui.same_line(0.0);
+                    }
+
+                    let name = if tool == *current {
+                        format!("[{}]", tool.get_name())
+                    } else {
+                        tool.get_name().to_string()
+                    };
+                    if ui.button(None, name.as_str()) {
+                        *current = tool;
+                    }
+                }
+
+                ui.checkbox(hash!(), "filled rectangles", fill);
+                ui.slider(hash!(), "spray size", SPRAY_RADIUS_RANGE, spray_radius);
+                ui.slider(hash!(), "density", SPRAY_DENSITY_RANGE, spray_density);
+            });
+    }
+
+    /// Draw the control panel.
+    fn draw_controls(&mut self) {
+        let paused = &mut self.paused;
+        let clock = &mut self.clock;
+        let sim = &mut self.sim;
+        let hint = match (self.timeline.get_entry(), &self.message, &self.selection) {
+            (Some(digits), _, _) => format!("Go to generation: {}_", digits),
+            (None, Some(message), _) => message.clone(),
+            (None, None, Some(selection)) => format!(
+                "Selected {}x{}",
+                selection.get_num_cols(),
+                selection.get_num_rows()
+            ),
+            (None, None, None) => format!("[{}] keys", self.keymap.describe_keys(Action::Help)),
+        };
+
+        widgets::Window::new(hash!(), vec2(10.0, 10.0), vec2(300.0, 150.0))
+            .label("Controls")
+            .ui(&mut root_ui(), |ui| {
+                ui.label(None, &format!("Generation: {}", sim.get_generation()));
+                ui.label(
+                    None,
+                    &format!("Population: {}", sim.get_grid().get_population()),
+                );
+                ui.label(None, &hint);
+
+                if ui.button(None, if *paused { "Resume" } else { "Pause" }) {
+                    *paused = !*paused;
+                }
+
+                ui.same_line(0.0);
+                if ui.button(None, "Back") {
+                    *paused = true;
+                    let _ = sim.step_backward();
+                }
+
+                ui.same_line(0.0);
+                if ui.button(None, "Step") {
+                    *paused = true;
+                    sim.step();
+                }
+
+                ui.same_line(0.0);
+                if ui.button(None, "Undo") {
+                    sim.undo();
+                }
+
+                ui.same_line(0.0);
+                if ui.button(None, "Redo") {
+                    sim.redo();
+                }
+
+                let mut rate = clock.get_rate() as f32;
+                ui.slider(hash!(), "gen/s", RATE_SLIDER_RANGE, &mut rate);
+                if rate != clock.get_rate() as f32 {
+                    clock.set_rate(rate as f64);
+                }
+            });
+    }
+}
+
+/// Horizontal distance between the centers of neighboring hexagons, per
+/// pixel of hexagon radius.
+const HEX_WIDTH: f32 = 1.732_050_8;
+
+/// Vertical distance between the centers of hexagons in neighboring rows, per
+/// pixel of hexagon radius.
+const HEX_HEIGHT: f32 = 1.5;
+
+/// State of the graphical user interface for a hexagonal grid, drawing
+/// pointy-top hexagons in place of square cells.
+struct HexApp {
+    /// The simulation being explored.
+    sim: HexSim,
+
+    /// Whether the simulation is paused.
+    paused: bool,
+
+    /// Schedule of the generations while playing.
+    clock: Clock,
+
+    /// The view onto the grid, its cell size being the hexagon radius and
+    /// its offset the screen position of the center of cell (0, 0).
+    camera: Camera,
+
+    /// Mouse position of the previous frame, used for panning.
+    last_mouse: Vec2,
+
+    /// Colors of the cells, grid lines, and background.
+    theme: Theme,
+}
+
+impl HexApp {
+    /// Create a new, paused [HexApp] with the grid centered in the window.
+    fn new(sim: HexSim, clock: Clock, theme: Theme) -> HexApp {
+        let grid = sim.get_grid();
+        let (num_rows, num_cols) = (grid.get_num_rows() as f32, grid.get_num_cols() as f32);
+        let radius = DEFAULT_CELL_SIZE / 2.0;
+
+        // the rows lean left, so the grid spans from the start of the last
+        // row to the end of the first row
+        let left = -(num_rows - 1.0).max(0.0) / 2.0 * HEX_WIDTH * radius;
+        let right = (num_cols - 1.0).max(0.0) * HEX_WIDTH * radius;
+        let bottom = (num_rows - 1.0).max(0.0) * HEX_HEIGHT * radius;
+        let center = vec2((left + right) / 2.0, bottom / 2.0);
+
+        HexApp {
+            sim,
+            paused: true,
+            clock,
+            camera: Camera {
+                cell_size: radius,
+                offset: vec2(screen_width(), screen_height()) / 2.0 - center,
+            },
+            last_mouse: Vec2::from(mouse_position()),
+            theme,
+        }
+    }
+
+    /// Get the screen position of the center of the cell at (q, r).
+    fn center(&self, q: u32, r: u32) -> Vec2 {
+        let Camera { cell_size, offset } = self.camera;
+
+        offset
+            + vec2(
+                (q as f32 - r as f32 / 2.0) * HEX_WIDTH,
+                r as f32 * HEX_HEIGHT,
+            ) * cell_size
+    }
+
+    /// Get the (q, r) of the cell under a screen position, if any.
+    fn cell_at(&self, position: Vec2) -> Option<(u32, u32)> {
+        let relative = (position - self.camera.offset) / self.camera.cell_size;
+        let r = relative.y / HEX_HEIGHT;
+        let q = relative.x / HEX_WIDTH + r / 2.0;
+
+        // round in cube coordinates, where x = q - r, z = r, and y = -x - z
+        let (x, z) = (q - r, r);
+        let y = -x - z;
+        let (mut rx, ry, mut rz) = (x.round(), y.round(), z.round());
+
+        let (dx, dy, dz) = ((rx - x).abs(), (ry - y).abs(), (rz - z).abs());
+        if dx > dy && dx > dz {
+            rx = -ry - rz;
+        } else if dy <= dz {
+            rz = -rx - ry;
+        }
+
+        let (q, r) = (rx + rz, rz);
+        let grid = self.sim.get_grid();
+
+        if q >= 0.0
+            && r >= 0.0
+            && (q as u32) < grid.get_num_cols()
+            && (r as u32) < grid.get_num_rows()
+        {
+            Some((q as u32, r as u32))
+        } else {
+            None
+        }
+    }
+
+    /// Handle keyboard and mouse input.
+    fn handle_input(&mut self) {
+        let mouse = Vec2::from(mouse_position());
+        let over_ui = root_ui().is_mouse_over(mouse);
+
+        if is_key_pressed(KeyCode::Space) {
+            self.paused = !self.paused;
+        }
+
+        if is_key_pressed(KeyCode::N) {
+            self.paused = true;
+            self.sim.step();
+        }
+
+        let (_, wheel) = mouse_wheel();
+        if wheel != 0.0 && !over_ui {
+            self.camera
+                .zoom(if wheel > 0.0 { 1.1 } else { 1.0 / 1.1 }, mouse);
+        }
+
+        if (is_mouse_button_down(MouseButton::Right) || is_mouse_button_down(MouseButton::Middle))
+            && !over_ui
+        {
+            self.camera.pan(mouse - self.last_mouse);
+        }
+
+        if is_mouse_button_pressed(MouseButton::Left) && !over_ui {
+            if let Some((q, r)) = self.cell_at(mouse) {
+                self.sim.toggle_cell(q, r);
+            }
+        }
+
+        self.last_mouse = mouse;
+    }
+
+    /// Advance the simulation by the generations due since the last frame.
+    fn update(&mut self) {
+        if self.paused {
+            self.clock.reset();
+            return;
+        }
+
+        for _ in 0..self
+            .clock
+            .advance(Duration::from_secs_f32(get_frame_time()))
+        {
+            self.sim.step();
+        }
+    }
+
+    /// Draw the visible cells, outlined when zoomed in far enough.
+    fn draw_grid(&self) {
+        let grid = self.sim.get_grid();
+        let radius = self.camera.cell_size;
+        let border = if radius * 2.0 >= GRID_LINE_CELL_SIZE {
+            1.0
+        } else {
+            0.0
+        };
+
+        let (alive, dead) = (color(self.theme.alive), color(self.theme.dead));
+        let line_color = color(self.theme.grid_lines);
+
+        for r in 0..grid.get_num_rows() {
+            for q in 0..grid.get_num_cols() {
+                let center = self.center(q, r);
+
+                if center.x < -radius
+                    || center.y < -radius
+                    || center.x > screen_width() + radius
+                    || center.y > screen_height() + radius
+                {
+                    continue;
+                }
+
+                let fill = if grid.is_cell_alive(q, r) {
+                    alive
+                } else {
+                    dead
+                };
+                draw_hexagon(center.x, center.y, radius, border, true, line_color, fill);
+            }
+        }
+    }
+
+    /// Draw the control panel.
+    fn draw_controls(&mut self) {
+        let paused = &mut self.paused;
+        let clock = &mut self.clock;
+        let sim = &mut self.sim;
+
+        widgets::Window::new(hash!(), vec2(10.0, 10.0), vec2(260.0, 150.0))
+            .label("Controls")
+            .ui(&mut root_ui(), |ui| {
+                ui.label(None, &format!("Rule: {}", sim.get_rule()));
+                ui.label(None, &format!("Generation: {}", sim.get_generation()));
+                ui.label(None, &format!("Population: {}", sim.get_population()));
+
+                if ui.button(None, if *paused { "Resume" } else { "Pause" }) {
+                    *paused = !*paused;
+                }
+
+                ui.same_line(0.0);
+                if ui.button(None, "Step") {
+                    *paused = true;
+                    sim.step();
+                }
+
+                let mut rate = clock.get_rate() as f32;
+                ui.slider(hash!(), "gen/s", RATE_SLIDER_RANGE, &mut rate);
+                if rate != clock.get_rate() as f32 {
+                    clock.set_rate(rate as f64);
+                }
+            });
+    }
+}
+
+/// State of the graphical user interface for a Lenia simulation, shading
+/// each cell by its value from the dead to the live color.
+struct LeniaApp {
+    /// The simulation being explored.
+    sim: LeniaSim,
+
+    /// Whether the simulation is paused.
+    paused: bool,
+
+    /// Schedule of the generations while playing.
+    clock: Clock,
+
+    /// The view onto the grid.
+    camera: Camera,
+
+    /// Mouse position of the previous frame, used for panning.
+    last_mouse: Vec2,
+
+    /// Texture of one pixel per cell, updated every frame.
+    texture: Option<Texture2D>,
+
+    /// Colors of the cells and background.
+    theme: Theme,
+}
+
+impl LeniaApp {
+    /// Create a new, paused [LeniaApp] with the grid fitting the window.
+    fn new(sim: LeniaSim, clock: Clock, theme: Theme) -> LeniaApp {
+        let area = Rect::new(
+            0.0,
+            0.0,
+            sim.get_num_cols() as f32,
+            sim.get_num_rows() as f32,
+        );
+
+        LeniaApp {
+            sim,
+            paused: true,
+            clock,
+            camera: Camera::fitting(area, DEFAULT_CELL_SIZE),
+            last_mouse: Vec2::from(mouse_position()),
+            texture: None,
+            theme,
+        }
+    }
+
+    /// Get the (row, col) of the cell under a screen position, if any.
+    fn cell_at(&self, position: Vec2) -> Option<(u32, u32)> {
+        let cell = ((position - self.camera.offset) / self.camera.cell_size).floor();
+
+        if cell.x >= 0.0
+            && cell.y >= 0.0
+            && (cell.x as u32) < self.sim.get_num_cols()
+            && (cell.y as u32) < self.sim.get_num_rows()
+        {
+            Some((cell.y as u32, cell.x as u32))
+        } else {
+            None
+        }
+    }
+
+    /// Bring the cells within half the kernel's radius of a cell to life.
+    fn paint(&mut self, row: u32, col: u32) {
+        let radius = (self.sim.get_rule().get_radius() / 2).max(1) as i64;
+
+        for row_offset in -radius..=radius {
+            for col_offset in -radius..=radius {
+                if row_offset * row_offset + col_offset * col_offset > radius * radius {
+                    continue;
+                }
+
+                let (row, col) = (row as i64 + row_offset, col as i64 + col_offset);
+                if row >= 0
+                    && col >= 0
+                    && row < self.sim.get_num_rows() as i64
+                    && col < self.sim.get_num_cols() as i64
+                {
+                    self.sim.set(row as u32, col as u32, 1.0);
+                }
+            }
+        }
+    }
+
+    /// Handle keyboard and mouse input.
+    fn handle_input(&mut self) {
+        let mouse = Vec2::from(mouse_position());
+        let over_ui = root_ui().is_mouse_over(mouse);
+
+        if is_key_pressed(KeyCode::Space) {
+            self.paused = !self.paused;
+        }
+
+        if is_key_pressed(KeyCode::N) {
+            self.paused = true;
+            self.sim.step();
+        }
+
+        let (_, wheel) = mouse_wheel();
+        if wheel != 0.0 && !over_ui {
+            self.camera
+                .zoom(if wheel > 0.0 { 1.1 } else { 1.0 / 1.1 }, mouse);
+        }
+
+        if (is_mouse_button_down(MouseButton::Right) || is_mouse_button_down(MouseButton::Middle))
+            && !over_ui
+        {
+            self.camera.pan(mouse - self.last_mouse);
+        }
+
+        if is_mouse_button_down(MouseButton::Left) && !over_ui {
+            if let Some((row, col)) = self.cell_at(mouse) {
+                self.paint(row, col);
+            }
+        }
+
+        self.last_mouse = mouse;
+    }
+
+    /// Advance the simulation by the generations due since the last frame.
+    fn update(&mut self) {
+        if self.paused {
+            self.clock.reset();
+            return;
+        }
+
+        for _ in 0..self
+            .clock
+            .advance(Duration::from_secs_f32(get_frame_time()))
+        {
+            self.sim.step();
+        }
+    }
+
+    /// Draw the cells as the pixels of a texture scaled to the cell size.
+    fn draw_grid(&mut self) {
+        let (num_rows, num_cols) = (self.sim.get_num_rows(), self.sim.get_num_cols());
+        let (alive, dead) = (self.theme.alive, self.theme.dead);
+
+        let mut image = Image::gen_image_color(num_cols as u16, num_rows as u16, color(dead));
+        for row in 0..num_rows {
+            for col in 0..num_cols {
+                let value = self.sim.get(row, col);
+                if value > 0.0 {
+                    image.set_pixel(col, row, color(dead.blend(alive, value)));
+                }
+            }
+        }
+
+        let texture = match &self.texture {
+            Some(texture) => {
+                texture.update(&image);
+                texture
+            }
+            None => {
+                let texture = Texture2D::from_image(&image);
+                texture.set_filter(FilterMode::Nearest);
+                self.texture.insert(texture)
+            }
+        };
+
+        let position = self.camera.position(0, 0);
+        draw_texture_ex(
+            texture,
+            position.x,
+            position.y,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(texture.size() * self.camera.cell_size),
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Draw the control panel.
+    fn draw_controls(&mut self) {
+        let paused = &mut self.paused;
+        let clock = &mut self.clock;
+        let sim = &mut self.sim;
+
+        widgets::Window::new(hash!(), vec2(10.0, 10.0), vec2(300.0, 150.0))
+            .label("Controls")
+            .ui(&mut root_ui(), |ui| {
+                ui.label(None, &format!("Rule: {}", sim.get_rule()));
+                ui.label(None, &format!("Generation: {}", sim.get_generation()));
+                ui.label(None, &format!("Mass: {:.1}", sim.get_mass()));
+
+                if ui.button(None, if *paused { "Resume" } else { "Pause" }) {
+                    *paused = !*paused;
+                }
+
+                ui.same_line(0.0);
+                if ui.button(None, "Step") {
+                    *paused = true;
+                    sim.step();
+                }
+
+                let mut rate = clock.get_rate() as f32;
+                ui.slider(hash!(), "gen/s", RATE_SLIDER_RANGE, &mut rate);
+                if rate != clock.get_rate() as f32 {
+                    clock.set_rate(rate as f64);
+                }
+            });
+    }
+}
+
+/// Explore a simulation in a window until the user closes it or quits.
+///
+/// The left mouse button draws with the tool picked on the toolbar, toggling
+/// and painting cells by default, the right or middle mouse button pans, and
+/// the scroll wheel zooms, down to one pixel per cell. Dragging with shift
+/// held selects cells, and clicking or dragging on the minimap of the whole
+/// grid jumps there. Keys perform the actions bound to them, listed by the
+/// help action ([?] by default).
+///
+/// # Arguments
+/// * `sim` - The simulation to explore, initially paused.
+/// * `session` - The session autosaving the simulation as it runs and
+///   saving it when the user quits, if any.
+/// * `clock` - Schedule of the generations while playing.
+/// * `keymap` - The keys bound to each action.
+/// * `theme` - Colors of the cells, grid lines, and background.
+pub fn run(sim: ConwaySim, session: Option<Session>, clock: Clock, keymap: Keymap, theme: Theme) {
+    let conf = Conf {
+        window_title: "Game of Life".to_string(),
+        window_width: 1024,
+        window_height: 768,
+        ..Default::default()
+    };
+
+    macroquad::Window::from_config(conf, async move {
+        let mut app = App::new(sim, session, clock, keymap, theme);
+
+        while !app.quit {
+            app.handle_input();
+            app.update();
+
+            clear_background(color(app.theme.background));
+            app.draw_grid();
+            app.draw_selection();
+            app.draw_stroke();
+            if app.show_graph {
+                app.draw_graph();
+            }
+            app.draw_timeline();
+            if app.show_minimap {
+                app.minimap
+                    .draw(app.sim.get_grid(), &app.camera, &app.theme);
+            }
+            app.draw_controls();
+            app.draw_tools();
+            if app.show_help {
+                app.draw_help();
+            }
+
+            next_frame().await;
+        }
+
+        if let Some(session) = &mut app.session {
+            if let Err(err) = session.save(&app.sim) {
+                eprintln!("error: {}", err);
+            }
+        }
+    });
+}
+
+/// Explore a hexagonal simulation in a window until the user closes it.
+///
+/// The left mouse button toggles cells, the right or middle mouse button
+/// pans, and the scroll wheel zooms. [space] plays or pauses and [n]
+/// advances a single generation.
+///
+/// # Arguments
+/// * `sim` - The simulation to explore, initially paused.
+/// * `clock` - Schedule of the generations while playing.
+/// * `theme` - Colors of the cells, grid lines, and background.
+pub fn run_hex(sim: HexSim, clock: Clock, theme: Theme) {
+    let conf = Conf {
+        window_title: "Game of Life (hexagonal)".to_string(),
+        window_width: 1024,
+        window_height: 768,
+        ..Default::default()
+    };
+
+    macroquad::Window::from_config(conf, async move {
+        let mut app = HexApp::new(sim, clock, theme);
+
+        loop {
+            app.handle_input();
+            app.update();
+
+            clear_background(color(app.theme.background));
+            app.draw_grid();
+            app.draw_controls();
+
+            next_frame().await;
+        }
+    });
+}
+
+/// Explore a Lenia simulation in a window until the user closes it.
+///
+/// The left mouse button brings a disc of cells to life, the right or middle
+/// mouse button pans, and the scroll wheel zooms. [space] plays or pauses
+/// and [n] advances a single generation.
+///
+/// # Arguments
+/// * `sim` - The simulation to explore, initially paused.
+/// * `clock` - Schedule of the generations while playing.
+/// * `theme` - Colors of the cells and background.
+pub fn run_lenia(sim: LeniaSim, clock: Clock, theme: Theme) {
+    let conf = Conf {
+        window_title: "Lenia".to_string(),
+        window_width: 1024,
+        window_height: 768,
+        ..Default::default()
+    };
+
+    macroquad::Window::from_config(conf, async move {
+        let mut app = LeniaApp::new(sim, clock, theme);
+
+        loop {
+            app.handle_input();
+            app.update();
+
+            clear_background(color(app.theme.background));
+            app.draw_grid();
+            app.draw_controls();
+
+            next_frame().await;
+        }
+    });
+}
@@ -0,0 +1,927 @@
+//! The HashLife algorithm for advancing large, sparse patterns.
+//!
+//! The universe is stored as a quadtree of canonical (hash-consed) nodes so
+//! that identical regions share storage, and the future of every node is
+//! memoized so that repeated regions are only ever computed once. This allows
+//! patterns with regular structure to be advanced by millions of generations
+//! nearly instantly.
+//!
+//! The universe is unbounded in principle but not in practice: its root
+//! node is at most [MAX_LEVEL], 2^62 cells across, so that every coordinate
+//! fits an `i64`. Cells must be within [COORDINATE_RANGE] along both axes,
+//! and a pattern can only be advanced while it stays far enough within it
+//! for HashLife to step it, with no more than 2^59 generations taken at a
+//! time. Beyond that, a [HashLifeError] is returned.
+//!
+//! ```
+//! use gameoflife_rs::hashlife::COORDINATE_RANGE;
+//! use gameoflife_rs::HashLifeSim;
+//!
+//! // a block is still, so it can be advanced as far as the generation goes
+//! let mut block = HashLifeSim::new();
+//! block.set_cells(&[(0, 0), (0, 1), (1, 0), (1, 1)])?;
+//! block.step_n(1 << 62)?;
+//! assert_eq!(block.get_population(), 4);
+//!
+//! // a glider flies off the edge of the universe long before
+//! let mut glider = HashLifeSim::new();
+//! glider.set_cells(&[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)])?;
+//! assert!(glider.step_n(u64::MAX).is_err());
+//! assert!(glider.set_cells(&[(COORDINATE_RANGE.end, 0)]).is_err());
+//! # Ok::<(), gameoflife_rs::HashLifeError>(())
+//! ```
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use crate::game::Grid;
+use crate::rle::RleError;
+use crate::rule::Rule;
+
+/// Index of a [Node] within a [Universe].
+pub(crate) type NodeId = u32;
+
+/// The dead leaf node.
+const DEAD: NodeId = 0;
+
+/// The live leaf node.
+const ALIVE: NodeId = 1;
+
+/// Number of nodes after which unreachable nodes are garbage collected.
+const GC_THRESHOLD: usize = 1 << 22;
+
+/// Largest level of the root node, 2^62 cells across, so that the
+/// coordinates of its cells and its size both fit an `i64`.
+pub(crate) const MAX_LEVEL: u8 = 62;
+
+/// Log2 of the most generations advanced in a single HashLife step, which
+/// needs a root node three levels larger.
+const MAX_STEP_LOG2: u8 = MAX_LEVEL - 3;
+
+/// Range of the row and column coordinates of the cells a [HashLifeSim] can
+/// hold, those of the largest root node.
+pub const COORDINATE_RANGE: Range<i64> = -(1 << (MAX_LEVEL - 1))..1 << (MAX_LEVEL - 1);
+
+/// Error raised when a [HashLifeSim] would need a larger universe than it
+/// can hold, or is given a [Rule] it cannot run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HashLifeError {
+    /// The cell at the (row, col) position is outside of
+    /// [COORDINATE_RANGE].
+    OutOfRange { row: i64, col: i64 },
+
+    /// Advancing the pattern would grow it beyond [COORDINATE_RANGE], or the
+    /// generation beyond a `u64`.
+    Overflow { generation: u64 },
+
+    /// The [Rule] causes dead cells without live neighbors to be born
+    /// (`B0`), which would fill the unbounded universe.
+    UnsupportedRule { rule: Rule },
+}
+
+impl fmt::Display for HashLifeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HashLifeError::OutOfRange { row, col } => {
+                write!(
+                    f,
+                    "cell ({}, {}) is outside of the HashLife universe",
+                    row, col
+                )
+            }
+            HashLifeError::Overflow { generation } => write!(
+                f,
+                "unable to advance past generation {} within the HashLife universe",
+                generation
+            ),
+            HashLifeError::UnsupportedRule { rule } => {
+                write!(f, "B0 rule {} is not supported by HashLife", rule)
+            }
+        }
+    }
+}
+
+impl error::Error for HashLifeError {}
+
+/// A square quadtree [Node] of size 2^level x 2^level cells.
+#[derive(Copy, Clone, Debug)]
+struct Node {
+    /// The level of the node, leaves are level 0 (a single cell).
+    level: u8,
+
+    /// The north west quadrant.
+    nw: NodeId,
+
+    /// The north east quadrant.
+    ne: NodeId,
+
+    /// The south west quadrant.
+    sw: NodeId,
+
+    /// The south east quadrant.
+    se: NodeId,
+
+    /// Number of live cells within the node.
+    population: u64,
+}
+
+/// Storage for the canonical [Node]s of a HashLife universe along with the
+/// memoized results of advancing them.
+struct Universe {
+    /// All nodes, indexed by [NodeId].
+    nodes: Vec<Node>,
+
+    /// Lookup of canonical nodes by their quadrants.
+    lookup: HashMap<[NodeId; 4], NodeId>,
+
+    /// Memoized results keyed by node and the log2 of the generations advanced.
+    results: HashMap<(NodeId, u8), NodeId>,
+
+    /// The empty node of each level.
+    empty: Vec<NodeId>,
+
+    /// The [Rule] used to advance nodes.
+    rule: Rule,
+}
+
+impl Universe {
+    /// Create a new, empty [Universe].
+    fn new(rule: Rule) -> Universe {
+        let leaf = |population| Node {
+            level: 0,
+            nw: DEAD,
+            ne: DEAD,
+            sw: DEAD,
+            se: DEAD,
+            population,
+        };
+
+        Universe {
+            nodes: vec![leaf(0), leaf(1)],
+            lookup: HashMap::new(),
+            results: HashMap::new(),
+            empty: vec![DEAD],
+            rule,
+        }
+    }
+
+    /// Get a [Node] by id.
+    fn node(&self, id: NodeId) -> Node {
+        self.nodes[id as usize]
+    }
+
+    /// Get the canonical node with the given quadrants.
+    fn join(&mut self, nw: NodeId, ne: NodeId, sw: NodeId, se: NodeId) -> NodeId {
+        let key = [nw, ne, sw, se];
+
+        if let Some(&id) = self.lookup.get(&key) {
+            return id;
+        }
+
+        let node = Node {
+            level: self.node(nw).level + 1,
+            nw,
+            ne,
+            sw,
+            se,
+            population: key.iter().map(|&child| self.node(child).population).sum(),
+        };
+
+        let id = self.nodes.len() as NodeId;
+        self.nodes.push(node);
+        self.lookup.insert(key, id);
+
+        id
+    }
+
+    /// Get the empty node of the given level.
+    fn empty(&mut self, level: u8) -> NodeId {
+        while self.empty.len() <= level as usize {
+            let child = *self.empty.last().unwrap();
+            let node = self.join(child, child, child, child);
+            self.empty.push(node);
+        }
+
+        self.empty[level as usize]
+    }
+
+    /// Surround a node with an empty border, doubling its size while keeping
+    /// it centered.
+    fn expand(&mut self, id: NodeId) -> NodeId {
+        let node = self.node(id);
+        let border = self.empty(node.level - 1);
+
+        let nw = self.join(border, border, border, node.nw);
+        let ne = self.join(border, border, node.ne, border);
+        let sw = self.join(border, node.sw, border, border);
+        let se = self.join(node.se, border, border, border);
+
+        self.join(nw, ne, sw, se)
+    }
+
+    /// Get the centered node of half the size (one level lower).
+    fn center(&mut self, id: NodeId) -> NodeId {
+        let node = self.node(id);
+
+        let nw = self.node(node.nw).se;
+        let ne = self.node(node.ne).sw;
+        let sw = self.node(node.sw).ne;
+        let se = self.node(node.se).nw;
+
+        self.join(nw, ne, sw, se)
+    }
+
+    /// Advance a level 2 (4x4) node by one generation, returning its center
+    /// level 1 (2x2) node.
+    fn step_base(&mut self, id: NodeId) -> NodeId {
+        let node = self.node(id);
+
+        // gather the 4x4 cells in row-major order
+        let mut cells = [[false; 4]; 4];
+        for (quadrant, &(row_offset, col_offset)) in [node.nw, node.ne, node.sw, node.se]
+            .iter()
+            .zip([(0, 0), (0, 2), (2, 0), (2, 2)].iter())
+        {
+            let quadrant = self.node(*quadrant);
+            cells[row_offset][col_offset] = quadrant.nw == ALIVE;
+            cells[row_offset][col_offset + 1] = quadrant.ne == ALIVE;
+            cells[row_offset + 1][col_offset] = quadrant.sw == ALIVE;
+            cells[row_offset + 1][col_offset + 1] = quadrant.se == ALIVE;
+        }
+
+        let rule = self.rule;
+        let next = |row: usize, col: usize| -> NodeId {
+            // count the live cells of the 3x3 block, excluding the center
+            let block = cells[(row - 1)..=(row + 1)]
+                .iter()
+                .flat_map(|line| &line[(col - 1)..=(col + 1)])
+                .filter(|&&alive| alive)
+                .count() as u8;
+            let count = block - cells[row][col] as u8;
+
+            let alive = if cells[row][col] {
+                rule.is_survival(count)
+            } else {
+                rule.is_birth(count)
+            };
+
+            if alive {
+                ALIVE
+            } else {
+                DEAD
+            }
+        };
+
+        let (nw, ne, sw, se) = (next(1, 1), next(1, 2), next(2, 1), next(2, 2));
+
+        self.join(nw, ne, sw, se)
+    }
+
+    /// Advance a node of level `k` by 2^`step_log2` generations, returning its
+    /// center node of level `k - 1`. `step_log2` must be at most `k - 2`.
+    fn successor(&mut self, id: NodeId, step_log2: u8) -> NodeId {
+        if let Some(&result) = self.results.get(&(id, step_log2)) {
+            return result;
+        }
+
+        let node = self.node(id);
+
+        let result = if node.population == 0 {
+            self.empty(node.level - 1)
+        } else if node.level == 2 {
+            self.step_base(id)
+        } else {
+            let full_speed = step_log2 == node.level - 2;
+
+            let (nw, ne, sw, se) = (
+                self.node(node.nw),
+                self.node(node.ne),
+                self.node(node.sw),
+                self.node(node.se),
+            );
+
+            // the nine overlapping sub-nodes of half the size
+            let n00 = node.nw;
+            let n01 = self.join(nw.ne, ne.nw, nw.se, ne.sw);
+            let n02 = node.ne;
+            let n10 = self.join(nw.sw, nw.se, sw.nw, sw.ne);
+            let n11 = self.join(nw.se, ne.sw, sw.ne, se.nw);
+            let n12 = self.join(ne.sw, ne.se, se.nw, se.ne);
+            let n20 = node.sw;
+            let n21 = self.join(sw.ne, se.nw, sw.se, se.sw);
+            let n22 = node.se;
+
+            // first half: advance the sub-nodes at full speed, or just take
+            //  their centers when advancing by fewer generations
+            let mut phase = [n00, n01, n02, n10, n11, n12, n20, n21, n22];
+            for sub_node in phase.iter_mut() {
+                *sub_node = if full_speed {
+                    self.successor(*sub_node, step_log2 - 1)
+                } else {
+                    self.center(*sub_node)
+                };
+            }
+
+            let [r00, r01, r02, r10, r11, r12, r20, r21, r22] = phase;
+
+            // second half: combine into four overlapping nodes and advance them
+            let second_step_log2 = if full_speed { step_log2 - 1 } else { step_log2 };
+
+            let q00 = self.join(r00, r01, r10, r11);
+            let q01 = self.join(r01, r02, r11, r12);
+            let q10 = self.join(r10, r11, r20, r21);
+            let q11 = self.join(r11, r12, r21, r22);
+
+            let nw = self.successor(q00, second_step_log2);
+            let ne = self.successor(q01, second_step_log2);
+            let sw = self.successor(q10, second_step_log2);
+            let se = self.successor(q11, second_step_log2);
+
+            self.join(nw, ne, sw, se)
+        };
+
+        self.results.insert((id, step_log2), result);
+
+        result
+    }
+
+    /// Determine if all live cells of a node are within the middle quarter of
+    /// its width, such that advancing it by up to 2^(level - 3) generations
+    /// cannot move cells past its result boundary, even at the speed of
+    /// light.
+    fn is_centered(&self, id: NodeId) -> bool {
+        let node = self.node(id);
+
+        if node.level < 3 {
+            return node.population == 0;
+        }
+
+        let (nw, ne, sw, se) = (
+            self.node(node.nw),
+            self.node(node.ne),
+            self.node(node.sw),
+            self.node(node.se),
+        );
+        let inner = self.node(self.node(nw.se).se).population
+            + self.node(self.node(ne.sw).sw).population
+            + self.node(self.node(sw.ne).ne).population
+            + self.node(self.node(se.nw).nw).population;
+
+        inner == node.population
+    }
+
+    /// Get a new node with the cell at (row, col), relative to the node's top
+    /// left corner, set to `alive`.
+    fn set(&mut self, id: NodeId, row: u64, col: u64, alive: bool) -> NodeId {
+        let node = self.node(id);
+
+        if node.level == 0 {
+            return if alive { ALIVE } else { DEAD };
+        }
+
+        let half = 1u64 << (node.level - 1);
+        let (mut nw, mut ne, mut sw, mut se) = (node.nw, node.ne, node.sw, node.se);
+
+        match (row < half, col < half) {
+            (true, true) => nw = self.set(nw, row, col, alive),
+            (true, false) => ne = self.set(ne, row, col - half, alive),
+            (false, true) => sw = self.set(sw, row - half, col, alive),
+            (false, false) => se = self.set(se, row - half, col - half, alive),
+        }
+
+        self.join(nw, ne, sw, se)
+    }
+
+    /// Determine if the cell at (row, col), relative to the node's top left
+    /// corner, is alive.
+    fn get(&self, id: NodeId, row: u64, col: u64) -> bool {
+        let node = self.node(id);
+
+        if node.population == 0 {
+            return false;
+        }
+
+        if node.level == 0 {
+            return id == ALIVE;
+        }
+
+        let half = 1u64 << (node.level - 1);
+
+        match (row < half, col < half) {
+            (true, true) => self.get(node.nw, row, col),
+            (true, false) => self.get(node.ne, row, col - half),
+            (false, true) => self.get(node.sw, row - half, col),
+            (false, false) => self.get(node.se, row - half, col - half),
+        }
+    }
+
+    /// Collect the live cells of a node whose top left corner is at
+    /// (row, col).
+    fn live_cells(&self, id: NodeId, row: i64, col: i64, cells: &mut Vec<(i64, i64)>) {
+        let node = self.node(id);
+
+        if node.population == 0 {
+            return;
+        }
+
+        if node.level == 0 {
+            cells.push((row, col));
+            return;
+        }
+
+        let half = 1i64 << (node.level - 1);
+
+        self.live_cells(node.nw, row, col, cells);
+        self.live_cells(node.ne, row, col + half, cells);
+        self.live_cells(node.sw, row + half, col, cells);
+        self.live_cells(node.se, row + half, col + half, cells);
+    }
+
+    /// Copy a node and its descendants from another [Universe].
+    fn copy_from(
+        &mut self,
+        other: &Universe,
+        id: NodeId,
+        copied: &mut HashMap<NodeId, NodeId>,
+    ) -> NodeId {
+        if id == DEAD || id == ALIVE {
+            return id;
+        }
+
+        if let Some(&copy) = copied.get(&id) {
+            return copy;
+        }
+
+        let node = other.node(id);
+        let nw = self.copy_from(other, node.nw, copied);
+        let ne = self.copy_from(other, node.ne, copied);
+        let sw = self.copy_from(other, node.sw, copied);
+        let se = self.copy_from(other, node.se, copied);
+
+        let copy = self.join(nw, ne, sw, se);
+        copied.insert(id, copy);
+
+        copy
+    }
+}
+
+/// Conway's Game of Life simulation using the HashLife algorithm.
+///
+/// Unlike [ConwaySim](crate::ConwaySim), the universe of a [HashLifeSim] is
+/// unbounded: cells are addressed by signed (row, col) coordinates and
+/// patterns may grow indefinitely.
+pub struct HashLifeSim {
+    /// Storage for the quadtree nodes.
+    universe: Universe,
+
+    /// The root node, centered on the origin.
+    root: NodeId,
+
+    /// The simulation's current generation.
+    generation: u64,
+}
+
+impl HashLifeSim {
+    /// Create a new, empty simulation.
+    pub fn new() -> HashLifeSim {
+        HashLifeSim::new_with_rule(Rule::default())
+    }
+
+    /// Create a new, empty simulation with the given [Rule].
+    ///
+    /// # Arguments
+    /// * `rule` - The [Rule] applied to each cell every generation.
+    ///
+    /// # Panics
+    /// Panics if the [Rule] causes dead cells without live neighbors to be
+    /// born (`B0`), as this would fill the unbounded universe. Use
+    /// [HashLifeSim::try_new_with_rule] to handle such rules as an error.
+    pub fn new_with_rule(rule: Rule) -> HashLifeSim {
+        HashLifeSim::try_new_with_rule(rule).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Create a new, empty simulation with the given [Rule], returning a
+    /// [HashLifeError::UnsupportedRule] if it is a `B0` rule.
+    ///
+    /// # Arguments
+    /// * `rule` - The [Rule] applied to each cell every generation.
+    pub fn try_new_with_rule(rule: Rule) -> Result<HashLifeSim, HashLifeError> {
+        if rule.is_birth(0) {
+            return Err(HashLifeError::UnsupportedRule { rule });
+        }
+
+        let mut universe = Universe::new(rule);
+        let root = universe.empty(3);
+
+        Ok(HashLifeSim {
+            universe,
+            root,
+            generation: 0,
+        })
+    }
+
+    /// Create a new simulation from a [Grid], with the [Grid]'s (0, 0) cell at
+    /// the simulation's (0, 0) cell.
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cells of the simulation.
+    pub fn new_with_grid(grid: &Grid) -> HashLifeSim {
+        let mut sim = HashLifeSim::new();
+
+        let mut cells: Vec<(i64, i64)> = Vec::new();
+        for row in 0..grid.get_num_rows() {
+            for col in 0..grid.get_num_cols() {
+                if grid.get(row, col).is_alive() {
+                    cells.push((row as i64, col as i64));
+                }
+            }
+        }
+
+        // a grid's coordinates are all within range
+        sim.set_cells(&cells).unwrap();
+
+        sim
+    }
+
+    /// Create a new simulation from an RLE pattern file.
+    ///
+    /// The simulation uses the [Rule] declared in the file's header, or
+    /// Conway's rule if none is declared. Returns an
+    /// [RleError::UnsupportedRule] if the declared rule is a `B0` rule.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the RLE pattern file.
+    pub fn load_rle_file<P: AsRef<Path>>(path: P) -> Result<HashLifeSim, RleError> {
+        let rle = fs::read_to_string(path)?;
+        let (grid, rule) = crate::rle::parse(&rle)?;
+        let rule = rule.unwrap_or_default();
+
+        let mut sim = HashLifeSim::new_with_grid(&grid);
+        sim.try_set_rule(rule)
+            .map_err(|_| RleError::UnsupportedRule { rule })?;
+
+        Ok(sim)
+    }
+
+    /// Get the simulation's [Rule].
+    pub fn get_rule(&self) -> Rule {
+        self.universe.rule
+    }
+
+    /// Set the [Rule] applied to each cell every generation.
+    ///
+    /// # Arguments
+    /// * `rule` - The new [Rule] of the simulation.
+    ///
+    /// # Panics
+    /// Panics if the [Rule] causes dead cells without live neighbors to be
+    /// born (`B0`). Use [HashLifeSim::try_set_rule] to handle such rules as
+    /// an error.
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.try_set_rule(rule)
+            .unwrap_or_else(|err| panic!("{}", err));
+    }
+
+    /// Set the [Rule] applied to each cell every generation, returning a
+    /// [HashLifeError::UnsupportedRule] and keeping the current [Rule] if it
+    /// is a `B0` rule.
+    ///
+    /// # Arguments
+    /// * `rule` - The new [Rule] of the simulation.
+    pub fn try_set_rule(&mut self, rule: Rule) -> Result<(), HashLifeError> {
+        if rule.is_birth(0) {
+            return Err(HashLifeError::UnsupportedRule { rule });
+        }
+
+        if rule != self.universe.rule {
+            self.universe.rule = rule;
+            self.universe.results.clear();
+        }
+
+        Ok(())
+    }
+
+    /// Get the simulation's current generation.
+    pub fn get_generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Get the number of live cells.
+    pub fn get_population(&self) -> u64 {
+        self.universe.node(self.root).population
+    }
+
+    /// Determine if any cell is alive.
+    pub fn is_any_cell_alive(&self) -> bool {
+        self.get_population() > 0
+    }
+
+    /// Get the offset of the root node's top left corner from the origin.
+    fn root_offset(&self) -> i64 {
+        1i64 << (self.universe.node(self.root).level - 1)
+    }
+
+    /// Determine if the specified cell is alive, which it never is outside
+    /// of the universe.
+    ///
+    /// ```
+    /// use gameoflife_rs::HashLifeSim;
+    ///
+    /// let mut sim = HashLifeSim::new();
+    /// sim.set_cells(&[(0, 0)]).unwrap();
+    ///
+    /// assert!(sim.is_cell_alive(0, 0));
+    /// assert!(!sim.is_cell_alive(i64::MAX, i64::MIN));
+    /// ```
+    pub fn is_cell_alive(&self, row: i64, col: i64) -> bool {
+        let offset = self.root_offset();
+        let size = offset * 2;
+
+        let (row, col) = match (row.checked_add(offset), col.checked_add(offset)) {
+            (Some(row), Some(col)) => (row, col),
+            _ => return false,
+        };
+        if row < 0 || col < 0 || row >= size || col >= size {
+            return false;
+        }
+
+        self.universe.get(self.root, row as u64, col as u64)
+    }
+
+    /// Set the specified cells as alive.
+    ///
+    /// Returns a [HashLifeError::OutOfRange] without setting any cell if a
+    /// cell is outside of [COORDINATE_RANGE].
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (row, col) to set as alive.
+    pub fn set_cells(&mut self, cells: &[(i64, i64)]) -> Result<(), HashLifeError> {
+        if let Some(&(row, col)) = cells
+            .iter()
+            .find(|(row, col)| !COORDINATE_RANGE.contains(row) || !COORDINATE_RANGE.contains(col))
+        {
+            return Err(HashLifeError::OutOfRange { row, col });
+        }
+
+        for &(row, col) in cells {
+            // grow the universe until it contains the cell
+            while row < -self.root_offset()
+                || col < -self.root_offset()
+                || row >= self.root_offset()
+                || col >= self.root_offset()
+            {
+                self.root = self.universe.expand(self.root);
+            }
+
+            let offset = self.root_offset();
+            self.root = self.universe.set(
+                self.root,
+                (row + offset) as u64,
+                (col + offset) as u64,
+                true,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Get the live cells as (row, col) tuples.
+    pub fn live_cells(&self) -> Vec<(i64, i64)> {
+        let mut cells: Vec<(i64, i64)> = Vec::new();
+        let offset = self.root_offset();
+
+        self.universe
+            .live_cells(self.root, -offset, -offset, &mut cells);
+
+        cells
+    }
+
+    /// Copy the bounding box of the live cells into a new [Grid], with the
+    /// top left live cell mapped to the (0, 0) cell, or an empty 0x0 [Grid]
+    /// if no cell is alive.
+    ///
+    /// Returns the (num_rows, num_cols) size of the bounding box, with each
+    /// dimension capped at `u32::MAX`, if it is too large for a [Grid].
+    pub(crate) fn to_bounding_grid(&self) -> Result<Grid, (u32, u32)> {
+        let cells = self.live_cells();
+        if cells.is_empty() {
+            return Ok(Grid::new(0, 0));
+        }
+
+        let min_row = cells.iter().map(|&(row, _)| row).min().unwrap_or(0);
+        let min_col = cells.iter().map(|&(_, col)| col).min().unwrap_or(0);
+        let max_row = cells.iter().map(|&(row, _)| row).max().unwrap_or(0);
+        let max_col = cells.iter().map(|&(_, col)| col).max().unwrap_or(0);
+
+        let dimension = |min: i64, max: i64| {
+            max.checked_sub(min)
+                .and_then(|span| span.checked_add(1))
+                .and_then(|len| u32::try_from(len).ok())
+        };
+
+        match (dimension(min_row, max_row), dimension(min_col, max_col)) {
+            (Some(num_rows), Some(num_cols)) if Grid::check_size(num_rows, num_cols).is_ok() => {
+                Ok(self.to_grid(min_row, min_col, num_rows, num_cols))
+            }
+            (num_rows, num_cols) => {
+                Err((num_rows.unwrap_or(u32::MAX), num_cols.unwrap_or(u32::MAX)))
+            }
+        }
+    }
+
+    /// Copy a rectangular region of the universe into a new [Grid].
+    ///
+    /// # Arguments
+    /// * `row` - Row of the region's top left cell.
+    /// * `col` - Column of the region's top left cell.
+    /// * `num_rows` - The number of rows (height) of the region.
+    /// * `num_cols` - The number of columns (width) of the region.
+    pub fn to_grid(&self, row: i64, col: i64, num_rows: u32, num_cols: u32) -> Grid {
+        let mut grid = Grid::new(num_rows, num_cols);
+
+        let cells: Vec<(u32, u32)> = self
+            .live_cells()
+            .into_iter()
+            .filter(|&(r, c)| {
+                r >= row && c >= col && r < row + num_rows as i64 && c < col + num_cols as i64
+            })
+            .map(|(r, c)| ((r - row) as u32, (c - col) as u32))
+            .collect();
+
+        grid.set_cells(&cells);
+
+        grid
+    }
+
+    /// Advance the simulation by one generation, as with
+    /// [HashLifeSim::step_n].
+    pub fn step(&mut self) -> Result<(), HashLifeError> {
+        self.step_n(1)
+    }
+
+    /// Advance the simulation by `n` generations.
+    ///
+    /// The simulation advances by each power of two making up `n` in a single
+    /// HashLife step, up to 2^59 generations at a time, so very large values
+    /// of `n` are cheap for patterns with regular structure.
+    ///
+    /// Returns a [HashLifeError::Overflow] if the pattern would grow beyond
+    /// [COORDINATE_RANGE] or the generation beyond a `u64`, leaving the
+    /// simulation at the last generation it reached.
+    pub fn step_n(&mut self, n: u64) -> Result<(), HashLifeError> {
+        if self.generation.checked_add(n).is_none() {
+            return Err(HashLifeError::Overflow {
+                generation: self.generation,
+            });
+        }
+
+        for step_log2 in 0..MAX_STEP_LOG2 {
+            if n & (1u64 << step_log2) != 0 {
+                self.advance(step_log2)?;
+            }
+        }
+
+        for _ in 0..n >> MAX_STEP_LOG2 {
+            self.advance(MAX_STEP_LOG2)?;
+        }
+
+        Ok(())
+    }
+
+    /// Advance the simulation by 2^`step_log2` generations in a single
+    /// HashLife step.
+    fn advance(&mut self, step_log2: u8) -> Result<(), HashLifeError> {
+        // grow the universe until advancing it cannot lose any cells
+        while self.universe.node(self.root).level < step_log2 + 3
+            || !self.universe.is_centered(self.root)
+        {
+            if self.universe.node(self.root).level >= MAX_LEVEL {
+                return Err(HashLifeError::Overflow {
+                    generation: self.generation,
+                });
+            }
+
+            self.root = self.universe.expand(self.root);
+        }
+
+        self.root = self.universe.successor(self.root, step_log2);
+        self.generation += 1u64 << step_log2;
+
+        if self.universe.nodes.len() > GC_THRESHOLD {
+            self.collect_garbage();
+        }
+
+        Ok(())
+    }
+
+    /// Advance the simulation to a later generation, as with
+    /// [HashLifeSim::step_n]. Does nothing if the simulation is already at or
+    /// past the generation.
+    ///
+    /// # Arguments
+    /// * `generation` - The generation to advance to.
+    pub fn advance_to(&mut self, generation: u64) -> Result<(), HashLifeError> {
+        self.step_n(generation.saturating_sub(self.generation))
+    }
+
+    /// Discard all nodes and memoized results that are unreachable from the
+    /// root node.
+    fn collect_garbage(&mut self) {
+        let mut universe = Universe::new(self.universe.rule);
+        let mut copied: HashMap<NodeId, NodeId> = HashMap::new();
+
+        self.root = universe.copy_from(&self.universe, self.root, &mut copied);
+        self.universe = universe;
+    }
+
+    /// Get the root node, whose center is the origin.
+    pub(crate) fn get_root(&self) -> NodeId {
+        self.root
+    }
+
+    /// Replace the root node, whose center is the origin, and the generation.
+    ///
+    /// # Panics
+    /// Panics if the root node is below level 3 or above [MAX_LEVEL].
+    pub(crate) fn set_root(&mut self, root: NodeId, generation: u64) {
+        assert!(
+            (3..=MAX_LEVEL).contains(&self.universe.node(root).level),
+            "root node is too small or too large"
+        );
+
+        self.root = root;
+        self.generation = generation;
+    }
+
+    /// Get the level of a node, which is 2^level cells across.
+    pub(crate) fn get_node_level(&self, id: NodeId) -> u8 {
+        self.universe.node(id).level
+    }
+
+    /// Get the number of live cells within a node.
+    pub(crate) fn get_node_population(&self, id: NodeId) -> u64 {
+        self.universe.node(id).population
+    }
+
+    /// Get the north west, north east, south west, and south east quadrants
+    /// of a node above level 0.
+    pub(crate) fn get_node_quadrants(&self, id: NodeId) -> [NodeId; 4] {
+        let node = self.universe.node(id);
+
+        [node.nw, node.ne, node.sw, node.se]
+    }
+
+    /// Get the canonical node with the given quadrants, which must all be of
+    /// the same level.
+    pub(crate) fn join_nodes(&mut self, quadrants: [NodeId; 4]) -> NodeId {
+        let [nw, ne, sw, se] = quadrants;
+
+        self.universe.join(nw, ne, sw, se)
+    }
+
+    /// Get the empty node of a level.
+    pub(crate) fn empty_node(&mut self, level: u8) -> NodeId {
+        self.universe.empty(level)
+    }
+
+    /// Get a new node with the cell at (row, col), relative to the node's top
+    /// left corner, set to alive.
+    pub(crate) fn set_node_cell(&mut self, id: NodeId, row: u64, col: u64) -> NodeId {
+        self.universe.set(id, row, col, true)
+    }
+
+    /// Determine if the cell at (row, col), relative to the node's top left
+    /// corner, is alive.
+    pub(crate) fn is_node_cell_alive(&self, id: NodeId, row: u64, col: u64) -> bool {
+        self.universe.get(id, row, col)
+    }
+}
+
+impl Default for HashLifeSim {
+    fn default() -> HashLifeSim {
+        HashLifeSim::new()
+    }
+}
+
+impl fmt::Display for HashLifeSim {
+    /// Display the bounding box of the live cells, or its size if it is too
+    /// large for a [Grid].
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_bounding_grid() {
+            Ok(grid) if grid.get_num_rows() == 0 => Ok(()),
+            Ok(grid) => grid.fmt(f),
+            Err((num_rows, num_cols)) => writeln!(
+                f,
+                "{} live cells in a {}x{} bounding box too large to display",
+                self.get_population(),
+                num_rows,
+                num_cols
+            ),
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//! Life on a hexagonal grid, where each cell has six neighbors.
+//!
+//! Cells are addressed by axial coordinates (q, r): `r` is the row and `q`
+//! the position along it. Each row is shifted half a cell to the left of the
+//! row above, so the six neighbors of (q, r) are (q ± 1, r), (q, r ± 1),
+//! (q + 1, r + 1), and (q - 1, r - 1). This is the layout Golly uses to
+//! store hexagonal patterns on a square grid, so a [Grid] loaded from such a
+//! pattern converts directly with [HexGrid::from_grid].
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::game::{Cell, Grid};
+use crate::rule::{Rule, RuleError};
+
+/// Offsets (dq, dr) of the six neighbors of a cell, clockwise from the
+/// right.
+pub const NEIGHBORS: [(i64, i64); 6] = [(1, 0), (1, 1), (0, 1), (-1, 0), (-1, -1), (0, -1)];
+
+/// A hexagonal [Rule] describing which of the six neighbor counts cause a
+/// dead cell to be born and which allow a live cell to survive, written with
+/// an `H` suffix such as `B2/S34H`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct HexRule {
+    /// The birth and survival neighbor counts, none above 6.
+    rule: Rule,
+}
+
+impl HexRule {
+    /// Create a new [HexRule] from lists of neighbor counts.
+    ///
+    /// # Arguments
+    /// * `birth` - Neighbor counts which cause a dead cell to be born.
+    /// * `survival` - Neighbor counts which allow a live cell to survive.
+    ///
+    /// # Panics
+    /// Panics if any neighbor count is greater than 6.
+    pub fn new(birth: &[u8], survival: &[u8]) -> HexRule {
+        assert!(
+            birth.iter().chain(survival.iter()).all(|&count| count <= 6),
+            "hexagonal neighbor counts are at most 6"
+        );
+
+        HexRule {
+            rule: Rule::new(birth, survival),
+        }
+    }
+
+    /// The hexagonal rule `B2/S34H`, which has gliders and oscillators.
+    pub fn b2_s34() -> HexRule {
+        HexRule::new(&[2], &[3, 4])
+    }
+
+    /// Determine if a dead cell with `neighbor_count` live neighbors is born.
+    pub fn is_birth(&self, neighbor_count: u8) -> bool {
+        self.rule.is_birth(neighbor_count)
+    }
+
+    /// Determine if a live cell with `neighbor_count` live neighbors survives.
+    pub fn is_survival(&self, neighbor_count: u8) -> bool {
+        self.rule.is_survival(neighbor_count)
+    }
+}
+
+impl Default for HexRule {
+    fn default() -> HexRule {
+        HexRule::b2_s34()
+    }
+}
+
+impl FromStr for HexRule {
+    type Err = RuleError;
+
+    /// Parse a rulestring in `B2/S34H` notation, or in any notation accepted
+    /// by [Rule] followed by an optional `H`.
+    fn from_str(rulestring: &str) -> Result<HexRule, RuleError> {
+        let trimmed = rulestring.trim();
+        let trimmed = trimmed
+            .strip_suffix('H')
+            .or_else(|| trimmed.strip_suffix('h'))
+            .unwrap_or(trimmed);
+
+        let rule: Rule = trimmed.parse().map_err(|_| RuleError::new(rulestring))?;
+
+        if (7..=8).any(|count| rule.is_birth(count) || rule.is_survival(count)) {
+            return Err(RuleError::new(rulestring));
+        }
+
+        Ok(HexRule { rule })
+    }
+}
+
+impl fmt::Display for HexRule {
+    /// Display the rule in `B2/S34H` notation.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}H", self.rule)
+    }
+}
+
+/// A bounded hexagonal grid of [Cell]s addressed by axial coordinates.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct HexGrid {
+    /// Number of rows (r) of the [HexGrid].
+    num_rows: u32,
+
+    /// Number of cells (q) in each row of the [HexGrid].
+    num_cols: u32,
+
+    /// The cells in row-major order.
+    cells: Vec<Cell>,
+}
+
+impl HexGrid {
+    /// Create a new [HexGrid] of dead cells.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (r) of the [HexGrid].
+    /// * `num_cols` - The number of cells (q) in each row.
+    pub fn new(num_rows: u32, num_cols: u32) -> HexGrid {
+        HexGrid {
+            num_rows,
+            num_cols,
+            cells: vec![Cell::Dead; num_rows as usize * num_cols as usize],
+        }
+    }
+
+    /// Create a new [HexGrid] from a square [Grid], the cell at (row, col)
+    /// becoming the cell at (q, r) = (col, row).
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cells.
+    pub fn from_grid(grid: &Grid) -> HexGrid {
+        HexGrid {
+            num_rows: grid.get_num_rows(),
+            num_cols: grid.get_num_cols(),
+            cells: grid.get_cells().to_vec(),
+        }
+    }
+
+    /// Get the number of rows (r) of the [HexGrid].
+    pub fn get_num_rows(&self) -> u32 {
+        self.num_rows
+    }
+
+    /// Get the number of cells (q) in each row of the [HexGrid].
+    pub fn get_num_cols(&self) -> u32 {
+        self.num_cols
+    }
+
+    /// Get all cells in row-major order.
+    pub fn get_cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// Convert an axial (q, r) position to a row-major index.
+    fn cell_to_index(&self, q: u32, r: u32) -> usize {
+        r as usize * self.num_cols as usize + q as usize
+    }
+
+    /// Get the state of the cell at (q, r).
+    pub fn get(&self, q: u32, r: u32) -> Cell {
+        self.cells[self.cell_to_index(q, r)]
+    }
+
+    /// Set the state of the cell at (q, r).
+    pub fn set(&mut self, q: u32, r: u32, state: Cell) {
+        let index = self.cell_to_index(q, r);
+        self.cells[index] = state;
+    }
+
+    /// Set the specified cells as alive.
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (q, r) to set as alive.
+    pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
+        for &(q, r) in cells {
+            self.set(q, r, Cell::Alive);
+        }
+    }
+
+    /// Determine if the cell at (q, r) is alive.
+    pub fn is_cell_alive(&self, q: u32, r: u32) -> bool {
+        self.get(q, r).is_alive()
+    }
+
+    /// Get the number of live cells.
+    pub fn get_population(&self) -> u64 {
+        self.cells.iter().filter(|cell| cell.is_alive()).count() as u64
+    }
+
+    /// Get the number of live neighbors of the cell at (q, r). Neighbors
+    /// outside of the [HexGrid] are dead.
+    pub fn count_neighbors(&self, q: u32, r: u32) -> u8 {
+        let mut count = 0;
+
+        for &(dq, dr) in NEIGHBORS.iter() {
+            let (q, r) = (q as i64 + dq, r as i64 + dr);
+
+            if (0..self.num_cols as i64).contains(&q)
+                && (0..self.num_rows as i64).contains(&r)
+                && self.is_cell_alive(q as u32, r as u32)
+            {
+                count += 1;
+            }
+        }
+
+        count
+    }
+}
+
+impl fmt::Display for HexGrid {
+    /// Display live cells as '●' and dead cells as '○', shifting each row
+    /// half a cell to the left of the row above.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (r, line) in self.cells.chunks(self.num_cols.max(1) as usize).enumerate() {
+            let indent = self.num_rows as usize - 1 - r;
+            write!(f, "{:indent$}", "", indent = indent)?;
+
+            for (q, cell) in line.iter().enumerate() {
+                if q > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", if cell.is_alive() { '●' } else { '○' })?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Simulation of a [HexRule] on a [HexGrid].
+#[derive(Clone, Debug)]
+pub struct HexSim {
+    /// The cells.
+    grid: HexGrid,
+
+    /// Buffer the next generation is computed into.
+    next: HexGrid,
+
+    /// The [HexRule] applied to each cell every generation.
+    rule: HexRule,
+
+    /// The simulation's current generation.
+    generation: u32,
+}
+
+impl HexSim {
+    /// Create a new simulation of dead cells.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (r) of the grid.
+    /// * `num_cols` - The number of cells (q) in each row.
+    /// * `rule` - The [HexRule] applied to each cell.
+    pub fn new(num_rows: u32, num_cols: u32, rule: HexRule) -> HexSim {
+        HexSim::new_with_grid(HexGrid::new(num_rows, num_cols), rule)
+    }
+
+    /// Create a new simulation from an existing [HexGrid].
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cells.
+    /// * `rule` - The [HexRule] applied to each cell.
+    pub fn new_with_grid(grid: HexGrid, rule: HexRule) -> HexSim {
+        HexSim {
+            next: grid.clone(),
+            grid,
+            rule,
+            generation: 0,
+        }
+    }
+
+    /// Get the simulation's [HexGrid].
+    pub fn get_grid(&self) -> &HexGrid {
+        &self.grid
+    }
+
+    /// Get the simulation's [HexRule].
+    pub fn get_rule(&self) -> HexRule {
+        self.rule
+    }
+
+    /// Set the [HexRule] applied to each cell every generation.
+    pub fn set_rule(&mut self, rule: HexRule) {
+        self.rule = rule;
+    }
+
+    /// Get the simulation's current generation.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Get the number of live cells.
+    pub fn get_population(&self) -> u64 {
+        self.grid.get_population()
+    }
+
+    /// Determine if any cell is alive.
+    pub fn is_any_cell_alive(&self) -> bool {
+        self.grid.cells.iter().any(|cell| cell.is_alive())
+    }
+
+    /// Set the specified cells as alive.
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (q, r) to set as alive.
+    pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
+        self.grid.set_cells(cells);
+    }
+
+    /// Set the state of the cell at (q, r).
+    pub fn set_cell(&mut self, q: u32, r: u32, state: Cell) {
+        self.grid.set(q, r, state);
+    }
+
+    /// Toggle the state of the cell at (q, r).
+    pub fn toggle_cell(&mut self, q: u32, r: u32) {
+        let state = if self.grid.is_cell_alive(q, r) {
+            Cell::Dead
+        } else {
+            Cell::Alive
+        };
+        self.grid.set(q, r, state);
+    }
+
+    /// Advance the simulation by one generation.
+    pub fn step(&mut self) {
+        self.generation += 1;
+
+        for r in 0..self.grid.num_rows {
+            for q in 0..self.grid.num_cols {
+                let neighbor_count = self.grid.count_neighbors(q, r);
+
+                let alive = if self.grid.is_cell_alive(q, r) {
+                    self.rule.is_survival(neighbor_count)
+                } else {
+                    self.rule.is_birth(neighbor_count)
+                };
+
+                self.next
+                    .set(q, r, if alive { Cell::Alive } else { Cell::Dead });
+            }
+        }
+
+        std::mem::swap(&mut self.grid, &mut self.next);
+    }
+
+    /// Advance the simulation by `n` generations.
+    pub fn step_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+}
+
+impl fmt::Display for HexSim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.grid.fmt(f)
+    }
+}
@@ -0,0 +1,155 @@
+//! Generation history allowing a simulation to step backwards.
+//!
+//! Rather than storing every generation, the [History] stores a snapshot of
+//! the world every N generations. Any earlier generation is reached by
+//! restoring the latest snapshot at or before it and re-simulating forward.
+
+use alloc::vec::Vec;
+use core::error;
+use core::fmt;
+
+use crate::rule::Rule;
+
+/// Error raised when a generation cannot be reached.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HistoryError {
+    /// History recording is not enabled for the simulation.
+    Disabled,
+
+    /// The generation is earlier than the oldest recorded snapshot.
+    Unavailable { generation: u32 },
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HistoryError::Disabled => write!(f, "history recording is not enabled"),
+            HistoryError::Unavailable { generation } => {
+                write!(
+                    f,
+                    "generation {} is no longer available in the history",
+                    generation
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for HistoryError {}
+
+/// A recorded [Snapshot] of a simulation.
+#[derive(Clone, Debug)]
+pub(crate) struct Snapshot<W> {
+    /// The generation of the snapshot.
+    pub(crate) generation: u32,
+
+    /// The world at the generation.
+    pub(crate) world: W,
+
+    /// The rule in effect from the generation onwards.
+    pub(crate) rule: Rule,
+}
+
+/// [History] of a simulation recorded as periodic snapshots.
+#[derive(Clone, Debug)]
+pub struct History<W> {
+    /// Number of generations between periodic snapshots.
+    interval: u32,
+
+    /// Maximum number of snapshots kept, the oldest are discarded first.
+    capacity: usize,
+
+    /// The snapshots, sorted by generation.
+    snapshots: Vec<Snapshot<W>>,
+}
+
+impl<W: Clone> History<W> {
+    /// Create a new, empty [History].
+    ///
+    /// # Arguments
+    /// * `interval` - Number of generations between snapshots (at least 1).
+    /// * `capacity` - Maximum number of snapshots kept (at least 1).
+    pub(crate) fn new(interval: u32, capacity: usize) -> History<W> {
+        History {
+            interval: interval.max(1),
+            capacity: capacity.max(1),
+            snapshots: Vec::new(),
+        }
+    }
+
+    /// Get the number of generations between periodic snapshots.
+    pub fn get_interval(&self) -> u32 {
+        self.interval
+    }
+
+    /// Get the maximum number of snapshots kept.
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get the generations of the recorded snapshots, oldest first.
+    pub fn generations(&self) -> Vec<u32> {
+        self.snapshots
+            .iter()
+            .map(|snapshot| snapshot.generation)
+            .collect()
+    }
+
+    /// Get the oldest generation which can be reached.
+    pub fn earliest_generation(&self) -> Option<u32> {
+        self.snapshots.first().map(|snapshot| snapshot.generation)
+    }
+
+    /// Record a snapshot, replacing any existing snapshot of the generation.
+    pub(crate) fn record(&mut self, generation: u32, world: &W, rule: Rule) {
+        let snapshot = Snapshot {
+            generation,
+            world: world.clone(),
+            rule,
+        };
+
+        match self
+            .snapshots
+            .binary_search_by_key(&generation, |snapshot| snapshot.generation)
+        {
+            Ok(index) => self.snapshots[index] = snapshot,
+            Err(index) => self.snapshots.insert(index, snapshot),
+        }
+
+        if self.snapshots.len() > self.capacity {
+            self.snapshots.remove(0);
+        }
+    }
+
+    /// Record a periodic snapshot after a step, if one is due and the
+    /// generation has not already been recorded.
+    pub(crate) fn record_step(&mut self, generation: u32, world: &W, rule: Rule) {
+        if !generation.is_multiple_of(self.interval) {
+            return;
+        }
+
+        if self
+            .snapshots
+            .binary_search_by_key(&generation, |snapshot| snapshot.generation)
+            .is_err()
+        {
+            self.record(generation, world, rule);
+        }
+    }
+
+    /// Record an edit of the world or rule: snapshots of the current and
+    /// later generations no longer describe the same run and are discarded.
+    pub(crate) fn record_edit(&mut self, generation: u32, world: &W, rule: Rule) {
+        self.snapshots
+            .retain(|snapshot| snapshot.generation < generation);
+        self.record(generation, world, rule);
+    }
+
+    /// Get the latest snapshot at or before a generation.
+    pub(crate) fn latest_at_or_before(&self, generation: u32) -> Option<&Snapshot<W>> {
+        self.snapshots
+            .iter()
+            .rev()
+            .find(|snapshot| snapshot.generation <= generation)
+    }
+}
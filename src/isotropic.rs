@@ -0,0 +1,399 @@
+//! Isotropic non-totalistic rules, written in Hensel notation.
+//!
+//! A Life-like [Rule] only counts a cell's live neighbors. An isotropic
+//! non-totalistic rule also looks at where they are: each count of live
+//! neighbors is split into the configurations which are the same up to
+//! rotation and reflection, named by a letter, e.g. `2a` for two adjacent
+//! neighbors and `2i` for two opposite edges. Rulestrings follow each count
+//! with the letters it includes, or with `-` and the letters it excludes, so
+//! Just Friends, `B2-a/S12`, is born on two neighbors unless they are
+//! adjacent. A count without letters includes all of its configurations.
+//!
+//! The letters of each count are, in Golly's order:
+//!
+//! | Neighbors | Letters         |
+//! |-----------|-----------------|
+//! | 0, 8      |                 |
+//! | 1, 7      | `ce`            |
+//! | 2, 6      | `cekain`        |
+//! | 3, 5      | `cekainyqjr`    |
+//! | 4         | `cekainyqjrtwz` |
+//!
+//! A letter of 5, 6, or 7 neighbors names the configuration whose dead
+//! neighbors form that letter of 3, 2, or 1 neighbors.
+//!
+//! Rules are displayed as Golly writes them, with the letters of each count
+//! in the order above and only whichever of the included or excluded letters
+//! are fewer:
+//!
+//! ```
+//! use gameoflife_rs::isotropic::IsotropicRule;
+//!
+//! let rule: IsotropicRule = "B2-a/S12".parse()?;
+//! assert_eq!(rule.to_string(), "B2-a/S12");
+//!
+//! let rule: IsotropicRule = "B2ak3ryceinak/S23".parse()?;
+//! assert_eq!(rule.to_string(), "B2ka3-qj/S23");
+//! # Ok::<(), gameoflife_rs::RuleError>(())
+//! ```
+//!
+//! An [IsotropicRule] looks up the next state of a cell in a table indexed
+//! by its whole neighborhood and implements [StateRule] over [Cell]s, so it
+//! runs on a [Sim](crate::Sim):
+//!
+//! ```
+//! use gameoflife_rs::isotropic::IsotropicRule;
+//! use gameoflife_rs::{Grid, Sim};
+//!
+//! let rule: IsotropicRule = "B2-a/S12".parse()?;
+//!
+//! let mut grid = Grid::new(8, 8);
+//! grid.set_cells(&[(3, 2), (3, 4)]);
+//!
+//! let mut sim = Sim::new(grid, rule);
+//! sim.step();
+//!
+//! // the cells between and beside the pair are born, as their two
+//! // neighbors are not adjacent, while the pair dies alone
+//! assert!(sim.get_grid().get(3, 3).is_alive());
+//! assert_eq!(sim.get_population(), 3);
+//! # Ok::<(), gameoflife_rs::RuleError>(())
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::automaton::StateRule;
+use crate::game::Cell;
+use crate::rule::{Rule, RuleError};
+
+/// Letters of the configurations of each number of live neighbors, in the
+/// order Golly lists them.
+const LETTERS: [&str; 9] = [
+    "",
+    "ce",
+    "cekain",
+    "cekainyqjr",
+    "cekainyqjrtwz",
+    "cekainyqjr",
+    "cekain",
+    "ce",
+    "",
+];
+
+/// A configuration of each letter of 0 to 4 live neighbors, in the order of
+/// [LETTERS], as the bits of the live neighbors from the top left row by
+/// row, skipping the cell itself.
+const CONFIGURATIONS: [&[u8]; 5] = [
+    &[0],
+    &[1, 2],
+    &[5, 10, 17, 3, 24, 36],
+    &[37, 26, 50, 11, 7, 13, 49, 38, 14, 25],
+    &[165, 90, 51, 15, 29, 39, 53, 54, 58, 27, 57, 46, 60],
+];
+
+/// Bits of all eight neighbors.
+const ALL_NEIGHBORS: u8 = 0xff;
+
+/// (row offset, col offset) of the eight neighbors of a cell, in the order
+/// of their bits.
+const OFFSETS: [(i8, i8); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Get the configurations a configuration is rotated and reflected to.
+fn orientations(configuration: u8) -> [u8; 8] {
+    let mut orientations = [0; 8];
+
+    for (index, orientation) in orientations.iter_mut().enumerate() {
+        for (bit, &(row, col)) in OFFSETS.iter().enumerate() {
+            if configuration & (1 << bit) == 0 {
+                continue;
+            }
+
+            // rotate a quarter turn clockwise per step, reflecting the
+            // second half of the orientations
+            let (mut row, mut col) = if index >= 4 { (row, -col) } else { (row, col) };
+            for _ in 0..index % 4 {
+                let rotated = (col, -row);
+                row = rotated.0;
+                col = rotated.1;
+            }
+
+            let image = OFFSETS
+                .iter()
+                .position(|&offset| offset == (row, col))
+                .unwrap();
+            *orientation |= 1 << image;
+        }
+    }
+
+    orientations
+}
+
+/// Get the configuration of a letter of a number of live neighbors, if the
+/// letter is one of that number's.
+///
+/// # Arguments
+/// * `count` - The number of live neighbors, from 0 to 8.
+/// * `letter` - The letter, or `None` for a number without letters.
+fn configuration(count: usize, letter: Option<char>) -> Option<u8> {
+    let index = match letter {
+        Some(letter) => LETTERS[count].find(letter)?,
+        None if LETTERS[count].is_empty() => 0,
+        None => return None,
+    };
+
+    if count <= 4 {
+        Some(CONFIGURATIONS[count][index])
+    } else {
+        Some(!CONFIGURATIONS[8 - count][index] & ALL_NEIGHBORS)
+    }
+}
+
+/// Parse the numbers of live neighbors of one half of a rulestring, each
+/// followed by the letters it includes or, after `-`, excludes.
+fn parse_conditions(conditions: &str) -> Option<[bool; 256]> {
+    let mut table = [false; 256];
+    let mut chars = conditions.chars().peekable();
+
+    while let Some(digit) = chars.next() {
+        let count = digit.to_digit(10).filter(|&count| count <= 8)? as usize;
+
+        let negated = chars.next_if_eq(&'-').is_some();
+        let mut letters = String::new();
+        while let Some(letter) = chars.next_if(|c| c.is_ascii_lowercase()) {
+            LETTERS[count].find(letter)?;
+            letters.push(letter);
+        }
+
+        if negated && letters.is_empty() {
+            return None;
+        }
+
+        let included: Vec<Option<char>> = if LETTERS[count].is_empty() {
+            vec![None]
+        } else {
+            LETTERS[count]
+                .chars()
+                .filter(|&letter| letters.is_empty() || letters.contains(letter) != negated)
+                .map(Some)
+                .collect()
+        };
+
+        for letter in included {
+            for orientation in orientations(configuration(count, letter)?).iter() {
+                table[*orientation as usize] = true;
+            }
+        }
+    }
+
+    Some(table)
+}
+
+/// Write the numbers of live neighbors of one half of a rulestring, with
+/// the letters of those which include only some configurations.
+fn write_conditions(f: &mut fmt::Formatter, table: &[bool; 256]) -> fmt::Result {
+    for (count, letters) in LETTERS.iter().enumerate() {
+        let included: String = if letters.is_empty() {
+            String::new()
+        } else {
+            letters
+                .chars()
+                .filter(|&letter| table[configuration(count, Some(letter)).unwrap() as usize])
+                .collect()
+        };
+
+        let all = if letters.is_empty() {
+            table[configuration(count, None).unwrap() as usize]
+        } else {
+            included.len() == letters.len()
+        };
+
+        if all {
+            write!(f, "{}", count)?;
+        } else if !included.is_empty() {
+            // as Golly does, list whichever of the included or excluded
+            // letters are fewer
+            let excluded: String = letters
+                .chars()
+                .filter(|&letter| !included.contains(letter))
+                .collect();
+
+            if excluded.len() < included.len() {
+                write!(f, "{}-{}", count, excluded)?;
+            } else {
+                write!(f, "{}{}", count, included)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// An isotropic non-totalistic rule, describing which configurations of live
+/// neighbors cause a dead cell to be born and which allow a live cell to
+/// survive.
+///
+/// Configurations are numbered by the bits of their live neighbors, from the
+/// top left neighbor (1) row by row to the bottom right neighbor (128),
+/// skipping the cell itself.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct IsotropicRule {
+    /// `birth[n]` is set if a dead cell with configuration `n` is born.
+    birth: [bool; 256],
+
+    /// `survival[n]` is set if a live cell with configuration `n` survives.
+    survival: [bool; 256],
+}
+
+impl IsotropicRule {
+    /// Just Friends, `B2-a/S12`: cells are born on two neighbors which are
+    /// not adjacent, and survive on one or two.
+    pub fn just_friends() -> IsotropicRule {
+        "B2-a/S12".parse().unwrap()
+    }
+
+    /// tlife, `B3/S2-i34q`: Conway's rule, except that live cells do not
+    /// survive between two neighbors on opposite edges (`2i`) and do survive
+    /// one configuration of four neighbors (`4q`).
+    pub fn tlife() -> IsotropicRule {
+        "B3/S2-i34q".parse().unwrap()
+    }
+
+    /// Determine if a dead cell with a configuration of live neighbors is
+    /// born.
+    ///
+    /// # Arguments
+    /// * `configuration` - The bits of the live neighbors.
+    pub fn is_birth(&self, configuration: u8) -> bool {
+        self.birth[configuration as usize]
+    }
+
+    /// Determine if a live cell with a configuration of live neighbors
+    /// survives.
+    ///
+    /// # Arguments
+    /// * `configuration` - The bits of the live neighbors.
+    pub fn is_survival(&self, configuration: u8) -> bool {
+        self.survival[configuration as usize]
+    }
+
+    /// Get the equivalent Life-like [Rule], if the rule includes either all
+    /// or none of the configurations of each number of live neighbors.
+    pub fn to_rule(&self) -> Option<Rule> {
+        let counts = |table: &[bool; 256]| -> Option<Vec<u8>> {
+            let mut counts = Vec::new();
+
+            for count in 0..=8 {
+                let mut configurations =
+                    (0..=ALL_NEIGHBORS).filter(|configuration| configuration.count_ones() == count);
+                let included = table[configurations.next().unwrap() as usize];
+
+                if configurations.any(|configuration| table[configuration as usize] != included) {
+                    return None;
+                }
+                if included {
+                    counts.push(count as u8);
+                }
+            }
+
+            Some(counts)
+        };
+
+        Some(Rule::new(&counts(&self.birth)?, &counts(&self.survival)?))
+    }
+}
+
+impl From<Rule> for IsotropicRule {
+    /// Convert a Life-like [Rule], including all configurations of the
+    /// numbers of live neighbors it includes.
+    fn from(rule: Rule) -> IsotropicRule {
+        let mut birth = [false; 256];
+        let mut survival = [false; 256];
+
+        for configuration in 0..=ALL_NEIGHBORS {
+            let count = configuration.count_ones() as u8;
+            birth[configuration as usize] = rule.is_birth(count);
+            survival[configuration as usize] = rule.is_survival(count);
+        }
+
+        IsotropicRule { birth, survival }
+    }
+}
+
+impl FromStr for IsotropicRule {
+    type Err = RuleError;
+
+    /// Parse a rulestring in Hensel notation, e.g. `B2-a/S12`.
+    fn from_str(rulestring: &str) -> Result<IsotropicRule, RuleError> {
+        let error = || RuleError::new(rulestring);
+
+        let mut parts = rulestring.trim().splitn(2, '/');
+        let first = parts.next().ok_or_else(error)?;
+        let second = parts.next().ok_or_else(error)?;
+
+        let mut birth = None;
+        let mut survival = None;
+
+        for part in [first, second].iter() {
+            let mut chars = part.chars();
+
+            match chars.next() {
+                Some('B') | Some('b') if birth.is_none() => {
+                    birth = Some(parse_conditions(chars.as_str()).ok_or_else(error)?)
+                }
+                Some('S') | Some('s') if survival.is_none() => {
+                    survival = Some(parse_conditions(chars.as_str()).ok_or_else(error)?)
+                }
+                _ => return Err(error()),
+            }
+        }
+
+        match (birth, survival) {
+            (Some(birth), Some(survival)) => Ok(IsotropicRule { birth, survival }),
+            _ => Err(error()),
+        }
+    }
+}
+
+impl fmt::Display for IsotropicRule {
+    /// Display the rule in Hensel notation, listing the letters of each
+    /// number of live neighbors only where it includes some but not all of
+    /// its configurations.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "B")?;
+        write_conditions(f, &self.birth)?;
+        write!(f, "/S")?;
+        write_conditions(f, &self.survival)
+    }
+}
+
+impl StateRule<Cell> for IsotropicRule {
+    fn next(&self, state: Cell, neighbors: &[Cell; 8]) -> Cell {
+        let configuration = neighbors
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.is_alive())
+            .fold(0u8, |configuration, (bit, _)| configuration | 1 << bit);
+
+        let alive = if state.is_alive() {
+            self.is_survival(configuration)
+        } else {
+            self.is_birth(configuration)
+        };
+
+        if alive {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        }
+    }
+}
@@ -0,0 +1,471 @@
+//! Rebindable keys of the interactive user interfaces.
+//!
+//! Each [Action] is bound to one or more [Key]s. The defaults can be
+//! rebound from the `[keys]` table of the configuration file, where a key is
+//! a single character, a character with control held such as `ctrl+c`, or
+//! one of the names accepted by [Key::from_str]:
+//!
+//! ```toml
+//! [keys]
+//! randomize = "R"
+//! pause = ["space", "enter"]
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use gameoflife_rs::{ConwaySim, Grid, Overlap, Pattern};
+
+/// Density of the soup filling the grid on [Action::Randomize].
+const RANDOM_DENSITY: f64 = 0.3;
+
+/// Number of edits which can be undone with [Action::Undo].
+pub const UNDO_CAPACITY: usize = 100;
+
+/// Number of generations between the snapshots [Action::StepBack] and the
+/// timeline restore and re-simulate from.
+pub const HISTORY_INTERVAL: u32 = 50;
+
+/// Number of snapshots kept for [Action::StepBack] and the timeline, reaching
+/// `HISTORY_INTERVAL * HISTORY_CAPACITY` generations back.
+pub const HISTORY_CAPACITY: usize = 200;
+
+/// Something the user can do by pressing a key.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Action {
+    /// Play or pause the simulation.
+    Pause,
+
+    /// Pause and advance a single generation.
+    Step,
+
+    /// Pause and move back a single generation.
+    StepBack,
+
+    /// Pause and move the timeline's scrubber towards its start.
+    ScrubBack,
+
+    /// Pause and move the timeline's scrubber towards its end.
+    ScrubForward,
+
+    /// Type a generation to move to.
+    GoTo,
+
+    /// Bookmark the current generation, or remove its bookmark.
+    Bookmark,
+
+    /// Pause and move to the previous bookmark.
+    PreviousBookmark,
+
+    /// Pause and move to the next bookmark.
+    NextBookmark,
+
+    /// Speed up the simulation.
+    Faster,
+
+    /// Slow down the simulation.
+    Slower,
+
+    /// Set every cell dead.
+    Clear,
+
+    /// Fill the grid with a random soup.
+    Randomize,
+
+    /// Undo the latest edit of the cells.
+    Undo,
+
+    /// Redo the latest undone edit.
+    Redo,
+
+    /// Start selecting a rectangle of cells, or clear the selection.
+    Select,
+
+    /// Copy the selected cells to the clipboard.
+    Copy,
+
+    /// Copy the selected cells to the clipboard and clear them.
+    Cut,
+
+    /// Paste the clipboard, selecting the pasted cells.
+    Paste,
+
+    /// Rotate the selected cells clockwise.
+    Rotate,
+
+    /// Mirror the selected cells left to right.
+    Flip,
+
+    /// Save the grid to an RLE file in the current directory.
+    Save,
+
+    /// Show or hide the population graph.
+    Graph,
+
+    /// Bring the live cells into view.
+    Fit,
+
+    /// Show or hide the minimap of the whole grid.
+    Minimap,
+
+    /// Show or hide the list of key bindings.
+    Help,
+
+    /// Quit.
+    Quit,
+}
+
+impl Action {
+    /// Every action, in the order they are listed.
+    pub const ALL: [Action; 27] = [
+        Action::Pause,
+        Action::Step,
+        Action::StepBack,
+        Action::ScrubBack,
+        Action::ScrubForward,
+        Action::GoTo,
+        Action::Bookmark,
+        Action::PreviousBookmark,
+        Action::NextBookmark,
+        Action::Faster,
+        Action::Slower,
+        Action::Clear,
+        Action::Randomize,
+        Action::Undo,
+        Action::Redo,
+        Action::Select,
+        Action::Copy,
+        Action::Cut,
+        Action::Paste,
+        Action::Rotate,
+        Action::Flip,
+        Action::Save,
+        Action::Graph,
+        Action::Fit,
+        Action::Minimap,
+        Action::Help,
+        Action::Quit,
+    ];
+
+    /// Get the name of the action in the configuration file.
+    pub fn get_name(self) -> &'static str {
+        match self {
+            Action::Pause => "pause",
+            Action::Step => "step",
+            Action::StepBack => "step-back",
+            Action::ScrubBack => "scrub-back",
+            Action::ScrubForward => "scrub-forward",
+            Action::GoTo => "go-to",
+            Action::Bookmark => "bookmark",
+            Action::PreviousBookmark => "previous-bookmark",
+            Action::NextBookmark => "next-bookmark",
+            Action::Faster => "faster",
+            Action::Slower => "slower",
+            Action::Clear => "clear",
+            Action::Randomize => "randomize",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::Select => "select",
+            Action::Copy => "copy",
+            Action::Cut => "cut",
+            Action::Paste => "paste",
+            Action::Rotate => "rotate",
+            Action::Flip => "flip",
+            Action::Save => "save",
+            Action::Graph => "graph",
+            Action::Fit => "fit",
+            Action::Minimap => "minimap",
+            Action::Help => "help",
+            Action::Quit => "quit",
+        }
+    }
+
+    /// Get a short description of the action for the help overlay.
+    pub fn get_description(self) -> &'static str {
+        match self {
+            Action::Pause => "play/pause",
+            Action::Step => "step one generation",
+            Action::StepBack => "step back one generation",
+            Action::ScrubBack => "scrub the timeline back",
+            Action::ScrubForward => "scrub the timeline forward",
+            Action::GoTo => "go to a generation",
+            Action::Bookmark => "bookmark the generation/remove it",
+            Action::PreviousBookmark => "go to the previous bookmark",
+            Action::NextBookmark => "go to the next bookmark",
+            Action::Faster => "speed up",
+            Action::Slower => "slow down",
+            Action::Clear => "clear the grid",
+            Action::Randomize => "fill with a random soup",
+            Action::Undo => "undo the last edit",
+            Action::Redo => "redo the last undone edit",
+            Action::Select => "select cells/clear the selection",
+            Action::Copy => "copy the selection",
+            Action::Cut => "cut the selection",
+            Action::Paste => "paste",
+            Action::Rotate => "rotate the selection clockwise",
+            Action::Flip => "flip the selection left to right",
+            Action::Save => "save the grid as RLE",
+            Action::Graph => "show/hide the population graph",
+            Action::Fit => "bring the pattern into view",
+            Action::Minimap => "show/hide the minimap",
+            Action::Help => "show/hide these keys",
+            Action::Quit => "quit",
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Action, String> {
+        Action::ALL
+            .iter()
+            .copied()
+            .find(|action| action.get_name() == name)
+            .ok_or_else(|| format!("unknown action '{}'", name))
+    }
+}
+
+/// A key of the keyboard.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Key {
+    /// A key typing a character, other than those named below.
+    Char(char),
+
+    /// A letter or digit key pressed with control held, as its lowercase
+    /// character.
+    Ctrl(char),
+
+    /// The space bar.
+    Space,
+
+    /// The enter (return) key.
+    Enter,
+
+    /// The escape key.
+    Esc,
+
+    /// The tab key.
+    Tab,
+
+    /// The backspace key.
+    Backspace,
+
+    /// The up arrow key.
+    Up,
+
+    /// The down arrow key.
+    Down,
+
+    /// The left arrow key.
+    Left,
+
+    /// The right arrow key.
+    Right,
+}
+
+impl From<char> for Key {
+    /// Get the key typing a character, naming the space and control
+    /// characters.
+    fn from(c: char) -> Key {
+        match c {
+            ' ' => Key::Space,
+            '\r' | '\n' => Key::Enter,
+            '\u{1b}' => Key::Esc,
+            '\t' => Key::Tab,
+            '\u{8}' | '\u{7f}' => Key::Backspace,
+            c => Key::Char(c),
+        }
+    }
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    /// Parse a single character, a letter or digit prefixed by `ctrl+`, or
+    /// the name of a key (space, enter, esc, tab, backspace, up, down, left,
+    /// or right) in any case.
+    fn from_str(name: &str) -> Result<Key, String> {
+        let mut chars = name.chars();
+
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(Key::from(c));
+        }
+
+        let lowercase = name.to_ascii_lowercase();
+
+        if let Some(key) = lowercase
+            .strip_prefix("ctrl+")
+            .or_else(|| lowercase.strip_prefix("ctrl-"))
+        {
+            let mut chars = key.chars();
+
+            return match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii_alphanumeric() => Ok(Key::Ctrl(c)),
+                _ => Err(format!("unknown key '{}'", name)),
+            };
+        }
+
+        match lowercase.as_str() {
+            "space" => Ok(Key::Space),
+            "enter" | "return" => Ok(Key::Enter),
+            "esc" | "escape" => Ok(Key::Esc),
+            "tab" => Ok(Key::Tab),
+            "backspace" => Ok(Key::Backspace),
+            "up" => Ok(Key::Up),
+            "down" => Ok(Key::Down),
+            "left" => Ok(Key::Left),
+            "right" => Ok(Key::Right),
+            _ => Err(format!("unknown key '{}'", name)),
+        }
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Key::Char(c) => write!(f, "{}", c),
+            Key::Ctrl(c) => write!(f, "ctrl+{}", c),
+            Key::Space => write!(f, "space"),
+            Key::Enter => write!(f, "enter"),
+            Key::Esc => write!(f, "esc"),
+            Key::Tab => write!(f, "tab"),
+            Key::Backspace => write!(f, "backspace"),
+            Key::Up => write!(f, "up"),
+            Key::Down => write!(f, "down"),
+            Key::Left => write!(f, "left"),
+            Key::Right => write!(f, "right"),
+        }
+    }
+}
+
+/// The keys bound to each [Action].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Keymap {
+    /// The keys of each action, in the order of [Action::ALL].
+    bindings: Vec<(Action, Vec<Key>)>,
+}
+
+impl Keymap {
+    /// Bind an action to keys instead of its current ones, unbinding the
+    /// keys from any other action.
+    ///
+    /// # Arguments
+    /// * `action` - The action to rebind.
+    /// * `keys` - The new keys of the action.
+    #[cfg_attr(not(feature = "config"), allow(dead_code))]
+    pub fn bind(&mut self, action: Action, keys: &[Key]) {
+        for (bound, bound_keys) in self.bindings.iter_mut() {
+            if *bound == action {
+                *bound_keys = keys.to_vec();
+            } else {
+                bound_keys.retain(|key| !keys.contains(key));
+            }
+        }
+    }
+
+    /// Get the action bound to a key, if any.
+    pub fn get_action(&self, key: Key) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|&(action, _)| action)
+    }
+
+    /// Get the keys bound to an action.
+    pub fn get_keys(&self, action: Action) -> &[Key] {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Get the keys of an action as text such as `space/p`, or `unbound`.
+    pub fn describe_keys(&self, action: Action) -> String {
+        let keys: Vec<String> = self.get_keys(action).iter().map(Key::to_string).collect();
+
+        if keys.is_empty() {
+            "unbound".to_string()
+        } else {
+            keys.join("/")
+        }
+    }
+
+    /// Get the keys and description of each action, for the help overlay.
+    pub fn help_entries(&self) -> Vec<(String, &'static str)> {
+        Action::ALL
+            .iter()
+            .map(|&action| (self.describe_keys(action), action.get_description()))
+            .collect()
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Keymap {
+        let keys = |action: Action| -> Vec<Key> {
+            match action {
+                Action::Pause => vec![Key::Space, Key::Char('p')],
+                Action::Step => vec![Key::Char('n'), Key::Char('s')],
+                Action::StepBack => vec![Key::Char('b')],
+                Action::ScrubBack => vec![Key::Char('[')],
+                Action::ScrubForward => vec![Key::Char(']')],
+                Action::GoTo => vec![Key::Char(':')],
+                Action::Bookmark => vec![Key::Char('*')],
+                Action::PreviousBookmark => vec![Key::Char('{')],
+                Action::NextBookmark => vec![Key::Char('}')],
+                Action::Faster => vec![Key::Char('+'), Key::Char('=')],
+                Action::Slower => vec![Key::Char('-')],
+                Action::Clear => vec![Key::Char('c')],
+                Action::Randomize => vec![Key::Char('r')],
+                Action::Undo => vec![Key::Ctrl('z'), Key::Char('u')],
+                Action::Redo => vec![Key::Ctrl('y'), Key::Char('U')],
+                Action::Select => vec![Key::Char('v')],
+                Action::Copy => vec![Key::Ctrl('c'), Key::Char('y')],
+                Action::Cut => vec![Key::Ctrl('x'), Key::Char('x')],
+                Action::Paste => vec![Key::Ctrl('v'), Key::Char('P')],
+                Action::Rotate => vec![Key::Char('>')],
+                Action::Flip => vec![Key::Char('|')],
+                Action::Save => vec![Key::Char('w')],
+                Action::Graph => vec![Key::Char('g')],
+                Action::Fit => vec![Key::Char('f')],
+                Action::Minimap => vec![Key::Char('m')],
+                Action::Help => vec![Key::Char('?')],
+                Action::Quit => vec![Key::Char('q'), Key::Esc],
+            }
+        };
+
+        Keymap {
+            bindings: Action::ALL
+                .iter()
+                .map(|&action| (action, keys(action)))
+                .collect(),
+        }
+    }
+}
+
+/// Fill the simulation's grid with a random soup, for [Action::Randomize].
+pub fn randomize(sim: &mut ConwaySim) {
+    let grid = sim.get_grid();
+    let soup = Grid::random(
+        grid.get_num_rows(),
+        grid.get_num_cols(),
+        RANDOM_DENSITY,
+        crate::random_seed(),
+    );
+
+    sim.stamp(&Pattern::new(soup), 0, 0, Overlap::Overwrite)
+        .expect("a soup of the grid's size fits the grid");
+}
+
+/// Save the simulation's grid to `generation-<N>.rle` in the current
+/// directory, for [Action::Save], returning the path written.
+pub fn save(sim: &ConwaySim) -> io::Result<PathBuf> {
+    let path = PathBuf::from(format!("generation-{}.rle", sim.get_generation()));
+    fs::write(&path, sim.get_grid().to_rle())?;
+
+    Ok(path)
+}
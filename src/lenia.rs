@@ -0,0 +1,603 @@
+//! Experimental continuous automata in the style of Bert Chan's Lenia.
+//!
+//! Lenia generalizes Life to continuous states, space, and time: each cell
+//! holds a value from 0.0 to 1.0, its neighborhood is a smooth ring-shaped
+//! kernel of any radius, and each generation adds a small fraction of a
+//! growth function of the weighted sum of its neighborhood. With suitable
+//! parameters, blobs of cells glide, rotate, and divide much like living
+//! creatures.
+//!
+//! A [LeniaSim] is drawn by quantizing its values onto a [StateGrid] of
+//! levels with [LeniaSim::to_state_grid], colored by [palette], so the
+//! renderers of multi-state rules draw it too.
+//!
+//! This module is experimental: its parameters and API may change.
+//!
+//! ```
+//! use gameoflife_rs::lenia::{LeniaRule, LeniaSim};
+//!
+//! let rule: LeniaRule = "R=13;T=10;b=1;m=0.15;s=0.015".parse()?;
+//! let mut sim = LeniaSim::random(64, 64, 0.5, 7, rule);
+//! sim.step_n(5);
+//!
+//! assert!((0.0..=1.0).contains(&sim.get(32, 32)));
+//! # Ok::<(), gameoflife_rs::RuleError>(())
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::game::{Cell, Grid};
+use crate::render::Rgb;
+use crate::rule::RuleError;
+use crate::states::StateGrid;
+use crate::topology::Topology;
+
+/// Characters drawing the values of cells as text, from 0.0 to 1.0.
+const RAMP: &[u8] = b" .:-=+*#%@";
+
+/// Largest radius of a kernel.
+pub const MAX_RADIUS: u32 = 100;
+
+/// The parameters of a Lenia automaton: the radius and shells of its kernel,
+/// the center and width of its growth function, and its time step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LeniaRule {
+    /// Radius of the kernel, in cells.
+    radius: u32,
+
+    /// Number of steps per unit of time, the inverse of the fraction of the
+    /// growth added each generation.
+    time_steps: u32,
+
+    /// Peak heights of the concentric shells of the kernel, from the inside
+    /// out.
+    peaks: Vec<f64>,
+
+    /// Neighborhood sum of the greatest growth.
+    mu: f64,
+
+    /// Width of the growth function around `mu`.
+    sigma: f64,
+}
+
+impl LeniaRule {
+    /// Create a new [LeniaRule].
+    ///
+    /// # Arguments
+    /// * `radius` - Radius of the kernel, in cells.
+    /// * `time_steps` - Number of generations per unit of time.
+    /// * `peaks` - Peak heights of the shells of the kernel, from the inside
+    ///   out.
+    /// * `mu` - Neighborhood sum of the greatest growth.
+    /// * `sigma` - Width of the growth function.
+    ///
+    /// # Panics
+    /// Panics if the radius is 0 or greater than [MAX_RADIUS], if
+    /// `time_steps` is 0, if there are no peaks, or if `sigma` is not
+    /// positive.
+    pub fn new(radius: u32, time_steps: u32, peaks: &[f64], mu: f64, sigma: f64) -> LeniaRule {
+        assert!(
+            (1..=MAX_RADIUS).contains(&radius),
+            "the radius must be from 1 to {}",
+            MAX_RADIUS
+        );
+        assert!(time_steps > 0, "there must be at least one time step");
+        assert!(!peaks.is_empty(), "the kernel must have a shell");
+        assert!(sigma > 0.0, "the growth width must be positive");
+
+        LeniaRule {
+            radius,
+            time_steps,
+            peaks: peaks.to_vec(),
+            mu,
+            sigma,
+        }
+    }
+
+    /// The parameters of Orbium, Lenia's glider, `R=13;T=10;b=1;m=0.15;s=0.015`.
+    pub fn orbium() -> LeniaRule {
+        LeniaRule::new(13, 10, &[1.0], 0.15, 0.015)
+    }
+
+    /// Get the radius of the kernel.
+    pub fn get_radius(&self) -> u32 {
+        self.radius
+    }
+
+    /// Get the number of generations per unit of time.
+    pub fn get_time_steps(&self) -> u32 {
+        self.time_steps
+    }
+
+    /// Get the peak heights of the shells of the kernel.
+    pub fn get_peaks(&self) -> &[f64] {
+        &self.peaks
+    }
+
+    /// Get the neighborhood sum of the greatest growth.
+    pub fn get_mu(&self) -> f64 {
+        self.mu
+    }
+
+    /// Get the width of the growth function.
+    pub fn get_sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    /// Get the growth of a cell, from -1.0 to 1.0, given the weighted sum of
+    /// its neighborhood.
+    ///
+    /// # Arguments
+    /// * `sum` - The sum of the neighborhood weighted by the kernel.
+    pub fn growth(&self, sum: f64) -> f64 {
+        let distance = (sum - self.mu) / self.sigma;
+
+        2.0 * (-distance * distance / 2.0).exp() - 1.0
+    }
+
+    /// Build the (row offset, col offset, weight) of each cell of the
+    /// kernel, the weights summing to 1.
+    fn kernel(&self) -> Vec<(i64, i64, f64)> {
+        let radius = self.radius as i64;
+        let num_peaks = self.peaks.len() as f64;
+        let mut kernel = Vec::new();
+
+        for row in -radius..=radius {
+            for col in -radius..=radius {
+                let distance = ((row * row + col * col) as f64).sqrt() / radius as f64;
+                if distance >= 1.0 {
+                    continue;
+                }
+
+                // each shell is a smooth bump between its inner and outer
+                // radii
+                let shell = distance * num_peaks;
+                let within = shell.fract();
+                let core = if within > 0.0 {
+                    (4.0 - 1.0 / (within * (1.0 - within))).exp()
+                } else {
+                    0.0
+                };
+
+                let weight = self.peaks[shell as usize] * core;
+                if weight > 0.0 {
+                    kernel.push((row, col, weight));
+                }
+            }
+        }
+
+        let total: f64 = kernel.iter().map(|&(_, _, weight)| weight).sum();
+        for (_, _, weight) in &mut kernel {
+            *weight /= total;
+        }
+
+        kernel
+    }
+}
+
+/// Parse a peak height, either a decimal or a fraction such as `1/2`.
+fn parse_peak(peak: &str) -> Option<f64> {
+    match peak.split_once('/') {
+        Some((numerator, denominator)) => {
+            let denominator: f64 = denominator.parse().ok()?;
+            if denominator == 0.0 {
+                return None;
+            }
+
+            Some(numerator.parse::<f64>().ok()? / denominator)
+        }
+        None => peak.parse().ok(),
+    }
+}
+
+impl FromStr for LeniaRule {
+    type Err = RuleError;
+
+    /// Parse the parameters in Lenia's `R=13;T=10;b=1,1/2;m=0.15;s=0.015`
+    /// notation, giving the radius, time steps, shell peaks, growth center,
+    /// and growth width, in any order.
+    fn from_str(rulestring: &str) -> Result<LeniaRule, RuleError> {
+        let error = || RuleError::new(rulestring);
+        let (mut radius, mut time_steps, mut peaks, mut mu, mut sigma) =
+            (None, None, None, None, None);
+
+        for parameter in rulestring
+            .trim()
+            .split(';')
+            .filter(|p| !p.trim().is_empty())
+        {
+            let (key, value) = parameter.split_once('=').ok_or_else(error)?;
+            let value = value.trim();
+
+            match key.trim() {
+                "R" => radius = Some(value.parse::<u32>().map_err(|_| error())?),
+                "T" => time_steps = Some(value.parse::<u32>().map_err(|_| error())?),
+                "b" => {
+                    let shells: Option<Vec<f64>> = value.split(',').map(parse_peak).collect();
+                    peaks = Some(shells.ok_or_else(error)?);
+                }
+                "m" => mu = Some(value.parse::<f64>().map_err(|_| error())?),
+                "s" => sigma = Some(value.parse::<f64>().map_err(|_| error())?),
+                _ => return Err(error()),
+            }
+        }
+
+        match (radius, time_steps, peaks, mu, sigma) {
+            (Some(radius), Some(time_steps), Some(peaks), Some(mu), Some(sigma))
+                if (1..=MAX_RADIUS).contains(&radius)
+                    && time_steps > 0
+                    && peaks.iter().all(|peak| peak.is_finite())
+                    && mu.is_finite()
+                    && sigma.is_finite()
+                    && sigma > 0.0 =>
+            {
+                Ok(LeniaRule::new(radius, time_steps, &peaks, mu, sigma))
+            }
+            _ => Err(error()),
+        }
+    }
+}
+
+impl fmt::Display for LeniaRule {
+    /// Display the parameters in Lenia's notation.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let peaks: Vec<String> = self.peaks.iter().map(|peak| peak.to_string()).collect();
+
+        write!(
+            f,
+            "R={};T={};b={};m={};s={}",
+            self.radius,
+            self.time_steps,
+            peaks.join(","),
+            self.mu,
+            self.sigma
+        )
+    }
+}
+
+/// Build a palette of colors for the levels of [LeniaSim::to_state_grid],
+/// blending from `dead` at level 0 to `alive` at the highest level.
+///
+/// # Arguments
+/// * `alive` - Color of cells of value 1.0.
+/// * `dead` - Color of cells of value 0.0.
+/// * `num_levels` - Number of levels, at least 2.
+pub fn palette(alive: Rgb, dead: Rgb, num_levels: u8) -> Vec<Rgb> {
+    (0..num_levels)
+        .map(|level| dead.blend(alive, level as f64 / (num_levels - 1).max(1) as f64))
+        .collect()
+}
+
+/// Simulation of a [LeniaRule] on a grid of continuous cell values.
+///
+/// Cells beyond the edges of the grid are found by its [Topology], of value
+/// 0.0 if it is bounded.
+#[derive(Clone, Debug)]
+pub struct LeniaSim {
+    /// Number of rows (height) of the grid.
+    num_rows: u32,
+
+    /// Number of columns (width) of the grid.
+    num_cols: u32,
+
+    /// Values of the cells, row by row.
+    cells: Vec<f64>,
+
+    /// Values of the cells padded by the radius of the kernel according to
+    /// the topology, reused between generations.
+    padded: Vec<f64>,
+
+    /// How cells on the edges find their neighbors.
+    topology: Topology,
+
+    /// The [LeniaRule] applied to each cell every generation.
+    rule: LeniaRule,
+
+    /// The (row offset, col offset, weight) of each cell of the kernel.
+    kernel: Vec<(i64, i64, f64)>,
+
+    /// The simulation's current generation.
+    generation: u32,
+}
+
+impl LeniaSim {
+    /// Create a new simulation of cells of value 0.0.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the grid.
+    /// * `num_cols` - The number of columns (width) of the grid.
+    /// * `rule` - The [LeniaRule] applied to each cell.
+    ///
+    /// # Panics
+    /// Panics if the grid has more than `u32::MAX` cells.
+    pub fn new(num_rows: u32, num_cols: u32, rule: LeniaRule) -> LeniaSim {
+        assert!(
+            num_rows as u64 * num_cols as u64 <= u32::MAX as u64,
+            "the grid must have at most {} cells",
+            u32::MAX
+        );
+
+        LeniaSim {
+            num_rows,
+            num_cols,
+            cells: vec![0.0; num_rows as usize * num_cols as usize],
+            padded: Vec::new(),
+            topology: Topology::Bounded,
+            kernel: rule.kernel(),
+            rule,
+            generation: 0,
+        }
+    }
+
+    /// Create a new simulation with the live cells of a [Grid] at 1.0 and its
+    /// topology.
+    ///
+    /// # Arguments
+    /// * `grid` - The initial live cells.
+    /// * `rule` - The [LeniaRule] applied to each cell.
+    pub fn from_grid(grid: &Grid, rule: LeniaRule) -> LeniaSim {
+        let mut sim = LeniaSim::new(grid.get_num_rows(), grid.get_num_cols(), rule);
+        sim.set_topology(grid.get_topology());
+
+        for row in 0..grid.get_num_rows() {
+            for col in 0..grid.get_num_cols() {
+                if grid.get(row, col) == Cell::Alive {
+                    sim.set(row, col, 1.0);
+                }
+            }
+        }
+
+        sim
+    }
+
+    /// Create a new simulation of a random soup, where each cell is given a
+    /// uniformly random value with the probability `density`.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the grid.
+    /// * `num_cols` - The number of columns (width) of the grid.
+    /// * `density` - Probability of each cell having a value, from 0.0 to
+    ///   1.0.
+    /// * `seed` - Seed of the random number generator.
+    /// * `rule` - The [LeniaRule] applied to each cell.
+    ///
+    /// # Panics
+    /// Panics if `density` is not within 0.0 to 1.0.
+    pub fn random(
+        num_rows: u32,
+        num_cols: u32,
+        density: f64,
+        seed: u64,
+        rule: LeniaRule,
+    ) -> LeniaSim {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut sim = LeniaSim::new(num_rows, num_cols, rule);
+
+        for value in &mut sim.cells {
+            if rng.random_bool(density) {
+                *value = rng.random::<f64>();
+            }
+        }
+
+        sim
+    }
+
+    /// Get the number of rows (height) of the grid.
+    pub fn get_num_rows(&self) -> u32 {
+        self.num_rows
+    }
+
+    /// Get the number of columns (width) of the grid.
+    pub fn get_num_cols(&self) -> u32 {
+        self.num_cols
+    }
+
+    /// Get the value of a cell, from 0.0 to 1.0.
+    ///
+    /// # Panics
+    /// Panics if the cell is outside the grid.
+    pub fn get(&self, row: u32, col: u32) -> f64 {
+        self.cells[self.cell_to_index(row, col)]
+    }
+
+    /// Set the value of a cell, clamped to 0.0 to 1.0.
+    ///
+    /// # Panics
+    /// Panics if the cell is outside the grid.
+    pub fn set(&mut self, row: u32, col: u32, value: f64) {
+        let index = self.cell_to_index(row, col);
+        self.cells[index] = value.clamp(0.0, 1.0);
+    }
+
+    /// Get the values of the cells, row by row.
+    pub fn get_cells(&self) -> &[f64] {
+        &self.cells
+    }
+
+    /// Get the index of a cell in the values.
+    fn cell_to_index(&self, row: u32, col: u32) -> usize {
+        assert!(
+            row < self.num_rows && col < self.num_cols,
+            "cell ({}, {}) is outside the {}x{} grid",
+            row,
+            col,
+            self.num_rows,
+            self.num_cols
+        );
+
+        row as usize * self.num_cols as usize + col as usize
+    }
+
+    /// Get the [Topology] deciding the neighbors of cells on the edges.
+    pub fn get_topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Set the [Topology] deciding the neighbors of cells on the edges.
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    /// Get the simulation's [LeniaRule].
+    pub fn get_rule(&self) -> &LeniaRule {
+        &self.rule
+    }
+
+    /// Set the [LeniaRule] applied to each cell every generation.
+    pub fn set_rule(&mut self, rule: LeniaRule) {
+        self.kernel = rule.kernel();
+        self.rule = rule;
+    }
+
+    /// Get the simulation's current generation.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Get the total value of the cells, Lenia's counterpart of the
+    /// population.
+    pub fn get_mass(&self) -> f64 {
+        self.cells.iter().sum()
+    }
+
+    /// Determine if any cell has a value above 0.0.
+    pub fn is_any_cell_alive(&self) -> bool {
+        self.cells.iter().any(|&value| value > 0.0)
+    }
+
+    /// Copy the values into `padded`, surrounded by the cells within the
+    /// radius beyond the edges according to the topology.
+    fn pad(&mut self) {
+        let radius = self.rule.get_radius() as i64;
+        let (num_rows, num_cols) = (self.num_rows, self.num_cols);
+        let padded_cols = num_cols as usize + 2 * radius as usize;
+        let padded_rows = num_rows as usize + 2 * radius as usize;
+
+        self.padded.clear();
+        self.padded.reserve(padded_rows * padded_cols);
+
+        for padded_row in 0..padded_rows as i64 {
+            for padded_col in 0..padded_cols as i64 {
+                let cell = self.topology.resolve(
+                    padded_row - radius,
+                    padded_col - radius,
+                    num_rows,
+                    num_cols,
+                );
+
+                self.padded.push(match cell {
+                    Some((row, col)) => self.cells[row as usize * num_cols as usize + col as usize],
+                    None => 0.0,
+                });
+            }
+        }
+    }
+
+    /// Advance the simulation by one generation.
+    pub fn step(&mut self) {
+        self.generation += 1;
+        self.pad();
+
+        let radius = self.rule.get_radius() as i64;
+        let padded_cols = self.num_cols as i64 + 2 * radius;
+        let dt = 1.0 / self.rule.get_time_steps() as f64;
+
+        // offsets of the kernel's cells from the padded index of its center
+        let kernel: Vec<(usize, f64)> = self
+            .kernel
+            .iter()
+            .map(|&(row, col, weight)| {
+                (
+                    ((row + radius) * padded_cols + col + radius) as usize,
+                    weight,
+                )
+            })
+            .collect();
+
+        for row in 0..self.num_rows as usize {
+            for col in 0..self.num_cols as usize {
+                // index of the kernel's top left corner
+                let corner = row * padded_cols as usize + col;
+                let sum: f64 = kernel
+                    .iter()
+                    .map(|&(offset, weight)| self.padded[corner + offset] * weight)
+                    .sum();
+
+                let value = &mut self.cells[row * self.num_cols as usize + col];
+                *value = (*value + dt * self.rule.growth(sum)).clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    /// Advance the simulation by `n` generations.
+    pub fn step_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+
+    /// Quantize the values onto a [StateGrid] of levels, for the renderers
+    /// of multi-state rules. Level 0 is a value of 0.0, and any greater value
+    /// is at least level 1.
+    ///
+    /// # Arguments
+    /// * `num_levels` - Number of levels, at least 2.
+    ///
+    /// # Panics
+    /// Panics if there are fewer than 2 levels.
+    pub fn to_state_grid(&self, num_levels: u8) -> StateGrid {
+        assert!(num_levels >= 2, "there must be at least 2 levels");
+
+        let mut grid = StateGrid::new(self.num_rows, self.num_cols);
+        let top = (num_levels - 1) as f64;
+
+        for row in 0..self.num_rows {
+            for col in 0..self.num_cols {
+                grid.set(row, col, (self.get(row, col) * top).ceil() as u8);
+            }
+        }
+
+        grid
+    }
+
+    /// Threshold the values onto a [Grid] of the same size and topology,
+    /// with the cells of at least `threshold` alive.
+    ///
+    /// # Arguments
+    /// * `threshold` - The lowest value of a live cell.
+    pub fn to_grid(&self, threshold: f64) -> Grid {
+        let mut grid = Grid::new(self.num_rows, self.num_cols);
+        grid.set_topology(self.topology);
+
+        for row in 0..self.num_rows {
+            for col in 0..self.num_cols {
+                if self.get(row, col) >= threshold {
+                    grid.set(row, col, Cell::Alive);
+                }
+            }
+        }
+
+        grid
+    }
+}
+
+impl fmt::Display for LeniaSim {
+    /// Draw the values as characters of increasing density.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let top = (RAMP.len() - 1) as f64;
+
+        for row in 0..self.num_rows {
+            let line: String = (0..self.num_cols)
+                .map(|col| RAMP[(self.get(row, col) * top).ceil() as usize] as char)
+                .collect();
+
+            writeln!(f, "{}", line)?;
+        }
+
+        Ok(())
+    }
+}
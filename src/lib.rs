@@ -0,0 +1,197 @@
+//! Conway's Game of Life simulation engine.
+//!
+//! The crate exposes the [Grid] the game is played on, the [Cell] states that
+//! populate it, and [ConwaySim], which advances a [Grid] one generation at a
+//! time according to the rules of Conway's Game of Life.
+//!
+//! ```
+//! use gameoflife_rs::ConwaySim;
+//!
+//! let mut sim = ConwaySim::new(5, 5);
+//! sim.set_cells(&[(2, 1), (2, 2), (2, 3)]);
+//! sim.step();
+//!
+//! assert!(sim.is_cell_alive(1, 2));
+//! assert!(!sim.is_cell_alive(2, 1));
+//! ```
+//!
+//! # `no_std`
+//!
+//! Without the default `std` feature the crate is `no_std` and only needs an
+//! allocator, e.g. to drive an LED matrix from a microcontroller. The core of
+//! rules, grids, stepping, patterns, and RLE parsing remains; file I/O,
+//! rendering, analysis, and the other automata require `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod activity;
+#[cfg(feature = "std")]
+pub mod analysis;
+#[cfg(feature = "std")]
+pub mod ant;
+pub mod automaton;
+#[cfg(feature = "simd")]
+mod bitgrid;
+pub mod bookmark;
+mod builder;
+#[cfg(any(feature = "simd", feature = "std"))]
+mod bitops;
+#[cfg(feature = "std")]
+pub mod checkpoint;
+#[cfg(feature = "std")]
+mod chunked;
+#[cfg(feature = "std")]
+pub mod colors;
+pub mod diff;
+#[cfg(feature = "embedded-graphics")]
+pub mod display;
+pub mod edit;
+mod error;
+mod find;
+mod game;
+#[cfg(feature = "std")]
+pub mod generations;
+#[cfg(feature = "std")]
+pub mod hashlife;
+#[cfg(feature = "std")]
+pub mod hex;
+pub mod history;
+#[cfg(feature = "std")]
+pub mod isotropic;
+#[cfg(feature = "std")]
+pub mod lenia;
+#[cfg(feature = "std")]
+pub mod life106;
+#[cfg(feature = "std")]
+pub mod life3d;
+#[cfg(feature = "std")]
+pub mod ltl;
+#[cfg(feature = "std")]
+pub mod macrocell;
+#[cfg(feature = "std")]
+pub mod margolus;
+pub mod observer;
+pub mod pattern;
+pub mod patterns;
+#[cfg(feature = "std")]
+pub mod plaintext;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "std")]
+pub mod render;
+#[cfg(feature = "std")]
+pub mod replay;
+#[cfg(feature = "std")]
+pub mod reversible;
+pub mod rle;
+pub mod rule;
+#[cfg(feature = "async")]
+pub mod runner;
+#[cfg(feature = "std")]
+mod scheduler;
+#[cfg(feature = "std")]
+pub mod ruletable;
+#[cfg(feature = "lua")]
+pub mod script;
+#[cfg(feature = "serde")]
+mod serialize;
+mod soup;
+#[cfg(feature = "std")]
+pub mod shared;
+#[cfg(feature = "std")]
+pub mod snapshot;
+#[cfg(feature = "std")]
+mod sparse;
+pub mod stats;
+mod states;
+mod status;
+pub mod stop;
+pub mod topology;
+pub mod transform;
+pub mod undo;
+pub mod view;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "std")]
+pub mod wireworld;
+mod world;
+
+pub use activity::Heatmap;
+#[cfg(feature = "std")]
+pub use analysis::{
+    Census, DensitySweep, Periodicity, SearchReport, SoupSearch, SweepReport, Velocity,
+    VelocityTracker,
+};
+#[cfg(feature = "std")]
+pub use ant::{AntRule, LangtonsAnt};
+pub use automaton::{CellState, Sim, StateRule};
+#[cfg(feature = "simd")]
+pub use bitgrid::BitGrid;
+pub use bookmark::Bookmark;
+pub use builder::SimBuilder;
+#[cfg(feature = "std")]
+pub use checkpoint::CheckpointError;
+#[cfg(feature = "std")]
+pub use chunked::ChunkedWorld;
+#[cfg(feature = "std")]
+pub use colors::{ColorSim, ColorVariant};
+pub use diff::GridDiff;
+#[cfg(feature = "embedded-graphics")]
+pub use display::{GridDrawing, GridStyle};
+pub use error::GolError;
+pub use game::{Cell, ConwaySim, Grid, GridError, CHUNK_SIZE};
+#[cfg(feature = "std")]
+pub use generations::{GenerationsRule, GenerationsSim};
+#[cfg(feature = "std")]
+pub use hashlife::{HashLifeError, HashLifeSim};
+#[cfg(feature = "std")]
+pub use hex::{HexGrid, HexRule, HexSim};
+pub use history::{History, HistoryError};
+#[cfg(feature = "std")]
+pub use isotropic::IsotropicRule;
+#[cfg(feature = "std")]
+pub use lenia::{LeniaRule, LeniaSim};
+#[cfg(feature = "std")]
+pub use life106::Life106Error;
+#[cfg(feature = "std")]
+pub use life3d::{Grid3D, Rule3D, Sim3D};
+#[cfg(feature = "std")]
+pub use ltl::{LtlRule, LtlSim};
+#[cfg(feature = "std")]
+pub use macrocell::MacrocellError;
+#[cfg(feature = "std")]
+pub use margolus::{BlockRule, MargolusSim};
+pub use observer::{CellChange, Diff};
+pub use pattern::{Overlap, Pattern, PatternMeta};
+#[cfg(feature = "std")]
+pub use plaintext::PlaintextError;
+#[cfg(feature = "std")]
+pub use replay::{Recorder, Recording, ReplayError, Replayer};
+#[cfg(feature = "std")]
+pub use reversible::SecondOrderSim;
+pub use rle::RleError;
+pub use rule::{Rule, RuleError};
+#[cfg(feature = "std")]
+pub use ruletable::{RuleTable, RuleTableError};
+#[cfg(feature = "async")]
+pub use runner::Runner;
+#[cfg(feature = "std")]
+pub use shared::SharedSim;
+#[cfg(feature = "std")]
+pub use snapshot::GridSnapshot;
+#[cfg(feature = "std")]
+pub use sparse::SparseWorld;
+pub use stats::{GenerationStats, Stats};
+pub use states::StateGrid;
+pub use soup::{Symmetry, SymmetryError};
+pub use status::{Cycle, SimStatus};
+pub use stop::StopCondition;
+pub use topology::{Edge, Topology, TopologyError};
+pub use transform::{Anchor, Transform};
+pub use undo::UndoStack;
+pub use view::GridView;
+#[cfg(feature = "std")]
+pub use wireworld::WireworldSim;
+pub use world::World;
@@ -0,0 +1,203 @@
+//! Support for the Life 1.06 coordinate-list pattern format.
+//!
+//! A Life 1.06 file starts with a `#Life 1.06` header line followed by one
+//! `x y` pair of live cell coordinates per line, where `x` is the column and
+//! `y` is the row. Coordinates may be negative, and must be within the
+//! [COORDINATE_RANGE] of a [HashLifeSim].
+
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::game::{ConwaySim, Grid, MAX_CELLS};
+use crate::hashlife::{HashLifeSim, COORDINATE_RANGE};
+
+/// The header line of a Life 1.06 file.
+const HEADER: &str = "#Life 1.06";
+
+/// Error raised while loading a Life 1.06 pattern.
+#[derive(Debug)]
+pub enum Life106Error {
+    /// The `#Life 1.06` header line is missing.
+    MissingHeader,
+
+    /// A line is not a valid `x y` coordinate pair within
+    /// [COORDINATE_RANGE].
+    InvalidCoordinate { line: usize },
+
+    /// The bounding box of the live cells is too large for a [Grid] to hold,
+    /// with each dimension capped at `u32::MAX`.
+    TooLarge { num_rows: u32, num_cols: u32 },
+
+    /// The pattern file could not be read or written.
+    Io(io::Error),
+}
+
+impl fmt::Display for Life106Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Life106Error::MissingHeader => write!(f, "missing '{}' header line", HEADER),
+            Life106Error::InvalidCoordinate { line } => {
+                write!(f, "invalid Life 1.06 coordinates on line {}", line)
+            }
+            Life106Error::TooLarge { num_rows, num_cols } => write!(
+                f,
+                "Life 1.06 pattern of {}x{} cells is too large",
+                num_rows, num_cols
+            ),
+            Life106Error::Io(err) => write!(f, "unable to access Life 1.06 file: {}", err),
+        }
+    }
+}
+
+impl error::Error for Life106Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Life106Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Life106Error {
+    fn from(err: io::Error) -> Life106Error {
+        Life106Error::Io(err)
+    }
+}
+
+/// Parse a Life 1.06 pattern into a list of (row, col) live cells.
+pub(crate) fn parse(life106: &str) -> Result<Vec<(i64, i64)>, Life106Error> {
+    let mut lines = life106.lines().enumerate();
+
+    match lines.next() {
+        Some((_, line)) if line.trim() == HEADER => {}
+        _ => return Err(Life106Error::MissingHeader),
+    }
+
+    let mut cells: Vec<(i64, i64)> = Vec::new();
+
+    for (number, line) in lines {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut coordinates = line.split_whitespace().map(str::parse::<i64>);
+
+        match (coordinates.next(), coordinates.next(), coordinates.next()) {
+            (Some(Ok(x)), Some(Ok(y)), None)
+                if COORDINATE_RANGE.contains(&x) && COORDINATE_RANGE.contains(&y) =>
+            {
+                cells.push((y, x))
+            }
+            _ => return Err(Life106Error::InvalidCoordinate { line: number + 1 }),
+        }
+    }
+
+    Ok(cells)
+}
+
+impl Grid {
+    /// Create a new [Grid] from a pattern in the Life 1.06 format.
+    ///
+    /// The [Grid] is sized to the bounding box of the live cells, with the
+    /// top left live cell coordinates mapped to the (0, 0) cell.
+    ///
+    /// # Arguments
+    /// * `life106` - The Life 1.06 encoded pattern.
+    pub fn from_life106(life106: &str) -> Result<Grid, Life106Error> {
+        let cells = parse(life106)?;
+
+        let min_row = cells.iter().map(|&(row, _)| row).min().unwrap_or(0);
+        let min_col = cells.iter().map(|&(_, col)| col).min().unwrap_or(0);
+        let max_row = cells.iter().map(|&(row, _)| row).max().unwrap_or(-1);
+        let max_col = cells.iter().map(|&(_, col)| col).max().unwrap_or(-1);
+
+        // the coordinates are within COORDINATE_RANGE, so their differences
+        // do not overflow
+        let dimension = |len: i64| u32::try_from(len).ok();
+        let (num_rows, num_cols) = match (
+            dimension(max_row - min_row + 1),
+            dimension(max_col - min_col + 1),
+        ) {
+            (Some(num_rows), Some(num_cols)) if num_rows as u64 * num_cols as u64 <= MAX_CELLS => {
+                (num_rows, num_cols)
+            }
+            (num_rows, num_cols) => {
+                return Err(Life106Error::TooLarge {
+                    num_rows: num_rows.unwrap_or(u32::MAX),
+                    num_cols: num_cols.unwrap_or(u32::MAX),
+                })
+            }
+        };
+
+        let mut grid = Grid::new(num_rows, num_cols);
+
+        let cells: Vec<(u32, u32)> = cells
+            .into_iter()
+            .map(|(row, col)| ((row - min_row) as u32, (col - min_col) as u32))
+            .collect();
+        grid.set_cells(&cells);
+
+        Ok(grid)
+    }
+
+    /// Encode the [Grid] in the Life 1.06 format.
+    pub fn to_life106(&self) -> String {
+        let mut life106 = format!("{}\n", HEADER);
+
+        for row in 0..self.get_num_rows() {
+            for col in 0..self.get_num_cols() {
+                if self.get(row, col).is_alive() {
+                    life106.push_str(&format!("{} {}\n", col, row));
+                }
+            }
+        }
+
+        life106
+    }
+
+    /// Save the [Grid] to a file in the Life 1.06 format.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the Life 1.06 pattern file.
+    pub fn save_life106_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Life106Error> {
+        fs::write(path, self.to_life106())?;
+
+        Ok(())
+    }
+}
+
+impl ConwaySim {
+    /// Create a new simulation from a Life 1.06 pattern file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the Life 1.06 pattern file.
+    pub fn load_life106_file<P: AsRef<Path>>(path: P) -> Result<ConwaySim, Life106Error> {
+        let life106 = fs::read_to_string(path)?;
+        let grid = Grid::from_life106(&life106)?;
+
+        Ok(ConwaySim::new_with_grid(grid))
+    }
+}
+
+impl HashLifeSim {
+    /// Create a new simulation from a Life 1.06 pattern file, keeping the
+    /// coordinates of the live cells.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the Life 1.06 pattern file.
+    pub fn load_life106_file<P: AsRef<Path>>(path: P) -> Result<HashLifeSim, Life106Error> {
+        let life106 = fs::read_to_string(path)?;
+
+        let mut sim = HashLifeSim::new();
+        // the parsed coordinates are all within range
+        sim.set_cells(&parse(&life106)?).unwrap();
+
+        Ok(sim)
+    }
+}
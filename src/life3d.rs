@@ -0,0 +1,385 @@
+//! Three-dimensional Life, where each cell has 26 neighbors in a cube.
+//!
+//! Rules follow Carter Bays' notation `EₗEᵤFₗFᵤ`: a live cell survives with
+//! between Eₗ and Eᵤ live neighbors, and a dead cell is born with between Fₗ
+//! and Fᵤ live neighbors. Bays' `4555` and `5766` rules support gliders and
+//! oscillators much like Conway's rule in two dimensions.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::game::{Cell, Grid};
+use crate::rule::RuleError;
+
+/// Greatest number of live neighbors of a cell.
+const MAX_NEIGHBORS: u8 = 26;
+
+/// A three-dimensional rule with ranges of neighbor counts for survival and
+/// birth.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Rule3D {
+    /// Fewest live neighbors with which a live cell survives.
+    survival_min: u8,
+
+    /// Most live neighbors with which a live cell survives.
+    survival_max: u8,
+
+    /// Fewest live neighbors with which a dead cell is born.
+    birth_min: u8,
+
+    /// Most live neighbors with which a dead cell is born.
+    birth_max: u8,
+}
+
+impl Rule3D {
+    /// Create a new [Rule3D] from ranges of neighbor counts.
+    ///
+    /// # Arguments
+    /// * `survival_min` - Fewest live neighbors with which a live cell survives.
+    /// * `survival_max` - Most live neighbors with which a live cell survives.
+    /// * `birth_min` - Fewest live neighbors with which a dead cell is born.
+    /// * `birth_max` - Most live neighbors with which a dead cell is born.
+    ///
+    /// # Panics
+    /// Panics if any neighbor count is greater than 26.
+    pub fn new(survival_min: u8, survival_max: u8, birth_min: u8, birth_max: u8) -> Rule3D {
+        assert!(
+            survival_max <= MAX_NEIGHBORS && birth_max <= MAX_NEIGHBORS,
+            "3D neighbor counts are at most {}",
+            MAX_NEIGHBORS
+        );
+
+        Rule3D {
+            survival_min,
+            survival_max,
+            birth_min,
+            birth_max,
+        }
+    }
+
+    /// Bays' rule `4555`.
+    pub fn bays_4555() -> Rule3D {
+        Rule3D::new(4, 5, 5, 5)
+    }
+
+    /// Bays' rule `5766`.
+    pub fn bays_5766() -> Rule3D {
+        Rule3D::new(5, 7, 6, 6)
+    }
+
+    /// Determine if a dead cell with `neighbor_count` live neighbors is born.
+    pub fn is_birth(&self, neighbor_count: u8) -> bool {
+        (self.birth_min..=self.birth_max).contains(&neighbor_count)
+    }
+
+    /// Determine if a live cell with `neighbor_count` live neighbors survives.
+    pub fn is_survival(&self, neighbor_count: u8) -> bool {
+        (self.survival_min..=self.survival_max).contains(&neighbor_count)
+    }
+}
+
+impl Default for Rule3D {
+    fn default() -> Rule3D {
+        Rule3D::bays_4555()
+    }
+}
+
+impl FromStr for Rule3D {
+    type Err = RuleError;
+
+    /// Parse a rulestring of four digits in Bays' notation, e.g. `4555`, or
+    /// of four comma separated counts for counts above 9, e.g. `4,5,12,12`.
+    fn from_str(rulestring: &str) -> Result<Rule3D, RuleError> {
+        let trimmed = rulestring.trim();
+
+        let counts: Option<Vec<u8>> = if trimmed.contains(',') {
+            trimmed
+                .split(',')
+                .map(|count| count.trim().parse().ok())
+                .collect()
+        } else {
+            trimmed
+                .chars()
+                .map(|digit| digit.to_digit(10).map(|count| count as u8))
+                .collect()
+        };
+
+        match counts.as_deref() {
+            Some(&[survival_min, survival_max, birth_min, birth_max])
+                if survival_max <= MAX_NEIGHBORS && birth_max <= MAX_NEIGHBORS =>
+            {
+                Ok(Rule3D::new(
+                    survival_min,
+                    survival_max,
+                    birth_min,
+                    birth_max,
+                ))
+            }
+            _ => Err(RuleError::new(rulestring)),
+        }
+    }
+}
+
+impl fmt::Display for Rule3D {
+    /// Display the rule in Bays' notation, separating the counts with commas
+    /// if any is above 9.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let counts = [
+            self.survival_min,
+            self.survival_max,
+            self.birth_min,
+            self.birth_max,
+        ];
+
+        if counts.iter().all(|&count| count <= 9) {
+            write!(f, "{}{}{}{}", counts[0], counts[1], counts[2], counts[3])
+        } else {
+            write!(f, "{},{},{},{}", counts[0], counts[1], counts[2], counts[3])
+        }
+    }
+}
+
+/// A bounded three-dimensional grid of [Cell]s, made of layers of rows and
+/// columns.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Grid3D {
+    /// Number of layers (depth) of the [Grid3D].
+    num_layers: u32,
+
+    /// Number of rows (height) of each layer.
+    num_rows: u32,
+
+    /// Number of columns (width) of each layer.
+    num_cols: u32,
+
+    /// The cells, layer by layer, each in row-major order.
+    cells: Vec<Cell>,
+}
+
+impl Grid3D {
+    /// Create a new [Grid3D] of dead cells.
+    ///
+    /// # Arguments
+    /// * `num_layers` - The number of layers (depth) of the [Grid3D].
+    /// * `num_rows` - The number of rows (height) of each layer.
+    /// * `num_cols` - The number of columns (width) of each layer.
+    pub fn new(num_layers: u32, num_rows: u32, num_cols: u32) -> Grid3D {
+        let num_cells = num_layers as usize * num_rows as usize * num_cols as usize;
+
+        Grid3D {
+            num_layers,
+            num_rows,
+            num_cols,
+            cells: vec![Cell::Dead; num_cells],
+        }
+    }
+
+    /// Get the number of layers (depth) of the [Grid3D].
+    pub fn get_num_layers(&self) -> u32 {
+        self.num_layers
+    }
+
+    /// Get the number of rows (height) of each layer.
+    pub fn get_num_rows(&self) -> u32 {
+        self.num_rows
+    }
+
+    /// Get the number of columns (width) of each layer.
+    pub fn get_num_cols(&self) -> u32 {
+        self.num_cols
+    }
+
+    /// Convert a (layer, row, col) position to an index into the cells.
+    fn cell_to_index(&self, layer: u32, row: u32, col: u32) -> usize {
+        (layer as usize * self.num_rows as usize + row as usize) * self.num_cols as usize
+            + col as usize
+    }
+
+    /// Get the state of a specified cell.
+    pub fn get(&self, layer: u32, row: u32, col: u32) -> Cell {
+        self.cells[self.cell_to_index(layer, row, col)]
+    }
+
+    /// Set the state of a specified cell.
+    pub fn set(&mut self, layer: u32, row: u32, col: u32, state: Cell) {
+        let index = self.cell_to_index(layer, row, col);
+        self.cells[index] = state;
+    }
+
+    /// Set the specified cells as alive.
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (layer, row, col) to set as alive.
+    pub fn set_cells(&mut self, cells: &[(u32, u32, u32)]) {
+        for &(layer, row, col) in cells {
+            self.set(layer, row, col, Cell::Alive);
+        }
+    }
+
+    /// Determine if a specified cell is alive.
+    pub fn is_cell_alive(&self, layer: u32, row: u32, col: u32) -> bool {
+        self.get(layer, row, col).is_alive()
+    }
+
+    /// Get the number of live cells.
+    pub fn get_population(&self) -> u64 {
+        self.cells.iter().filter(|cell| cell.is_alive()).count() as u64
+    }
+
+    /// Get the number of live cells among the 26 neighbors of a cell.
+    /// Neighbors outside of the [Grid3D] are dead.
+    pub fn count_neighbors(&self, layer: u32, row: u32, col: u32) -> u8 {
+        let mut count = 0;
+
+        for neighbor_layer in layer.saturating_sub(1)..=(layer + 1).min(self.num_layers - 1) {
+            for neighbor_row in row.saturating_sub(1)..=(row + 1).min(self.num_rows - 1) {
+                for neighbor_col in col.saturating_sub(1)..=(col + 1).min(self.num_cols - 1) {
+                    if (neighbor_layer, neighbor_row, neighbor_col) != (layer, row, col)
+                        && self.is_cell_alive(neighbor_layer, neighbor_row, neighbor_col)
+                    {
+                        count += 1;
+                    }
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Get a copy of one layer as a [Grid].
+    ///
+    /// # Panics
+    /// Panics if the layer is out of bounds.
+    pub fn layer(&self, layer: u32) -> Grid {
+        assert!(layer < self.num_layers, "layer {} is out of bounds", layer);
+
+        let mut grid = Grid::new(self.num_rows, self.num_cols);
+
+        for row in 0..self.num_rows {
+            for col in 0..self.num_cols {
+                if self.is_cell_alive(layer, row, col) {
+                    grid.set(row, col, Cell::Alive);
+                }
+            }
+        }
+
+        grid
+    }
+}
+
+/// Simulation of a [Rule3D] on a [Grid3D].
+#[derive(Clone, Debug)]
+pub struct Sim3D {
+    /// The cells.
+    grid: Grid3D,
+
+    /// Buffer the next generation is computed into.
+    next: Grid3D,
+
+    /// The [Rule3D] applied to each cell every generation.
+    rule: Rule3D,
+
+    /// The simulation's current generation.
+    generation: u32,
+}
+
+impl Sim3D {
+    /// Create a new simulation of dead cells.
+    ///
+    /// # Arguments
+    /// * `num_layers` - The number of layers (depth) of the grid.
+    /// * `num_rows` - The number of rows (height) of each layer.
+    /// * `num_cols` - The number of columns (width) of each layer.
+    /// * `rule` - The [Rule3D] applied to each cell.
+    pub fn new(num_layers: u32, num_rows: u32, num_cols: u32, rule: Rule3D) -> Sim3D {
+        Sim3D::new_with_grid(Grid3D::new(num_layers, num_rows, num_cols), rule)
+    }
+
+    /// Create a new simulation from an existing [Grid3D].
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cells.
+    /// * `rule` - The [Rule3D] applied to each cell.
+    pub fn new_with_grid(grid: Grid3D, rule: Rule3D) -> Sim3D {
+        Sim3D {
+            next: grid.clone(),
+            grid,
+            rule,
+            generation: 0,
+        }
+    }
+
+    /// Get the simulation's [Grid3D].
+    pub fn get_grid(&self) -> &Grid3D {
+        &self.grid
+    }
+
+    /// Get the simulation's [Rule3D].
+    pub fn get_rule(&self) -> Rule3D {
+        self.rule
+    }
+
+    /// Set the [Rule3D] applied to each cell every generation.
+    pub fn set_rule(&mut self, rule: Rule3D) {
+        self.rule = rule;
+    }
+
+    /// Get the simulation's current generation.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Get the number of live cells.
+    pub fn get_population(&self) -> u64 {
+        self.grid.get_population()
+    }
+
+    /// Determine if any cell is alive.
+    pub fn is_any_cell_alive(&self) -> bool {
+        self.grid.cells.iter().any(|cell| cell.is_alive())
+    }
+
+    /// Set the specified cells as alive.
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (layer, row, col) to set as alive.
+    pub fn set_cells(&mut self, cells: &[(u32, u32, u32)]) {
+        self.grid.set_cells(cells);
+    }
+
+    /// Set the state of a specified cell.
+    pub fn set_cell(&mut self, layer: u32, row: u32, col: u32, state: Cell) {
+        self.grid.set(layer, row, col, state);
+    }
+
+    /// Advance the simulation by one generation.
+    pub fn step(&mut self) {
+        self.generation += 1;
+
+        for layer in 0..self.grid.num_layers {
+            for row in 0..self.grid.num_rows {
+                for col in 0..self.grid.num_cols {
+                    let neighbor_count = self.grid.count_neighbors(layer, row, col);
+
+                    let alive = if self.grid.is_cell_alive(layer, row, col) {
+                        self.rule.is_survival(neighbor_count)
+                    } else {
+                        self.rule.is_birth(neighbor_count)
+                    };
+
+                    let state = if alive { Cell::Alive } else { Cell::Dead };
+                    self.next.set(layer, row, col, state);
+                }
+            }
+        }
+
+        std::mem::swap(&mut self.grid, &mut self.next);
+    }
+
+    /// Advance the simulation by `n` generations.
+    pub fn step_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+}
@@ -0,0 +1,622 @@
+//! Larger than Life rules, where cells count the live cells within a radius.
+//!
+//! A Larger than Life rule generalizes Life-like rules to neighborhoods of
+//! any radius: a dead cell is born, and a live cell survives, if the number
+//! of live cells in its neighborhood falls within one of the rule's birth or
+//! survival ranges. As in Generations rules, a rule with more than 2 states
+//! decays cells which do not survive through the extra states, and only live
+//! cells count.
+//!
+//! Rulestrings use Golly's notation, `R5,C0,M1,S34..58,B34..45,NM`, giving
+//! the radius, number of states, whether the middle cell counts itself,
+//! survival and birth ranges, and the Moore (`NM`), von Neumann (`NN`), or
+//! circular (`NC`) neighborhood.
+//!
+//! Neighbor counts are summed from prefix sums along each row of the grid,
+//! padded by the radius according to its topology, so a step costs one
+//! subtraction per row of the neighborhood rather than one read per
+//! neighbor.
+//!
+//! ```
+//! use gameoflife_rs::ltl::{LtlRule, LtlSim};
+//! use gameoflife_rs::Grid;
+//!
+//! let rule: LtlRule = "R5,C0,M1,S33..57,B34..45,NM".parse()?;
+//! let mut sim = LtlSim::from_grid(&Grid::random(64, 64, 0.5, 1), rule);
+//! sim.step_n(10);
+//!
+//! assert!(sim.is_any_cell_alive());
+//! # Ok::<(), gameoflife_rs::RuleError>(())
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::game::{Cell, Grid};
+use crate::rule::RuleError;
+
+/// State of a dead cell.
+pub const DEAD: u8 = 0;
+
+/// State of a live cell.
+pub const ALIVE: u8 = 1;
+
+/// Largest radius of a neighborhood, as in Golly.
+pub const MAX_RADIUS: u32 = 500;
+
+/// The cells within the radius of a cell which are its neighbors.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum LtlNeighborhood {
+    /// The square of cells within the radius on both axes.
+    Moore,
+
+    /// The diamond of cells whose row and column offsets sum to at most the
+    /// radius.
+    VonNeumann,
+
+    /// The disc of cells whose distance is at most the radius, rounded as in
+    /// Golly's HROT rules.
+    Circular,
+}
+
+impl LtlNeighborhood {
+    /// Get the number of columns to either side of a cell the neighborhood
+    /// spans on a row.
+    ///
+    /// # Arguments
+    /// * `radius` - Radius of the neighborhood.
+    /// * `row_offset` - Offset of the row from the cell, within the radius.
+    fn half_width(self, radius: u32, row_offset: u32) -> u32 {
+        match self {
+            LtlNeighborhood::Moore => radius,
+            LtlNeighborhood::VonNeumann => radius - row_offset,
+            LtlNeighborhood::Circular => {
+                let limit = (radius * radius + radius - row_offset * row_offset) as u64;
+                let mut width = (limit as f64).sqrt() as u64;
+
+                // correct the rounding of the square root
+                while width * width > limit {
+                    width -= 1;
+                }
+                while (width + 1) * (width + 1) <= limit {
+                    width += 1;
+                }
+
+                width as u32
+            }
+        }
+    }
+
+    /// Get the letter of the neighborhood in rulestrings.
+    fn letter(self) -> char {
+        match self {
+            LtlNeighborhood::Moore => 'M',
+            LtlNeighborhood::VonNeumann => 'N',
+            LtlNeighborhood::Circular => 'C',
+        }
+    }
+}
+
+/// A Larger than Life rule with birth and survival ranges of neighbor counts
+/// within a radius.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LtlRule {
+    /// Radius of the neighborhood.
+    radius: u32,
+
+    /// Number of cell states, including the dead and live states.
+    num_states: u8,
+
+    /// Whether a cell counts itself among its neighbors.
+    middle: bool,
+
+    /// Inclusive (min, max) ranges of counts which cause a dead cell to be
+    /// born.
+    birth: Vec<(u32, u32)>,
+
+    /// Inclusive (min, max) ranges of counts which allow a live cell to
+    /// survive.
+    survival: Vec<(u32, u32)>,
+
+    /// The cells within the radius which are neighbors.
+    neighborhood: LtlNeighborhood,
+}
+
+impl LtlRule {
+    /// Create a new two-state [LtlRule] of a Moore neighborhood, where cells
+    /// do not count themselves.
+    ///
+    /// # Arguments
+    /// * `radius` - Radius of the neighborhood.
+    /// * `birth` - Inclusive (min, max) ranges of counts which cause a dead
+    ///   cell to be born.
+    /// * `survival` - Inclusive (min, max) ranges of counts which allow a
+    ///   live cell to survive.
+    ///
+    /// # Panics
+    /// Panics if the radius is 0 or greater than [MAX_RADIUS].
+    pub fn new(radius: u32, birth: &[(u32, u32)], survival: &[(u32, u32)]) -> LtlRule {
+        assert!(
+            (1..=MAX_RADIUS).contains(&radius),
+            "the radius must be from 1 to {}",
+            MAX_RADIUS
+        );
+
+        LtlRule {
+            radius,
+            num_states: 2,
+            middle: false,
+            birth: birth.to_vec(),
+            survival: survival.to_vec(),
+            neighborhood: LtlNeighborhood::Moore,
+        }
+    }
+
+    /// Set the number of cell states, returning the updated [LtlRule].
+    ///
+    /// # Arguments
+    /// * `num_states` - Number of cell states, at least 2.
+    ///
+    /// # Panics
+    /// Panics if there are fewer than 2 states.
+    pub fn with_num_states(mut self, num_states: u8) -> LtlRule {
+        assert!(
+            num_states >= 2,
+            "Larger than Life rules have at least 2 states"
+        );
+        self.num_states = num_states;
+        self
+    }
+
+    /// Set whether cells count themselves, returning the updated [LtlRule].
+    ///
+    /// # Arguments
+    /// * `middle` - Whether a live cell counts towards its own neighbors.
+    pub fn with_middle(mut self, middle: bool) -> LtlRule {
+        self.middle = middle;
+        self
+    }
+
+    /// Set the [LtlNeighborhood], returning the updated [LtlRule].
+    ///
+    /// # Arguments
+    /// * `neighborhood` - The cells within the radius which are neighbors.
+    pub fn with_neighborhood(mut self, neighborhood: LtlNeighborhood) -> LtlRule {
+        self.neighborhood = neighborhood;
+        self
+    }
+
+    /// Bosco's Rule, `R5,C0,M1,S33..57,B34..45,NM`, home of the bosco
+    /// spaceship.
+    pub fn bosco() -> LtlRule {
+        LtlRule::new(5, &[(34, 45)], &[(33, 57)]).with_middle(true)
+    }
+
+    /// Get the radius of the neighborhood.
+    pub fn get_radius(&self) -> u32 {
+        self.radius
+    }
+
+    /// Get the number of cell states, including the dead and live states.
+    pub fn get_num_states(&self) -> u8 {
+        self.num_states
+    }
+
+    /// Determine if a live cell counts towards its own neighbors.
+    pub fn is_middle_counted(&self) -> bool {
+        self.middle
+    }
+
+    /// Get the [LtlNeighborhood] of the rule.
+    pub fn get_neighborhood(&self) -> LtlNeighborhood {
+        self.neighborhood
+    }
+
+    /// Get the next state of a cell.
+    ///
+    /// # Arguments
+    /// * `state` - The current state of the cell.
+    /// * `count` - The number of live cells in its neighborhood, including
+    ///   itself if the middle cell counts.
+    pub fn next_state(&self, state: u8, count: u32) -> u8 {
+        let within = |ranges: &[(u32, u32)]| {
+            ranges
+                .iter()
+                .any(|&(min, max)| (min..=max).contains(&count))
+        };
+
+        match state {
+            DEAD if within(&self.birth) => ALIVE,
+            DEAD => DEAD,
+            ALIVE if within(&self.survival) => ALIVE,
+            _ if state + 1 < self.num_states => state + 1,
+            _ => DEAD,
+        }
+    }
+}
+
+/// Parse a count or `min..max` range of counts.
+fn parse_range(range: &str) -> Option<(u32, u32)> {
+    match range.split_once("..") {
+        Some((min, max)) => {
+            let (min, max) = (min.parse().ok()?, max.parse().ok()?);
+            if min <= max {
+                Some((min, max))
+            } else {
+                None
+            }
+        }
+        None => range.parse().ok().map(|count| (count, count)),
+    }
+}
+
+impl FromStr for LtlRule {
+    type Err = RuleError;
+
+    /// Parse a rulestring in Golly's `R5,C0,M1,S34..58,B34..45,NM` notation
+    /// (case insensitive, where `S` and `B` may list several ranges), or in
+    /// Evans' `r,bmin,bmax,smin,smax` notation, where cells count
+    /// themselves.
+    fn from_str(rulestring: &str) -> Result<LtlRule, RuleError> {
+        let error = || RuleError::new(rulestring);
+        let parts: Vec<String> = rulestring
+            .trim()
+            .split(',')
+            .map(|part| part.trim().to_ascii_uppercase())
+            .collect();
+
+        // Evans' notation is five bare numbers
+        if parts.len() == 5 && parts.iter().all(|part| part.parse::<u32>().is_ok()) {
+            let numbers: Vec<u32> = parts.iter().map(|part| part.parse().unwrap()).collect();
+            let rulestring = format!(
+                "R{},C0,M1,S{}..{},B{}..{},NM",
+                numbers[0], numbers[3], numbers[4], numbers[1], numbers[2]
+            );
+            return rulestring.parse().map_err(|_| error());
+        }
+
+        let (mut radius, mut num_states, mut middle) = (None, 2, false);
+        let (mut birth, mut survival) = (None, None);
+        let mut neighborhood = LtlNeighborhood::Moore;
+
+        // the ranges bare numbers are added to, after an `S` or `B`
+        let mut ranges: Option<&mut Vec<(u32, u32)>> = None;
+
+        for part in &parts {
+            let mut chars = part.chars();
+            let prefix = chars.next().ok_or_else(error)?;
+            let value = chars.as_str();
+
+            match prefix {
+                'R' => radius = Some(value.parse::<u32>().map_err(|_| error())?),
+                'C' => {
+                    // C0 and C1 both denote two states
+                    num_states = value.parse::<u8>().map_err(|_| error())?.max(2);
+                }
+                'M' => {
+                    middle = match value {
+                        "0" => false,
+                        "1" => true,
+                        _ => return Err(error()),
+                    }
+                }
+                'N' => {
+                    neighborhood = match value {
+                        "M" => LtlNeighborhood::Moore,
+                        "N" => LtlNeighborhood::VonNeumann,
+                        "C" => LtlNeighborhood::Circular,
+                        _ => return Err(error()),
+                    }
+                }
+                'S' | 'B' => {
+                    let list = if prefix == 'S' {
+                        &mut survival
+                    } else {
+                        &mut birth
+                    };
+                    if list.is_some() {
+                        return Err(error());
+                    }
+
+                    let list = list.insert(Vec::new());
+                    if !value.is_empty() {
+                        list.push(parse_range(value).ok_or_else(error)?);
+                    }
+                    ranges = Some(list);
+                    continue;
+                }
+                _ => match ranges.as_mut() {
+                    Some(list) => {
+                        list.push(parse_range(part).ok_or_else(error)?);
+                        continue;
+                    }
+                    None => return Err(error()),
+                },
+            }
+
+            ranges = None;
+        }
+
+        match (radius, birth, survival) {
+            (Some(radius), Some(birth), Some(survival)) if (1..=MAX_RADIUS).contains(&radius) => {
+                Ok(LtlRule {
+                    radius,
+                    num_states,
+                    middle,
+                    birth,
+                    survival,
+                    neighborhood,
+                })
+            }
+            _ => Err(error()),
+        }
+    }
+}
+
+impl fmt::Display for LtlRule {
+    /// Display the rule in Golly's notation.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let num_states = if self.num_states == 2 {
+            0
+        } else {
+            self.num_states
+        };
+        write!(f, "R{},C{},M{}", self.radius, num_states, self.middle as u8)?;
+
+        for (prefix, ranges) in [('S', &self.survival), ('B', &self.birth)] {
+            write!(f, ",{}", prefix)?;
+
+            for (index, &(min, max)) in ranges.iter().enumerate() {
+                if index > 0 {
+                    write!(f, ",")?;
+                }
+                if min == max {
+                    write!(f, "{}", min)?;
+                } else {
+                    write!(f, "{}..{}", min, max)?;
+                }
+            }
+        }
+
+        write!(f, ",N{}", self.neighborhood.letter())
+    }
+}
+
+/// Simulation of an [LtlRule] on a [Grid] of cell states.
+///
+/// Cells beyond the edges of the grid are found by its
+/// [Topology](crate::topology::Topology), dead if it is bounded.
+#[derive(Clone, Debug)]
+pub struct LtlSim {
+    /// The cell states.
+    grid: Grid<u8>,
+
+    /// Buffer the next generation is computed into.
+    next: Grid<u8>,
+
+    /// Prefix sums of the live cells along each row of the grid padded by
+    /// the radius, reused between generations.
+    sums: Vec<u32>,
+
+    /// The [LtlRule] applied to each cell every generation.
+    rule: LtlRule,
+
+    /// The simulation's current generation.
+    generation: u32,
+}
+
+impl LtlSim {
+    /// Create a new simulation of dead cells.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the grid.
+    /// * `num_cols` - The number of columns (width) of the grid.
+    /// * `rule` - The [LtlRule] applied to each cell.
+    pub fn new(num_rows: u32, num_cols: u32, rule: LtlRule) -> LtlSim {
+        LtlSim::new_with_grid(Grid::filled(num_rows, num_cols, DEAD), rule)
+    }
+
+    /// Create a new simulation from existing cell states.
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cell states.
+    /// * `rule` - The [LtlRule] applied to each cell.
+    pub fn new_with_grid(grid: Grid<u8>, rule: LtlRule) -> LtlSim {
+        LtlSim {
+            next: grid.clone(),
+            grid,
+            sums: Vec::new(),
+            rule,
+            generation: 0,
+        }
+    }
+
+    /// Create a new simulation with the live cells and topology of a [Grid].
+    ///
+    /// # Arguments
+    /// * `grid` - The initial live cells.
+    /// * `rule` - The [LtlRule] applied to each cell.
+    pub fn from_grid(grid: &Grid, rule: LtlRule) -> LtlSim {
+        let mut states = Grid::filled(grid.get_num_rows(), grid.get_num_cols(), DEAD);
+        states.set_topology(grid.get_topology());
+
+        for row in 0..grid.get_num_rows() {
+            for col in 0..grid.get_num_cols() {
+                if grid.get(row, col).is_alive() {
+                    states.set(row, col, ALIVE);
+                }
+            }
+        }
+
+        LtlSim::new_with_grid(states, rule)
+    }
+
+    /// Get the simulation's cell states.
+    pub fn get_grid(&self) -> &Grid<u8> {
+        &self.grid
+    }
+
+    /// Get the simulation's [LtlRule].
+    pub fn get_rule(&self) -> &LtlRule {
+        &self.rule
+    }
+
+    /// Set the [LtlRule] applied to each cell every generation. Cells in
+    /// states the new rule does not have die.
+    pub fn set_rule(&mut self, rule: LtlRule) {
+        let num_states = rule.get_num_states();
+        self.rule = rule;
+
+        for row in 0..self.grid.get_num_rows() {
+            for col in 0..self.grid.get_num_cols() {
+                if self.grid.get(row, col) >= num_states {
+                    self.grid.set(row, col, DEAD);
+                }
+            }
+        }
+    }
+
+    /// Get the simulation's current generation.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Get the number of live cells, excluding decaying cells.
+    pub fn get_population(&self) -> u64 {
+        self.grid
+            .get_cells()
+            .iter()
+            .filter(|&&state| state == ALIVE)
+            .count() as u64
+    }
+
+    /// Determine if any cell is alive or decaying.
+    pub fn is_any_cell_alive(&self) -> bool {
+        self.grid.get_cells().iter().any(|&state| state != DEAD)
+    }
+
+    /// Set the specified cells as alive.
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (row, col) to set as alive.
+    pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
+        for &(row, col) in cells {
+            self.grid.set(row, col, ALIVE);
+        }
+    }
+
+    /// Set the state of a specified cell.
+    pub fn set_cell(&mut self, row: u32, col: u32, state: u8) {
+        self.grid
+            .set(row, col, state.min(self.rule.get_num_states() - 1));
+    }
+
+    /// Sum the live cells along each row of the grid padded by the radius
+    /// into `sums`, where the sum of padded columns `start..end` of padded
+    /// row `row` is `sums[row * stride + end] - sums[row * stride + start]`.
+    fn sum_rows(&mut self) {
+        let radius = self.rule.get_radius() as i64;
+        let grid = &self.grid;
+        let (num_rows, num_cols) = (grid.get_num_rows(), grid.get_num_cols());
+        let topology = grid.get_topology();
+
+        let padded_rows = num_rows as usize + 2 * radius as usize;
+        let stride = num_cols as usize + 2 * radius as usize + 1;
+
+        self.sums.clear();
+        self.sums.resize(padded_rows * stride, 0);
+
+        for padded_row in 0..padded_rows {
+            let row = padded_row as i64 - radius;
+            let sums = &mut self.sums[padded_row * stride..(padded_row + 1) * stride];
+
+            for padded_col in 0..stride - 1 {
+                let col = padded_col as i64 - radius;
+                let alive = match topology.resolve(row, col, num_rows, num_cols) {
+                    Some((row, col)) => grid.get(row, col) == ALIVE,
+                    None => false,
+                };
+
+                sums[padded_col + 1] = sums[padded_col] + alive as u32;
+            }
+        }
+    }
+
+    /// Advance the simulation by one generation.
+    pub fn step(&mut self) {
+        self.generation += 1;
+        self.sum_rows();
+
+        let rule = &self.rule;
+        let radius = rule.get_radius();
+        let (num_rows, num_cols) = (self.grid.get_num_rows(), self.grid.get_num_cols());
+        let stride = num_cols as usize + 2 * radius as usize + 1;
+
+        // half widths of the neighborhood on each row, from the top
+        let widths: Vec<u32> = (0..=2 * radius)
+            .map(|row| {
+                rule.get_neighborhood()
+                    .half_width(radius, row.abs_diff(radius))
+            })
+            .collect();
+
+        self.next.clone_from(&self.grid);
+        self.next.clear_dirty();
+
+        for row in 0..num_rows {
+            for col in 0..num_cols {
+                let state = self.grid.get(row, col);
+
+                // decaying cells ignore their neighbors
+                let count = if state <= ALIVE {
+                    let center = (col + radius) as usize;
+                    let mut count: u32 = widths
+                        .iter()
+                        .enumerate()
+                        .map(|(offset, &width)| {
+                            let sums = &self.sums[(row as usize + offset) * stride..];
+                            sums[center + width as usize + 1] - sums[center - width as usize]
+                        })
+                        .sum();
+
+                    if !rule.is_middle_counted() && state == ALIVE {
+                        count -= 1;
+                    }
+                    count
+                } else {
+                    0
+                };
+
+                let next = rule.next_state(state, count);
+                if next != state {
+                    self.next.set(row, col, next);
+                }
+            }
+        }
+
+        std::mem::swap(&mut self.grid, &mut self.next);
+    }
+
+    /// Advance the simulation by `n` generations.
+    pub fn step_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+}
+
+impl fmt::Display for LtlSim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut cells = Grid::new(self.grid.get_num_rows(), self.grid.get_num_cols());
+
+        for row in 0..cells.get_num_rows() {
+            for col in 0..cells.get_num_cols() {
+                if self.grid.get(row, col) == ALIVE {
+                    cells.set(row, col, Cell::Alive);
+                }
+            }
+        }
+
+        cells.fmt(f)
+    }
+}
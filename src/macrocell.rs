@@ -0,0 +1,342 @@
+//! Support for Golly's macrocell pattern format.
+//!
+//! A macrocell file stores the quadtree of a [HashLifeSim] directly, so
+//! patterns far too large or too sparse for RLE, such as the output of
+//! billions of generations, stay small. It starts with a `[M2]` header line,
+//! optionally followed by `#R rule` and `#G generation` lines, then one node
+//! per line:
+//!
+//! * A leaf of 8x8 cells is written as rows of `.` (dead) and `*` (alive),
+//!   each ended by `$`, omitting trailing dead cells and rows.
+//! * A larger node is written as `level nw ne sw se`, where the level is the
+//!   log2 of its width and each quadrant refers to an earlier line by its
+//!   1-based number among the node lines, or is `0` if the quadrant is empty.
+//!   Levels above 62, larger than a [HashLifeSim] can hold, are rejected.
+//!
+//! The last node is the root, centered on the origin.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::game::ConwaySim;
+use crate::hashlife::{HashLifeSim, NodeId, MAX_LEVEL};
+use crate::rule::Rule;
+
+/// The header line of a macrocell file.
+const HEADER: &str = "[M2] (gameoflife-rs)";
+
+/// Level of the 8x8 leaf nodes written as rows of cells.
+const LEAF_LEVEL: u8 = 3;
+
+/// Error raised while loading a macrocell pattern.
+#[derive(Debug)]
+pub enum MacrocellError {
+    /// The `[M2]` header line is missing.
+    MissingHeader,
+
+    /// The `#R` line holds an invalid rulestring, or one HashLife does not
+    /// support.
+    InvalidRule { line: usize },
+
+    /// A line is neither a leaf nor a node.
+    InvalidLine { line: usize },
+
+    /// A node refers to a node which is not defined before it, or is not one
+    /// level smaller.
+    InvalidReference { line: usize },
+
+    /// The bounding box of the live cells is too large for a [Grid](crate::Grid) to hold,
+    /// with each dimension capped at `u32::MAX`.
+    TooLarge { num_rows: u32, num_cols: u32 },
+
+    /// The pattern's generation is too large for a [ConwaySim] to count.
+    Overflow { generation: u64 },
+
+    /// The pattern file could not be read or written.
+    Io(io::Error),
+}
+
+impl fmt::Display for MacrocellError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MacrocellError::MissingHeader => write!(f, "missing '[M2]' header line"),
+            MacrocellError::InvalidRule { line } => {
+                write!(f, "invalid or unsupported rule on line {}", line)
+            }
+            MacrocellError::InvalidLine { line } => {
+                write!(f, "invalid macrocell node on line {}", line)
+            }
+            MacrocellError::InvalidReference { line } => {
+                write!(f, "invalid macrocell node reference on line {}", line)
+            }
+            MacrocellError::TooLarge { num_rows, num_cols } => write!(
+                f,
+                "macrocell pattern of {}x{} cells is too large",
+                num_rows, num_cols
+            ),
+            MacrocellError::Overflow { generation } => {
+                write!(f, "macrocell generation {} is too large", generation)
+            }
+            MacrocellError::Io(err) => write!(f, "unable to access macrocell file: {}", err),
+        }
+    }
+}
+
+impl error::Error for MacrocellError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            MacrocellError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for MacrocellError {
+    fn from(err: io::Error) -> MacrocellError {
+        MacrocellError::Io(err)
+    }
+}
+
+/// Parse a leaf line of `.`, `*`, and `$` into an 8x8 node.
+fn parse_leaf(sim: &mut HashLifeSim, line: &str) -> Option<NodeId> {
+    let mut node = sim.empty_node(LEAF_LEVEL);
+    let (mut row, mut col) = (0u64, 0u64);
+
+    for c in line.chars() {
+        match c {
+            '.' => col += 1,
+            '*' if row < 8 && col < 8 => {
+                node = sim.set_node_cell(node, row, col);
+                col += 1;
+            }
+            '$' => {
+                row += 1;
+                col = 0;
+            }
+            _ => return None,
+        }
+    }
+
+    Some(node)
+}
+
+/// Encode an 8x8 node as a leaf line of `.`, `*`, and `$`.
+fn encode_leaf(sim: &HashLifeSim, id: NodeId) -> String {
+    let mut leaf = String::new();
+
+    for row in 0..8 {
+        let mut line: String = (0..8)
+            .map(|col| {
+                if sim.is_node_cell_alive(id, row, col) {
+                    '*'
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        line.truncate(line.trim_end_matches('.').len());
+
+        leaf.push_str(&line);
+        leaf.push('$');
+    }
+
+    leaf.truncate(leaf.trim_end_matches('$').len() + 1);
+
+    leaf
+}
+
+impl HashLifeSim {
+    /// Create a new simulation from a pattern in the macrocell format.
+    ///
+    /// The simulation uses the [Rule] and generation declared in the file,
+    /// or Conway's rule and generation 0 if none are declared.
+    ///
+    /// # Arguments
+    /// * `macrocell` - The macrocell encoded pattern.
+    pub fn from_macrocell(macrocell: &str) -> Result<HashLifeSim, MacrocellError> {
+        let mut lines = macrocell.lines().enumerate();
+
+        match lines.next() {
+            Some((_, line)) if line.trim_start().starts_with("[M2]") => {}
+            _ => return Err(MacrocellError::MissingHeader),
+        }
+
+        let mut sim = HashLifeSim::new();
+        let mut generation = 0;
+        let mut nodes: Vec<NodeId> = Vec::new();
+
+        for (number, line) in lines {
+            let line = line.trim();
+            let number = number + 1;
+
+            if let Some(rulestring) = line.strip_prefix("#R") {
+                match rulestring.trim().parse::<Rule>() {
+                    Ok(rule) if !rule.is_birth(0) => sim.set_rule(rule),
+                    _ => return Err(MacrocellError::InvalidRule { line: number }),
+                }
+            } else if let Some(value) = line.strip_prefix("#G") {
+                generation = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| MacrocellError::InvalidLine { line: number })?;
+            } else if line.is_empty() || line.starts_with('#') {
+                continue;
+            } else if line.starts_with(|c: char| c.is_ascii_digit()) {
+                let fields: Vec<usize> = line
+                    .split_whitespace()
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| MacrocellError::InvalidLine { line: number })?;
+
+                let (level, references) = match fields.split_first() {
+                    Some((&level, references))
+                        if references.len() == 4
+                            && level > LEAF_LEVEL as usize
+                            && level <= MAX_LEVEL as usize =>
+                    {
+                        (level as u8, references)
+                    }
+                    _ => return Err(MacrocellError::InvalidLine { line: number }),
+                };
+
+                let mut quadrants = [0; 4];
+                for (quadrant, &reference) in quadrants.iter_mut().zip(references) {
+                    *quadrant = match reference {
+                        0 => sim.empty_node(level - 1),
+                        _ => match nodes.get(reference - 1) {
+                            Some(&id) if sim.get_node_level(id) == level - 1 => id,
+                            _ => return Err(MacrocellError::InvalidReference { line: number }),
+                        },
+                    };
+                }
+
+                nodes.push(sim.join_nodes(quadrants));
+            } else {
+                let leaf = parse_leaf(&mut sim, line)
+                    .ok_or(MacrocellError::InvalidLine { line: number })?;
+                nodes.push(leaf);
+            }
+        }
+
+        let root = nodes.last().copied().unwrap_or_else(|| sim.get_root());
+        sim.set_root(root, generation);
+
+        Ok(sim)
+    }
+
+    /// Encode the simulation in the macrocell format, including its [Rule]
+    /// and generation.
+    pub fn to_macrocell(&self) -> String {
+        let mut macrocell = format!(
+            "{}\n#R {}\n#G {}\n",
+            HEADER,
+            self.get_rule(),
+            self.get_generation()
+        );
+
+        let root = self.get_root();
+        if self.get_node_population(root) == 0 {
+            return macrocell;
+        }
+
+        // number each distinct node after its quadrants, so every reference
+        // is to an earlier line
+        let mut numbers: HashMap<NodeId, usize> = HashMap::new();
+        let mut pending: Vec<(NodeId, bool)> = vec![(root, false)];
+
+        while let Some((id, visited)) = pending.pop() {
+            if numbers.contains_key(&id) {
+                continue;
+            }
+
+            let level = self.get_node_level(id);
+            if level == LEAF_LEVEL {
+                macrocell.push_str(&encode_leaf(self, id));
+                macrocell.push('\n');
+            } else if visited {
+                let references: Vec<String> = self
+                    .get_node_quadrants(id)
+                    .iter()
+                    .map(|quadrant| numbers.get(quadrant).copied().unwrap_or(0).to_string())
+                    .collect();
+                macrocell.push_str(&format!("{} {}\n", level, references.join(" ")));
+            } else {
+                pending.push((id, true));
+                for &quadrant in self.get_node_quadrants(id).iter().rev() {
+                    if self.get_node_population(quadrant) > 0 {
+                        pending.push((quadrant, false));
+                    }
+                }
+                continue;
+            }
+
+            numbers.insert(id, numbers.len() + 1);
+        }
+
+        macrocell
+    }
+
+    /// Create a new simulation from a macrocell pattern file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the macrocell pattern file.
+    pub fn load_macrocell_file<P: AsRef<Path>>(path: P) -> Result<HashLifeSim, MacrocellError> {
+        let macrocell = fs::read_to_string(path)?;
+
+        HashLifeSim::from_macrocell(&macrocell)
+    }
+
+    /// Save the simulation to a file in the macrocell format.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the macrocell pattern file.
+    pub fn save_macrocell_file<P: AsRef<Path>>(&self, path: P) -> Result<(), MacrocellError> {
+        fs::write(path, self.to_macrocell())?;
+
+        Ok(())
+    }
+}
+
+impl ConwaySim {
+    /// Create a new simulation from a pattern in the macrocell format.
+    ///
+    /// The grid is sized to the bounding box of the live cells, with the
+    /// top left live cell mapped to the (0, 0) cell, so the pattern must be
+    /// small enough to be stored cell by cell. The simulation starts at the
+    /// generation declared in the file.
+    ///
+    /// Returns a [MacrocellError::TooLarge] if the bounding box is too large
+    /// for a [Grid](crate::Grid), and a [MacrocellError::Overflow] if the generation is
+    /// beyond a `u32`.
+    ///
+    /// # Arguments
+    /// * `macrocell` - The macrocell encoded pattern.
+    pub fn from_macrocell(macrocell: &str) -> Result<ConwaySim, MacrocellError> {
+        let hashlife = HashLifeSim::from_macrocell(macrocell)?;
+        let grid = hashlife
+            .to_bounding_grid()
+            .map_err(|(num_rows, num_cols)| MacrocellError::TooLarge { num_rows, num_cols })?;
+
+        let generation = hashlife.get_generation();
+        let generation =
+            u32::try_from(generation).map_err(|_| MacrocellError::Overflow { generation })?;
+
+        Ok(ConwaySim::restore(grid, generation, hashlife.get_rule()))
+    }
+
+    /// Create a new simulation from a macrocell pattern file, as with
+    /// [ConwaySim::from_macrocell].
+    ///
+    /// # Arguments
+    /// * `path` - Path to the macrocell pattern file.
+    pub fn load_macrocell_file<P: AsRef<Path>>(path: P) -> Result<ConwaySim, MacrocellError> {
+        let macrocell = fs::read_to_string(path)?;
+
+        ConwaySim::from_macrocell(&macrocell)
+    }
+}
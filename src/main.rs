@@ -1,25 +1,642 @@
-mod game;
+#[cfg(feature = "serve")]
+mod api;
+mod cli;
+#[cfg(any(feature = "tui", feature = "gui"))]
+mod clock;
+#[cfg(feature = "config")]
+mod config;
+#[cfg(feature = "fetch")]
+mod fetch;
+#[cfg(feature = "gui")]
+mod gui;
+#[cfg(any(feature = "tui", feature = "gui"))]
+mod keymap;
+#[cfg(feature = "serve")]
+mod multiplayer;
+mod repl;
+#[cfg(any(feature = "tui", feature = "gui"))]
+mod selection;
+#[cfg(feature = "serve")]
+mod serve;
+mod session;
+mod simulate;
+mod sweep;
+#[cfg(any(feature = "tui", feature = "gui"))]
+mod timeline;
+#[cfg(feature = "tui")]
+mod tui;
 
-fn main() {
-    let mut sim = game::ConwaySim::new(5, 5);
+use std::process;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "config")]
+use clap::ArgMatches;
+use clap::{CommandFactory, FromArgMatches};
+use gameoflife_rs::render::ansi::{AnsiPalette, AnsiRenderer};
+use gameoflife_rs::render::{braille, Rgb, Theme};
+use gameoflife_rs::{
+    lenia, patterns, Anchor, AntRule, ConwaySim, Grid, HexGrid, HexRule, HexSim, LangtonsAnt,
+    LeniaRule, LeniaSim, SoupSearch, StopCondition,
+};
+
+use cli::{Cli, Command, RenderMode};
+#[cfg(any(feature = "tui", feature = "gui"))]
+use clock::Clock;
+#[cfg(any(feature = "tui", feature = "gui"))]
+use keymap::Keymap;
+use session::Session;
+
+/// Exit with an error if a grid cannot be of a size.
+fn check_size(num_rows: u32, num_cols: u32) {
+    if let Err(err) = Grid::check_size(num_rows, num_cols) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+/// Pick a seed for random soups from the current time.
+fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// Settings of the renderers beyond the command-line flags.
+struct Settings {
+    /// Colors of every renderer.
+    theme: Theme,
+
+    /// Keys of `--render tui` and `--render gui`.
+    #[cfg(any(feature = "tui", feature = "gui"))]
+    keymap: Keymap,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            theme: Theme {
+                old: Some(Rgb(40, 90, 200)),
+                ..Theme::default()
+            },
+            #[cfg(any(feature = "tui", feature = "gui"))]
+            keymap: Keymap::default(),
+        }
+    }
+}
+
+/// Fill in the flags not given on the command line from the configuration
+/// file, returning the settings it holds.
+#[cfg(feature = "config")]
+fn apply_config(cli: &mut Cli, matches: &ArgMatches) -> Settings {
+    let config = config::Config::load(cli.config.as_deref()).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    });
+
+    config.apply(cli, matches);
+
+    Settings {
+        theme: config.theme(Settings::default().theme),
+        #[cfg(any(feature = "tui", feature = "gui"))]
+        keymap: config.keymap(),
+    }
+}
+
+/// Build the initial simulation from the command-line arguments.
+fn build_sim(cli: &Cli) -> ConwaySim {
+    if let Some(path) = &cli.from_checkpoint {
+        return ConwaySim::load_checkpoint(path).unwrap_or_else(|err| {
+            eprintln!("error: {}: {}", path.display(), err);
+            process::exit(1);
+        });
+    }
+
+    let mut sim = match &cli.pattern {
+        Some(path) => {
+            let sim = patterns::load(path).unwrap_or_else(|err| {
+                eprintln!("error: {}: {}", path.display(), err);
+                process::exit(1);
+            });
 
-    sim.set_cells(&[
-        (2, 1),
-        (2, 2),
-        (2, 3)
-    ]);
+            let pattern = sim.get_grid();
+            let num_rows = cli.rows.unwrap_or_else(|| pattern.get_num_rows());
+            let num_cols = cli.cols.unwrap_or_else(|| pattern.get_num_cols());
+            check_size(num_rows, num_cols);
+
+            let mut centered =
+                ConwaySim::new_with_grid(pattern.resized(num_rows, num_cols, Anchor::Center));
+            centered.set_rule(sim.get_rule());
+            centered
+        }
+        None if cli.density.is_some() => {
+            let num_rows = cli.rows.unwrap_or(5);
+            let num_cols = cli.cols.unwrap_or(5);
+            check_size(num_rows, num_cols);
+
+            let density = cli.density.unwrap_or_default();
+            if !(0.0..=1.0).contains(&density) {
+                eprintln!("error: density must be within 0.0 to 1.0");
+                process::exit(1);
+            }
+
+            if cli.symmetry.is_square_only() && num_rows != num_cols {
+                eprintln!("error: {} symmetry requires a square grid", cli.symmetry);
+                process::exit(1);
+            }
+
+            let seed = cli.seed.unwrap_or_else(random_seed);
+
+            ConwaySim::new_with_grid(Grid::random_symmetric(
+                num_rows,
+                num_cols,
+                density,
+                seed,
+                cli.symmetry,
+            ))
+        }
+        None => {
+            let num_rows = cli.rows.unwrap_or(5);
+            let num_cols = cli.cols.unwrap_or(5);
+            check_size(num_rows, num_cols);
+
+            // default to a blinker in the center of the grid
+            ConwaySim::new_with_grid(patterns::blinker().resized(
+                num_rows,
+                num_cols,
+                Anchor::Center,
+            ))
+        }
+    };
+
+    if let Some(rule) = cli.rule {
+        sim.set_rule(rule);
+    }
+
+    sim.set_topology(cli.topology);
+
+    sim
+}
+
+/// Run Langton's Ant with a single ant in the center of the grid.
+fn run_ant(cli: &Cli, rule: AntRule, theme: &Theme) {
+    if ![RenderMode::Text, RenderMode::Ansi, RenderMode::Quiet].contains(&cli.render) {
+        eprintln!("error: Langton's Ant renders only as text, ansi, or quiet");
+        process::exit(1);
+    }
+
+    let num_rows = cli.rows.unwrap_or(5);
+    let num_cols = cli.cols.unwrap_or(5);
+    check_size(num_rows, num_cols);
+
+    let mut sim = LangtonsAnt::new_centered(num_rows, num_cols, rule);
+
+    // one color per cell state, plus red for the cells the ants stand on
+    let num_states = sim.get_rule().get_num_states();
+    let mut palette = theme.get_state_palette(num_states);
+    palette.push(Rgb(220, 40, 40));
+
+    let mut ansi = AnsiRenderer::new(AnsiPalette::from(theme));
+
+    for generation in 0..cli.generations {
+        if generation > 0 && cli.delay > 0 {
+            thread::sleep(Duration::from_millis(cli.delay));
+        }
 
-    for _i in 0..105 {
         sim.step();
-        
+
+        match cli.render {
+            RenderMode::Text => {
+                println!("Generation: {}", sim.get_generation());
+                print!("{}", sim);
+                println!();
+            }
+            RenderMode::Ansi => {
+                let mut frame = sim.get_grid().clone();
+                for ant in sim.get_ants() {
+                    frame.set(ant.get_row(), ant.get_col(), num_states);
+                }
+
+                let caption = format!(
+                    "Generation: {}  Rule: {}",
+                    sim.get_generation(),
+                    sim.get_rule()
+                );
+                print!("{}", ansi.render_states(&frame, &palette, &caption));
+            }
+            _ => {}
+        }
+    }
+
+    if cli.render == RenderMode::Quiet {
         println!("Generation: {}", sim.get_generation());
         print!("{}", sim);
+    }
+}
+
+/// Run a hexagonal rule on the cells of the initial simulation.
+#[cfg_attr(not(feature = "gui"), allow(unused_variables))]
+fn run_hex(cli: &Cli, rule: HexRule, theme: Theme) {
+    let grid = HexGrid::from_grid(build_sim(cli).get_grid());
+    let mut sim = HexSim::new_with_grid(grid, rule);
+
+    #[cfg(feature = "gui")]
+    {
+        if cli.render == RenderMode::Gui {
+            gui::run_hex(sim, Clock::from_flags(cli.speed, cli.delay), theme);
+            return;
+        }
+    }
+
+    if ![RenderMode::Text, RenderMode::Quiet].contains(&cli.render) {
+        eprintln!("error: hexagonal grids render only as text, quiet, or in the gui");
+        process::exit(1);
+    }
+
+    let mut generations_run = 0;
+
+    while generations_run < cli.generations && sim.is_any_cell_alive() {
+        if generations_run > 0 && cli.delay > 0 {
+            thread::sleep(Duration::from_millis(cli.delay));
+        }
+
+        sim.step();
+        generations_run += 1;
+
+        if cli.render == RenderMode::Text {
+            println!("Generation: {}", sim.get_generation());
+            print!("{}", sim);
+            println!();
+        }
+    }
+
+    if cli.render == RenderMode::Quiet {
+        println!("Generation: {}", sim.get_generation());
+        println!("Population: {}", sim.get_population());
+    }
+}
+
+/// Number of shades of the values of Lenia cells drawn by `--render ansi`.
+const LENIA_LEVELS: u8 = 16;
+
+/// Run Lenia on the cells of the initial simulation, or on a soup of random
+/// values if a density is given.
+#[cfg_attr(not(feature = "gui"), allow(unused_variables))]
+fn run_lenia(cli: &Cli, rule: LeniaRule, theme: Theme) {
+    let grid = build_sim(cli).get_grid().clone();
+    let mut sim = match cli.density {
+        Some(density) if cli.pattern.is_none() => {
+            let seed = cli.seed.unwrap_or_else(random_seed);
+            LeniaSim::random(
+                grid.get_num_rows(),
+                grid.get_num_cols(),
+                density,
+                seed,
+                rule,
+            )
+        }
+        _ => LeniaSim::from_grid(&grid, rule),
+    };
+    sim.set_topology(cli.topology);
+
+    #[cfg(feature = "gui")]
+    {
+        if cli.render == RenderMode::Gui {
+            gui::run_lenia(sim, Clock::from_flags(cli.speed, cli.delay), theme);
+            return;
+        }
+    }
+
+    if ![RenderMode::Text, RenderMode::Ansi, RenderMode::Quiet].contains(&cli.render) {
+        eprintln!("error: Lenia renders only as text, ansi, quiet, or in the gui");
+        process::exit(1);
+    }
+
+    let palette = lenia::palette(theme.alive, theme.dead, LENIA_LEVELS);
+    let mut ansi = AnsiRenderer::new(AnsiPalette::from(&theme));
+
+    for generation in 0..cli.generations {
+        if generation > 0 && cli.delay > 0 {
+            thread::sleep(Duration::from_millis(cli.delay));
+        }
+
+        sim.step();
+
+        match cli.render {
+            RenderMode::Text => {
+                println!("Generation: {}", sim.get_generation());
+                print!("{}", sim);
+                println!();
+            }
+            RenderMode::Ansi => {
+                let caption = format!(
+                    "Generation: {}  Mass: {:.1}",
+                    sim.get_generation(),
+                    sim.get_mass()
+                );
+                print!(
+                    "{}",
+                    ansi.render_states(&sim.to_state_grid(LENIA_LEVELS), &palette, &caption)
+                );
+            }
+            _ => {}
+        }
+    }
+
+    if cli.render == RenderMode::Quiet {
+        println!("Generation: {}", sim.get_generation());
+        println!("Mass: {:.1}", sim.get_mass());
+    }
+}
+
+/// Build the initial simulation, resuming the session of the `resume`
+/// subcommand or creating the session of --session, if any.
+fn start_session(cli: &Cli) -> (ConwaySim, Option<Session>) {
+    let started = match (&cli.command, &cli.session) {
+        (Some(Command::Resume(args)), _) => {
+            Session::resume(&args.name, args.autosave).map(|(session, sim)| (sim, Some(session)))
+        }
+        (_, Some(name)) => {
+            let sim = build_sim(cli);
+            Session::create(name, cli.autosave)
+                .and_then(|mut session| session.save(&sim).map(|()| (sim, Some(session))))
+        }
+        _ => Ok((build_sim(cli), None)),
+    };
+
+    started.unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    })
+}
+
+/// Run a Lua script on a simulation, returning the simulation as the script
+/// left it.
+#[cfg(feature = "lua")]
+fn run_script(sim: ConwaySim, path: &std::path::Path) -> ConwaySim {
+    let result = gameoflife_rs::script::Script::new(sim).and_then(|script| {
+        script.run_file(path)?;
+        Ok(script.into_sim())
+    });
+
+    result.unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    })
+}
+
+/// Search random soups and print a census of the objects they leave behind.
+fn run_search(cli: &Cli, num_soups: u64) {
+    let mut search = SoupSearch::new();
+    let (num_rows, num_cols) = (cli.rows.unwrap_or(16), cli.cols.unwrap_or(16));
+    check_size(num_rows, num_cols);
+    search.set_size(num_rows, num_cols);
+
+    if cli.symmetry.is_square_only() && num_rows != num_cols {
+        eprintln!("error: {} symmetry requires square soups", cli.symmetry);
+        process::exit(1);
+    }
+    search.set_symmetry(cli.symmetry);
+
+    if let Some(density) = cli.density {
+        if !(0.0..=1.0).contains(&density) {
+            eprintln!("error: density must be within 0.0 to 1.0");
+            process::exit(1);
+        }
+
+        search.set_density(density);
+    }
+
+    if let Some(rule) = cli.rule {
+        search.set_rule(rule);
+    }
+
+    if let Some(num_threads) = cli.threads {
+        search.set_num_threads(num_threads);
+    }
+
+    let seed = cli.seed.unwrap_or_else(random_seed);
+    println!("Seed: {}", seed);
+    print!("{}", search.run(num_soups, seed));
+}
+
+fn main() {
+    let matches = Cli::command().get_matches();
+    #[allow(unused_mut)]
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+
+    #[cfg(feature = "config")]
+    let settings = apply_config(&mut cli, &matches);
+    #[cfg(not(feature = "config"))]
+    let settings = Settings::default();
+
+    // a resumed session runs as if started with the subcommand's flags
+    if let Some(Command::Resume(args)) = &cli.command {
+        let (generations, render) = (args.generations, args.render);
+        cli.generations = generations;
+        cli.render = render;
+    }
+
+    if let Some(Command::Simulate(args)) = &cli.command {
+        if simulate::run(args).is_err() {
+            process::exit(1);
+        }
+
+        return;
+    }
+
+    if let Some(Command::Sweep(args)) = &cli.command {
+        if let Err(err) = sweep::run(args) {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+
+        return;
+    }
+
+    #[cfg(feature = "serve")]
+    {
+        if let Some(Command::Serve(args)) = &cli.command {
+            if let Err(err) = serve::run(args) {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+
+            return;
+        }
+    }
+
+    #[cfg(feature = "fetch")]
+    {
+        if let Some(Command::Fetch(args)) = &cli.command {
+            if let Err(err) = fetch::run(args) {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+
+            return;
+        }
+    }
+
+    if let Some(num_soups) = cli.search {
+        run_search(&cli, num_soups);
+        return;
+    }
+
+    if let Some(rule) = cli.hex {
+        run_hex(&cli, rule, settings.theme);
+        return;
+    }
+
+    if let Some(rule) = &cli.ant {
+        run_ant(&cli, rule.clone(), &settings.theme);
+        return;
+    }
+
+    if let Some(rule) = &cli.lenia {
+        run_lenia(&cli, rule.clone(), settings.theme);
+        return;
+    }
+
+    let (mut sim, mut session) = start_session(&cli);
+
+    #[cfg(feature = "lua")]
+    {
+        if let Some(path) = &cli.script {
+            sim = run_script(sim, path);
+        }
+    }
+
+    #[cfg(feature = "gui")]
+    {
+        if cli.render == RenderMode::Gui {
+            gui::run(
+                sim,
+                session,
+                Clock::from_flags(cli.speed, cli.delay),
+                settings.keymap,
+                settings.theme,
+            );
+            return;
+        }
+    }
+
+    #[cfg(feature = "tui")]
+    {
+        if cli.render == RenderMode::Tui {
+            if let Err(err) = tui::run(
+                sim,
+                session,
+                Clock::from_flags(cli.speed, cli.delay),
+                settings.keymap,
+            ) {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+
+            return;
+        }
+    }
+
+    if cli.render == RenderMode::Repl {
+        if let Err(err) = repl::run(sim, session) {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+
+        return;
+    }
+
+    if cli.stats.is_some() {
+        sim.enable_stats();
+    }
+
+    let mut ansi = AnsiRenderer::new(AnsiPalette::from(&settings.theme));
+
+    let mut stop = vec![
+        StopCondition::MaxGenerations(cli.generations),
+        StopCondition::Extinct,
+    ];
+    if cli.until_settled {
+        stop.push(StopCondition::Settled);
+    }
+    let stop = StopCondition::Any(stop);
+
+    let mut generations_run = 0;
+
+    while !stop.is_met(&sim, generations_run) {
+        if generations_run > 0 && cli.delay > 0 {
+            thread::sleep(Duration::from_millis(cli.delay));
+        }
+
+        if cli.render == RenderMode::Diff {
+            let diff = sim.step_diff();
+            println!("Generation: {}", sim.get_generation());
+            print!("{}", diff);
+            println!();
+        } else {
+            sim.step();
+        }
+        generations_run += 1;
+
+        if let Some(session) = &mut session {
+            if let Err(err) = session.autosave(&sim) {
+                eprintln!("warning: unable to autosave: {}", err);
+            }
+        }
+
+        match cli.render {
+            RenderMode::Text => {
+                println!("Generation: {}", sim.get_generation());
+                print!("{}", sim);
+                println!("Any cell alive? {}", sim.is_any_cell_alive());
+                println!();
+            }
+            RenderMode::Ansi => {
+                let caption = format!(
+                    "Generation: {}  Population: {}",
+                    sim.get_generation(),
+                    sim.get_population()
+                );
+                print!("{}", ansi.render(sim.get_grid(), &caption));
+            }
+            RenderMode::Braille => {
+                println!("Generation: {}", sim.get_generation());
+                print!("{}", braille::render(sim.get_grid()));
+                println!();
+            }
+            _ => {}
+        }
+    }
+
+    if cli.render == RenderMode::Quiet {
+        println!("Generation: {}", sim.get_generation());
         println!("Any cell alive? {}", sim.is_any_cell_alive());
-        println!();
+        println!("Status: {}", sim.get_status());
+        if let Some(cycle) = sim.get_cycle() {
+            println!("The simulation {}.", cycle);
+        }
+    }
 
-        if !sim.is_any_cell_alive() {
-            break;
+    if let Some(session) = &mut session {
+        if let Err(err) = session.save(&sim) {
+            eprintln!("error: {}", err);
+            process::exit(1);
         }
+    }
 
+    if let Some(path) = &cli.checkpoint {
+        if let Err(err) = sim.save_checkpoint(path) {
+            eprintln!("error: {}: {}", path.display(), err);
+            process::exit(1);
+        }
+    }
+
+    if let (Some(path), Some(stats)) = (&cli.stats, sim.get_stats()) {
+        if let Err(err) = stats.save_csv_file(path) {
+            eprintln!("error: {}: {}", path.display(), err);
+            process::exit(1);
+        }
     }
 }
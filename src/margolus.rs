@@ -0,0 +1,374 @@
+//! Block cellular automata on the Margolus neighborhood.
+//!
+//! Rather than updating each cell from its neighbors, a block automaton
+//! divides the grid into 2x2 blocks and replaces each block as a whole by a
+//! [BlockRule]. The partition alternates: even generations start the blocks
+//! at (0, 0) and odd generations at (1, 1), so that information crosses the
+//! block boundaries.
+//!
+//! A rule which permutes the 16 possible blocks, such as [BlockRule::critters]
+//! or [BlockRule::billiard_ball], is reversible: [MargolusSim::step_backward]
+//! undoes a generation exactly by applying the inverse permutation to the
+//! previous partition.
+//!
+//! ```
+//! use gameoflife_rs::margolus::{BlockRule, MargolusSim};
+//! use gameoflife_rs::topology::Topology;
+//! use gameoflife_rs::Grid;
+//!
+//! let mut grid = Grid::random(32, 32, 0.2, 5);
+//! grid.set_topology(Topology::Torus);
+//!
+//! let mut sim = MargolusSim::new_with_grid(grid.clone(), BlockRule::critters());
+//! sim.step_n(100);
+//! while sim.step_backward() {}
+//!
+//! assert_eq!(sim.get_grid(), &grid);
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::game::{Cell, Grid};
+use crate::rule::RuleError;
+use crate::topology::Edge;
+
+/// Bit of the top left cell of a block.
+pub const UPPER_LEFT: u8 = 1;
+
+/// Bit of the top right cell of a block.
+pub const UPPER_RIGHT: u8 = 2;
+
+/// Bit of the bottom left cell of a block.
+pub const LOWER_LEFT: u8 = 4;
+
+/// Bit of the bottom right cell of a block.
+pub const LOWER_RIGHT: u8 = 8;
+
+/// (row offset, col offset, bit) of the cells of a block.
+const BLOCK_CELLS: [(u32, u32, u8); 4] = [
+    (0, 0, UPPER_LEFT),
+    (0, 1, UPPER_RIGHT),
+    (1, 0, LOWER_LEFT),
+    (1, 1, LOWER_RIGHT),
+];
+
+/// A block rule mapping each of the 16 possible 2x2 blocks to its next
+/// block.
+///
+/// Blocks are numbered by the bits of their live cells: [UPPER_LEFT] (1),
+/// [UPPER_RIGHT] (2), [LOWER_LEFT] (4), and [LOWER_RIGHT] (8), as in
+/// MCell's rulestrings.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BlockRule {
+    /// The next block of each block.
+    table: [u8; 16],
+}
+
+impl BlockRule {
+    /// Create a new [BlockRule] from the next block of each block.
+    ///
+    /// # Arguments
+    /// * `table` - The next block of each block, from 0 to 15.
+    ///
+    /// # Panics
+    /// Panics if any block is greater than 15.
+    pub fn new(table: [u8; 16]) -> BlockRule {
+        assert!(
+            table.iter().all(|&block| block < 16),
+            "blocks must be from 0 to 15"
+        );
+
+        BlockRule { table }
+    }
+
+    /// Critters, `MS,D15;14;13;3;11;5;6;1;7;9;10;2;12;4;8;0`: blocks of two
+    /// live cells are unchanged, others are inverted, and blocks which had
+    /// three live cells are also rotated by 180°. Reversible, with gliders.
+    pub fn critters() -> BlockRule {
+        BlockRule::new([15, 14, 13, 3, 11, 5, 6, 1, 7, 9, 10, 2, 12, 4, 8, 0])
+    }
+
+    /// Tron, `MS,D15;1;2;3;4;5;6;7;8;9;10;11;12;13;14;0`: blocks of all dead
+    /// or all live cells are inverted, others are unchanged. Reversible.
+    pub fn tron() -> BlockRule {
+        BlockRule::new([15, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 0])
+    }
+
+    /// The Billiard Ball Machine, `MS,D0;8;4;3;2;5;9;7;1;6;10;11;12;13;14;15`:
+    /// a lone cell moves to the opposite corner of its block, two diagonal
+    /// cells collide and turn to the other diagonal, and other blocks are
+    /// unchanged. Reversible, computing with colliding balls.
+    pub fn billiard_ball() -> BlockRule {
+        BlockRule::new([0, 8, 4, 3, 2, 5, 9, 7, 1, 6, 10, 11, 12, 13, 14, 15])
+    }
+
+    /// Get the next block of each block.
+    pub fn get_table(&self) -> [u8; 16] {
+        self.table
+    }
+
+    /// Get the next block of a block.
+    ///
+    /// # Arguments
+    /// * `block` - The bits of the live cells of the block, from 0 to 15.
+    pub fn apply(&self, block: u8) -> u8 {
+        self.table[block as usize & 15]
+    }
+
+    /// Determine if the rule is reversible, mapping no two blocks to the
+    /// same block.
+    pub fn is_reversible(&self) -> bool {
+        self.inverse().is_some()
+    }
+
+    /// Get the rule undoing this rule, if it is reversible.
+    pub fn inverse(&self) -> Option<BlockRule> {
+        let mut table = [16; 16];
+
+        for (block, &next) in self.table.iter().enumerate() {
+            if table[next as usize] != 16 {
+                return None;
+            }
+            table[next as usize] = block as u8;
+        }
+
+        Some(BlockRule { table })
+    }
+}
+
+impl FromStr for BlockRule {
+    type Err = RuleError;
+
+    /// Parse a rulestring in MCell's Margolus notation, e.g.
+    /// `MS,D15;14;13;3;11;5;6;1;7;9;10;2;12;4;8;0`, listing the next block of
+    /// each block.
+    fn from_str(rulestring: &str) -> Result<BlockRule, RuleError> {
+        let error = || RuleError::new(rulestring);
+        let trimmed = rulestring.trim();
+        let blocks = trimmed
+            .strip_prefix("MS,D")
+            .or_else(|| trimmed.strip_prefix("ms,d"))
+            .ok_or_else(error)?;
+
+        let mut table = [0; 16];
+        let mut count = 0;
+
+        for block in blocks.split(';') {
+            let block: u8 = block.trim().parse().map_err(|_| error())?;
+            if count == 16 || block > 15 {
+                return Err(error());
+            }
+
+            table[count] = block;
+            count += 1;
+        }
+
+        if count != 16 {
+            return Err(error());
+        }
+
+        Ok(BlockRule { table })
+    }
+}
+
+impl fmt::Display for BlockRule {
+    /// Display the rule in MCell's Margolus notation.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let blocks: Vec<String> = self.table.iter().map(|block| block.to_string()).collect();
+
+        write!(f, "MS,D{}", blocks.join(";"))
+    }
+}
+
+/// Simulation of a [BlockRule] on the alternating Margolus partition of a
+/// [Grid].
+///
+/// On a [Grid] whose rows or columns wrap around and are even in number,
+/// blocks wrap around that axis. Otherwise, blocks which do not fit within
+/// the [Grid] are left unchanged, so reversible rules stay reversible.
+#[derive(Clone, Debug)]
+pub struct MargolusSim {
+    /// The current generation of cells.
+    grid: Grid,
+
+    /// The [BlockRule] applied to each block every generation.
+    rule: BlockRule,
+
+    /// The rule undoing a generation, if the rule is reversible.
+    inverse: Option<BlockRule>,
+
+    /// The simulation's current generation.
+    generation: u32,
+}
+
+impl MargolusSim {
+    /// Create a new simulation of dead cells.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the grid.
+    /// * `num_cols` - The number of columns (width) of the grid.
+    /// * `rule` - The [BlockRule] applied to each block.
+    pub fn new(num_rows: u32, num_cols: u32, rule: BlockRule) -> MargolusSim {
+        MargolusSim::new_with_grid(Grid::new(num_rows, num_cols), rule)
+    }
+
+    /// Create a new simulation from an existing [Grid].
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cells, and their topology.
+    /// * `rule` - The [BlockRule] applied to each block.
+    pub fn new_with_grid(grid: Grid, rule: BlockRule) -> MargolusSim {
+        MargolusSim {
+            grid,
+            inverse: rule.inverse(),
+            rule,
+            generation: 0,
+        }
+    }
+
+    /// Get the simulation's [Grid].
+    pub fn get_grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Get the simulation's [Grid] for modification.
+    pub fn get_grid_mut(&mut self) -> &mut Grid {
+        &mut self.grid
+    }
+
+    /// Get the simulation's [BlockRule].
+    pub fn get_rule(&self) -> BlockRule {
+        self.rule
+    }
+
+    /// Set the [BlockRule] applied to each block every generation.
+    pub fn set_rule(&mut self, rule: BlockRule) {
+        self.inverse = rule.inverse();
+        self.rule = rule;
+    }
+
+    /// Get the simulation's current generation.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Get the number of live cells.
+    pub fn get_population(&self) -> u64 {
+        self.grid
+            .get_cells()
+            .iter()
+            .filter(|cell| cell.is_alive())
+            .count() as u64
+    }
+
+    /// Get the offset of the blocks of a generation's partition, 0 for even
+    /// generations and 1 for odd generations.
+    ///
+    /// # Arguments
+    /// * `generation` - The generation stepped from.
+    pub fn get_partition_offset(generation: u32) -> u32 {
+        generation % 2
+    }
+
+    /// Apply a [BlockRule] to every block of a partition.
+    ///
+    /// # Arguments
+    /// * `rule` - The [BlockRule] applied to each block.
+    /// * `offset` - Offset of the blocks from (0, 0) along both axes.
+    fn apply_partition(&mut self, rule: BlockRule, offset: u32) {
+        let (num_rows, num_cols) = (self.grid.get_num_rows(), self.grid.get_num_cols());
+        let (row_edge, col_edge) = self.grid.get_topology().get_edges();
+        let wrap_rows = row_edge == Edge::Wrap && num_rows % 2 == 0;
+        let wrap_cols = col_edge == Edge::Wrap && num_cols % 2 == 0;
+
+        // the last block starts on the last row or column when it wraps
+        // around, and fits within the grid otherwise
+        let row_end = if wrap_rows {
+            num_rows
+        } else {
+            num_rows.saturating_sub(1)
+        };
+        let col_end = if wrap_cols {
+            num_cols
+        } else {
+            num_cols.saturating_sub(1)
+        };
+
+        for top in (offset..row_end).step_by(2) {
+            for left in (offset..col_end).step_by(2) {
+                let cell = |(row_offset, col_offset): (u32, u32)| {
+                    (
+                        (top + row_offset) % num_rows,
+                        (left + col_offset) % num_cols,
+                    )
+                };
+
+                let block = BLOCK_CELLS
+                    .iter()
+                    .filter(|&&(row_offset, col_offset, _)| {
+                        let (row, col) = cell((row_offset, col_offset));
+                        self.grid.get(row, col).is_alive()
+                    })
+                    .fold(0, |block, &(_, _, bit)| block | bit);
+
+                let next = rule.apply(block);
+                if next == block {
+                    continue;
+                }
+
+                for &(row_offset, col_offset, bit) in BLOCK_CELLS.iter() {
+                    let (row, col) = cell((row_offset, col_offset));
+                    let state = if next & bit != 0 {
+                        Cell::Alive
+                    } else {
+                        Cell::Dead
+                    };
+
+                    if self.grid.get(row, col) != state {
+                        self.grid.set(row, col, state);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Advance the simulation by one generation.
+    pub fn step(&mut self) {
+        let offset = MargolusSim::get_partition_offset(self.generation);
+
+        self.apply_partition(self.rule, offset);
+        self.generation += 1;
+    }
+
+    /// Advance the simulation by `n` generations.
+    pub fn step_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+
+    /// Undo one generation by applying the inverse of the rule to the
+    /// previous generation's partition.
+    ///
+    /// Returns false, leaving the cells unchanged, if the rule is not
+    /// reversible or the simulation is at generation 0.
+    pub fn step_backward(&mut self) -> bool {
+        let inverse = match self.inverse {
+            Some(inverse) if self.generation > 0 => inverse,
+            _ => return false,
+        };
+
+        self.generation -= 1;
+        let offset = MargolusSim::get_partition_offset(self.generation);
+        self.apply_partition(inverse, offset);
+
+        true
+    }
+}
+
+impl fmt::Display for MargolusSim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.grid.fmt(f)
+    }
+}
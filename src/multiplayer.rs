@@ -0,0 +1,225 @@
+//! Shared worlds which several players place cells into while the
+//! simulation runs.
+//!
+//! Each player who joins is given the next color of a [ColorVariant] in
+//! turn, the cells they place take their color, and newborn cells inherit the
+//! majority color of their parents. Placements are applied between
+//! generations, so two players placing the same cell are served in order:
+//! the first places it and the second is told it is occupied. Each player may
+//! place a limited number of cells per second, refilled continuously up to a
+//! burst of one second's worth.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Instant;
+
+use gameoflife_rs::{ColorSim, ColorVariant};
+
+/// Why a cell a player tried to place was not placed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Rejection {
+    /// The cell is outside of the grid.
+    OutOfBounds,
+
+    /// The cell is already alive.
+    Occupied,
+
+    /// The player has placed too many cells too quickly.
+    RateLimited,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rejection::OutOfBounds => write!(f, "out of bounds"),
+            Rejection::Occupied => write!(f, "occupied"),
+            Rejection::RateLimited => write!(f, "rate limited"),
+        }
+    }
+}
+
+/// Outcome of a player placing cells.
+#[derive(Clone, Debug, Default)]
+pub struct Placement {
+    /// The (row, col) of each cell placed.
+    pub placed: Vec<(u32, u32)>,
+
+    /// The (row, col) of each cell not placed, and why.
+    pub rejected: Vec<((i64, i64), Rejection)>,
+}
+
+/// Changes of a generation of a [SharedWorld].
+#[derive(Clone, Debug, Default)]
+pub struct ColorDiff {
+    /// The (row, col, color) of each cell born.
+    pub births: Vec<(u32, u32, u8)>,
+
+    /// The (row, col) of each cell which died.
+    pub deaths: Vec<(u32, u32)>,
+}
+
+/// A player of a [SharedWorld].
+#[derive(Copy, Clone, Debug)]
+struct Player {
+    /// Color of the cells the player places.
+    color: u8,
+
+    /// Number of cells the player may place now.
+    allowance: f64,
+
+    /// When the allowance was last refilled.
+    refilled: Instant,
+}
+
+/// A multi-color simulation shared by players placing cells into it.
+#[derive(Clone, Debug)]
+pub struct SharedWorld {
+    /// The simulation.
+    sim: ColorSim,
+
+    /// The connected players, by id.
+    players: HashMap<u32, Player>,
+
+    /// Id of the next player to join.
+    next_player: u32,
+
+    /// Number of cells each player may place per second.
+    rate_limit: u32,
+}
+
+impl SharedWorld {
+    /// Create a new [SharedWorld] without any players.
+    ///
+    /// # Arguments
+    /// * `sim` - The simulation the players share.
+    /// * `rate_limit` - Number of cells each player may place per second.
+    pub fn new(sim: ColorSim, rate_limit: u32) -> SharedWorld {
+        SharedWorld {
+            sim,
+            players: HashMap::new(),
+            next_player: 1,
+            rate_limit,
+        }
+    }
+
+    /// Get the shared simulation.
+    pub fn get_sim(&self) -> &ColorSim {
+        &self.sim
+    }
+
+    /// Get the variant resolving the colors of newborn cells.
+    pub fn get_variant(&self) -> ColorVariant {
+        self.sim.get_variant()
+    }
+
+    /// Add a player, returning their id and color.
+    pub fn join(&mut self) -> (u32, u8) {
+        let id = self.next_player;
+        self.next_player += 1;
+
+        let num_colors = self.get_variant().get_num_colors() as u32;
+        let color = ((id - 1) % num_colors + 1) as u8;
+
+        self.players.insert(
+            id,
+            Player {
+                color,
+                allowance: self.rate_limit as f64,
+                refilled: Instant::now(),
+            },
+        );
+
+        (id, color)
+    }
+
+    /// Remove a player.
+    pub fn leave(&mut self, id: u32) {
+        self.players.remove(&id);
+    }
+
+    /// Place cells of a player's color, skipping cells which are out of
+    /// bounds or already alive and any beyond the player's rate limit.
+    ///
+    /// # Arguments
+    /// * `id` - Id of the player placing the cells.
+    /// * `cells` - The (row, col) of each cell to place.
+    pub fn place(&mut self, id: u32, cells: &[(i64, i64)]) -> Placement {
+        let mut placement = Placement::default();
+
+        let rate_limit = self.rate_limit as f64;
+        let player = match self.players.get_mut(&id) {
+            Some(player) => player,
+            None => return placement,
+        };
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(player.refilled).as_secs_f64();
+        player.allowance = (player.allowance + elapsed * rate_limit).min(rate_limit);
+        player.refilled = now;
+
+        let grid = self.sim.get_grid();
+        let (num_rows, num_cols) = (grid.get_num_rows() as i64, grid.get_num_cols() as i64);
+
+        for &(row, col) in cells {
+            let rejection = if !(0..num_rows).contains(&row) || !(0..num_cols).contains(&col) {
+                Some(Rejection::OutOfBounds)
+            } else if self.sim.get_grid().get(row as u32, col as u32) != 0 {
+                Some(Rejection::Occupied)
+            } else if player.allowance < 1.0 {
+                Some(Rejection::RateLimited)
+            } else {
+                None
+            };
+
+            match rejection {
+                Some(rejection) => placement.rejected.push(((row, col), rejection)),
+                None => {
+                    player.allowance -= 1.0;
+                    self.sim.set_cell(row as u32, col as u32, player.color);
+                    placement.placed.push((row as u32, col as u32));
+                }
+            }
+        }
+
+        placement
+    }
+
+    /// Advance the simulation by one generation, returning its changes.
+    pub fn step(&mut self) -> ColorDiff {
+        let previous = self.sim.get_grid().clone();
+        self.sim.step();
+
+        let grid = self.sim.get_grid();
+        let mut diff = ColorDiff::default();
+
+        for row in 0..grid.get_num_rows() {
+            for col in 0..grid.get_num_cols() {
+                match (previous.get(row, col), grid.get(row, col)) {
+                    (0, 0) => {}
+                    (0, color) => diff.births.push((row, col, color)),
+                    (_, 0) => diff.deaths.push((row, col)),
+                    _ => {}
+                }
+            }
+        }
+
+        diff
+    }
+
+    /// Get the (row, col, color) of each live cell.
+    pub fn live_cells(&self) -> Vec<(u32, u32, u8)> {
+        let grid = self.sim.get_grid();
+        let mut cells = Vec::new();
+
+        for row in 0..grid.get_num_rows() {
+            for col in 0..grid.get_num_cols() {
+                let color = grid.get(row, col);
+                if color != 0 {
+                    cells.push((row, col, color));
+                }
+            }
+        }
+
+        cells
+    }
+}
@@ -0,0 +1,177 @@
+//! Callbacks notified as a simulation advances.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::world::World;
+
+/// The cells which changed state between two generations.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Diff {
+    /// The (row, col) of each cell born, in row-major order.
+    pub births: Vec<(i64, i64)>,
+
+    /// The (row, col) of each cell which died, in row-major order.
+    pub deaths: Vec<(i64, i64)>,
+}
+
+impl Diff {
+    /// Determine if no cell changed state.
+    pub fn is_empty(&self) -> bool {
+        self.births.is_empty() && self.deaths.is_empty()
+    }
+
+    /// Get the number of cells which changed state.
+    pub fn len(&self) -> usize {
+        self.births.len() + self.deaths.len()
+    }
+
+    /// Get each cell which changed state as a [CellChange], in row-major
+    /// order, such as for a renderer redrawing only the changed cells.
+    pub fn changes(&self) -> Vec<CellChange> {
+        let mut changes = Vec::with_capacity(self.len());
+        let (mut births, mut deaths) =
+            (self.births.iter().peekable(), self.deaths.iter().peekable());
+
+        loop {
+            let change = match (births.peek(), deaths.peek()) {
+                (Some(&&born), Some(&&died)) if born <= died => {
+                    births.next();
+                    CellChange::Born(born.0, born.1)
+                }
+                (_, Some(&&died)) => {
+                    deaths.next();
+                    CellChange::Died(died.0, died.1)
+                }
+                (Some(&&born), None) => {
+                    births.next();
+                    CellChange::Born(born.0, born.1)
+                }
+                (None, None) => break,
+            };
+
+            changes.push(change);
+        }
+
+        changes
+    }
+}
+
+impl fmt::Display for Diff {
+    /// Write each [CellChange] on its own line, in row-major order.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for change in self.changes() {
+            writeln!(f, "{}", change)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A cell which changed state between two generations.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum CellChange {
+    /// The cell at (row, col) was born.
+    Born(i64, i64),
+
+    /// The cell at (row, col) died.
+    Died(i64, i64),
+}
+
+impl CellChange {
+    /// Get the (row, col) of the cell.
+    pub fn get_cell(self) -> (i64, i64) {
+        match self {
+            CellChange::Born(row, col) | CellChange::Died(row, col) => (row, col),
+        }
+    }
+
+    /// Determine if the cell is now alive.
+    pub fn is_alive(self) -> bool {
+        matches!(self, CellChange::Born(..))
+    }
+}
+
+impl fmt::Display for CellChange {
+    /// Write the change as `+row,col` for a birth or `-row,col` for a death,
+    /// as in a diff.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CellChange::Born(row, col) => write!(f, "+{},{}", row, col),
+            CellChange::Died(row, col) => write!(f, "-{},{}", row, col),
+        }
+    }
+}
+
+/// Callback notified of each generation with its number, [World], and the
+/// [Diff] from the previous generation.
+pub type GenerationObserver<W> = Box<dyn FnMut(u32, &W, &Diff) + Send>;
+
+/// Callback notified of each cell changing state with the generation, the
+/// cell's (row, col), and whether it is now alive.
+pub type CellChangeObserver = Box<dyn FnMut(u32, i64, i64, bool) + Send>;
+
+/// The observers registered on a simulation.
+pub(crate) struct Observers<W: World> {
+    /// Observers of each generation.
+    generation: Vec<GenerationObserver<W>>,
+
+    /// Observers of each cell change.
+    cell_change: Vec<CellChangeObserver>,
+}
+
+impl<W: World> Observers<W> {
+    /// Create a new, empty set of [Observers].
+    pub(crate) fn new() -> Observers<W> {
+        Observers {
+            generation: Vec::new(),
+            cell_change: Vec::new(),
+        }
+    }
+
+    /// Determine if no observers are registered.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.generation.is_empty() && self.cell_change.is_empty()
+    }
+
+    /// Register an observer of each generation.
+    pub(crate) fn add_generation(&mut self, observer: GenerationObserver<W>) {
+        self.generation.push(observer);
+    }
+
+    /// Register an observer of each cell change.
+    pub(crate) fn add_cell_change(&mut self, observer: CellChangeObserver) {
+        self.cell_change.push(observer);
+    }
+
+    /// Remove all observers.
+    pub(crate) fn clear(&mut self) {
+        self.generation.clear();
+        self.cell_change.clear();
+    }
+
+    /// Notify the observers of a new generation.
+    ///
+    /// # Arguments
+    /// * `generation` - The new generation.
+    /// * `world` - The [World] at the new generation.
+    /// * `previous` - The [World] at the previous generation.
+    pub(crate) fn notify(&mut self, generation: u32, world: &W, previous: &W) {
+        let diff = world.diff(previous);
+
+        for observer in self.generation.iter_mut() {
+            observer(generation, world, &diff);
+        }
+
+        for observer in self.cell_change.iter_mut() {
+            for &(row, col) in diff.births.iter() {
+                observer(generation, row, col, true);
+            }
+
+            for &(row, col) in diff.deaths.iter() {
+                observer(generation, row, col, false);
+            }
+        }
+    }
+}
@@ -0,0 +1,295 @@
+//! Patterns which can be stamped into a [Grid] to compose larger worlds.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
+
+use crate::game::{Cell, ConwaySim, Grid, GridError};
+use crate::patterns;
+use crate::world::{StateHasher, World};
+
+/// A rotation or reflection of a (row, col) position.
+type Orientation = fn(i64, i64) -> (i64, i64);
+
+/// The eight rotations and reflections of a square.
+const ORIENTATIONS: [Orientation; 8] = [
+    |row, col| (row, col),
+    |row, col| (row, -col),
+    |row, col| (-row, col),
+    |row, col| (-row, -col),
+    |row, col| (col, row),
+    |row, col| (col, -row),
+    |row, col| (-col, row),
+    |row, col| (-col, -row),
+];
+
+/// Get the canonical form of a set of live cells: the cells shifted to the
+/// origin in the orientation with the smallest sorted list of cells, the same
+/// for every translation, rotation, and reflection of them.
+///
+/// # Arguments
+/// * `cells` - The (row, col) of each live cell.
+pub(crate) fn canonical_cells(cells: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    let mut best: Option<Vec<(i64, i64)>> = None;
+
+    for orient in ORIENTATIONS.iter() {
+        let mut oriented: Vec<(i64, i64)> =
+            cells.iter().map(|&(row, col)| orient(row, col)).collect();
+
+        let min_row = oriented.iter().map(|&(row, _)| row).min().unwrap_or(0);
+        let min_col = oriented.iter().map(|&(_, col)| col).min().unwrap_or(0);
+        for cell in oriented.iter_mut() {
+            *cell = (cell.0 - min_row, cell.1 - min_col);
+        }
+        oriented.sort_unstable();
+
+        if best.as_ref().is_none_or(|best| oriented < *best) {
+            best = Some(oriented);
+        }
+    }
+
+    best.unwrap_or_default()
+}
+
+/// Hash the canonical form of a set of live cells, as found by
+/// [canonical_cells].
+///
+/// # Arguments
+/// * `cells` - The (row, col) of each live cell.
+pub(crate) fn canonical_hash(cells: &[(i64, i64)]) -> u64 {
+    let mut hasher = StateHasher::default();
+    canonical_cells(cells).hash(&mut hasher);
+
+    hasher.finish()
+}
+
+/// How a stamped [Pattern] combines with the cells already in a [Grid].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Overlap {
+    /// Replace every cell under the pattern, so its dead cells clear the
+    /// [Grid].
+    Overwrite,
+
+    /// Set the live cells of the pattern, keeping the cells of the [Grid].
+    Or,
+
+    /// Set the live cells of the pattern as with [Overlap::Or], but fail
+    /// without changing the [Grid] if any of them lands on a live cell.
+    Error,
+}
+
+/// Provenance of a [Pattern], as recorded in the header of a pattern file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PatternMeta {
+    /// Name of the pattern (`#N` in RLE, `!Name:` in plaintext).
+    pub name: Option<String>,
+
+    /// Author of the pattern (`#O` in RLE, `!Author:` in plaintext).
+    pub author: Option<String>,
+
+    /// Lines of free-form comments (`#C` in RLE, `!` in plaintext).
+    pub comments: Vec<String>,
+}
+
+/// A rectangular block of cells, such as a built-in or loaded pattern.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pattern {
+    /// Name, author, and comments of the pattern.
+    meta: PatternMeta,
+
+    /// The cells of the pattern.
+    grid: Grid,
+}
+
+impl Pattern {
+    /// Create a new, unnamed [Pattern] from the cells of a [Grid].
+    ///
+    /// # Arguments
+    /// * `grid` - The cells of the pattern.
+    pub fn new(grid: Grid) -> Pattern {
+        Pattern {
+            meta: PatternMeta::default(),
+            grid,
+        }
+    }
+
+    /// Look up a built-in [Pattern] by name, as accepted by
+    /// [patterns::by_name].
+    ///
+    /// # Arguments
+    /// * `name` - The name of the pattern.
+    pub fn by_name(name: &str) -> Option<Pattern> {
+        patterns::by_name(name).map(|grid| Pattern::new(grid).with_name(name.trim()))
+    }
+
+    /// Set the name of the [Pattern].
+    ///
+    /// # Arguments
+    /// * `name` - The new name of the pattern.
+    pub fn with_name(mut self, name: &str) -> Pattern {
+        self.meta.name = Some(name.to_string());
+        self
+    }
+
+    /// Set the name, author, and comments of the [Pattern].
+    ///
+    /// # Arguments
+    /// * `meta` - The new [PatternMeta] of the pattern.
+    pub fn with_meta(mut self, meta: PatternMeta) -> Pattern {
+        self.meta = meta;
+        self
+    }
+
+    /// Get the name of the [Pattern], if known.
+    pub fn get_name(&self) -> Option<&str> {
+        self.meta.name.as_deref()
+    }
+
+    /// Get the name, author, and comments of the [Pattern].
+    pub fn get_meta(&self) -> &PatternMeta {
+        &self.meta
+    }
+
+    /// Get the cells of the [Pattern].
+    pub fn get_grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Get the number of rows (height) of the [Pattern].
+    pub fn get_num_rows(&self) -> u32 {
+        self.grid.get_num_rows()
+    }
+
+    /// Get the number of columns (width) of the [Pattern].
+    pub fn get_num_cols(&self) -> u32 {
+        self.grid.get_num_cols()
+    }
+
+    /// Get a hash of the live cells of the [Pattern] which is the same
+    /// wherever they lie within it and however they are rotated or
+    /// reflected, so that copies of an object can be recognized whatever
+    /// their orientation.
+    ///
+    /// Hashes are comparable between patterns hashed by the same build of
+    /// the crate.
+    ///
+    /// ```
+    /// use gameoflife_rs::{Pattern, Transform};
+    ///
+    /// let glider = Pattern::by_name("glider").unwrap();
+    /// let mirrored = Pattern::new(glider.get_grid().transformed(Transform::FlipHorizontal));
+    ///
+    /// assert_eq!(glider.canonical_hash(), mirrored.canonical_hash());
+    /// assert_ne!(glider.canonical_hash(), Pattern::by_name("blinker").unwrap().canonical_hash());
+    /// ```
+    pub fn canonical_hash(&self) -> u64 {
+        canonical_hash(&self.grid.live_cells())
+    }
+}
+
+impl From<Grid> for Pattern {
+    fn from(grid: Grid) -> Pattern {
+        Pattern::new(grid)
+    }
+}
+
+impl Grid {
+    /// Paste a [Pattern] with its top left cell at (row, col).
+    ///
+    /// Returns a [GridError::OutOfBounds] if the pattern does not fit within
+    /// the [Grid], or a [GridError::Conflict] if the overlap policy is
+    /// [Overlap::Error] and a live cell of the pattern lands on a live cell.
+    /// Live cells under the pattern's dead cells do not conflict. The [Grid]
+    /// is left unchanged on error.
+    ///
+    /// ```
+    /// use gameoflife_rs::{Grid, GridError, Overlap, Pattern};
+    ///
+    /// let glider = Pattern::by_name("glider").unwrap();
+    /// let mut grid = Grid::new(8, 8);
+    /// grid.set_cells(&[(1, 1)]);
+    ///
+    /// // the glider's top left cell is dead, so it fits around (1, 1)
+    /// assert!(grid.stamp(&glider, 1, 1, Overlap::Error).is_ok());
+    /// assert_eq!(
+    ///     grid.stamp(&glider, 1, 1, Overlap::Error),
+    ///     Err(GridError::Conflict { row: 1, col: 2 })
+    /// );
+    /// ```
+    ///
+    /// # Arguments
+    /// * `pattern` - The [Pattern] to paste.
+    /// * `row` - Row of the pattern's top left cell.
+    /// * `col` - Column of the pattern's top left cell.
+    /// * `overlap` - How the pattern combines with the cells of the [Grid].
+    pub fn stamp(
+        &mut self,
+        pattern: &Pattern,
+        row: u32,
+        col: u32,
+        overlap: Overlap,
+    ) -> Result<(), GridError> {
+        let (num_rows, num_cols) = (pattern.get_num_rows(), pattern.get_num_cols());
+
+        if num_rows == 0 || num_cols == 0 {
+            return Ok(());
+        }
+
+        // check the bottom right cell of the pattern fits
+        let last_row = row.saturating_add(num_rows - 1);
+        let last_col = col.saturating_add(num_cols - 1);
+        self.try_get(last_row, last_col)?;
+
+        if overlap == Overlap::Error {
+            for pattern_row in 0..num_rows {
+                for pattern_col in 0..num_cols {
+                    let (cell_row, cell_col) = (row + pattern_row, col + pattern_col);
+
+                    if pattern.grid.get(pattern_row, pattern_col).is_alive()
+                        && self.get(cell_row, cell_col).is_alive()
+                    {
+                        return Err(GridError::Conflict {
+                            row: cell_row,
+                            col: cell_col,
+                        });
+                    }
+                }
+            }
+        }
+
+        for pattern_row in 0..num_rows {
+            for pattern_col in 0..num_cols {
+                let state = pattern.grid.get(pattern_row, pattern_col);
+
+                if state == Cell::Alive || overlap == Overlap::Overwrite {
+                    self.set(row + pattern_row, col + pattern_col, state);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ConwaySim {
+    /// Paste a [Pattern] into the simulation's [Grid] with its top left cell
+    /// at (row, col), as with [Grid::stamp].
+    ///
+    /// # Arguments
+    /// * `pattern` - The [Pattern] to paste.
+    /// * `row` - Row of the pattern's top left cell.
+    /// * `col` - Column of the pattern's top left cell.
+    /// * `overlap` - How the pattern combines with the cells of the [Grid].
+    pub fn stamp(
+        &mut self,
+        pattern: &Pattern,
+        row: u32,
+        col: u32,
+        overlap: Overlap,
+    ) -> Result<(), GridError> {
+        self.get_world_mut().stamp(pattern, row, col, overlap)?;
+        self.record_edit();
+
+        Ok(())
+    }
+}
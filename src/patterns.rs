@@ -0,0 +1,128 @@
+//! Library of canonical named patterns.
+//!
+//! Each pattern is returned as a [Grid] just large enough to contain it.
+
+#[cfg(feature = "std")]
+use std::ffi::OsStr;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+#[cfg(feature = "std")]
+use crate::error::GolError;
+#[cfg(feature = "std")]
+use crate::game::ConwaySim;
+use crate::game::Grid;
+
+/// Names of all built-in patterns, as accepted by [by_name].
+pub const NAMES: &[&str] = &[
+    "glider",
+    "blinker",
+    "toad",
+    "beacon",
+    "pulsar",
+    "lwss",
+    "gosper-glider-gun",
+    "r-pentomino",
+];
+
+/// Decode a built-in RLE pattern.
+fn from_rle(rle: &str) -> Grid {
+    Grid::from_rle(rle).expect("built-in patterns are valid RLE")
+}
+
+/// The glider, the smallest spaceship, travelling south east.
+pub fn glider() -> Grid {
+    from_rle("x = 3, y = 3\nbo$2bo$3o!")
+}
+
+/// The blinker, the smallest oscillator (period 2).
+pub fn blinker() -> Grid {
+    from_rle("x = 3, y = 1\n3o!")
+}
+
+/// The toad, a period 2 oscillator.
+pub fn toad() -> Grid {
+    from_rle("x = 4, y = 2\nb3o$3o!")
+}
+
+/// The beacon, a period 2 oscillator.
+pub fn beacon() -> Grid {
+    from_rle("x = 4, y = 4\n2o$2o$2b2o$2b2o!")
+}
+
+/// The pulsar, a period 3 oscillator.
+pub fn pulsar() -> Grid {
+    from_rle(
+        "x = 13, y = 13\n\
+         2b3o3b3o2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2$2b3o3b3o$o4bobo4bo$\
+         o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+    )
+}
+
+/// The lightweight spaceship (LWSS), travelling west.
+pub fn lwss() -> Grid {
+    from_rle("x = 5, y = 4\nbo2bo$o4b$o3bo$4o!")
+}
+
+/// The Gosper glider gun, emitting a glider every 30 generations.
+pub fn gosper_glider_gun() -> Grid {
+    from_rle(
+        "x = 36, y = 9\n\
+         24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4b\
+         obo$10bo5bo7bo$11bo3bo$12b2o!",
+    )
+}
+
+/// The R-pentomino, a methuselah which stabilizes after 1103 generations.
+pub fn r_pentomino() -> Grid {
+    from_rle("x = 3, y = 3\nb2o$2o$bo!")
+}
+
+/// Look up a built-in pattern by name.
+///
+/// Names are case insensitive and may use spaces or underscores in place of
+/// dashes, e.g. `"Gosper glider gun"`.
+///
+/// # Arguments
+/// * `name` - The name of the pattern, one of [NAMES].
+pub fn by_name(name: &str) -> Option<Grid> {
+    let name = name.trim().to_lowercase().replace([' ', '_'], "-");
+
+    match name.as_str() {
+        "glider" => Some(glider()),
+        "blinker" => Some(blinker()),
+        "toad" => Some(toad()),
+        "beacon" => Some(beacon()),
+        "pulsar" => Some(pulsar()),
+        "lwss" | "lightweight-spaceship" => Some(lwss()),
+        "gosper-glider-gun" | "glider-gun" | "gosper-gun" => Some(gosper_glider_gun()),
+        "r-pentomino" => Some(r_pentomino()),
+        _ => None,
+    }
+}
+
+/// Load a pattern file, choosing the format by its extension, or a built-in
+/// pattern by name if no such file exists.
+///
+/// Files ending in `.cells` are read as plaintext, `.lif` and `.life` as
+/// Life 1.06, `.mc` as macrocell, and any other file as RLE.
+///
+/// # Arguments
+/// * `path` - Path to the pattern file, or the name of a built-in pattern.
+#[cfg(feature = "std")]
+pub fn load<P: AsRef<Path>>(path: P) -> Result<ConwaySim, GolError> {
+    let path = path.as_ref();
+
+    if !path.exists() {
+        if let Some(grid) = path.to_str().and_then(by_name) {
+            return Ok(ConwaySim::new_with_grid(grid));
+        }
+    }
+
+    match path.extension().and_then(OsStr::to_str) {
+        Some("cells") => Ok(ConwaySim::load_plaintext_file(path)?),
+        Some("lif") | Some("life") => Ok(ConwaySim::load_life106_file(path)?),
+        Some("mc") => Ok(ConwaySim::load_macrocell_file(path)?),
+        _ => Ok(ConwaySim::load_rle_file(path)?),
+    }
+}
@@ -0,0 +1,244 @@
+//! Support for the LifeWiki plaintext (`.cells`) pattern format.
+//!
+//! A plaintext file consists of optional `!` comment lines followed by one
+//! line per row of the pattern, where `.` is a dead cell and `O` is a live
+//! cell. Trailing dead cells of a row may be omitted.
+//!
+//! The `!Name:` and `!Author:` lines and the remaining comment lines are kept
+//! as a [PatternMeta] when loading a [Pattern], and written back when saving
+//! it.
+
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::game::{ConwaySim, Grid, MAX_CELLS};
+use crate::pattern::{Pattern, PatternMeta};
+
+/// Error raised while loading a plaintext pattern.
+#[derive(Debug)]
+pub enum PlaintextError {
+    /// An unexpected character was found in the pattern rows.
+    InvalidCharacter {
+        line: usize,
+        column: usize,
+        character: char,
+    },
+
+    /// The pattern has too many rows or columns for a [Grid] to hold.
+    TooLarge { num_rows: u32, num_cols: u32 },
+
+    /// The pattern file could not be read or written.
+    Io(io::Error),
+}
+
+impl fmt::Display for PlaintextError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlaintextError::InvalidCharacter {
+                line,
+                column,
+                character,
+            } => {
+                write!(
+                    f,
+                    "invalid plaintext character '{}' on line {}, column {}",
+                    character, line, column
+                )
+            }
+            PlaintextError::TooLarge { num_rows, num_cols } => write!(
+                f,
+                "plaintext pattern of {}x{} cells is too large",
+                num_rows, num_cols
+            ),
+            PlaintextError::Io(err) => write!(f, "unable to access plaintext file: {}", err),
+        }
+    }
+}
+
+impl error::Error for PlaintextError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            PlaintextError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PlaintextError {
+    fn from(err: io::Error) -> PlaintextError {
+        PlaintextError::Io(err)
+    }
+}
+
+/// Parse the `!` comment lines of a plaintext encoded pattern into a
+/// [PatternMeta].
+fn parse_meta(plaintext: &str) -> PatternMeta {
+    let mut meta = PatternMeta::default();
+
+    for line in plaintext.lines() {
+        let text = match line.strip_prefix('!') {
+            Some(text) => text.trim(),
+            None => continue,
+        };
+
+        if let Some(name) = text.strip_prefix("Name:") {
+            meta.name = Some(name.trim().to_string());
+        } else if let Some(author) = text.strip_prefix("Author:") {
+            meta.author = Some(author.trim().to_string());
+        } else {
+            meta.comments.push(text.to_string());
+        }
+    }
+
+    meta
+}
+
+impl Grid {
+    /// Create a new [Grid] from a pattern in the plaintext format.
+    ///
+    /// The [Grid] is as tall as the number of pattern rows and as wide as the
+    /// longest pattern row.
+    ///
+    /// # Arguments
+    /// * `plaintext` - The plaintext encoded pattern.
+    pub fn from_plaintext(plaintext: &str) -> Result<Grid, PlaintextError> {
+        let mut cells: Vec<(u32, u32)> = Vec::new();
+        let mut num_rows: u32 = 0;
+        let mut num_cols: u32 = 0;
+
+        for (number, line) in plaintext.lines().enumerate() {
+            if line.starts_with('!') {
+                continue;
+            }
+
+            let line = line.trim_end();
+
+            for (col, character) in line.chars().enumerate() {
+                match character {
+                    '.' => {}
+                    'O' | '*' => cells.push((num_rows, col as u32)),
+                    character => {
+                        return Err(PlaintextError::InvalidCharacter {
+                            line: number + 1,
+                            column: col + 1,
+                            character,
+                        })
+                    }
+                }
+            }
+
+            let width = u32::try_from(line.chars().count()).unwrap_or(u32::MAX);
+            num_cols = num_cols.max(width);
+            num_rows = num_rows.saturating_add(1);
+        }
+
+        if num_rows as u64 * num_cols as u64 > MAX_CELLS {
+            return Err(PlaintextError::TooLarge { num_rows, num_cols });
+        }
+
+        let mut grid = Grid::new(num_rows, num_cols);
+        grid.set_cells(&cells);
+
+        Ok(grid)
+    }
+
+    /// Encode the [Grid] in the plaintext format.
+    pub fn to_plaintext(&self) -> String {
+        let mut plaintext = String::new();
+
+        for row in 0..self.get_num_rows() {
+            for col in 0..self.get_num_cols() {
+                plaintext.push(if self.get(row, col).is_alive() {
+                    'O'
+                } else {
+                    '.'
+                });
+            }
+
+            plaintext.push('\n');
+        }
+
+        plaintext
+    }
+
+    /// Save the [Grid] to a file in the plaintext format.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the plaintext pattern file.
+    pub fn save_plaintext_file<P: AsRef<Path>>(&self, path: P) -> Result<(), PlaintextError> {
+        fs::write(path, self.to_plaintext())?;
+
+        Ok(())
+    }
+}
+
+impl ConwaySim {
+    /// Create a new simulation from a plaintext pattern file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the plaintext pattern file.
+    pub fn load_plaintext_file<P: AsRef<Path>>(path: P) -> Result<ConwaySim, PlaintextError> {
+        let plaintext = fs::read_to_string(path)?;
+        let grid = Grid::from_plaintext(&plaintext)?;
+
+        Ok(ConwaySim::new_with_grid(grid))
+    }
+}
+
+impl Pattern {
+    /// Create a new [Pattern] from a pattern in the plaintext format, keeping
+    /// the name, author, and comments declared in its `!` lines.
+    ///
+    /// # Arguments
+    /// * `plaintext` - The plaintext encoded pattern.
+    pub fn from_plaintext(plaintext: &str) -> Result<Pattern, PlaintextError> {
+        let grid = Grid::from_plaintext(plaintext)?;
+
+        Ok(Pattern::new(grid).with_meta(parse_meta(plaintext)))
+    }
+
+    /// Encode the [Pattern] in the plaintext format, including its name,
+    /// author, and comments.
+    pub fn to_plaintext(&self) -> String {
+        let meta = self.get_meta();
+        let mut plaintext = String::new();
+
+        if let Some(name) = &meta.name {
+            plaintext.push_str(&format!("!Name: {}\n", name));
+        }
+        if let Some(author) = &meta.author {
+            plaintext.push_str(&format!("!Author: {}\n", author));
+        }
+        for comment in &meta.comments {
+            plaintext.push_str(&format!("!{}\n", comment));
+        }
+
+        plaintext.push_str(&self.get_grid().to_plaintext());
+
+        plaintext
+    }
+
+    /// Create a new [Pattern] from a plaintext pattern file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the plaintext pattern file.
+    pub fn load_plaintext_file<P: AsRef<Path>>(path: P) -> Result<Pattern, PlaintextError> {
+        let plaintext = fs::read_to_string(path)?;
+
+        Pattern::from_plaintext(&plaintext)
+    }
+
+    /// Save the [Pattern] to a file in the plaintext format.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the plaintext pattern file.
+    pub fn save_plaintext_file<P: AsRef<Path>>(&self, path: P) -> Result<(), PlaintextError> {
+        fs::write(path, self.to_plaintext())?;
+
+        Ok(())
+    }
+}
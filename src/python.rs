@@ -0,0 +1,298 @@
+//! Python bindings for driving a simulation from Python, e.g. in a notebook.
+//!
+//! The extension module is named `gameoflife_rs` and is built with
+//! [maturin](https://www.maturin.rs) (`maturin develop --release`, see
+//! `pyproject.toml`). Boards are exported to and imported from NumPy as
+//! two-dimensional `uint8` arrays of shape `(rows, cols)`, 0 for dead cells
+//! and 1 for live cells.
+//!
+//! ```python
+//! import gameoflife_rs as gol
+//!
+//! sim = gol.load_rle("glider.rle")
+//! sim.step(100)
+//! board = sim.to_numpy()
+//! ```
+
+use numpy::ndarray::Array2;
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+use pyo3::exceptions::{PyIOError, PyIndexError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::game::{Cell, ConwaySim, Grid};
+use crate::rule::Rule;
+
+/// Raise an [IndexError](PyIndexError) if (row, col) lies outside of a
+/// [Grid].
+fn check_bounds(grid: &Grid, row: u32, col: u32) -> PyResult<()> {
+    grid.try_get(row, col)
+        .map(|_| ())
+        .map_err(|err| PyIndexError::new_err(err.to_string()))
+}
+
+/// Export the cells of a [Grid] as a NumPy array.
+fn grid_to_numpy<'py>(py: Python<'py>, grid: &Grid) -> Bound<'py, PyArray2<u8>> {
+    let cells: Vec<u8> = grid
+        .get_cells()
+        .iter()
+        .map(|cell| cell.is_alive() as u8)
+        .collect();
+    let shape = (grid.get_num_rows() as usize, grid.get_num_cols() as usize);
+
+    Array2::from_shape_vec(shape, cells)
+        .expect("the cells of a grid fill its shape")
+        .into_pyarray(py)
+}
+
+/// Import the cells of a [Grid] from a NumPy array, any non-zero value
+/// being a live cell.
+fn grid_from_numpy(array: PyReadonlyArray2<u8>) -> Grid {
+    let array = array.as_array();
+    let (num_rows, num_cols) = array.dim();
+    let mut grid = Grid::new(num_rows as u32, num_cols as u32);
+
+    for ((row, col), &value) in array.indexed_iter() {
+        if value != 0 {
+            grid.set(row as u32, col as u32, Cell::Alive);
+        }
+    }
+
+    grid
+}
+
+/// A bounded grid of cells, exposed to Python as `Grid`.
+#[pyclass(name = "Grid")]
+#[derive(Clone)]
+pub struct PyGrid {
+    /// The wrapped [Grid].
+    grid: Grid,
+}
+
+#[pymethods]
+impl PyGrid {
+    /// Create a new grid of dead cells.
+    #[new]
+    fn new(rows: u32, cols: u32) -> PyGrid {
+        PyGrid {
+            grid: Grid::new(rows, cols),
+        }
+    }
+
+    /// Create a new grid from a pattern in the RLE format.
+    #[staticmethod]
+    fn from_rle(rle: &str) -> PyResult<PyGrid> {
+        Grid::from_rle(rle)
+            .map(|grid| PyGrid { grid })
+            .map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+
+    /// Create a new grid from a 2D `uint8` NumPy array.
+    #[staticmethod]
+    fn from_numpy(array: PyReadonlyArray2<u8>) -> PyGrid {
+        PyGrid {
+            grid: grid_from_numpy(array),
+        }
+    }
+
+    /// Create a new grid filled with a random soup.
+    #[staticmethod]
+    fn random(rows: u32, cols: u32, density: f64, seed: u64) -> PyResult<PyGrid> {
+        if !(0.0..=1.0).contains(&density) {
+            return Err(PyValueError::new_err("density must be within 0.0 to 1.0"));
+        }
+
+        Ok(PyGrid {
+            grid: Grid::random(rows, cols, density, seed),
+        })
+    }
+
+    /// The number of rows (height) of the grid.
+    #[getter]
+    fn rows(&self) -> u32 {
+        self.grid.get_num_rows()
+    }
+
+    /// The number of columns (width) of the grid.
+    #[getter]
+    fn cols(&self) -> u32 {
+        self.grid.get_num_cols()
+    }
+
+    /// The number of live cells.
+    #[getter]
+    fn population(&self) -> u64 {
+        self.grid.get_population()
+    }
+
+    /// Determine if a cell is alive.
+    fn get(&self, row: u32, col: u32) -> PyResult<bool> {
+        check_bounds(&self.grid, row, col)?;
+        Ok(self.grid.get(row, col).is_alive())
+    }
+
+    /// Set a cell alive, or dead if `alive` is false.
+    #[pyo3(signature = (row, col, alive = true))]
+    fn set(&mut self, row: u32, col: u32, alive: bool) -> PyResult<()> {
+        check_bounds(&self.grid, row, col)?;
+
+        let state = if alive { Cell::Alive } else { Cell::Dead };
+        self.grid.set(row, col, state);
+        Ok(())
+    }
+
+    /// Export the cells as a 2D `uint8` NumPy array.
+    fn to_numpy<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<u8>> {
+        grid_to_numpy(py, &self.grid)
+    }
+
+    fn __str__(&self) -> String {
+        self.grid.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Grid(rows={}, cols={}, population={})",
+            self.grid.get_num_rows(),
+            self.grid.get_num_cols(),
+            self.grid.get_population()
+        )
+    }
+}
+
+/// A simulation on a bounded grid, exposed to Python as `ConwaySim`.
+#[pyclass(name = "ConwaySim", unsendable)]
+pub struct PySim {
+    /// The wrapped simulation.
+    sim: ConwaySim,
+}
+
+#[pymethods]
+impl PySim {
+    /// Create a new simulation of dead cells.
+    #[new]
+    fn new(rows: u32, cols: u32) -> PySim {
+        PySim {
+            sim: ConwaySim::new(rows, cols),
+        }
+    }
+
+    /// Create a new simulation from a copy of a grid.
+    #[staticmethod]
+    fn from_grid(grid: &PyGrid) -> PySim {
+        PySim {
+            sim: ConwaySim::new_with_grid(grid.grid.clone()),
+        }
+    }
+
+    /// Create a new simulation from a 2D `uint8` NumPy array.
+    #[staticmethod]
+    fn from_numpy(array: PyReadonlyArray2<u8>) -> PySim {
+        PySim {
+            sim: ConwaySim::new_with_grid(grid_from_numpy(array)),
+        }
+    }
+
+    /// The rulestring in B/S notation; assigning a rulestring changes the
+    /// rule.
+    #[getter]
+    fn get_rule(&self) -> String {
+        self.sim.get_rule().to_string()
+    }
+
+    #[setter]
+    fn set_rule(&mut self, rulestring: &str) -> PyResult<()> {
+        let rule: Rule = rulestring
+            .parse()
+            .map_err(|err: crate::rule::RuleError| PyValueError::new_err(err.to_string()))?;
+        self.sim.set_rule(rule);
+        Ok(())
+    }
+
+    /// The current generation.
+    #[getter]
+    fn generation(&self) -> u32 {
+        self.sim.get_generation()
+    }
+
+    /// The number of live cells.
+    #[getter]
+    fn population(&self) -> u64 {
+        self.sim.get_population()
+    }
+
+    /// Whether the simulation is running, extinct, stable, or oscillating.
+    #[getter]
+    fn status(&self) -> String {
+        self.sim.get_status().to_string()
+    }
+
+    /// A copy of the current generation's grid.
+    #[getter]
+    fn grid(&self) -> PyGrid {
+        PyGrid {
+            grid: self.sim.get_grid().clone(),
+        }
+    }
+
+    /// Advance the simulation by `n` generations.
+    #[pyo3(signature = (n = 1))]
+    fn step(&mut self, n: u32) {
+        for _ in 0..n {
+            self.sim.step();
+        }
+    }
+
+    /// Determine if a cell is alive.
+    fn get(&self, row: u32, col: u32) -> PyResult<bool> {
+        check_bounds(self.sim.get_grid(), row, col)?;
+        Ok(self.sim.is_cell_alive(row, col))
+    }
+
+    /// Set a cell alive, or dead if `alive` is false.
+    #[pyo3(signature = (row, col, alive = true))]
+    fn set(&mut self, row: u32, col: u32, alive: bool) -> PyResult<()> {
+        check_bounds(self.sim.get_grid(), row, col)?;
+
+        let state = if alive { Cell::Alive } else { Cell::Dead };
+        self.sim.set_cell(row, col, state);
+        Ok(())
+    }
+
+    /// Export the current generation as a 2D `uint8` NumPy array.
+    fn to_numpy<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<u8>> {
+        grid_to_numpy(py, self.sim.get_grid())
+    }
+
+    fn __str__(&self) -> String {
+        self.sim.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ConwaySim(rows={}, cols={}, generation={}, population={})",
+            self.sim.get_grid().get_num_rows(),
+            self.sim.get_grid().get_num_cols(),
+            self.sim.get_generation(),
+            self.sim.get_population()
+        )
+    }
+}
+
+/// Load a simulation from a pattern file in the RLE format, under the rule
+/// given in its header.
+#[pyfunction]
+fn load_rle(path: &str) -> PyResult<PySim> {
+    ConwaySim::load_rle_file(path)
+        .map(|sim| PySim { sim })
+        .map_err(|err| PyIOError::new_err(err.to_string()))
+}
+
+/// The `gameoflife_rs` Python extension module.
+#[pymodule]
+fn gameoflife_rs(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyGrid>()?;
+    module.add_class::<PySim>()?;
+    module.add_function(wrap_pyfunction!(load_rle, module)?)?;
+
+    Ok(())
+}
@@ -0,0 +1,202 @@
+//! Colored terminal rendering using ANSI escape codes.
+//!
+//! Each cell is drawn as two spaces with a 24-bit background color, and every
+//! frame after the first moves the cursor back up so that an animation
+//! redraws in place instead of scrolling.
+
+use std::fmt::Write;
+
+use super::{Rgb, Theme};
+use crate::game::Grid;
+use crate::states::StateGrid;
+
+/// Colors used to draw the cells.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AnsiPalette {
+    /// Color of newborn live cells.
+    pub alive: Rgb,
+
+    /// Color of dead cells.
+    pub dead: Rgb,
+
+    /// Color live cells fade to as they age, if an age gradient is drawn.
+    pub old: Option<Rgb>,
+}
+
+impl Default for AnsiPalette {
+    fn default() -> AnsiPalette {
+        AnsiPalette {
+            alive: Rgb::WHITE,
+            dead: Rgb::DARK_GREY,
+            old: None,
+        }
+    }
+}
+
+impl From<&Theme> for AnsiPalette {
+    fn from(theme: &Theme) -> AnsiPalette {
+        AnsiPalette {
+            alive: theme.alive,
+            dead: theme.dead,
+            old: theme.old,
+        }
+    }
+}
+
+/// Renderer of successive generations of a [Grid] to a terminal.
+///
+/// The renderer tracks how many generations each cell has been alive for to
+/// draw the age gradient of its [AnsiPalette].
+#[derive(Clone, Debug)]
+pub struct AnsiRenderer {
+    /// Colors used to draw the cells.
+    palette: AnsiPalette,
+
+    /// Age at which live cells reach the old color of the palette.
+    gradient_span: u32,
+
+    /// Number of consecutive frames each cell has been alive for.
+    ages: Vec<u32>,
+
+    /// Number of lines of the previous frame, to move the cursor back over.
+    lines_drawn: usize,
+}
+
+impl AnsiRenderer {
+    /// Create a new [AnsiRenderer].
+    ///
+    /// # Arguments
+    /// * `palette` - Colors used to draw the cells.
+    pub fn new(palette: AnsiPalette) -> AnsiRenderer {
+        AnsiRenderer {
+            palette,
+            gradient_span: 32,
+            ages: Vec::new(),
+            lines_drawn: 0,
+        }
+    }
+
+    /// Set the age in generations at which live cells reach the old color of
+    /// the palette.
+    pub fn set_gradient_span(&mut self, generations: u32) {
+        self.gradient_span = generations.max(1);
+    }
+
+    /// Forget the previous frame so that the next one is drawn below it
+    /// rather than over it.
+    pub fn reset(&mut self) {
+        self.ages.clear();
+        self.lines_drawn = 0;
+    }
+
+    /// Get the color of a live cell of an age.
+    fn alive_color(&self, age: u32) -> Rgb {
+        match self.palette.old {
+            Some(old) => {
+                let t = (age.saturating_sub(1).min(self.gradient_span)) as f64
+                    / self.gradient_span as f64;
+                self.palette.alive.blend(old, t)
+            }
+            None => self.palette.alive,
+        }
+    }
+
+    /// Render the next frame, redrawing over the previous frame.
+    ///
+    /// # Arguments
+    /// * `grid` - The [Grid] to draw.
+    /// * `caption` - Text printed below the grid, or an empty string for none.
+    pub fn render(&mut self, grid: &Grid, caption: &str) -> String {
+        let cells = grid.get_cells();
+        if self.ages.len() != cells.len() {
+            self.ages = vec![0; cells.len()];
+        }
+
+        for (age, cell) in self.ages.iter_mut().zip(cells.iter()) {
+            *age = if cell.is_alive() {
+                age.saturating_add(1)
+            } else {
+                0
+            };
+        }
+
+        let colors: Vec<Rgb> = self
+            .ages
+            .iter()
+            .map(|&age| match age {
+                0 => self.palette.dead,
+                age => self.alive_color(age),
+            })
+            .collect();
+
+        self.draw(grid.get_num_cols(), &colors, caption)
+    }
+
+    /// Render the next frame of a multi-state automaton, redrawing over the
+    /// previous frame.
+    ///
+    /// # Arguments
+    /// * `grid` - The [StateGrid] to draw.
+    /// * `palette` - Colors indexed by cell state, such as built by
+    ///   [state_palette](super::state_palette). States past the end of the
+    ///   palette use its last color.
+    /// * `caption` - Text printed below the grid, or an empty string for none.
+    pub fn render_states(&mut self, grid: &StateGrid, palette: &[Rgb], caption: &str) -> String {
+        let fallback = palette.last().copied().unwrap_or(self.palette.alive);
+
+        let colors: Vec<Rgb> = grid
+            .get_states()
+            .iter()
+            .map(|&state| palette.get(state as usize).copied().unwrap_or(fallback))
+            .collect();
+
+        self.draw(grid.get_num_cols(), &colors, caption)
+    }
+
+    /// Draw rows of cell colors followed by a caption, moving the cursor back
+    /// over the previous frame first.
+    fn draw(&mut self, num_cols: u32, colors: &[Rgb], caption: &str) -> String {
+        let mut frame = String::new();
+
+        // move the cursor back to the start of the previous frame
+        if self.lines_drawn > 0 {
+            let _ = write!(frame, "\x1b[{}F", self.lines_drawn);
+        }
+
+        let mut lines_drawn = 0;
+
+        for line in colors.chunks(num_cols.max(1) as usize) {
+            let mut current: Option<Rgb> = None;
+
+            for &color in line {
+                // only emit a color when it changes
+                if current != Some(color) {
+                    let Rgb(red, green, blue) = color;
+                    let _ = write!(frame, "\x1b[48;2;{};{};{}m", red, green, blue);
+                    current = Some(color);
+                }
+
+                frame.push_str("  ");
+            }
+
+            frame.push_str("\x1b[0m\n");
+            lines_drawn += 1;
+        }
+
+        for line in caption.lines() {
+            // clear the rest of the line in case the caption got shorter
+            let _ = writeln!(frame, "{}\x1b[K", line);
+            lines_drawn += 1;
+        }
+
+        self.lines_drawn = lines_drawn;
+
+        frame
+    }
+}
+
+impl Default for AnsiRenderer {
+    fn default() -> AnsiRenderer {
+        AnsiRenderer::new(AnsiPalette::default())
+    }
+}
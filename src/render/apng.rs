@@ -0,0 +1,138 @@
+//! Export of simulations as animated PNGs (APNG), which unlike GIFs are not
+//! limited to 256 colors.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use ::png::{BitDepth, ColorType, Encoder, Writer};
+
+use super::png::{PngError, PngOptions};
+use super::{FrameSink, Raster, Rgb};
+use crate::game::{ConwaySim, Grid};
+
+/// Delay between frames in milliseconds.
+const FRAME_DELAY_MS: u16 = 100;
+
+/// A [FrameSink] encoding frames as an animated PNG which loops forever.
+///
+/// Unlike other formats, an APNG declares its number of frames up front, so
+/// exactly that many frames must be added before finishing.
+pub struct ApngSink<W: Write> {
+    /// The writer of the PNG, past its header.
+    writer: Writer<W>,
+
+    /// The raster the frames are drawn into.
+    raster: Raster,
+
+    /// The (rows, cols) size of the grids of the frames.
+    size: (u32, u32),
+}
+
+impl<W: Write> ApngSink<W> {
+    /// Create a new [ApngSink] writing the APNG into a writer.
+    ///
+    /// # Arguments
+    /// * `writer` - Destination of the APNG.
+    /// * `num_rows` - The number of rows (height) of the grids of the frames.
+    /// * `num_cols` - The number of columns (width) of the grids of the frames.
+    /// * `num_frames` - The number of frames which will be added.
+    /// * `options` - The [PngOptions] of the frames.
+    pub fn new(
+        writer: W,
+        num_rows: u32,
+        num_cols: u32,
+        num_frames: u32,
+        options: &PngOptions,
+    ) -> Result<ApngSink<W>, PngError> {
+        if options.cell_size == 0 {
+            return Err(PngError::InvalidCellSize);
+        }
+
+        let raster = Raster::new(
+            num_rows,
+            num_cols,
+            options.cell_size,
+            options.grid_lines.is_some(),
+        );
+
+        if raster.width > i32::MAX as u64 || raster.height > i32::MAX as u64 {
+            return Err(PngError::TooLarge {
+                width: raster.width,
+                height: raster.height,
+            });
+        }
+
+        let grid_lines = options.grid_lines.unwrap_or(options.dead);
+        let palette: Vec<u8> = [options.dead, options.alive, grid_lines]
+            .iter()
+            .flat_map(|&Rgb(red, green, blue)| [red, green, blue])
+            .collect();
+
+        let mut encoder = Encoder::new(writer, raster.width as u32, raster.height as u32);
+        encoder.set_color(ColorType::Indexed);
+        encoder.set_depth(BitDepth::Eight);
+        encoder.set_palette(palette);
+        encoder.set_animated(num_frames.max(1), 0)?;
+        encoder.set_frame_delay(FRAME_DELAY_MS, 1000)?;
+
+        Ok(ApngSink {
+            writer: encoder.write_header()?,
+            raster,
+            size: (num_rows, num_cols),
+        })
+    }
+}
+
+impl<W: Write> FrameSink for ApngSink<W> {
+    type Error = PngError;
+
+    fn add_frame(&mut self, grid: &Grid) -> Result<(), PngError> {
+        let (num_rows, num_cols) = (grid.get_num_rows(), grid.get_num_cols());
+        if (num_rows, num_cols) != self.size {
+            return Err(PngError::WrongFrameSize { num_rows, num_cols });
+        }
+
+        self.raster.draw(grid);
+        self.writer.write_image_data(&self.raster.pixels)?;
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), PngError> {
+        self.writer.finish()?;
+
+        Ok(())
+    }
+}
+
+/// Export a simulation as an animated PNG which loops forever.
+///
+/// The first frame is the current generation, followed by one frame for each
+/// of the next `generations` generations. The simulation is left at the last
+/// exported generation.
+///
+/// # Arguments
+/// * `sim` - The simulation to export.
+/// * `generations` - The number of generations to advance the simulation.
+/// * `options` - The [PngOptions] of the frames.
+/// * `path` - Path of the APNG file.
+pub fn export<P: AsRef<Path>>(
+    sim: &mut ConwaySim,
+    generations: u32,
+    options: &PngOptions,
+    path: P,
+) -> Result<(), PngError> {
+    let grid = sim.get_grid();
+    let (num_rows, num_cols) = (grid.get_num_rows(), grid.get_num_cols());
+
+    if options.cell_size == 0 {
+        return Err(PngError::InvalidCellSize);
+    }
+
+    let file = BufWriter::new(File::create(path)?);
+    let num_frames = generations.saturating_add(1);
+    let sink = ApngSink::new(file, num_rows, num_cols, num_frames, options)?;
+
+    super::record(sim, generations, sink)
+}
@@ -0,0 +1,62 @@
+//! High-density terminal rendering using Unicode braille patterns.
+//!
+//! Each character of the braille block (U+2800 to U+28FF) has eight dots in
+//! two columns of four, so a block of 2×4 cells is drawn as a single
+//! character and a 160×80 grid fits in 80 columns and 20 lines.
+
+use crate::game::Grid;
+
+/// Number of cell columns drawn by each character.
+pub const CELL_COLS: u32 = 2;
+
+/// Number of cell rows drawn by each character.
+pub const CELL_ROWS: u32 = 4;
+
+/// The empty braille pattern, to which the dot bits are added.
+const BLANK: u32 = 0x2800;
+
+/// Bit of the dot drawing each cell of a block, by [row][col] in the block.
+const DOT_BITS: [[u32; CELL_COLS as usize]; CELL_ROWS as usize] =
+    [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Get the braille character of the block of cells with its top left cell at
+/// (row, col).
+fn block_char(grid: &Grid, row: u32, col: u32) -> char {
+    let mut pattern = BLANK;
+
+    for (row_offset, bits) in DOT_BITS.iter().enumerate() {
+        for (col_offset, &bit) in bits.iter().enumerate() {
+            let cell_row = row + row_offset as u32;
+            let cell_col = col + col_offset as u32;
+
+            if cell_row < grid.get_num_rows()
+                && cell_col < grid.get_num_cols()
+                && grid.get(cell_row, cell_col).is_alive()
+            {
+                pattern |= bit;
+            }
+        }
+    }
+
+    // every pattern of the block is a valid character
+    char::from_u32(pattern).unwrap_or(' ')
+}
+
+/// Render a [Grid] as lines of braille characters, each drawing a block of
+/// 2×4 cells. Blocks past the edges of the [Grid] are drawn as dead cells.
+///
+/// # Arguments
+/// * `grid` - The [Grid] to draw.
+pub fn render(grid: &Grid) -> String {
+    let mut frame = String::new();
+
+    for row in (0..grid.get_num_rows()).step_by(CELL_ROWS as usize) {
+        for col in (0..grid.get_num_cols()).step_by(CELL_COLS as usize) {
+            frame.push(block_char(grid, row, col));
+        }
+
+        frame.push('\n');
+    }
+
+    frame
+}
@@ -0,0 +1,191 @@
+//! Export of simulations as animated GIFs.
+
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use ::gif::{Encoder, EncodingError, Frame, Repeat};
+
+use super::{FrameSink, Raster, Rgb, Theme};
+use crate::game::{ConwaySim, Grid};
+
+/// Delay between frames in hundredths of a second.
+const FRAME_DELAY: u16 = 10;
+
+/// Error raised while exporting a GIF.
+#[derive(Debug)]
+pub enum GifError {
+    /// The cell size is zero.
+    InvalidCellSize,
+
+    /// The frames would exceed the maximum GIF dimensions of 65535 pixels.
+    TooLarge { width: u64, height: u64 },
+
+    /// A frame is not the size of the grid the sink was created for.
+    WrongFrameSize { num_rows: u32, num_cols: u32 },
+
+    /// The frames could not be encoded.
+    Encoding(EncodingError),
+
+    /// The GIF file could not be written.
+    Io(io::Error),
+}
+
+impl fmt::Display for GifError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GifError::InvalidCellSize => write!(f, "cell size must be at least 1 pixel"),
+            GifError::TooLarge { width, height } => write!(
+                f,
+                "frames of {}x{} pixels exceed the maximum GIF size",
+                width, height
+            ),
+            GifError::WrongFrameSize { num_rows, num_cols } => write!(
+                f,
+                "frame of {}x{} cells differs from the size of the animation",
+                num_rows, num_cols
+            ),
+            GifError::Encoding(err) => write!(f, "unable to encode GIF: {}", err),
+            GifError::Io(err) => write!(f, "unable to write GIF file: {}", err),
+        }
+    }
+}
+
+impl error::Error for GifError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            GifError::Encoding(err) => Some(err),
+            GifError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for GifError {
+    fn from(err: io::Error) -> GifError {
+        GifError::Io(err)
+    }
+}
+
+impl From<EncodingError> for GifError {
+    fn from(err: EncodingError) -> GifError {
+        GifError::Encoding(err)
+    }
+}
+
+/// A [FrameSink] encoding frames as an animated GIF which loops forever.
+pub struct GifSink<W: Write> {
+    /// The encoder of the GIF.
+    encoder: Encoder<W>,
+
+    /// The raster the frames are drawn into.
+    raster: Raster,
+
+    /// The (rows, cols) size of the grids of the frames.
+    size: (u32, u32),
+}
+
+impl<W: Write> GifSink<W> {
+    /// Create a new [GifSink] writing the GIF into a writer.
+    ///
+    /// # Arguments
+    /// * `writer` - Destination of the GIF.
+    /// * `num_rows` - The number of rows (height) of the grids of the frames.
+    /// * `num_cols` - The number of columns (width) of the grids of the frames.
+    /// * `cell_size` - Size of a cell in pixels.
+    /// * `theme` - The [Theme] whose live and dead cell colors are drawn.
+    pub fn new(
+        writer: W,
+        num_rows: u32,
+        num_cols: u32,
+        cell_size: u32,
+        theme: &Theme,
+    ) -> Result<GifSink<W>, GifError> {
+        if cell_size == 0 {
+            return Err(GifError::InvalidCellSize);
+        }
+
+        let raster = Raster::new(num_rows, num_cols, cell_size, false);
+        let (width, height) = (raster.width, raster.height);
+
+        if width > u16::MAX as u64 || height > u16::MAX as u64 {
+            return Err(GifError::TooLarge { width, height });
+        }
+
+        let palette: Vec<u8> = [theme.dead, theme.alive]
+            .iter()
+            .flat_map(|&Rgb(red, green, blue)| [red, green, blue])
+            .collect();
+
+        let mut encoder = Encoder::new(writer, width as u16, height as u16, &palette)?;
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        Ok(GifSink {
+            encoder,
+            raster,
+            size: (num_rows, num_cols),
+        })
+    }
+}
+
+impl<W: Write> FrameSink for GifSink<W> {
+    type Error = GifError;
+
+    fn add_frame(&mut self, grid: &Grid) -> Result<(), GifError> {
+        let (num_rows, num_cols) = (grid.get_num_rows(), grid.get_num_cols());
+        if (num_rows, num_cols) != self.size {
+            return Err(GifError::WrongFrameSize { num_rows, num_cols });
+        }
+
+        self.raster.draw(grid);
+
+        self.encoder.write_frame(&Frame {
+            delay: FRAME_DELAY,
+            width: self.raster.width as u16,
+            height: self.raster.height as u16,
+            buffer: self.raster.pixels.as_slice().into(),
+            ..Frame::default()
+        })?;
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), GifError> {
+        self.encoder.into_inner()?.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Export a simulation as an animated GIF which loops forever, in the colors
+/// of the default [Theme].
+///
+/// The first frame is the current generation, followed by one frame for each
+/// of the next `generations` generations. The simulation is left at the last
+/// exported generation.
+///
+/// # Arguments
+/// * `sim` - The simulation to export.
+/// * `generations` - The number of generations to advance the simulation.
+/// * `cell_size` - Size of a cell in pixels.
+/// * `path` - Path of the GIF file.
+pub fn export<P: AsRef<Path>>(
+    sim: &mut ConwaySim,
+    generations: u32,
+    cell_size: u32,
+    path: P,
+) -> Result<(), GifError> {
+    let grid = sim.get_grid();
+    let (num_rows, num_cols) = (grid.get_num_rows(), grid.get_num_cols());
+
+    if cell_size == 0 {
+        return Err(GifError::InvalidCellSize);
+    }
+
+    let file = BufWriter::new(File::create(path)?);
+    let sink = GifSink::new(file, num_rows, num_cols, cell_size, &Theme::default())?;
+
+    super::record(sim, generations, sink)
+}
@@ -0,0 +1,269 @@
+//! Rendering of simulations to terminals, images, and animation files.
+//!
+//! Each image and animation format is behind a feature of the same name so
+//! that library users only pay for the encoders they need.
+
+pub mod ansi;
+#[cfg(feature = "apng")]
+pub mod apng;
+pub mod braille;
+#[cfg(feature = "gif")]
+pub mod gif;
+#[cfg(feature = "png")]
+pub mod png;
+pub mod slices;
+#[cfg(feature = "svg")]
+pub mod svg;
+#[cfg(feature = "webp")]
+mod vp8l;
+#[cfg(feature = "webp")]
+pub mod webp;
+
+use crate::game::{ConwaySim, Grid};
+
+/// An RGB color.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// Color around the grid by default.
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+
+    /// Color of dead cells by default.
+    pub const DARK_GREY: Rgb = Rgb(24, 24, 24);
+
+    /// Color of grid lines by default.
+    pub const GREY: Rgb = Rgb(64, 64, 64);
+
+    /// Color of live cells by default.
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    /// Blend two colors, `t` ranging from 0.0 (all `self`) to 1.0 (all `to`).
+    pub fn blend(self, to: Rgb, t: f64) -> Rgb {
+        let channel =
+            |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * t).round() as u8;
+
+        Rgb(
+            channel(self.0, to.0),
+            channel(self.1, to.1),
+            channel(self.2, to.2),
+        )
+    }
+}
+
+/// Build a palette of colors indexed by cell state for a multi-state rule:
+/// state 0 is `dead`, state 1 is `alive`, and each further state fades from
+/// `alive` towards `dead`.
+///
+/// # Arguments
+/// * `alive` - Color of live cells.
+/// * `dead` - Color of dead cells.
+/// * `num_states` - Number of cell states.
+pub fn state_palette(alive: Rgb, dead: Rgb, num_states: u8) -> Vec<Rgb> {
+    let mut palette = vec![dead, alive];
+
+    for state in 2..num_states {
+        palette.push(alive.blend(dead, (state - 1) as f64 / (num_states - 1) as f64));
+    }
+
+    palette.truncate(num_states as usize);
+
+    palette
+}
+
+/// Colors shared by every renderer, so that a simulation looks alike in the
+/// terminal, the graphical user interface, and exported images.
+///
+/// Each renderer's own options, such as
+/// [AnsiPalette](ansi::AnsiPalette), are created from a theme with [From].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Theme {
+    /// Color of newborn live cells.
+    pub alive: Rgb,
+
+    /// Color of dead cells.
+    pub dead: Rgb,
+
+    /// Color around the grid, such as the window behind it.
+    pub background: Rgb,
+
+    /// Color of the lines drawn between cells.
+    pub grid_lines: Rgb,
+
+    /// Color live cells fade to as they age, if an age gradient is drawn.
+    pub old: Option<Rgb>,
+
+    /// Colors of the first cell states of multi-state rules, overriding the
+    /// states fading from `alive` towards `dead`.
+    pub states: Vec<Rgb>,
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme {
+            alive: Rgb::WHITE,
+            dead: Rgb::DARK_GREY,
+            background: Rgb::BLACK,
+            grid_lines: Rgb::GREY,
+            old: None,
+            states: Vec::new(),
+        }
+    }
+}
+
+impl Theme {
+    /// Get the colors indexed by cell state for a multi-state rule, as built
+    /// by [state_palette] with the theme's states overriding the first.
+    ///
+    /// # Arguments
+    /// * `num_states` - Number of cell states.
+    pub fn get_state_palette(&self, num_states: u8) -> Vec<Rgb> {
+        let mut palette = state_palette(self.alive, self.dead, num_states);
+
+        for (color, &state) in palette.iter_mut().zip(self.states.iter()) {
+            *color = state;
+        }
+
+        palette
+    }
+}
+
+/// A destination for the frames of an animation, implemented by each
+/// animation exporter so that they all share the run loop of [record].
+pub trait FrameSink {
+    /// Error raised while adding a frame or finishing the animation.
+    type Error;
+
+    /// Add a frame showing a [Grid] after the frames added so far.
+    ///
+    /// # Arguments
+    /// * `grid` - The [Grid] of the frame, the size the sink was created for.
+    fn add_frame(&mut self, grid: &Grid) -> Result<(), Self::Error>;
+
+    /// Finish the animation once every frame was added.
+    fn finish(self) -> Result<(), Self::Error>
+    where
+        Self: Sized;
+}
+
+/// Record a simulation as an animation, one frame per generation.
+///
+/// The first frame is the current generation, followed by one frame for each
+/// of the next `generations` generations. The simulation is left at the last
+/// recorded generation.
+///
+/// # Arguments
+/// * `sim` - The simulation to record.
+/// * `generations` - The number of generations to advance the simulation.
+/// * `sink` - The [FrameSink] receiving the frames.
+pub fn record<S: FrameSink>(
+    sim: &mut ConwaySim,
+    generations: u32,
+    mut sink: S,
+) -> Result<(), S::Error> {
+    for frame in 0..=generations {
+        if frame > 0 {
+            sim.step();
+        }
+
+        sink.add_frame(sim.get_grid())?;
+    }
+
+    sink.finish()
+}
+
+/// Palette index of a dead cell in a [Raster].
+#[cfg(any(feature = "gif", feature = "png", feature = "webp"))]
+pub(crate) const DEAD: u8 = 0;
+
+/// Palette index of a live cell in a [Raster].
+#[cfg(any(feature = "gif", feature = "png", feature = "webp"))]
+pub(crate) const ALIVE: u8 = 1;
+
+/// Palette index of a grid line in a [Raster].
+#[cfg(any(feature = "gif", feature = "png", feature = "webp"))]
+pub(crate) const GRID_LINE: u8 = 2;
+
+/// A [Grid] rasterized into rows of palette indices.
+#[cfg(any(feature = "gif", feature = "png", feature = "webp"))]
+pub(crate) struct Raster {
+    /// Width of the image in pixels.
+    pub(crate) width: u64,
+
+    /// Height of the image in pixels.
+    pub(crate) height: u64,
+
+    /// Size of a cell in pixels, excluding grid lines.
+    cell_size: u32,
+
+    /// Whether grid lines are drawn around each cell.
+    grid_lines: bool,
+
+    /// The palette indices, row by row.
+    pub(crate) pixels: Vec<u8>,
+}
+
+#[cfg(any(feature = "gif", feature = "png", feature = "webp"))]
+impl Raster {
+    /// Create a new, empty [Raster] sized for a [Grid].
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the [Grid].
+    /// * `num_cols` - The number of columns (width) of the [Grid].
+    /// * `cell_size` - Size of a cell in pixels.
+    /// * `grid_lines` - Whether a one pixel grid line surrounds each cell.
+    pub(crate) fn new(num_rows: u32, num_cols: u32, cell_size: u32, grid_lines: bool) -> Raster {
+        let pitch = cell_size as u64 + grid_lines as u64;
+
+        Raster {
+            width: num_cols as u64 * pitch + grid_lines as u64,
+            height: num_rows as u64 * pitch + grid_lines as u64,
+            cell_size,
+            grid_lines,
+            pixels: Vec::new(),
+        }
+    }
+
+    /// Draw a [Grid] of the size the [Raster] was created for.
+    pub(crate) fn draw(&mut self, grid: &Grid) {
+        let width = self.width as usize;
+        let cell_size = self.cell_size as usize;
+
+        self.pixels.clear();
+
+        for row in 0..grid.get_num_rows() {
+            if self.grid_lines {
+                self.pixels.extend(std::iter::repeat_n(GRID_LINE, width));
+            }
+
+            let start = self.pixels.len();
+
+            for col in 0..grid.get_num_cols() {
+                if self.grid_lines {
+                    self.pixels.push(GRID_LINE);
+                }
+
+                let index = if grid.get(row, col).is_alive() {
+                    ALIVE
+                } else {
+                    DEAD
+                };
+
+                self.pixels.extend(std::iter::repeat_n(index, cell_size));
+            }
+
+            if self.grid_lines {
+                self.pixels.push(GRID_LINE);
+            }
+
+            // repeat the pixel row for the height of the cells
+            for _ in 1..cell_size {
+                self.pixels.extend_from_within(start..start + width);
+            }
+        }
+
+        if self.grid_lines {
+            self.pixels.extend(std::iter::repeat_n(GRID_LINE, width));
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Rendering of grids as PNG images.
+
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use ::png::{BitDepth, ColorType, Encoder, EncodingError};
+
+use super::{Raster, Rgb, Theme};
+use crate::game::Grid;
+
+/// Error raised while rendering a PNG.
+#[derive(Debug)]
+pub enum PngError {
+    /// The cell size is zero.
+    InvalidCellSize,
+
+    /// The image would exceed the maximum PNG dimensions.
+    TooLarge { width: u64, height: u64 },
+
+    /// A frame of an animated PNG is not the size of the grid the sink was
+    /// created for.
+    WrongFrameSize { num_rows: u32, num_cols: u32 },
+
+    /// The image could not be encoded.
+    Encoding(EncodingError),
+
+    /// The PNG file could not be written.
+    Io(io::Error),
+}
+
+impl fmt::Display for PngError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PngError::InvalidCellSize => write!(f, "cell size must be at least 1 pixel"),
+            PngError::TooLarge { width, height } => write!(
+                f,
+                "an image of {}x{} pixels exceeds the maximum PNG size",
+                width, height
+            ),
+            PngError::WrongFrameSize { num_rows, num_cols } => write!(
+                f,
+                "frame of {}x{} cells differs from the size of the animation",
+                num_rows, num_cols
+            ),
+            PngError::Encoding(err) => write!(f, "unable to encode PNG: {}", err),
+            PngError::Io(err) => write!(f, "unable to write PNG file: {}", err),
+        }
+    }
+}
+
+impl error::Error for PngError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            PngError::Encoding(err) => Some(err),
+            PngError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PngError {
+    fn from(err: io::Error) -> PngError {
+        PngError::Io(err)
+    }
+}
+
+impl From<EncodingError> for PngError {
+    fn from(err: EncodingError) -> PngError {
+        PngError::Encoding(err)
+    }
+}
+
+/// Options controlling how a [Grid] is rendered.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PngOptions {
+    /// Size of a cell in pixels, excluding grid lines.
+    pub cell_size: u32,
+
+    /// Color of live cells.
+    pub alive: Rgb,
+
+    /// Color of dead cells.
+    pub dead: Rgb,
+
+    /// Color of the one pixel grid lines drawn around each cell, if any.
+    pub grid_lines: Option<Rgb>,
+}
+
+impl Default for PngOptions {
+    fn default() -> PngOptions {
+        PngOptions {
+            cell_size: 8,
+            alive: Rgb::WHITE,
+            dead: Rgb::DARK_GREY,
+            grid_lines: None,
+        }
+    }
+}
+
+impl From<&Theme> for PngOptions {
+    /// Take the cell colors of a [Theme], without grid lines unless set to
+    /// the theme's afterwards.
+    fn from(theme: &Theme) -> PngOptions {
+        PngOptions {
+            alive: theme.alive,
+            dead: theme.dead,
+            ..PngOptions::default()
+        }
+    }
+}
+
+/// Render a [Grid] as an encoded PNG image.
+///
+/// # Arguments
+/// * `grid` - The [Grid] to render.
+/// * `options` - The [PngOptions] of the image.
+pub fn encode(grid: &Grid, options: &PngOptions) -> Result<Vec<u8>, PngError> {
+    let mut png: Vec<u8> = Vec::new();
+    write(grid, options, &mut png)?;
+
+    Ok(png)
+}
+
+/// Render a [Grid] to a PNG file.
+///
+/// # Arguments
+/// * `grid` - The [Grid] to render.
+/// * `options` - The [PngOptions] of the image.
+/// * `path` - Path of the PNG file.
+pub fn save<P: AsRef<Path>>(grid: &Grid, options: &PngOptions, path: P) -> Result<(), PngError> {
+    let file = BufWriter::new(File::create(path)?);
+
+    write(grid, options, file)
+}
+
+/// Render a [Grid] as a PNG image into a writer.
+///
+/// # Arguments
+/// * `grid` - The [Grid] to render.
+/// * `options` - The [PngOptions] of the image.
+/// * `writer` - Destination of the PNG image.
+pub fn write<W: io::Write>(grid: &Grid, options: &PngOptions, writer: W) -> Result<(), PngError> {
+    if options.cell_size == 0 {
+        return Err(PngError::InvalidCellSize);
+    }
+
+    let mut raster = Raster::new(
+        grid.get_num_rows(),
+        grid.get_num_cols(),
+        options.cell_size,
+        options.grid_lines.is_some(),
+    );
+
+    if raster.width > i32::MAX as u64 || raster.height > i32::MAX as u64 {
+        return Err(PngError::TooLarge {
+            width: raster.width,
+            height: raster.height,
+        });
+    }
+
+    raster.draw(grid);
+
+    let grid_lines = options.grid_lines.unwrap_or(options.dead);
+    let palette: Vec<u8> = [options.dead, options.alive, grid_lines]
+        .iter()
+        .flat_map(|&Rgb(red, green, blue)| [red, green, blue])
+        .collect();
+
+    let mut encoder = Encoder::new(writer, raster.width as u32, raster.height as u32);
+    encoder.set_color(ColorType::Indexed);
+    encoder.set_depth(BitDepth::Eight);
+    encoder.set_palette(palette);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&raster.pixels)?;
+    writer.finish()?;
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! Terminal rendering of a [Grid3D] as a row of two-dimensional slices.
+//!
+//! Each layer is drawn as a grid of text symbols headed by its layer number,
+//! and successive layers are placed side by side, wrapping onto further bands
+//! of slices once a band holds `slices_per_band` of them.
+
+use crate::life3d::Grid3D;
+
+/// Columns of spaces between neighboring slices.
+const GAP: usize = 2;
+
+/// Render the layers of a [Grid3D] side by side.
+///
+/// # Arguments
+/// * `grid` - The [Grid3D] to draw.
+/// * `slices_per_band` - The number of layers drawn side by side before
+///   wrapping onto a new band of slices.
+pub fn render(grid: &Grid3D, slices_per_band: u32) -> String {
+    let layers: Vec<u32> = (0..grid.get_num_layers()).collect();
+    let mut frame = String::new();
+
+    for (band, band_layers) in layers.chunks(slices_per_band.max(1) as usize).enumerate() {
+        if band > 0 {
+            frame.push('\n');
+        }
+
+        let headers: Vec<String> = band_layers
+            .iter()
+            .map(|layer| format!("z={}", layer))
+            .collect();
+
+        // each slice is as wide as its cells or its header, whichever is wider
+        let widths: Vec<usize> = headers
+            .iter()
+            .map(|header| header.chars().count().max(grid.get_num_cols() as usize) + GAP)
+            .collect();
+
+        let mut line = String::new();
+        for (header, &width) in headers.iter().zip(widths.iter()) {
+            line.push_str(&format!("{:width$}", header, width = width));
+        }
+        frame.push_str(line.trim_end());
+        frame.push('\n');
+
+        for row in 0..grid.get_num_rows() {
+            let mut line = String::new();
+
+            for (&layer, &width) in band_layers.iter().zip(widths.iter()) {
+                for col in 0..grid.get_num_cols() {
+                    let alive = grid.is_cell_alive(layer, row, col);
+                    line.push(if alive { '◼' } else { '◻' });
+                }
+
+                let padding = width - grid.get_num_cols() as usize;
+                line.push_str(&" ".repeat(padding));
+            }
+
+            frame.push_str(line.trim_end());
+            frame.push('\n');
+        }
+    }
+
+    frame
+}
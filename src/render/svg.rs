@@ -0,0 +1,217 @@
+//! Rendering of grids as SVG vector images, which scale to any size for
+//! papers and posters.
+//!
+//! Live cells are drawn as rectangles over a background of the dead cell
+//! color. Runs of live cells along a row are merged into a single rectangle
+//! unless their corners are rounded, keeping the files of large grids small.
+
+use std::error;
+use std::fmt;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use super::{Rgb, Theme};
+use crate::game::Grid;
+
+/// Width of the grid lines as a fraction of the cell size.
+const GRID_LINE_WIDTH: f64 = 0.05;
+
+/// Error raised while rendering an SVG.
+#[derive(Debug)]
+pub enum SvgError {
+    /// The cell size is not a positive, finite number.
+    InvalidCellSize,
+
+    /// The SVG file could not be written.
+    Io(io::Error),
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SvgError::InvalidCellSize => write!(f, "cell size must be a positive number"),
+            SvgError::Io(err) => write!(f, "unable to write SVG file: {}", err),
+        }
+    }
+}
+
+impl error::Error for SvgError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            SvgError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SvgError {
+    fn from(err: io::Error) -> SvgError {
+        SvgError::Io(err)
+    }
+}
+
+/// Style of the cells of an SVG image.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SvgStyle {
+    /// Size of a cell in SVG user units (pixels when shown unscaled).
+    pub cell_size: f64,
+
+    /// Radius of the corners of live cells as a fraction of the cell size,
+    /// from 0.0 for squares to 0.5 for circles.
+    pub corner_radius: f64,
+
+    /// Color of live cells.
+    pub alive: Rgb,
+
+    /// Color of dead cells, or [None] for a transparent background.
+    pub dead: Option<Rgb>,
+
+    /// Color of the lines drawn between cells, if any.
+    pub grid_lines: Option<Rgb>,
+}
+
+impl Default for SvgStyle {
+    fn default() -> SvgStyle {
+        SvgStyle {
+            cell_size: 10.0,
+            corner_radius: 0.0,
+            alive: Rgb::WHITE,
+            dead: Some(Rgb::DARK_GREY),
+            grid_lines: None,
+        }
+    }
+}
+
+impl From<&Theme> for SvgStyle {
+    /// Take the cell colors of a [Theme], without grid lines unless set to
+    /// the theme's afterwards.
+    fn from(theme: &Theme) -> SvgStyle {
+        SvgStyle {
+            alive: theme.alive,
+            dead: Some(theme.dead),
+            ..SvgStyle::default()
+        }
+    }
+}
+
+/// Format a color as an SVG hex color such as `#ffffff`.
+fn hex(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2)
+}
+
+/// Render a [Grid] as the text of an SVG image.
+///
+/// # Arguments
+/// * `grid` - The [Grid] to render.
+/// * `style` - The [SvgStyle] of the cells.
+pub fn encode(grid: &Grid, style: &SvgStyle) -> Result<String, SvgError> {
+    if !(style.cell_size.is_finite() && style.cell_size > 0.0) {
+        return Err(SvgError::InvalidCellSize);
+    }
+
+    let size = style.cell_size;
+    let (num_rows, num_cols) = (grid.get_num_rows(), grid.get_num_cols());
+    let (width, height) = (num_cols as f64 * size, num_rows as f64 * size);
+    let radius = style.corner_radius.clamp(0.0, 0.5) * size;
+
+    // writing to a String cannot fail
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">",
+        w = width,
+        h = height
+    );
+
+    if let Some(dead) = style.dead {
+        let _ = writeln!(
+            svg,
+            "<rect width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            width,
+            height,
+            hex(dead)
+        );
+    }
+
+    let _ = writeln!(svg, "<g fill=\"{}\">", hex(style.alive));
+    for row in 0..num_rows {
+        let mut col = 0;
+
+        while col < num_cols {
+            if !grid.get(row, col).is_alive() {
+                col += 1;
+                continue;
+            }
+
+            // merge the run of live cells, unless each is rounded
+            let start = col;
+            col += 1;
+            while radius == 0.0 && col < num_cols && grid.get(row, col).is_alive() {
+                col += 1;
+            }
+
+            let _ = write!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"",
+                start as f64 * size,
+                row as f64 * size,
+                (col - start) as f64 * size,
+                size
+            );
+            if radius > 0.0 {
+                let _ = write!(svg, " rx=\"{}\"", radius);
+            }
+            let _ = writeln!(svg, "/>");
+        }
+    }
+    let _ = writeln!(svg, "</g>");
+
+    if let Some(grid_lines) = style.grid_lines {
+        let mut path = String::new();
+        for row in 0..=num_rows {
+            let _ = write!(path, "M0 {}H{}", row as f64 * size, width);
+        }
+        for col in 0..=num_cols {
+            let _ = write!(path, "M{} 0V{}", col as f64 * size, height);
+        }
+
+        let _ = writeln!(
+            svg,
+            "<path d=\"{}\" stroke=\"{}\" stroke-width=\"{}\" fill=\"none\"/>",
+            path,
+            hex(grid_lines),
+            size * GRID_LINE_WIDTH
+        );
+    }
+
+    svg.push_str("</svg>\n");
+
+    Ok(svg)
+}
+
+/// Render a [Grid] as an SVG image into a writer.
+///
+/// # Arguments
+/// * `grid` - The [Grid] to render.
+/// * `style` - The [SvgStyle] of the cells.
+/// * `writer` - The writer the SVG is written to.
+pub fn write<W: Write>(grid: &Grid, style: &SvgStyle, mut writer: W) -> Result<(), SvgError> {
+    writer.write_all(encode(grid, style)?.as_bytes())?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Render a [Grid] to an SVG file.
+///
+/// # Arguments
+/// * `grid` - The [Grid] to render.
+/// * `style` - The [SvgStyle] of the cells.
+/// * `path` - Path of the SVG file.
+pub fn export<P: AsRef<Path>>(grid: &Grid, style: &SvgStyle, path: P) -> Result<(), SvgError> {
+    let file = BufWriter::new(File::create(path)?);
+
+    write(grid, style, file)
+}
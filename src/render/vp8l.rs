@@ -0,0 +1,501 @@
+//! Encoding of images in VP8L, the lossless bitstream of WebP.
+//!
+//! The encoder aims to be small rather than thorough. A color indexing
+//! transform packs the palette indices of up to 16 colors several to a byte,
+//! and backward references repeat runs of the previous pixel or of the pixel
+//! above, which covers the blocks of cells drawn by a [Raster]. Neither a
+//! color cache nor meta prefix codes are used.
+//!
+//! [Raster]: super::Raster
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::Rgb;
+
+/// Largest width or height of a VP8L image in pixels.
+pub(crate) const MAX_SIZE: u64 = 1 << 14;
+
+/// Signature starting every VP8L bitstream.
+const SIGNATURE: u32 = 0x2f;
+
+/// Type of the color indexing transform.
+const COLOR_INDEXING_TRANSFORM: u32 = 3;
+
+/// Number of literal symbols of each of the green, red, blue, and alpha
+/// alphabets.
+const NUM_LITERALS: usize = 256;
+
+/// Number of length prefix symbols following the literals of the green
+/// alphabet.
+const NUM_LENGTH_CODES: usize = 24;
+
+/// Number of symbols of the distance alphabet.
+const NUM_DISTANCE_CODES: usize = 40;
+
+/// Shortest run worth a backward reference rather than literals.
+const MIN_LENGTH: usize = 3;
+
+/// Longest run of a backward reference.
+const MAX_LENGTH: usize = 4096;
+
+/// Distance code of the pixel above, (0, 1) in the distance map.
+const PIXEL_ABOVE: u32 = 1;
+
+/// Distance code of the previous pixel, (1, 0) in the distance map.
+const PREVIOUS_PIXEL: u32 = 2;
+
+/// Longest code of a prefix code.
+const MAX_CODE_LENGTH: u8 = 15;
+
+/// Longest code of the prefix code of code lengths.
+const MAX_CODE_LENGTH_CODE_LENGTH: u8 = 7;
+
+/// Order the code lengths of the prefix code of code lengths are written in.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    17, 18, 0, 1, 2, 3, 4, 5, 16, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+];
+
+/// Writer of a bitstream, least significant bit first.
+struct BitWriter {
+    /// The bytes written so far.
+    bytes: Vec<u8>,
+
+    /// The bits not yet written as a byte.
+    bits: u64,
+
+    /// Number of bits not yet written as a byte.
+    num_bits: u32,
+}
+
+impl BitWriter {
+    /// Create a new, empty [BitWriter].
+    fn new() -> BitWriter {
+        BitWriter {
+            bytes: Vec::new(),
+            bits: 0,
+            num_bits: 0,
+        }
+    }
+
+    /// Write the low `num_bits` bits of a value, at most 32.
+    fn write(&mut self, value: u32, num_bits: u32) {
+        self.bits |= (value as u64) << self.num_bits;
+        self.num_bits += num_bits;
+
+        while self.num_bits >= 8 {
+            self.bytes.push(self.bits as u8);
+            self.bits >>= 8;
+            self.num_bits -= 8;
+        }
+    }
+
+    /// Get the bytes written, padding the last byte with zeros.
+    fn finish(mut self) -> Vec<u8> {
+        if self.num_bits > 0 {
+            self.bytes.push(self.bits as u8);
+        }
+
+        self.bytes
+    }
+}
+
+/// A symbol of the LZ77 coded image.
+#[derive(Copy, Clone, Debug)]
+enum Token {
+    /// A pixel as ARGB.
+    Literal(u32),
+
+    /// A run of pixels repeating earlier pixels.
+    Copy { length: u32, distance_code: u32 },
+}
+
+/// Split a length or distance code of at least 1 into its prefix symbol, the
+/// number of extra bits, and the value of the extra bits.
+fn prefix_encode(value: u32) -> (usize, u32, u32) {
+    let value = value - 1;
+    if value < 4 {
+        return (value as usize, 0, 0);
+    }
+
+    let highest_bit = 31 - value.leading_zeros();
+    let second_highest_bit = (value >> (highest_bit - 1)) & 1;
+    let num_extra_bits = highest_bit - 1;
+
+    (
+        (2 * highest_bit + second_highest_bit) as usize,
+        num_extra_bits,
+        value & ((1 << num_extra_bits) - 1),
+    )
+}
+
+/// A canonical prefix code of an alphabet.
+struct PrefixCode {
+    /// The length of the code of each symbol, 0 if unused.
+    lengths: Vec<u8>,
+
+    /// The code of each symbol, bit-reversed to be written least significant
+    /// bit first.
+    codes: Vec<u16>,
+}
+
+impl PrefixCode {
+    /// Create the canonical [PrefixCode] with the code lengths of each symbol.
+    fn from_lengths(lengths: Vec<u8>) -> PrefixCode {
+        let mut counts = [0u16; MAX_CODE_LENGTH as usize + 1];
+        for &length in lengths.iter().filter(|&&length| length > 0) {
+            counts[length as usize] += 1;
+        }
+
+        let mut next_code = [0u16; MAX_CODE_LENGTH as usize + 1];
+        for length in 1..=MAX_CODE_LENGTH as usize {
+            next_code[length] = (next_code[length - 1] + counts[length - 1]) << 1;
+        }
+
+        let codes = lengths
+            .iter()
+            .map(|&length| {
+                if length == 0 {
+                    return 0;
+                }
+
+                let code = next_code[length as usize];
+                next_code[length as usize] += 1;
+
+                code.reverse_bits() >> (16 - length)
+            })
+            .collect();
+
+        PrefixCode { lengths, codes }
+    }
+
+    /// Write the code of a symbol.
+    fn write(&self, writer: &mut BitWriter, symbol: usize) {
+        writer.write(self.codes[symbol] as u32, self.lengths[symbol] as u32);
+    }
+}
+
+/// Get the lengths of the codes of an optimal prefix code for the
+/// frequencies of the symbols of an alphabet, none longer than a limit.
+///
+/// At least two symbols get a code, so that the code is complete, even if
+/// fewer are used.
+fn code_lengths(frequencies: &[u32], limit: u8) -> Vec<u8> {
+    let mut symbols: Vec<usize> = (0..frequencies.len())
+        .filter(|&symbol| frequencies[symbol] > 0)
+        .collect();
+    let missing = 2usize.saturating_sub(symbols.len());
+    symbols.extend(
+        (0..frequencies.len())
+            .filter(|&symbol| frequencies[symbol] == 0)
+            .take(missing),
+    );
+
+    // flatten the frequencies until the Huffman tree is shallow enough
+    let mut lengths = vec![0; frequencies.len()];
+    let mut floor = 1;
+    loop {
+        let mut parents = vec![None; symbols.len()];
+        let mut nodes: BinaryHeap<Reverse<(u64, usize)>> = symbols
+            .iter()
+            .enumerate()
+            .map(|(leaf, &symbol)| Reverse(((frequencies[symbol] as u64).max(floor), leaf)))
+            .collect();
+
+        while let (Some(Reverse((weight1, node1))), Some(Reverse((weight2, node2)))) =
+            (nodes.pop(), nodes.pop())
+        {
+            let parent = parents.len();
+            parents.push(None);
+            parents[node1] = Some(parent);
+            parents[node2] = Some(parent);
+            nodes.push(Reverse((weight1 + weight2, parent)));
+        }
+
+        let mut deepest = 0;
+        for (leaf, &symbol) in symbols.iter().enumerate() {
+            let mut depth = 0;
+            let mut node = leaf;
+            while let Some(parent) = parents[node] {
+                node = parent;
+                depth += 1;
+            }
+
+            lengths[symbol] = depth;
+            deepest = deepest.max(depth);
+        }
+
+        if deepest <= limit {
+            return lengths;
+        }
+
+        floor *= 2;
+    }
+}
+
+/// Write a prefix code fitted to the frequencies of the symbols of an
+/// alphabet, and get the code.
+fn write_prefix_code(writer: &mut BitWriter, frequencies: &[u32]) -> PrefixCode {
+    let used: Vec<usize> = (0..frequencies.len())
+        .filter(|&symbol| frequencies[symbol] > 0)
+        .collect();
+
+    // a simple code of one or two 8 bit symbols, one symbol taking no bits
+    if used.len() <= 2 && used.iter().all(|&symbol| symbol < NUM_LITERALS) {
+        let symbols = if used.is_empty() { vec![0] } else { used };
+
+        writer.write(1, 1);
+        writer.write(symbols.len() as u32 - 1, 1);
+        if symbols[0] <= 1 {
+            writer.write(0, 1);
+            writer.write(symbols[0] as u32, 1);
+        } else {
+            writer.write(1, 1);
+            writer.write(symbols[0] as u32, 8);
+        }
+
+        let mut lengths = vec![0; frequencies.len()];
+        if let [first, second] = symbols[..] {
+            writer.write(second as u32, 8);
+            lengths[first] = 1;
+            lengths[second] = 1;
+        }
+
+        return PrefixCode::from_lengths(lengths);
+    }
+
+    let lengths = code_lengths(frequencies, MAX_CODE_LENGTH);
+
+    // the code lengths with runs of zeros shortened to repeat symbols, each
+    // (symbol, number of extra bits, extra bits)
+    let mut tokens = Vec::new();
+    let mut symbol = 0;
+    while symbol < lengths.len() {
+        let zeros = lengths[symbol..]
+            .iter()
+            .take_while(|&&length| length == 0)
+            .count();
+
+        if zeros >= 11 {
+            let run = zeros.min(138);
+            tokens.push((18, 7, run as u32 - 11));
+            symbol += run;
+        } else if zeros >= 3 {
+            tokens.push((17, 3, zeros as u32 - 3));
+            symbol += zeros;
+        } else {
+            tokens.push((lengths[symbol] as usize, 0, 0));
+            symbol += 1;
+        }
+    }
+
+    let mut frequencies = [0; CODE_LENGTH_ORDER.len()];
+    for &(symbol, _, _) in tokens.iter() {
+        frequencies[symbol] += 1;
+    }
+    let code_length_code =
+        PrefixCode::from_lengths(code_lengths(&frequencies, MAX_CODE_LENGTH_CODE_LENGTH));
+
+    let num_code_lengths = CODE_LENGTH_ORDER
+        .iter()
+        .rposition(|&symbol| code_length_code.lengths[symbol] > 0)
+        .map_or(0, |last| last + 1)
+        .max(4);
+
+    writer.write(0, 1);
+    writer.write(num_code_lengths as u32 - 4, 4);
+    for &symbol in CODE_LENGTH_ORDER[..num_code_lengths].iter() {
+        writer.write(code_length_code.lengths[symbol] as u32, 3);
+    }
+
+    // every symbol of the alphabet is coded
+    writer.write(0, 1);
+    for (symbol, num_extra_bits, extra_bits) in tokens {
+        code_length_code.write(writer, symbol);
+        writer.write(extra_bits, num_extra_bits);
+    }
+
+    PrefixCode::from_lengths(lengths)
+}
+
+/// Split pixels into literals and runs repeating the previous pixel or the
+/// pixel above, taking the longer run.
+fn backward_references(pixels: &[u32], width: usize) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < pixels.len() {
+        let limit = MAX_LENGTH.min(pixels.len() - index);
+        let run = |distance: usize| {
+            if index < distance {
+                return 0;
+            }
+
+            (index..index + limit)
+                .take_while(|&pixel| pixels[pixel] == pixels[pixel - distance])
+                .count()
+        };
+
+        let (previous, above) = (run(1), run(width));
+        let (length, distance_code) = if previous >= above {
+            (previous, PREVIOUS_PIXEL)
+        } else {
+            (above, PIXEL_ABOVE)
+        };
+
+        if length >= MIN_LENGTH {
+            tokens.push(Token::Copy {
+                length: length as u32,
+                distance_code,
+            });
+            index += length;
+        } else {
+            tokens.push(Token::Literal(pixels[index]));
+            index += 1;
+        }
+    }
+
+    tokens
+}
+
+/// Write an image of ARGB pixels as prefix coded LZ77.
+///
+/// # Arguments
+/// * `writer` - The writer of the bitstream.
+/// * `pixels` - The pixels, row by row.
+/// * `width` - The width of the image in pixels.
+/// * `is_main` - Whether the image is the main image rather than the image
+///   of a transform.
+fn write_image(writer: &mut BitWriter, pixels: &[u32], width: usize, is_main: bool) {
+    let tokens = backward_references(pixels, width);
+
+    // no color cache
+    writer.write(0, 1);
+    if is_main {
+        // no meta prefix codes
+        writer.write(0, 1);
+    }
+
+    let mut green = vec![0; NUM_LITERALS + NUM_LENGTH_CODES];
+    let mut red = vec![0; NUM_LITERALS];
+    let mut blue = vec![0; NUM_LITERALS];
+    let mut alpha = vec![0; NUM_LITERALS];
+    let mut distance = vec![0; NUM_DISTANCE_CODES];
+
+    for &token in tokens.iter() {
+        match token {
+            Token::Literal(argb) => {
+                green[(argb >> 8) as usize & 0xff] += 1;
+                red[(argb >> 16) as usize & 0xff] += 1;
+                blue[argb as usize & 0xff] += 1;
+                alpha[(argb >> 24) as usize] += 1;
+            }
+            Token::Copy {
+                length,
+                distance_code,
+            } => {
+                green[NUM_LITERALS + prefix_encode(length).0] += 1;
+                distance[prefix_encode(distance_code).0] += 1;
+            }
+        }
+    }
+
+    let green = write_prefix_code(writer, &green);
+    let red = write_prefix_code(writer, &red);
+    let blue = write_prefix_code(writer, &blue);
+    let alpha = write_prefix_code(writer, &alpha);
+    let distance = write_prefix_code(writer, &distance);
+
+    for token in tokens {
+        match token {
+            Token::Literal(argb) => {
+                green.write(writer, (argb >> 8) as usize & 0xff);
+                red.write(writer, (argb >> 16) as usize & 0xff);
+                blue.write(writer, argb as usize & 0xff);
+                alpha.write(writer, (argb >> 24) as usize);
+            }
+            Token::Copy {
+                length,
+                distance_code,
+            } => {
+                let (symbol, num_extra_bits, extra_bits) = prefix_encode(length);
+                green.write(writer, NUM_LITERALS + symbol);
+                writer.write(extra_bits, num_extra_bits);
+
+                let (symbol, num_extra_bits, extra_bits) = prefix_encode(distance_code);
+                distance.write(writer, symbol);
+                writer.write(extra_bits, num_extra_bits);
+            }
+        }
+    }
+}
+
+/// Encode an image of palette indices as a VP8L bitstream.
+///
+/// # Arguments
+/// * `width` - The width of the image in pixels, from 1 to [MAX_SIZE].
+/// * `height` - The height of the image in pixels, from 1 to [MAX_SIZE].
+/// * `palette` - The colors of the indices, from 1 to 256 colors.
+/// * `indices` - The palette index of each pixel, row by row.
+pub(crate) fn encode(width: u32, height: u32, palette: &[Rgb], indices: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter::new();
+
+    writer.write(SIGNATURE, 8);
+    writer.write(width - 1, 14);
+    writer.write(height - 1, 14);
+    // no alpha, version 0
+    writer.write(0, 1);
+    writer.write(0, 3);
+
+    // the palette, each color but the first stored as the difference from
+    // the previous color
+    writer.write(1, 1);
+    writer.write(COLOR_INDEXING_TRANSFORM, 2);
+    writer.write(palette.len() as u32 - 1, 8);
+
+    let colors: Vec<u32> = palette
+        .iter()
+        .map(|&Rgb(red, green, blue)| u32::from_be_bytes([0xff, red, green, blue]))
+        .collect();
+    let differences: Vec<u32> = (0..colors.len())
+        .map(|index| match index {
+            0 => colors[0],
+            _ => {
+                let (color, previous) =
+                    (colors[index].to_le_bytes(), colors[index - 1].to_le_bytes());
+                u32::from_le_bytes([
+                    color[0].wrapping_sub(previous[0]),
+                    color[1].wrapping_sub(previous[1]),
+                    color[2].wrapping_sub(previous[2]),
+                    color[3].wrapping_sub(previous[3]),
+                ])
+            }
+        })
+        .collect();
+    write_image(&mut writer, &differences, differences.len(), false);
+
+    // no further transforms
+    writer.write(0, 1);
+
+    // bundle 2, 4, or 8 indices of small palettes into the green channel
+    let width_bits = match palette.len() {
+        0..=2 => 3,
+        3..=4 => 2,
+        5..=16 => 1,
+        _ => 0,
+    };
+    let bits_per_index = 8 >> width_bits;
+    let width = width as usize;
+    let packed_width = (width + (1 << width_bits) - 1) >> width_bits;
+
+    let mut packed = vec![0xff00_0000; packed_width * height as usize];
+    for (row, indices) in indices.chunks(width).enumerate() {
+        for (col, &index) in indices.iter().enumerate() {
+            let shift = 8 + (col & ((1 << width_bits) - 1)) * bits_per_index;
+            packed[row * packed_width + (col >> width_bits)] |= (index as u32) << shift;
+        }
+    }
+    write_image(&mut writer, &packed, packed_width, true);
+
+    writer.finish()
+}
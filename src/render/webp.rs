@@ -0,0 +1,278 @@
+//! Export of simulations as animated WebPs, losslessly compressed far
+//! smaller than GIFs and not limited to 256 colors.
+
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use super::{vp8l, FrameSink, Raster, Rgb, Theme};
+use crate::game::{ConwaySim, Grid};
+
+/// Delay between frames in milliseconds.
+const FRAME_DELAY_MS: u32 = 100;
+
+/// Flag of the VP8X chunk marking an animation.
+const ANIMATION_FLAG: u8 = 0x02;
+
+/// Flag of an ANMF chunk replacing the canvas rather than alpha blending.
+const NO_BLENDING_FLAG: u8 = 0x02;
+
+/// Error raised while exporting a WebP.
+#[derive(Debug)]
+pub enum WebpError {
+    /// The cell size is zero.
+    InvalidCellSize,
+
+    /// The frames would exceed the maximum lossless WebP dimensions of 16384
+    /// pixels.
+    TooLarge { width: u64, height: u64 },
+
+    /// A frame is not the size of the grid the sink was created for.
+    WrongFrameSize { num_rows: u32, num_cols: u32 },
+
+    /// The WebP file could not be written.
+    Io(io::Error),
+}
+
+impl fmt::Display for WebpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WebpError::InvalidCellSize => write!(f, "cell size must be at least 1 pixel"),
+            WebpError::TooLarge { width, height } => write!(
+                f,
+                "frames of {}x{} pixels exceed the maximum WebP size",
+                width, height
+            ),
+            WebpError::WrongFrameSize { num_rows, num_cols } => write!(
+                f,
+                "frame of {}x{} cells differs from the size of the animation",
+                num_rows, num_cols
+            ),
+            WebpError::Io(err) => write!(f, "unable to write WebP file: {}", err),
+        }
+    }
+}
+
+impl error::Error for WebpError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            WebpError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for WebpError {
+    fn from(err: io::Error) -> WebpError {
+        WebpError::Io(err)
+    }
+}
+
+/// Options controlling how the frames of a WebP are rendered.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct WebpOptions {
+    /// Size of a cell in pixels, excluding grid lines.
+    pub cell_size: u32,
+
+    /// Color of live cells.
+    pub alive: Rgb,
+
+    /// Color of dead cells.
+    pub dead: Rgb,
+
+    /// Color of the one pixel grid lines drawn around each cell, if any.
+    pub grid_lines: Option<Rgb>,
+}
+
+impl Default for WebpOptions {
+    fn default() -> WebpOptions {
+        WebpOptions {
+            cell_size: 8,
+            alive: Rgb::WHITE,
+            dead: Rgb::DARK_GREY,
+            grid_lines: None,
+        }
+    }
+}
+
+impl From<&Theme> for WebpOptions {
+    /// Take the cell colors of a [Theme], without grid lines unless set to
+    /// the theme's afterwards.
+    fn from(theme: &Theme) -> WebpOptions {
+        WebpOptions {
+            alive: theme.alive,
+            dead: theme.dead,
+            ..WebpOptions::default()
+        }
+    }
+}
+
+/// Append a RIFF chunk, padded to an even size.
+fn push_chunk(out: &mut Vec<u8>, fourcc: &[u8; 4], payload: &[u8]) {
+    out.extend_from_slice(fourcc);
+    out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    out.extend_from_slice(payload);
+
+    if payload.len() % 2 == 1 {
+        out.push(0);
+    }
+}
+
+/// Get the three low bytes of a value, least significant first.
+fn u24(value: u32) -> [u8; 3] {
+    let [low, middle, high, _] = value.to_le_bytes();
+
+    [low, middle, high]
+}
+
+/// A [FrameSink] encoding frames as an animated WebP which loops forever.
+///
+/// The frames are compressed as they are added, and the WebP is written once
+/// finished.
+pub struct WebpSink<W: Write> {
+    /// Destination of the WebP.
+    writer: W,
+
+    /// The raster the frames are drawn into.
+    raster: Raster,
+
+    /// The (rows, cols) size of the grids of the frames.
+    size: (u32, u32),
+
+    /// The colors of the dead cells, live cells, and grid lines.
+    palette: [Rgb; 3],
+
+    /// The ANMF chunks of the frames added so far.
+    frames: Vec<u8>,
+}
+
+impl<W: Write> WebpSink<W> {
+    /// Create a new [WebpSink] writing the WebP into a writer.
+    ///
+    /// # Arguments
+    /// * `writer` - Destination of the WebP.
+    /// * `num_rows` - The number of rows (height) of the grids of the frames.
+    /// * `num_cols` - The number of columns (width) of the grids of the frames.
+    /// * `options` - The [WebpOptions] of the frames.
+    pub fn new(
+        writer: W,
+        num_rows: u32,
+        num_cols: u32,
+        options: &WebpOptions,
+    ) -> Result<WebpSink<W>, WebpError> {
+        if options.cell_size == 0 {
+            return Err(WebpError::InvalidCellSize);
+        }
+
+        let raster = Raster::new(
+            num_rows,
+            num_cols,
+            options.cell_size,
+            options.grid_lines.is_some(),
+        );
+        let (width, height) = (raster.width, raster.height);
+
+        if width == 0 || height == 0 || width > vp8l::MAX_SIZE || height > vp8l::MAX_SIZE {
+            return Err(WebpError::TooLarge { width, height });
+        }
+
+        Ok(WebpSink {
+            writer,
+            raster,
+            size: (num_rows, num_cols),
+            palette: [
+                options.dead,
+                options.alive,
+                options.grid_lines.unwrap_or(options.dead),
+            ],
+            frames: Vec::new(),
+        })
+    }
+}
+
+impl<W: Write> FrameSink for WebpSink<W> {
+    type Error = WebpError;
+
+    fn add_frame(&mut self, grid: &Grid) -> Result<(), WebpError> {
+        let (num_rows, num_cols) = (grid.get_num_rows(), grid.get_num_cols());
+        if (num_rows, num_cols) != self.size {
+            return Err(WebpError::WrongFrameSize { num_rows, num_cols });
+        }
+
+        self.raster.draw(grid);
+
+        let (width, height) = (self.raster.width as u32, self.raster.height as u32);
+        let bitstream = vp8l::encode(width, height, &self.palette, &self.raster.pixels);
+
+        // the frame covers the canvas from its top left corner
+        let mut frame = Vec::with_capacity(bitstream.len() + 32);
+        frame.extend_from_slice(&u24(0));
+        frame.extend_from_slice(&u24(0));
+        frame.extend_from_slice(&u24(width - 1));
+        frame.extend_from_slice(&u24(height - 1));
+        frame.extend_from_slice(&u24(FRAME_DELAY_MS));
+        frame.push(NO_BLENDING_FLAG);
+        push_chunk(&mut frame, b"VP8L", &bitstream);
+
+        push_chunk(&mut self.frames, b"ANMF", &frame);
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<(), WebpError> {
+        let (width, height) = (self.raster.width as u32, self.raster.height as u32);
+
+        let mut canvas = vec![ANIMATION_FLAG, 0, 0, 0];
+        canvas.extend_from_slice(&u24(width - 1));
+        canvas.extend_from_slice(&u24(height - 1));
+
+        // an opaque background of the dead cell color, looping forever
+        let Rgb(red, green, blue) = self.palette[0];
+        let animation = [blue, green, red, 0xff, 0, 0];
+
+        let mut chunks = b"WEBP".to_vec();
+        push_chunk(&mut chunks, b"VP8X", &canvas);
+        push_chunk(&mut chunks, b"ANIM", &animation);
+        chunks.extend_from_slice(&self.frames);
+
+        self.writer.write_all(b"RIFF")?;
+        self.writer
+            .write_all(&(chunks.len() as u32).to_le_bytes())?;
+        self.writer.write_all(&chunks)?;
+        self.writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Export a simulation as an animated WebP which loops forever.
+///
+/// The first frame is the current generation, followed by one frame for each
+/// of the next `generations` generations. The simulation is left at the last
+/// exported generation.
+///
+/// # Arguments
+/// * `sim` - The simulation to export.
+/// * `generations` - The number of generations to advance the simulation.
+/// * `options` - The [WebpOptions] of the frames.
+/// * `path` - Path of the WebP file.
+pub fn export<P: AsRef<Path>>(
+    sim: &mut ConwaySim,
+    generations: u32,
+    options: &WebpOptions,
+    path: P,
+) -> Result<(), WebpError> {
+    let grid = sim.get_grid();
+    let (num_rows, num_cols) = (grid.get_num_rows(), grid.get_num_cols());
+
+    if options.cell_size == 0 {
+        return Err(WebpError::InvalidCellSize);
+    }
+
+    let file = BufWriter::new(File::create(path)?);
+    let sink = WebpSink::new(file, num_rows, num_cols, options)?;
+
+    super::record(sim, generations, sink)
+}
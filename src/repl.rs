@@ -0,0 +1,304 @@
+//! Interactive command mode driving a simulation from typed commands.
+
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use gameoflife_rs::analysis::measure_velocity;
+use gameoflife_rs::{patterns, Cell, ConwaySim, Overlap, Pattern, Rule};
+
+use crate::session::Session;
+
+/// Number of generations `velocity` measures over when none is given.
+const VELOCITY_GENERATIONS: u32 = 64;
+
+/// Largest speed, as a fraction of c, `velocity` counts as stationary.
+const STATIONARY_SPEED: f64 = 0.01;
+
+/// Summary of the commands, printed by `help`.
+const HELP: &str = "\
+commands:
+  step [N]           advance N generations (default 1)
+  set ROW COL        set a cell alive
+  unset ROW COL      set a cell dead
+  clear              set every cell dead
+  load PATTERN R C   place a built-in pattern or pattern file at (R, C)
+  rule [RULE]        show or change the rule, e.g. rule B36/S23
+  show               print the grid
+  stats              print statistics of the current generation
+  velocity [N]       measure how fast the pattern drifts over N generations
+                     (default 64) without advancing it
+  bookmark LABEL     bookmark the current generation under a label
+  bookmarks [list]   list the bookmarks
+  bookmarks goto LABEL
+                     return to a bookmarked generation
+  bookmarks remove LABEL
+                     remove a bookmark
+  help               print this summary
+  quit               exit";
+
+/// A command typed at the prompt.
+#[derive(Clone, Debug, PartialEq)]
+enum Command {
+    Step(u32),
+    Set(u32, u32, Cell),
+    Clear,
+    Load(String, u32, u32),
+    Rule(Option<Rule>),
+    Show,
+    Stats,
+    Velocity(u32),
+    Bookmark(String),
+    ListBookmarks,
+    GotoBookmark(String),
+    RemoveBookmark(String),
+    Help,
+    Quit,
+}
+
+/// Parse a numeric argument of a command.
+fn parse_arg<T: std::str::FromStr>(arg: Option<&str>, name: &str) -> Result<T, String> {
+    let arg = arg.ok_or_else(|| format!("missing {}", name))?;
+
+    arg.parse()
+        .map_err(|_| format!("invalid {} '{}'", name, arg))
+}
+
+/// Parse the label of a bookmark from the remaining words of a command,
+/// which may be quoted.
+fn parse_label<'a>(words: impl Iterator<Item = &'a str>) -> Result<String, String> {
+    let label = words.collect::<Vec<&str>>().join(" ");
+    let label = label
+        .strip_prefix('"')
+        .and_then(|label| label.strip_suffix('"'))
+        .unwrap_or(&label);
+
+    if label.is_empty() {
+        Err("missing label".to_string())
+    } else {
+        Ok(label.to_string())
+    }
+}
+
+/// Parse a line of input, returning [None] for blank lines.
+fn parse_command(line: &str) -> Result<Option<Command>, String> {
+    let mut words = line.split_whitespace();
+
+    let name = match words.next() {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+
+    let command = match name {
+        "step" | "s" => match words.next() {
+            Some(count) => Command::Step(parse_arg(Some(count), "generation count")?),
+            None => Command::Step(1),
+        },
+        "set" | "unset" => {
+            let row = parse_arg(words.next(), "row")?;
+            let col = parse_arg(words.next(), "column")?;
+            let state = if name == "set" {
+                Cell::Alive
+            } else {
+                Cell::Dead
+            };
+
+            Command::Set(row, col, state)
+        }
+        "clear" => Command::Clear,
+        "load" => {
+            let pattern = words.next().ok_or("missing pattern")?.to_string();
+            let row = parse_arg(words.next(), "row")?;
+            let col = parse_arg(words.next(), "column")?;
+
+            Command::Load(pattern, row, col)
+        }
+        "rule" => match words.next() {
+            Some(rule) => Command::Rule(Some(rule.parse().map_err(|err| format!("{}", err))?)),
+            None => Command::Rule(None),
+        },
+        "show" | "print" => Command::Show,
+        "stats" => Command::Stats,
+        "velocity" => match words.next() {
+            Some(count) => Command::Velocity(parse_arg(Some(count), "generation count")?),
+            None => Command::Velocity(VELOCITY_GENERATIONS),
+        },
+        "bookmark" => Command::Bookmark(parse_label(&mut words)?),
+        "bookmarks" => match words.next() {
+            Some("list") | None => Command::ListBookmarks,
+            Some("goto") => Command::GotoBookmark(parse_label(&mut words)?),
+            Some("remove") => Command::RemoveBookmark(parse_label(&mut words)?),
+            Some(other) => return Err(format!("unknown bookmarks command '{}'", other)),
+        },
+        "help" | "?" => Command::Help,
+        "quit" | "exit" | "q" => Command::Quit,
+        _ => return Err(format!("unknown command '{}', try 'help'", name)),
+    };
+
+    match words.next() {
+        Some(extra) => Err(format!("unexpected argument '{}'", extra)),
+        None => Ok(Some(command)),
+    }
+}
+
+/// Place the live cells of a pattern with its top left cell at (row, col).
+fn load(sim: &mut ConwaySim, pattern: &str, row: u32, col: u32) -> Result<(), String> {
+    let loaded = patterns::load(pattern).map_err(|err| err.to_string())?;
+    let pattern = Pattern::new(loaded.get_grid().clone());
+
+    sim.stamp(&pattern, row, col, Overlap::Or)
+        .map_err(|err| err.to_string())
+}
+
+/// Print the statistics of the current generation.
+fn print_stats(sim: &ConwaySim, output: &mut impl Write) -> io::Result<()> {
+    writeln!(output, "generation: {}", sim.get_generation())?;
+    writeln!(output, "population: {}", sim.get_population())?;
+    writeln!(output, "rule: {}", sim.get_rule())?;
+    writeln!(output, "status: {}", sim.get_status())?;
+    if let Some(cycle) = sim.get_cycle() {
+        writeln!(output, "{}", cycle)?;
+    }
+
+    if let Some(stats) = sim.get_stats() {
+        if let Some(latest) = stats.latest() {
+            writeln!(output, "births: {}", latest.births)?;
+            writeln!(output, "deaths: {}", latest.deaths)?;
+            writeln!(output, "density: {:.3}", latest.density)?;
+        }
+
+        writeln!(output, "peak population: {}", stats.peak_population())?;
+    }
+
+    Ok(())
+}
+
+/// Execute a command, returning false once the user quits.
+fn execute(sim: &mut ConwaySim, command: Command, output: &mut impl Write) -> io::Result<bool> {
+    match command {
+        Command::Step(count) => {
+            sim.step_n(count);
+
+            writeln!(
+                output,
+                "generation {}, population {}",
+                sim.get_generation(),
+                sim.get_population()
+            )?;
+        }
+        Command::Set(row, col, state) => match sim.get_grid().try_get(row, col) {
+            Ok(_) => sim.set_cell(row, col, state),
+            Err(err) => writeln!(output, "error: {}", err)?,
+        },
+        Command::Clear => sim.clear(),
+        Command::Load(pattern, row, col) => {
+            if let Err(err) = load(sim, &pattern, row, col) {
+                writeln!(output, "error: {}: {}", pattern, err)?;
+            }
+        }
+        Command::Rule(Some(rule)) => {
+            sim.set_rule(rule);
+
+            // changing the rule discards the recorded statistics
+            sim.enable_stats();
+        }
+        Command::Rule(None) => writeln!(output, "{}", sim.get_rule())?,
+        Command::Show => write!(output, "{}", sim)?,
+        Command::Stats => print_stats(sim, output)?,
+        Command::Velocity(count) => match measure_velocity(sim, count) {
+            Some(velocity) => writeln!(output, "{}", velocity.get_drift(STATIONARY_SPEED))?,
+            None => writeln!(output, "no live cells to measure")?,
+        },
+        Command::Bookmark(label) => {
+            sim.bookmark(&label);
+            writeln!(
+                output,
+                "bookmarked generation {} as '{}'",
+                sim.get_generation(),
+                label
+            )?;
+        }
+        Command::ListBookmarks => {
+            if sim.get_bookmarks().is_empty() {
+                writeln!(output, "no bookmarks")?;
+            }
+
+            for bookmark in sim.get_bookmarks() {
+                writeln!(
+                    output,
+                    "generation {}: {}",
+                    bookmark.get_generation(),
+                    bookmark.get_label()
+                )?;
+            }
+        }
+        Command::GotoBookmark(label) => {
+            if sim.goto_bookmark(&label) {
+                writeln!(
+                    output,
+                    "generation {}, population {}",
+                    sim.get_generation(),
+                    sim.get_population()
+                )?;
+            } else {
+                writeln!(output, "error: no bookmark '{}'", label)?;
+            }
+        }
+        Command::RemoveBookmark(label) => {
+            if !sim.remove_bookmark(&label) {
+                writeln!(output, "error: no bookmark '{}'", label)?;
+            }
+        }
+        Command::Help => writeln!(output, "{}", HELP)?,
+        Command::Quit => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+/// Drive a simulation from commands typed at the terminal, or piped in as a
+/// script, until the input ends or the user quits.
+///
+/// # Arguments
+/// * `sim` - The simulation to drive.
+/// * `session` - The session autosaving the simulation after each command
+///   and saving it when the input ends, if any.
+pub fn run(mut sim: ConwaySim, mut session: Option<Session>) -> io::Result<()> {
+    let stdin = io::stdin();
+    let prompt = stdin.is_terminal();
+    let mut lines = stdin.lock().lines();
+    let mut output = io::stdout().lock();
+
+    sim.enable_stats();
+
+    loop {
+        if prompt {
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+
+        match parse_command(&line) {
+            Ok(Some(command)) => {
+                if !execute(&mut sim, command, &mut output)? {
+                    break;
+                }
+            }
+            Ok(None) => {}
+            Err(err) => writeln!(output, "error: {}", err)?,
+        }
+
+        if let Some(session) = &mut session {
+            if let Err(err) = session.autosave(&sim) {
+                writeln!(output, "warning: unable to autosave: {}", err)?;
+            }
+        }
+    }
+
+    match &mut session {
+        Some(session) => session.save(&sim).map_err(io::Error::other),
+        None => Ok(()),
+    }
+}
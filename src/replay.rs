@@ -0,0 +1,589 @@
+//! Deterministic recording and replay of simulation runs.
+//!
+//! A [Recorder] wraps a simulation and records its initial state and every
+//! edit made through it, timestamped with the generation it was made at. As
+//! the rules are deterministic, a [Replayer] reproduces the exact run from
+//! the resulting [Recording], and [Replayer::verify] checks that it did.
+//!
+//! Recordings are saved as text, one entry per line:
+//!
+//! ```text
+//! #Replay 1
+//! rule B3/S23
+//! size 20 40
+//! generation 0
+//! row 5 10 11 12
+//! @12 set 3 4
+//! @12 unset 3 5
+//! @30 rule B36/S23
+//! @41 size 30 50
+//! end 100
+//! ```
+//!
+//! `row` lines list the live columns of a row of the initial [Grid]. Edits
+//! set a cell alive or dead, change the rule, or replace the [Grid] with an
+//! empty one of a new size.
+
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::diff::GridDiff;
+use crate::game::{Cell, ConwaySim, Grid, GridError};
+use crate::pattern::{Overlap, Pattern};
+use crate::rule::Rule;
+use crate::transform::Anchor;
+use crate::world::World;
+
+/// The header line of a recording.
+const HEADER: &str = "#Replay 1";
+
+/// Error raised while loading a [Recording].
+#[derive(Debug)]
+pub enum ReplayError {
+    /// The `#Replay 1` header line is missing.
+    MissingHeader,
+
+    /// A line is not a valid recording entry.
+    InvalidLine { line: usize },
+
+    /// The recording file could not be read or written.
+    Io(io::Error),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::MissingHeader => write!(f, "missing '{}' header line", HEADER),
+            ReplayError::InvalidLine { line } => {
+                write!(f, "invalid recording entry on line {}", line)
+            }
+            ReplayError::Io(err) => write!(f, "unable to access recording file: {}", err),
+        }
+    }
+}
+
+impl error::Error for ReplayError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ReplayError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReplayError {
+    fn from(err: io::Error) -> ReplayError {
+        ReplayError::Io(err)
+    }
+}
+
+/// An edit of a simulation.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Edit {
+    /// Set the [Cell] at (row, col).
+    Set { row: u32, col: u32, state: Cell },
+
+    /// Change the [Rule].
+    Rule(Rule),
+
+    /// Replace the [Grid] with an empty one of a new size.
+    Size { num_rows: u32, num_cols: u32 },
+}
+
+/// An [Edit] made at a generation.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Event {
+    /// The generation the edit was made at, before stepping further.
+    pub generation: u32,
+
+    /// The edit.
+    pub edit: Edit,
+}
+
+/// The initial state and edits of a simulation run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Recording {
+    /// The initial [Grid].
+    grid: Grid,
+
+    /// The initial [Rule].
+    rule: Rule,
+
+    /// The initial generation.
+    generation: u32,
+
+    /// The edits, in the order they were made.
+    events: Vec<Event>,
+
+    /// The generation the run ended at.
+    end: u32,
+}
+
+impl Recording {
+    /// Get the initial [Grid] of the run.
+    pub fn get_grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Get the initial [Rule] of the run.
+    pub fn get_rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Get the initial generation of the run.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Get the edits made during the run, in the order they were made.
+    pub fn get_events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Get the generation the run ended at.
+    pub fn get_end(&self) -> u32 {
+        self.end
+    }
+
+    /// Parse a [Recording] from its text format.
+    ///
+    /// # Arguments
+    /// * `text` - The recording, as written by [Recording::to_text].
+    pub fn from_text(text: &str) -> Result<Recording, ReplayError> {
+        let mut lines = text.lines().enumerate();
+
+        match lines.next() {
+            Some((_, line)) if line.trim() == HEADER => {}
+            _ => return Err(ReplayError::MissingHeader),
+        }
+
+        let mut recording = Recording {
+            grid: Grid::new(0, 0),
+            rule: Rule::default(),
+            generation: 0,
+            events: Vec::new(),
+            end: 0,
+        };
+
+        let mut size = (0, 0);
+
+        for (number, line) in lines {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            recording
+                .parse_line(line, &mut size)
+                .ok_or(ReplayError::InvalidLine { line: number + 1 })?;
+        }
+
+        Ok(recording)
+    }
+
+    /// Parse a line of the text format into the [Recording], returning
+    /// [None] if it is invalid.
+    ///
+    /// # Arguments
+    /// * `line` - The line to parse.
+    /// * `size` - The (num_rows, num_cols) size of the [Grid] after the
+    ///   edits so far, updated by the line.
+    fn parse_line(&mut self, line: &str, size: &mut (u32, u32)) -> Option<()> {
+        let mut words = line.split_whitespace();
+        let keyword = words.next()?;
+
+        if let Some(generation) = keyword.strip_prefix('@') {
+            let generation: u32 = generation.parse().ok()?;
+
+            let action = words.next()?;
+
+            let edit = match action {
+                "set" | "unset" => {
+                    let row: u32 = words.next()?.parse().ok()?;
+                    let col: u32 = words.next()?.parse().ok()?;
+                    let state = if action == "set" {
+                        Cell::Alive
+                    } else {
+                        Cell::Dead
+                    };
+
+                    // the cell must be within the grid at that point of the run
+                    let (num_rows, num_cols) = *size;
+                    if row >= num_rows || col >= num_cols {
+                        return None;
+                    }
+
+                    Edit::Set { row, col, state }
+                }
+                "rule" => Edit::Rule(words.next()?.parse().ok()?),
+                "size" => {
+                    let num_rows = words.next()?.parse().ok()?;
+                    let num_cols = words.next()?.parse().ok()?;
+                    *size = (num_rows, num_cols);
+
+                    Edit::Size { num_rows, num_cols }
+                }
+                _ => return None,
+            };
+
+            // edits are replayed in order, so must not go back in time
+            let previous = self
+                .events
+                .last()
+                .map_or(self.generation, |event| event.generation);
+            if generation < previous {
+                return None;
+            }
+
+            self.events.push(Event { generation, edit });
+        } else {
+            match keyword {
+                "rule" => self.rule = words.next()?.parse().ok()?,
+                "size" => {
+                    let num_rows = words.next()?.parse().ok()?;
+                    let num_cols = words.next()?.parse().ok()?;
+                    self.grid = Grid::new(num_rows, num_cols);
+                    *size = (num_rows, num_cols);
+                }
+                "generation" => self.generation = words.next()?.parse().ok()?,
+                "row" => {
+                    let row: u32 = words.next()?.parse().ok()?;
+
+                    for col in words.by_ref() {
+                        let col: u32 = col.parse().ok()?;
+                        self.grid.try_set(row, col, Cell::Alive).ok()?;
+                    }
+                }
+                "end" => self.end = words.next()?.parse().ok()?,
+                _ => return None,
+            }
+        }
+
+        // reject trailing words
+        match words.next() {
+            Some(_) => None,
+            None => Some(()),
+        }
+    }
+
+    /// Encode the [Recording] in its text format.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+
+        text.push_str(HEADER);
+        text.push('\n');
+        text.push_str(&format!("rule {}\n", self.rule));
+        text.push_str(&format!(
+            "size {} {}\n",
+            self.grid.get_num_rows(),
+            self.grid.get_num_cols()
+        ));
+        text.push_str(&format!("generation {}\n", self.generation));
+
+        for row in 0..self.grid.get_num_rows() {
+            let cols: Vec<String> = (0..self.grid.get_num_cols())
+                .filter(|&col| self.grid.get(row, col).is_alive())
+                .map(|col| col.to_string())
+                .collect();
+
+            if !cols.is_empty() {
+                text.push_str(&format!("row {} {}\n", row, cols.join(" ")));
+            }
+        }
+
+        for event in self.events.iter() {
+            let entry = match event.edit {
+                Edit::Set {
+                    row,
+                    col,
+                    state: Cell::Alive,
+                } => format!("set {} {}", row, col),
+                Edit::Set {
+                    row,
+                    col,
+                    state: Cell::Dead,
+                } => format!("unset {} {}", row, col),
+                Edit::Rule(rule) => format!("rule {}", rule),
+                Edit::Size { num_rows, num_cols } => format!("size {} {}", num_rows, num_cols),
+            };
+
+            text.push_str(&format!("@{} {}\n", event.generation, entry));
+        }
+
+        text.push_str(&format!("end {}\n", self.end));
+
+        text
+    }
+
+    /// Load a [Recording] from a file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the recording file.
+    pub fn load_file<P: AsRef<Path>>(path: P) -> Result<Recording, ReplayError> {
+        Recording::from_text(&fs::read_to_string(path)?)
+    }
+
+    /// Save the [Recording] to a file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the recording file.
+    pub fn save_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ReplayError> {
+        fs::write(path, self.to_text())?;
+
+        Ok(())
+    }
+}
+
+/// Wrapper of a simulation recording every edit made through it.
+pub struct Recorder {
+    /// The simulation being recorded.
+    sim: ConwaySim,
+
+    /// The recording so far.
+    recording: Recording,
+}
+
+impl Recorder {
+    /// Start recording a simulation from its current state.
+    ///
+    /// # Arguments
+    /// * `sim` - The simulation to record.
+    pub fn new(sim: ConwaySim) -> Recorder {
+        let recording = Recording {
+            grid: sim.get_grid().clone(),
+            rule: sim.get_rule(),
+            generation: sim.get_generation(),
+            events: Vec::new(),
+            end: sim.get_generation(),
+        };
+
+        Recorder { sim, recording }
+    }
+
+    /// Get the simulation being recorded.
+    pub fn get_sim(&self) -> &ConwaySim {
+        &self.sim
+    }
+
+    /// Advance the simulation by one generation.
+    pub fn step(&mut self) {
+        self.sim.step();
+    }
+
+    /// Advance the simulation by `n` generations.
+    pub fn step_n(&mut self, n: u32) {
+        self.sim.step_n(n);
+    }
+
+    /// Edit the simulation with a closure, recording the cells and rule it
+    /// changes. The closure must not step the simulation.
+    ///
+    /// # Arguments
+    /// * `edit` - The closure editing the simulation.
+    pub fn edit<T>(&mut self, edit: impl FnOnce(&mut ConwaySim) -> T) -> T {
+        let before = self.sim.get_grid().clone();
+        let rule = self.sim.get_rule();
+
+        let result = edit(&mut self.sim);
+
+        let mut edits: Vec<Edit> = Vec::new();
+
+        if self.sim.get_rule() != rule {
+            edits.push(Edit::Rule(self.sim.get_rule()));
+        }
+
+        let after = self.sim.get_grid();
+        let (num_rows, num_cols) = (after.get_num_rows(), after.get_num_cols());
+
+        let diff = if (num_rows, num_cols) != (before.get_num_rows(), before.get_num_cols()) {
+            edits.push(Edit::Size { num_rows, num_cols });
+            World::diff(after, &Grid::new(num_rows, num_cols))
+        } else {
+            World::diff(after, &before)
+        };
+
+        for (cells, state) in [(diff.births, Cell::Alive), (diff.deaths, Cell::Dead)] {
+            for (row, col) in cells {
+                edits.push(Edit::Set {
+                    row: row as u32,
+                    col: col as u32,
+                    state,
+                });
+            }
+        }
+
+        let generation = self.sim.get_generation();
+        self.recording
+            .events
+            .extend(edits.into_iter().map(|edit| Event { generation, edit }));
+
+        result
+    }
+
+    /// Set the specified [Cell] of the simulation's [Grid].
+    pub fn set_cell(&mut self, row: u32, col: u32, state: Cell) {
+        self.edit(|sim| sim.set_cell(row, col, state));
+    }
+
+    /// Set the cells of the simulation's [Grid].
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (row, col) to set as [Cell::Alive].
+    pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
+        self.edit(|sim| sim.set_cells(cells));
+    }
+
+    /// Toggle the specified [Cell] of the simulation's [Grid].
+    pub fn toggle_cell(&mut self, row: u32, col: u32) {
+        self.edit(|sim| sim.toggle_cell(row, col));
+    }
+
+    /// Set every [Cell] of the simulation's [Grid] as [Cell::Dead].
+    pub fn clear(&mut self) {
+        self.edit(|sim| sim.clear());
+    }
+
+    /// Paste a [Pattern] into the simulation's [Grid], as with
+    /// [Grid::stamp].
+    pub fn stamp(
+        &mut self,
+        pattern: &Pattern,
+        row: u32,
+        col: u32,
+        overlap: Overlap,
+    ) -> Result<(), GridError> {
+        self.edit(|sim| sim.stamp(pattern, row, col, overlap))
+    }
+
+    /// Set the [Rule] applied to each cell every generation.
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.edit(|sim| sim.set_rule(rule));
+    }
+
+    /// Grow or shrink the simulation's [Grid], as with [Grid::resized].
+    pub fn resize(&mut self, num_rows: u32, num_cols: u32, anchor: Anchor) {
+        self.edit(|sim| sim.resize(num_rows, num_cols, anchor));
+    }
+
+    /// Stop recording, returning the [Recording] ending at the current
+    /// generation and the simulation.
+    pub fn finish(mut self) -> (Recording, ConwaySim) {
+        self.recording.end = self.sim.get_generation();
+
+        (self.recording, self.sim)
+    }
+}
+
+/// Reproduction of a recorded simulation run.
+pub struct Replayer {
+    /// The simulation being replayed.
+    sim: ConwaySim,
+
+    /// The recorded edits.
+    events: Vec<Event>,
+
+    /// Index of the next edit to apply.
+    next_event: usize,
+
+    /// The generation the recorded run ended at.
+    end: u32,
+}
+
+impl Replayer {
+    /// Start replaying a [Recording] from its initial state.
+    ///
+    /// # Arguments
+    /// * `recording` - The recorded run.
+    pub fn new(recording: Recording) -> Replayer {
+        let sim = ConwaySim::restore(recording.grid, recording.generation, recording.rule);
+
+        let mut replayer = Replayer {
+            sim,
+            events: recording.events,
+            next_event: 0,
+            end: recording.end,
+        };
+        replayer.apply_edits();
+
+        replayer
+    }
+
+    /// Get the simulation being replayed.
+    pub fn get_sim(&self) -> &ConwaySim {
+        &self.sim
+    }
+
+    /// Determine if the replay has reached the end of the recorded run.
+    pub fn is_finished(&self) -> bool {
+        self.sim.get_generation() >= self.end
+    }
+
+    /// Apply the edits made at or before the current generation.
+    fn apply_edits(&mut self) {
+        let generation = self.sim.get_generation();
+        let mut edited = false;
+
+        while let Some(event) = self.events.get(self.next_event) {
+            if event.generation > generation {
+                break;
+            }
+
+            match event.edit {
+                Edit::Set { row, col, state } => self.sim.get_world_mut().set(row, col, state),
+                Edit::Rule(rule) => self.sim.set_rule(rule),
+                Edit::Size { num_rows, num_cols } => {
+                    *self.sim.get_world_mut() = Grid::new(num_rows, num_cols);
+                }
+            }
+
+            self.next_event += 1;
+            edited = true;
+        }
+
+        if edited {
+            self.sim.record_edit();
+        }
+    }
+
+    /// Advance the simulation by one generation, then apply the edits made
+    /// at the new generation.
+    pub fn step(&mut self) {
+        self.sim.step();
+        self.apply_edits();
+    }
+
+    /// Advance the simulation to a generation, applying the edits made along
+    /// the way.
+    ///
+    /// # Arguments
+    /// * `generation` - The generation to advance to.
+    pub fn advance_to(&mut self, generation: u32) {
+        while self.sim.get_generation() < generation {
+            self.step();
+        }
+    }
+
+    /// Replay the rest of the recorded run, returning the simulation at its
+    /// end.
+    pub fn finish(mut self) -> ConwaySim {
+        self.advance_to(self.end);
+
+        self.sim
+    }
+
+    /// Replay the rest of the recorded run and compare its end with the
+    /// [Grid] the recorded run ended with.
+    ///
+    /// Returns the [GridDiff] from the replayed [Grid] to the expected one,
+    /// empty if the replay reproduced the run exactly.
+    ///
+    /// # Arguments
+    /// * `expected` - The final [Grid] of the recorded run.
+    pub fn verify(self, expected: &Grid) -> GridDiff {
+        self.finish().get_grid().diff(expected)
+    }
+}
@@ -0,0 +1,174 @@
+//! Reversible second-order variants of Life-like rules.
+//!
+//! Most rules lose information: many generations lead to the same next one,
+//! so there is no way back. Fredkin's second-order technique makes any rule
+//! reversible by computing each generation from the two before it: a cell of
+//! the next generation is alive if the [Rule] would bring it to life from the
+//! current generation, toggled by its state in the previous one. Knowing the
+//! last two generations, the one before them is found the same way, so
+//! [SecondOrderSim::step_backward] retraces a run exactly without storing it.
+//!
+//! ```
+//! use gameoflife_rs::reversible::SecondOrderSim;
+//! use gameoflife_rs::{Grid, Rule};
+//!
+//! let soup = Grid::random(32, 32, 0.3, 9);
+//! let mut sim = SecondOrderSim::new_with_grid(soup.clone(), Rule::default());
+//!
+//! sim.step_n(50);
+//! while sim.step_backward() {}
+//!
+//! assert_eq!(sim.get_grid(), &soup);
+//! ```
+
+use std::fmt;
+
+use crate::game::{Cell, Grid};
+use crate::rule::Rule;
+
+/// Simulation of the reversible second-order variant of a Life-like [Rule].
+///
+/// Cells beyond the edges of the grid are found by its
+/// [Topology](crate::topology::Topology).
+#[derive(Clone, Debug)]
+pub struct SecondOrderSim {
+    /// The generation before the current one.
+    previous: Grid,
+
+    /// The current generation of cells.
+    grid: Grid,
+
+    /// Buffer the rule's next generation is computed into.
+    next: Grid,
+
+    /// The [Rule] applied to each cell every generation.
+    rule: Rule,
+
+    /// The simulation's current generation.
+    generation: u32,
+}
+
+impl SecondOrderSim {
+    /// Create a new simulation from an existing [Grid], with a previous
+    /// generation of dead cells.
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cells, and their topology.
+    /// * `rule` - The [Rule] applied to each cell.
+    pub fn new_with_grid(grid: Grid, rule: Rule) -> SecondOrderSim {
+        let mut previous = Grid::new(grid.get_num_rows(), grid.get_num_cols());
+        previous.set_topology(grid.get_topology());
+
+        SecondOrderSim::new_with_grids(previous, grid, rule)
+    }
+
+    /// Create a new simulation from its previous and current generations.
+    ///
+    /// # Arguments
+    /// * `previous` - The generation before the initial one.
+    /// * `grid` - The initial cells, and their topology.
+    /// * `rule` - The [Rule] applied to each cell.
+    ///
+    /// # Panics
+    /// Panics if the grids differ in size.
+    pub fn new_with_grids(previous: Grid, grid: Grid, rule: Rule) -> SecondOrderSim {
+        assert!(
+            previous.get_num_rows() == grid.get_num_rows()
+                && previous.get_num_cols() == grid.get_num_cols(),
+            "the previous and current generations must be the same size"
+        );
+
+        SecondOrderSim {
+            next: grid.clone(),
+            previous,
+            grid,
+            rule,
+            generation: 0,
+        }
+    }
+
+    /// Get the simulation's current [Grid].
+    pub fn get_grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Get the generation before the current one.
+    pub fn get_previous(&self) -> &Grid {
+        &self.previous
+    }
+
+    /// Get the simulation's [Rule].
+    pub fn get_rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Get the simulation's current generation.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Get the number of live cells.
+    pub fn get_population(&self) -> u64 {
+        self.grid.count_alive()
+    }
+
+    /// Replace `older` with the generation after `newer` and `older`, the
+    /// rule's next generation of `newer` toggled by the cells of `older`.
+    fn advance(&mut self, forward: bool) {
+        let (newer, older) = if forward {
+            (&self.grid, &mut self.previous)
+        } else {
+            (&self.previous, &mut self.grid)
+        };
+
+        newer.step_states_into(&self.rule, &mut self.next);
+
+        for row in 0..older.get_num_rows() {
+            for col in 0..older.get_num_cols() {
+                let toggled = self.next.get(row, col).is_alive() != older.get(row, col).is_alive();
+                let state = if toggled { Cell::Alive } else { Cell::Dead };
+
+                if older.get(row, col) != state {
+                    older.set(row, col, state);
+                }
+            }
+        }
+    }
+
+    /// Advance the simulation by one generation.
+    pub fn step(&mut self) {
+        self.advance(true);
+        std::mem::swap(&mut self.previous, &mut self.grid);
+        self.generation += 1;
+    }
+
+    /// Advance the simulation by `n` generations.
+    pub fn step_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+
+    /// Undo one generation exactly, recomputing the generation before the
+    /// previous one.
+    ///
+    /// Returns false, leaving the cells unchanged, if the simulation is at
+    /// generation 0.
+    pub fn step_backward(&mut self) -> bool {
+        if self.generation == 0 {
+            return false;
+        }
+
+        self.advance(false);
+        std::mem::swap(&mut self.previous, &mut self.grid);
+        self.generation -= 1;
+
+        true
+    }
+}
+
+impl fmt::Display for SecondOrderSim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.grid.fmt(f)
+    }
+}
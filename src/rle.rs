@@ -0,0 +1,653 @@
+//! Support for the Run Length Encoded (RLE) pattern format used by Golly and
+//! LifeWiki.
+//!
+//! An RLE file consists of optional `#` comment lines, a header line of the
+//! form `x = <width>, y = <height>[, rule = <rule>]`, and the encoded pattern
+//! where `b` is a dead cell, `o` is a live cell, `$` ends a row, and `!` ends
+//! the pattern. Any tag may be preceded by a run count.
+//!
+//! The rule may end with the suffix Golly writes for a bounded grid, e.g.
+//! `B3/S23:T64,64` for a 64x64 torus. It is ignored, as the pattern is
+//! loaded into a [Grid] of its own size.
+//!
+//! ```
+//! use gameoflife_rs::ConwaySim;
+//!
+//! let sim = ConwaySim::from_rle("x = 3, y = 1, rule = B36/S23:T64,64\n3o!")?;
+//!
+//! assert_eq!(sim.get_rule().to_string(), "B36/S23");
+//! assert_eq!(sim.get_population(), 3);
+//! # Ok::<(), gameoflife_rs::RleError>(())
+//! ```
+//!
+//! The `#N` name, `#O` author, and `#C` comment lines are kept as a
+//! [PatternMeta] when loading a [Pattern], and written back when saving it.
+//!
+//! Multi-state patterns, such as Wireworld circuits saved by Golly, use `.`
+//! for state 0, `A` to `X` for states 1 to 24, and a prefix `p` to `y` for
+//! each further block of 24 states, e.g. `pA` for state 25.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::fs;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+#[cfg(feature = "std")]
+use crate::game::ConwaySim;
+use crate::game::{Grid, MAX_CELLS};
+use crate::pattern::{Pattern, PatternMeta};
+use crate::rule::Rule;
+use crate::states::StateGrid;
+
+/// Error raised while loading an RLE pattern.
+#[derive(Debug)]
+pub enum RleError {
+    /// The `x = .., y = ..` header line is missing.
+    MissingHeader,
+
+    /// The header line could not be parsed.
+    InvalidHeader { line: usize, header: String },
+
+    /// The header declares a width or height of zero.
+    Empty { num_rows: u32, num_cols: u32 },
+
+    /// The header declares more cells than a grid can hold.
+    TooLarge { num_rows: u32, num_cols: u32 },
+
+    /// An unexpected character was found in the pattern data.
+    InvalidTag {
+        line: usize,
+        column: usize,
+        tag: char,
+    },
+
+    /// A run count does not fit in 32 bits.
+    Overflow { line: usize, column: usize },
+
+    /// The pattern data places a live cell outside the declared dimensions.
+    OutOfBounds {
+        line: usize,
+        column: usize,
+        row: u32,
+        col: u32,
+    },
+
+    /// The header declares a [Rule] the simulation loading the pattern
+    /// cannot run, such as a `B0` rule for HashLife.
+    UnsupportedRule { rule: Rule },
+
+    /// The pattern file could not be read or written.
+    #[cfg(feature = "std")]
+    Io(io::Error),
+}
+
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RleError::MissingHeader => write!(f, "missing RLE header line"),
+            RleError::InvalidHeader { line, header } => {
+                write!(f, "invalid RLE header on line {}: {}", line, header)
+            }
+            RleError::Empty { num_rows, num_cols } => {
+                write!(f, "RLE pattern of {}x{} cells is empty", num_rows, num_cols)
+            }
+            RleError::TooLarge { num_rows, num_cols } => {
+                write!(
+                    f,
+                    "RLE pattern of {}x{} cells is too large",
+                    num_rows, num_cols
+                )
+            }
+            RleError::InvalidTag { line, column, tag } => write!(
+                f,
+                "invalid RLE tag '{}' on line {}, column {}",
+                tag, line, column
+            ),
+            RleError::Overflow { line, column } => write!(
+                f,
+                "RLE run count on line {}, column {} is too large",
+                line, column
+            ),
+            RleError::OutOfBounds {
+                line,
+                column,
+                row,
+                col,
+            } => write!(
+                f,
+                "RLE cell ({}, {}) on line {}, column {} is outside of the pattern bounds",
+                row, col, line, column
+            ),
+            RleError::UnsupportedRule { rule } => {
+                write!(f, "RLE rule {} is not supported", rule)
+            }
+            #[cfg(feature = "std")]
+            RleError::Io(err) => write!(f, "unable to access RLE file: {}", err),
+        }
+    }
+}
+
+impl error::Error for RleError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            RleError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for RleError {
+    fn from(err: io::Error) -> RleError {
+        RleError::Io(err)
+    }
+}
+
+/// Get the lines of an RLE encoded pattern which are neither blank nor
+/// comments, with their line numbers counted from 1.
+fn data_lines(rle: &str) -> impl Iterator<Item = (usize, &str)> {
+    rle.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+}
+
+/// Parse the `x = <width>, y = <height>[, rule = <rule>]` header line into
+/// (num_rows, num_cols, rulestring), dropping any bounded grid suffix from
+/// the rulestring.
+///
+/// # Arguments
+/// * `number` - The line number of the header, for error reporting.
+/// * `line` - The header line.
+fn parse_header(number: usize, line: &str) -> Result<(u32, u32, Option<&str>), RleError> {
+    let invalid = || RleError::InvalidHeader {
+        line: number,
+        header: line.to_string(),
+    };
+
+    let mut width: Option<u32> = None;
+    let mut height: Option<u32> = None;
+    let mut rule: Option<&str> = None;
+
+    let mut fields = Some(line);
+
+    while let Some(remaining) = fields {
+        let (field, rest) = match remaining.split_once(',') {
+            Some((field, rest)) => (field, Some(rest)),
+            None => (remaining, None),
+        };
+        fields = rest;
+
+        let (key, value) = field.split_once('=').ok_or_else(invalid)?;
+
+        match key.trim() {
+            "x" => width = value.trim().parse().ok(),
+            "y" => height = value.trim().parse().ok(),
+            "rule" => {
+                // the rule runs to the end of the line, as a bounded grid
+                // suffix such as `:T64,64` holds a comma
+                let (_, value) = remaining.split_once('=').ok_or_else(invalid)?;
+                let value = value.split_once(':').map_or(value, |(rule, _)| rule).trim();
+
+                rule = Some(value);
+                fields = None;
+            }
+            // any other keys are ignored
+            _ => {}
+        }
+    }
+
+    match (height, width) {
+        (Some(num_rows), Some(num_cols)) if num_rows == 0 || num_cols == 0 => {
+            Err(RleError::Empty { num_rows, num_cols })
+        }
+        (Some(num_rows), Some(num_cols)) if num_rows as u64 * num_cols as u64 > MAX_CELLS => {
+            Err(RleError::TooLarge { num_rows, num_cols })
+        }
+        (Some(num_rows), Some(num_cols)) => Ok((num_rows, num_cols, rule)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Add a digit to the run count being read, failing if it overflows.
+fn push_digit(
+    count: Option<u32>,
+    digit: u32,
+    line: usize,
+    column: usize,
+) -> Result<Option<u32>, RleError> {
+    count
+        .unwrap_or(0)
+        .checked_mul(10)
+        .and_then(|count| count.checked_add(digit))
+        .map(Some)
+        .ok_or(RleError::Overflow { line, column })
+}
+
+/// Parse an RLE encoded pattern into a [Grid] and the [Rule] declared in its
+/// header, if any.
+pub(crate) fn parse(rle: &str) -> Result<(Grid, Option<Rule>), RleError> {
+    let mut lines = data_lines(rle);
+
+    let (num_rows, num_cols, rule) = match lines.next() {
+        Some((number, line)) if line.trim().starts_with('x') => {
+            let line = line.trim();
+            let (num_rows, num_cols, rule) = parse_header(number, line)?;
+            let rule = match rule {
+                Some(rule) => Some(rule.parse().map_err(|_| RleError::InvalidHeader {
+                    line: number,
+                    header: line.to_string(),
+                })?),
+                None => None,
+            };
+
+            (num_rows, num_cols, rule)
+        }
+        _ => return Err(RleError::MissingHeader),
+    };
+
+    let mut grid = Grid::new(num_rows, num_cols);
+    let mut cells: Vec<(u32, u32)> = Vec::new();
+
+    let mut row: u32 = 0;
+    let mut col: u32 = 0;
+    let mut count: Option<u32> = None;
+
+    'lines: for (number, line) in lines {
+        for (index, tag) in line.chars().enumerate() {
+            let column = index + 1;
+
+            if let Some(digit) = tag.to_digit(10) {
+                count = push_digit(count, digit, number, column)?;
+                continue;
+            }
+
+            let run = count.take().unwrap_or(1);
+
+            match tag {
+                // dead cells
+                'b' | '.' => col = col.saturating_add(run),
+
+                // end of row(s)
+                '$' => {
+                    row = row.saturating_add(run);
+                    col = 0;
+                }
+
+                // end of pattern
+                '!' => break 'lines,
+
+                // any other letter is a live cell
+                tag if tag.is_ascii_alphabetic() => {
+                    for _ in 0..run {
+                        if row >= num_rows || col >= num_cols {
+                            return Err(RleError::OutOfBounds {
+                                line: number,
+                                column,
+                                row,
+                                col,
+                            });
+                        }
+
+                        cells.push((row, col));
+                        col += 1;
+                    }
+                }
+
+                tag if tag.is_whitespace() => {}
+
+                tag => {
+                    return Err(RleError::InvalidTag {
+                        line: number,
+                        column,
+                        tag,
+                    })
+                }
+            }
+        }
+    }
+
+    grid.set_cells(&cells);
+
+    Ok((grid, rule))
+}
+
+/// Parse the `#N`, `#O`, and `#C` lines of an RLE encoded pattern into a
+/// [PatternMeta].
+fn parse_meta(rle: &str) -> PatternMeta {
+    let mut meta = PatternMeta::default();
+
+    for line in rle.lines().map(str::trim) {
+        let mut chars = line.chars();
+
+        if chars.next() != Some('#') {
+            continue;
+        }
+
+        let kind = chars.next();
+        let text = chars.as_str().trim();
+
+        match kind {
+            Some('N') => meta.name = Some(text.to_string()),
+            Some('O') => meta.author = Some(text.to_string()),
+            Some('C') | Some('c') => meta.comments.push(text.to_string()),
+            // other lines, e.g. the position or rule, are not metadata
+            _ => {}
+        }
+    }
+
+    meta
+}
+
+/// Append a run of a tag, extending the last run if it has the same tag.
+fn push_run(runs: &mut Vec<(u32, char)>, count: u32, tag: char) {
+    match runs.last_mut() {
+        Some((run, last)) if *last == tag => *run += count,
+        _ => runs.push((count, tag)),
+    }
+}
+
+/// Encode a [Grid] in the RLE format, preceded by the lines of a
+/// [PatternMeta].
+fn encode(grid: &Grid, meta: &PatternMeta) -> String {
+    /// Longest line of pattern data, as written by Golly.
+    const LINE_LENGTH: usize = 70;
+
+    let mut rle = String::new();
+
+    if let Some(name) = &meta.name {
+        rle.push_str(&format!("#N {}\n", name));
+    }
+    if let Some(author) = &meta.author {
+        rle.push_str(&format!("#O {}\n", author));
+    }
+    for comment in &meta.comments {
+        rle.push_str(format!("#C {}", comment).trim_end());
+        rle.push('\n');
+    }
+
+    rle.push_str(&format!(
+        "x = {}, y = {}\n",
+        grid.get_num_cols(),
+        grid.get_num_rows()
+    ));
+
+    // runs of (count, tag), dropping dead cells at the end of each row and
+    // merging empty rows into a single run of `$`
+    let mut runs: Vec<(u32, char)> = Vec::new();
+
+    for row in 0..grid.get_num_rows() {
+        if row > 0 {
+            push_run(&mut runs, 1, '$');
+        }
+
+        let mut dead = 0;
+        for col in 0..grid.get_num_cols() {
+            if grid.get(row, col).is_alive() {
+                if dead > 0 {
+                    push_run(&mut runs, dead, 'b');
+                    dead = 0;
+                }
+                push_run(&mut runs, 1, 'o');
+            } else {
+                dead += 1;
+            }
+        }
+    }
+
+    if let Some((_, '$')) = runs.last() {
+        runs.pop();
+    }
+    runs.push((1, '!'));
+
+    let mut line = String::new();
+    for (count, tag) in runs {
+        let token = match count {
+            1 => tag.to_string(),
+            count => format!("{}{}", count, tag),
+        };
+
+        if line.len() + token.len() > LINE_LENGTH {
+            rle.push_str(&line);
+            rle.push('\n');
+            line.clear();
+        }
+        line.push_str(&token);
+    }
+    rle.push_str(&line);
+    rle.push('\n');
+
+    rle
+}
+
+/// Parse a multi-state RLE encoded pattern into a [StateGrid] and the
+/// rulestring declared in its header, if any.
+pub(crate) fn parse_states(rle: &str) -> Result<(StateGrid, Option<String>), RleError> {
+    let mut lines = data_lines(rle);
+
+    let (num_rows, num_cols, rule) = match lines.next() {
+        Some((number, line)) if line.trim().starts_with('x') => parse_header(number, line.trim())?,
+        _ => return Err(RleError::MissingHeader),
+    };
+
+    let mut grid = StateGrid::new(num_rows, num_cols);
+
+    let mut row: u32 = 0;
+    let mut col: u32 = 0;
+    let mut count: Option<u32> = None;
+    let mut prefix: Option<u8> = None;
+
+    'lines: for (number, line) in lines {
+        for (index, tag) in line.chars().enumerate() {
+            let column = index + 1;
+            let invalid = RleError::InvalidTag {
+                line: number,
+                column,
+                tag,
+            };
+
+            if let Some(digit) = tag.to_digit(10) {
+                count = push_digit(count, digit, number, column)?;
+                continue;
+            }
+
+            let state = match tag {
+                // two-state tags
+                'b' | '.' => Some(0),
+                'o' => Some(1),
+
+                // states 1 to 24, offset by the prefix block
+                'A'..='X' => {
+                    let block = prefix.take().unwrap_or(0) as u32;
+                    let state = block * 24 + (tag as u32 - 'A' as u32 + 1);
+                    if state > u8::MAX as u32 {
+                        return Err(invalid);
+                    }
+                    Some(state as u8)
+                }
+
+                // prefix of the next state's block
+                'p'..='y' if prefix.is_none() => {
+                    prefix = Some(tag as u8 - b'p' + 1);
+                    continue;
+                }
+
+                _ => None,
+            };
+
+            if prefix.is_some() {
+                return Err(invalid);
+            }
+
+            let run = count.take().unwrap_or(1);
+
+            match (tag, state) {
+                // dead cells
+                (_, Some(0)) => col = col.saturating_add(run),
+
+                (_, Some(state)) => {
+                    for _ in 0..run {
+                        grid.try_set(row, col, state)
+                            .map_err(|_| RleError::OutOfBounds {
+                                line: number,
+                                column,
+                                row,
+                                col,
+                            })?;
+                        col += 1;
+                    }
+                }
+
+                // end of row(s)
+                ('$', _) => {
+                    row = row.saturating_add(run);
+                    col = 0;
+                }
+
+                // end of pattern
+                ('!', _) => break 'lines,
+
+                (tag, _) if tag.is_whitespace() => {}
+
+                _ => return Err(invalid),
+            }
+        }
+    }
+
+    Ok((grid, rule.map(str::to_string)))
+}
+
+impl Grid {
+    /// Create a new [Grid] from a pattern in the RLE format.
+    ///
+    /// The [Grid] is sized to the `x` (columns) and `y` (rows) dimensions
+    /// declared in the header.
+    ///
+    /// # Arguments
+    /// * `rle` - The RLE encoded pattern.
+    pub fn from_rle(rle: &str) -> Result<Grid, RleError> {
+        parse(rle).map(|(grid, _rule)| grid)
+    }
+
+    /// Encode the [Grid] in the RLE format.
+    pub fn to_rle(&self) -> String {
+        encode(self, &PatternMeta::default())
+    }
+}
+
+impl Pattern {
+    /// Create a new [Pattern] from a pattern in the RLE format, keeping the
+    /// name, author, and comments declared in its `#N`, `#O`, and `#C` lines.
+    ///
+    /// # Arguments
+    /// * `rle` - The RLE encoded pattern.
+    pub fn from_rle(rle: &str) -> Result<Pattern, RleError> {
+        let (grid, _rule) = parse(rle)?;
+
+        Ok(Pattern::new(grid).with_meta(parse_meta(rle)))
+    }
+
+    /// Encode the [Pattern] in the RLE format, including its name, author,
+    /// and comments.
+    pub fn to_rle(&self) -> String {
+        encode(self.get_grid(), self.get_meta())
+    }
+
+    /// Create a new [Pattern] from an RLE pattern file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the RLE pattern file.
+    #[cfg(feature = "std")]
+    pub fn load_rle_file<P: AsRef<Path>>(path: P) -> Result<Pattern, RleError> {
+        let rle = fs::read_to_string(path)?;
+
+        Pattern::from_rle(&rle)
+    }
+
+    /// Save the [Pattern] to a file in the RLE format.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the RLE pattern file.
+    #[cfg(feature = "std")]
+    pub fn save_rle_file<P: AsRef<Path>>(&self, path: P) -> Result<(), RleError> {
+        fs::write(path, self.to_rle())?;
+
+        Ok(())
+    }
+}
+
+impl StateGrid {
+    /// Create a new [StateGrid] from a multi-state pattern in the RLE format.
+    ///
+    /// The [StateGrid] is sized to the `x` (columns) and `y` (rows)
+    /// dimensions declared in the header.
+    ///
+    /// # Arguments
+    /// * `rle` - The RLE encoded pattern.
+    pub fn from_rle(rle: &str) -> Result<StateGrid, RleError> {
+        parse_states(rle).map(|(grid, _rule)| grid)
+    }
+}
+
+impl Grid<u8> {
+    /// Create a new [Grid] of `u8` states from a multi-state pattern in the
+    /// RLE format, e.g. for an automaton loaded from a rule file.
+    ///
+    /// # Arguments
+    /// * `rle` - The RLE encoded pattern.
+    pub fn from_states_rle(rle: &str) -> Result<Grid<u8>, RleError> {
+        let (states, _rule) = parse_states(rle)?;
+        let mut grid = Grid::filled(states.get_num_rows(), states.get_num_cols(), 0);
+
+        for row in 0..states.get_num_rows() {
+            for col in 0..states.get_num_cols() {
+                grid.set(row, col, states.get(row, col));
+            }
+        }
+
+        Ok(grid)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ConwaySim {
+    /// Create a new simulation from a pattern in the RLE format.
+    ///
+    /// The simulation uses the [Rule] declared in the header, or Conway's
+    /// rule if none is declared.
+    ///
+    /// # Arguments
+    /// * `rle` - The RLE encoded pattern.
+    pub fn from_rle(rle: &str) -> Result<ConwaySim, RleError> {
+        let (grid, rule) = parse(rle)?;
+
+        let mut sim = ConwaySim::new_with_grid(grid);
+        sim.set_rule(rule.unwrap_or_default());
+
+        Ok(sim)
+    }
+
+    /// Create a new simulation from an RLE pattern file.
+    ///
+    /// The simulation uses the [Rule] declared in the file's header, or
+    /// Conway's rule if none is declared.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the RLE pattern file.
+    pub fn load_rle_file<P: AsRef<Path>>(path: P) -> Result<ConwaySim, RleError> {
+        let rle = fs::read_to_string(path)?;
+
+        ConwaySim::from_rle(&rle)
+    }
+}
@@ -0,0 +1,165 @@
+//! Life-like birth/survival rules expressed in B/S notation.
+
+use alloc::string::{String, ToString};
+use core::error;
+use core::fmt;
+use core::str::FromStr;
+
+/// Error raised while parsing a rulestring.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RuleError {
+    /// The rulestring that failed to parse.
+    rulestring: String,
+}
+
+impl RuleError {
+    /// Create a new [RuleError] for a rulestring.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn new(rulestring: &str) -> RuleError {
+        RuleError {
+            rulestring: rulestring.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid rulestring: '{}'", self.rulestring)
+    }
+}
+
+impl error::Error for RuleError {}
+
+/// A Life-like [Rule] describing which neighbor counts cause a dead cell to be
+/// born and which allow a live cell to survive.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Rule {
+    /// `birth[n]` is set if a dead cell with `n` live neighbors is born.
+    birth: [bool; 9],
+
+    /// `survival[n]` is set if a live cell with `n` live neighbors survives.
+    survival: [bool; 9],
+}
+
+impl Rule {
+    /// Create a new [Rule] from lists of neighbor counts.
+    ///
+    /// # Arguments
+    /// * `birth` - Neighbor counts which cause a dead cell to be born.
+    /// * `survival` - Neighbor counts which allow a live cell to survive.
+    ///
+    /// # Panics
+    /// Panics if any neighbor count is greater than 8.
+    pub fn new(birth: &[u8], survival: &[u8]) -> Rule {
+        let mut rule = Rule {
+            birth: [false; 9],
+            survival: [false; 9],
+        };
+
+        for &count in birth {
+            rule.birth[count as usize] = true;
+        }
+
+        for &count in survival {
+            rule.survival[count as usize] = true;
+        }
+
+        rule
+    }
+
+    /// Conway's Game of Life, `B3/S23`.
+    pub fn conway() -> Rule {
+        Rule::new(&[3], &[2, 3])
+    }
+
+    /// Determine if a dead cell with `neighbor_count` live neighbors is born.
+    pub fn is_birth(&self, neighbor_count: u8) -> bool {
+        self.birth[neighbor_count as usize]
+    }
+
+    /// Determine if a live cell with `neighbor_count` live neighbors survives.
+    pub fn is_survival(&self, neighbor_count: u8) -> bool {
+        self.survival[neighbor_count as usize]
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Rule {
+        Rule::conway()
+    }
+}
+
+/// Parse a list of neighbor count digits into a lookup table.
+pub(crate) fn parse_counts(digits: &str) -> Option<[bool; 9]> {
+    let mut counts = [false; 9];
+
+    for digit in digits.chars() {
+        match digit.to_digit(10) {
+            Some(count) if count <= 8 => counts[count as usize] = true,
+            _ => return None,
+        }
+    }
+
+    Some(counts)
+}
+
+impl FromStr for Rule {
+    type Err = RuleError;
+
+    /// Parse a rulestring in `B3/S23` notation (case insensitive, in either
+    /// order) or in the legacy `23/3` survival/birth notation.
+    fn from_str(rulestring: &str) -> Result<Rule, RuleError> {
+        let error = || RuleError {
+            rulestring: rulestring.to_string(),
+        };
+
+        let mut parts = rulestring.trim().splitn(2, '/');
+        let first = parts.next().ok_or_else(error)?;
+        let second = parts.next().ok_or_else(error)?;
+
+        let mut birth: Option<[bool; 9]> = None;
+        let mut survival: Option<[bool; 9]> = None;
+
+        for part in [first, second].iter() {
+            let mut chars = part.chars();
+
+            match chars.next() {
+                Some('B') | Some('b') if birth.is_none() => {
+                    birth = Some(parse_counts(chars.as_str()).ok_or_else(error)?)
+                }
+                Some('S') | Some('s') if survival.is_none() => {
+                    survival = Some(parse_counts(chars.as_str()).ok_or_else(error)?)
+                }
+                _ => {}
+            }
+        }
+
+        match (birth, survival) {
+            (Some(birth), Some(survival)) => Ok(Rule { birth, survival }),
+
+            // legacy notation lists the survival counts first
+            (None, None) => Ok(Rule {
+                birth: parse_counts(second).ok_or_else(error)?,
+                survival: parse_counts(first).ok_or_else(error)?,
+            }),
+
+            _ => Err(error()),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "B")?;
+        for count in (0..9).filter(|&count| self.birth[count]) {
+            write!(f, "{}", count)?;
+        }
+
+        write!(f, "/S")?;
+        for count in (0..9).filter(|&count| self.survival[count]) {
+            write!(f, "{}", count)?;
+        }
+
+        Ok(())
+    }
+}
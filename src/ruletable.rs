@@ -0,0 +1,746 @@
+//! Support for the `.rule` files Golly uses to define automata at runtime.
+//!
+//! A `.rule` file starts with a `@RULE <name>` line and defines the
+//! transitions of its automaton in either a `@TABLE` or a `@TREE` section;
+//! other sections, such as `@COLORS` and `@ICONS`, are ignored. A
+//! [RuleTable] loaded from one implements [StateRule] over `u8` states, so
+//! it runs on a [Sim](crate::Sim) like any compiled-in automaton:
+//!
+//! ```
+//! use gameoflife_rs::ruletable::RuleTable;
+//! use gameoflife_rs::{Grid, Sim};
+//!
+//! let table: RuleTable = "@RULE Life
+//! @TABLE
+//! n_states:2
+//! neighborhood:Moore
+//! symmetries:permute
+//! var a={0,1}
+//! var b={0,1}
+//! var c={0,1}
+//! var d={0,1}
+//! var e={0,1}
+//! var f={0,1}
+//! var g={0,1}
+//! var h={0,1}
+//! 0,1,1,1,0,0,0,0,0,1
+//! 1,1,1,0,0,0,0,0,0,1
+//! 1,1,1,1,0,0,0,0,0,1
+//! 1,a,b,c,d,e,f,g,h,0"
+//!     .parse()?;
+//!
+//! let grid = Grid::from_states_rle("x = 3, y = 3\n$3o!")?;
+//! let mut sim = Sim::new(grid, table);
+//! sim.step();
+//!
+//! assert_eq!(sim.get_grid().get(0, 1), 1);
+//! assert_eq!(sim.get_grid().get(1, 0), 0);
+//! # Ok::<(), gameoflife_rs::GolError>(())
+//! ```
+//!
+//! # Tables
+//!
+//! A table declares its `n_states`, `neighborhood` (`Moore` or
+//! `vonNeumann`), and `symmetries` (`none`, `rotate4`, `rotate8`,
+//! `reflect_horizontal`, `rotate4reflect`, `rotate8reflect`, or `permute`),
+//! followed by `var` declarations and transitions. Each transition lists the
+//! state of the cell, of its neighbors clockwise from north, and the next
+//! state of the cell, separated by commas or, with at most 10 states,
+//! written as consecutive digits. A variable appearing more than once in a
+//! transition takes the same value everywhere in it. The first matching
+//! transition applies, and cells matching none keep their state.
+//!
+//! Transitions are expanded by their bound variables and symmetries, then
+//! looked up through one bitmask of matching transitions per neighbor
+//! position and state, as Golly does.
+//!
+//! # Trees
+//!
+//! A tree declares its `num_states`, `num_neighbors` (4 or 8), and
+//! `num_nodes`, followed by one line per node giving its level and children.
+//! The last node is the root, indexed by the states of the neighbors in the
+//! order NW, NE, SW, SE, N, W, E, S (or N, W, E, S) and of the cell itself.
+
+use std::collections::{HashMap, HashSet};
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::automaton::StateRule;
+
+/// Set of states, one bit per state.
+type StateSet = [u64; 4];
+
+/// [StateSet] holding every state, matching any neighbor.
+const ANY_STATE: StateSet = [u64::MAX; 4];
+
+/// Indices into the neighbors passed to a [StateRule] of the neighbors of
+/// the Moore neighborhood, clockwise from north.
+const MOORE_CLOCKWISE: [usize; 8] = [1, 2, 4, 7, 6, 5, 3, 0];
+
+/// Indices into the neighbors passed to a [StateRule] of the neighbors of
+/// the von Neumann neighborhood, clockwise from north.
+const VON_NEUMANN_CLOCKWISE: [usize; 4] = [1, 4, 6, 3];
+
+/// Indices into the neighbors passed to a [StateRule] of the neighbors of
+/// the Moore neighborhood, in the order they index a rule tree.
+const MOORE_TREE_ORDER: [usize; 8] = [0, 2, 5, 7, 1, 3, 4, 6];
+
+/// Indices into the neighbors passed to a [StateRule] of the neighbors of
+/// the von Neumann neighborhood, in the order they index a rule tree.
+const VON_NEUMANN_TREE_ORDER: [usize; 4] = [1, 3, 4, 6];
+
+/// Error raised while loading a `.rule` file.
+#[derive(Debug)]
+pub enum RuleTableError {
+    /// The file has neither a `@TABLE` nor a `@TREE` section.
+    MissingSection,
+
+    /// A line of the table or tree is malformed, or refers to an undefined
+    /// variable, state, or node.
+    InvalidLine { line: usize },
+
+    /// A line declares a neighborhood or symmetries which are not supported.
+    Unsupported { line: usize },
+
+    /// The rule file could not be read.
+    Io(io::Error),
+}
+
+impl fmt::Display for RuleTableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleTableError::MissingSection => write!(f, "missing @TABLE or @TREE section"),
+            RuleTableError::InvalidLine { line } => write!(f, "invalid rule line {}", line),
+            RuleTableError::Unsupported { line } => {
+                write!(f, "unsupported neighborhood or symmetries on line {}", line)
+            }
+            RuleTableError::Io(err) => write!(f, "unable to access rule file: {}", err),
+        }
+    }
+}
+
+impl error::Error for RuleTableError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            RuleTableError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for RuleTableError {
+    fn from(err: io::Error) -> RuleTableError {
+        RuleTableError::Io(err)
+    }
+}
+
+/// The neighbors a transition of a [RuleTable] depends on.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Neighborhood {
+    /// The eight orthogonal and diagonal neighbors.
+    Moore,
+
+    /// The four orthogonal neighbors.
+    VonNeumann,
+}
+
+impl Neighborhood {
+    /// Get the indices of the neighbors, clockwise from north, into the
+    /// neighbors passed to a [StateRule].
+    fn clockwise(self) -> &'static [usize] {
+        match self {
+            Neighborhood::Moore => &MOORE_CLOCKWISE,
+            Neighborhood::VonNeumann => &VON_NEUMANN_CLOCKWISE,
+        }
+    }
+}
+
+/// The symmetries under which the transitions of a table also apply.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Symmetries {
+    None,
+    Rotate4,
+    Rotate8,
+    ReflectHorizontal,
+    Rotate4Reflect,
+    Rotate8Reflect,
+    Permute,
+}
+
+impl Symmetries {
+    /// Parse the name of the symmetries in a table.
+    fn parse(name: &str) -> Option<Symmetries> {
+        match name {
+            "none" => Some(Symmetries::None),
+            "rotate4" => Some(Symmetries::Rotate4),
+            "rotate8" => Some(Symmetries::Rotate8),
+            "reflect_horizontal" => Some(Symmetries::ReflectHorizontal),
+            "rotate4reflect" => Some(Symmetries::Rotate4Reflect),
+            "rotate8reflect" => Some(Symmetries::Rotate8Reflect),
+            "permute" => Some(Symmetries::Permute),
+            _ => None,
+        }
+    }
+
+    /// Determine if the symmetries apply to a neighborhood.
+    fn supports(self, neighborhood: Neighborhood) -> bool {
+        match self {
+            Symmetries::Rotate8 | Symmetries::Rotate8Reflect => neighborhood == Neighborhood::Moore,
+            _ => true,
+        }
+    }
+
+    /// Get the arrangements of the neighbors, clockwise from north, under
+    /// which a transition applies.
+    fn arrangements(self, neighbors: &[StateSet]) -> Vec<Vec<StateSet>> {
+        let count = neighbors.len();
+        let rotations = |step: usize| -> Vec<Vec<StateSet>> {
+            (0..count)
+                .step_by(step)
+                .map(|offset| {
+                    (0..count)
+                        .map(|i| neighbors[(i + offset) % count])
+                        .collect()
+                })
+                .collect()
+        };
+        let reflected = |arrangements: Vec<Vec<StateSet>>| -> Vec<Vec<StateSet>> {
+            let mut all = arrangements.clone();
+            all.extend(
+                arrangements
+                    .iter()
+                    .map(|sets| (0..count).map(|i| sets[(count - i) % count]).collect()),
+            );
+            all
+        };
+
+        match self {
+            Symmetries::None => vec![neighbors.to_vec()],
+            Symmetries::Rotate4 => rotations(count / 4),
+            Symmetries::Rotate8 => rotations(1),
+            Symmetries::ReflectHorizontal => reflected(vec![neighbors.to_vec()]),
+            Symmetries::Rotate4Reflect => reflected(rotations(count / 4)),
+            Symmetries::Rotate8Reflect => reflected(rotations(1)),
+            Symmetries::Permute => permutations(neighbors),
+        }
+    }
+}
+
+/// Get the distinct permutations of a list of [StateSet]s.
+fn permutations(sets: &[StateSet]) -> Vec<Vec<StateSet>> {
+    let mut sets = sets.to_vec();
+    sets.sort_unstable();
+
+    let mut all = vec![sets.clone()];
+
+    // step through the permutations in lexicographic order, which skips
+    // those swapping equal sets
+    loop {
+        let pivot = match (1..sets.len()).rev().find(|&i| sets[i - 1] < sets[i]) {
+            Some(i) => i - 1,
+            None => return all,
+        };
+        let successor = (pivot + 1..sets.len())
+            .rev()
+            .find(|&i| sets[pivot] < sets[i])
+            .expect("a later set is greater than the pivot");
+
+        sets.swap(pivot, successor);
+        sets[pivot + 1..].reverse();
+        all.push(sets.clone());
+    }
+}
+
+/// Create a [StateSet] of a single state.
+fn single(state: u8) -> StateSet {
+    let mut set = [0; 4];
+    set[state as usize / 64] |= 1 << (state % 64);
+    set
+}
+
+/// An entry of a transition: a state or the values of a variable.
+#[derive(Clone, Debug)]
+enum Entry<'a> {
+    State(u8),
+    Variable(&'a str),
+}
+
+/// Transitions of a table compiled into bitmasks of the transitions which
+/// match each state at each position.
+#[derive(Clone, Debug)]
+struct Table {
+    /// Number of states of the automaton.
+    num_states: usize,
+
+    /// Number of 64-bit words of each bitmask.
+    num_words: usize,
+
+    /// Bitmasks of the transitions matching each (position, state), the cell
+    /// itself being position 8.
+    masks: Vec<u64>,
+
+    /// Next state of the cell under each transition.
+    outputs: Vec<u8>,
+}
+
+impl Table {
+    /// Compile expanded transitions of (inputs, output) into bitmasks.
+    fn new(num_states: usize, transitions: &[([StateSet; 9], u8)]) -> Table {
+        let num_words = transitions.len().div_ceil(64);
+        let mut masks = vec![0; 9 * num_states * num_words];
+
+        for (index, (inputs, _)) in transitions.iter().enumerate() {
+            for (position, set) in inputs.iter().enumerate() {
+                for state in 0..num_states {
+                    if set[state / 64] & (1 << (state % 64)) != 0 {
+                        let mask = (position * num_states + state) * num_words;
+                        masks[mask + index / 64] |= 1 << (index % 64);
+                    }
+                }
+            }
+        }
+
+        Table {
+            num_states,
+            num_words,
+            masks,
+            outputs: transitions.iter().map(|&(_, output)| output).collect(),
+        }
+    }
+
+    /// Get the next state of the first transition matching a cell, if any.
+    fn lookup(&self, state: u8, neighbors: &[u8; 8]) -> Option<u8> {
+        if (state as usize) >= self.num_states
+            || neighbors
+                .iter()
+                .any(|&neighbor| neighbor as usize >= self.num_states)
+        {
+            return None;
+        }
+
+        let mask = |position: usize, state: u8| {
+            let start = (position * self.num_states + state as usize) * self.num_words;
+            &self.masks[start..start + self.num_words]
+        };
+
+        for word in 0..self.num_words {
+            let mut matches = mask(8, state)[word];
+            for (position, &neighbor) in neighbors.iter().enumerate() {
+                matches &= mask(position, neighbor)[word];
+            }
+
+            if matches != 0 {
+                return Some(self.outputs[word * 64 + matches.trailing_zeros() as usize]);
+            }
+        }
+
+        None
+    }
+}
+
+/// A decision tree whose leaves are the next states of cells.
+#[derive(Clone, Debug)]
+struct Tree {
+    /// Number of states of the automaton.
+    num_states: usize,
+
+    /// Order in which the neighbors index the nodes, before the cell itself.
+    order: &'static [usize],
+
+    /// Children of each node, the last being the root. The children of
+    /// nodes of level 1 are states, those of higher levels nodes.
+    nodes: Vec<Vec<u32>>,
+}
+
+impl Tree {
+    /// Get the next state of a cell by walking the tree.
+    fn lookup(&self, state: u8, neighbors: &[u8; 8]) -> Option<u8> {
+        let inputs = self.order.iter().map(|&index| neighbors[index]);
+        let mut node = self.nodes.len() - 1;
+
+        for input in inputs.chain(Some(state)) {
+            if input as usize >= self.num_states {
+                return None;
+            }
+            node = self.nodes[node][input as usize] as usize;
+        }
+
+        // the last child was a leaf
+        Some(node as u8)
+    }
+}
+
+/// How a [RuleTable] finds the next states of cells.
+#[derive(Clone, Debug)]
+enum Engine {
+    Table(Table),
+    Tree(Tree),
+}
+
+/// An automaton loaded from a Golly `.rule` file.
+#[derive(Clone, Debug)]
+pub struct RuleTable {
+    /// Name of the rule, from its `@RULE` line.
+    name: String,
+
+    /// Number of states of the automaton.
+    num_states: usize,
+
+    /// The neighbors the transitions depend on.
+    neighborhood: Neighborhood,
+
+    /// The compiled table or tree.
+    engine: Engine,
+}
+
+impl RuleTable {
+    /// Load a [RuleTable] from a `.rule` file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the rule file.
+    pub fn load_file<P: AsRef<Path>>(path: P) -> Result<RuleTable, RuleTableError> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// Get the name of the rule, empty if the file has no `@RULE` line.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the number of states of the automaton.
+    pub fn get_num_states(&self) -> usize {
+        self.num_states
+    }
+
+    /// Get the [Neighborhood] the transitions depend on.
+    pub fn get_neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+}
+
+impl StateRule<u8> for RuleTable {
+    /// Get the next state of a cell. Cells which match no transition, or
+    /// whose neighborhood holds a state beyond those of the automaton, keep
+    /// their state.
+    fn next(&self, state: u8, neighbors: &[u8; 8]) -> u8 {
+        let next = match &self.engine {
+            Engine::Table(table) => table.lookup(state, neighbors),
+            Engine::Tree(tree) => tree.lookup(state, neighbors),
+        };
+
+        next.unwrap_or(state)
+    }
+}
+
+impl FromStr for RuleTable {
+    type Err = RuleTableError;
+
+    /// Parse the contents of a `.rule` file, preferring its `@TABLE` section
+    /// to its `@TREE` section if it has both.
+    fn from_str(rule: &str) -> Result<RuleTable, RuleTableError> {
+        let mut name = String::new();
+        let mut sections: HashMap<&str, Vec<(usize, &str)>> = HashMap::new();
+        let mut section = "";
+
+        for (index, line) in rule.lines().enumerate() {
+            let line = line.trim();
+
+            if let Some(header) = line.strip_prefix('@') {
+                let mut words = header.split_whitespace();
+                section = words.next().unwrap_or("");
+                if section == "RULE" {
+                    name = words.next().unwrap_or("").to_string();
+                }
+                continue;
+            }
+
+            // drop comments and blank lines
+            let line = line.split('#').next().unwrap_or("").trim();
+            if !line.is_empty() {
+                sections.entry(section).or_default().push((index + 1, line));
+            }
+        }
+
+        let (num_states, neighborhood, engine) = if let Some(lines) = sections.get("TABLE") {
+            parse_table(lines)?
+        } else if let Some(lines) = sections.get("TREE") {
+            parse_tree(lines)?
+        } else {
+            return Err(RuleTableError::MissingSection);
+        };
+
+        Ok(RuleTable {
+            name,
+            num_states,
+            neighborhood,
+            engine,
+        })
+    }
+}
+
+/// Parse the lines of a `@TABLE` section into its number of states,
+/// [Neighborhood], and compiled [Table].
+fn parse_table(lines: &[(usize, &str)]) -> Result<(usize, Neighborhood, Engine), RuleTableError> {
+    let mut num_states: Option<usize> = None;
+    let mut neighborhood = Neighborhood::Moore;
+    let mut symmetries = (Symmetries::None, 0);
+    let mut variables: HashMap<&str, Vec<u8>> = HashMap::new();
+
+    let mut transitions: Vec<([StateSet; 9], u8)> = Vec::new();
+    let mut seen: HashSet<[StateSet; 9]> = HashSet::new();
+
+    for &(number, line) in lines {
+        let invalid = || RuleTableError::InvalidLine { line: number };
+
+        if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim();
+
+            match key.trim() {
+                "n_states" => match value.parse() {
+                    Ok(count) if (1..=256).contains(&count) => num_states = Some(count),
+                    _ => return Err(invalid()),
+                },
+                "neighborhood" => {
+                    neighborhood = match value {
+                        "Moore" => Neighborhood::Moore,
+                        "vonNeumann" => Neighborhood::VonNeumann,
+                        _ => return Err(RuleTableError::Unsupported { line: number }),
+                    }
+                }
+                "symmetries" => match Symmetries::parse(value) {
+                    Some(parsed) => symmetries = (parsed, number),
+                    None => return Err(RuleTableError::Unsupported { line: number }),
+                },
+                _ => return Err(invalid()),
+            }
+            continue;
+        }
+
+        let num_states = num_states.ok_or_else(invalid)?;
+        let state = |entry: &str| match entry.parse::<usize>() {
+            Ok(state) if state < num_states => Some(state as u8),
+            _ => None,
+        };
+
+        if let Some(declaration) = line.strip_prefix("var ") {
+            let (name, values) = declaration.split_once('=').ok_or_else(invalid)?;
+            let values = values.trim();
+            let values = values
+                .strip_prefix('{')
+                .and_then(|values| values.strip_suffix('}'))
+                .unwrap_or(values);
+
+            let mut states: Vec<u8> = Vec::new();
+            for value in values.split(',').map(str::trim) {
+                match (state(value), variables.get(value)) {
+                    (Some(value), _) => states.push(value),
+                    (None, Some(values)) => states.extend_from_slice(values),
+                    (None, None) => return Err(invalid()),
+                }
+            }
+
+            variables.insert(name.trim(), states);
+            continue;
+        }
+
+        let (symmetries, symmetries_line) = symmetries;
+        if !symmetries.supports(neighborhood) {
+            return Err(RuleTableError::Unsupported {
+                line: symmetries_line,
+            });
+        }
+
+        let entries: Vec<&str> = if line.contains(',') {
+            line.split(',').map(str::trim).collect()
+        } else if num_states <= 10 {
+            line.char_indices()
+                .map(|(index, digit)| &line[index..index + digit.len_utf8()])
+                .collect()
+        } else {
+            return Err(invalid());
+        };
+
+        let clockwise = neighborhood.clockwise();
+        if entries.len() != clockwise.len() + 2 {
+            return Err(invalid());
+        }
+
+        let mut parsed: Vec<Entry> = Vec::new();
+        for &entry in &entries {
+            parsed.push(match (state(entry), variables.contains_key(entry)) {
+                (Some(state), _) => Entry::State(state),
+                (None, true) => Entry::Variable(entry),
+                (None, false) => return Err(invalid()),
+            });
+        }
+
+        // variables appearing more than once take the same value throughout
+        let mut bound: Vec<&str> = Vec::new();
+        for entry in &parsed {
+            if let Entry::Variable(name) = entry {
+                let count = entries.iter().filter(|&&other| other == *name).count();
+                if count > 1 && !bound.contains(name) {
+                    bound.push(name);
+                }
+            }
+        }
+        if let Some(Entry::Variable(name)) = parsed.last() {
+            if !bound.contains(name) {
+                return Err(invalid());
+            }
+        }
+
+        for values in bindings(&bound, &variables) {
+            let set = |entry: &Entry| match entry {
+                Entry::State(state) => single(*state),
+                Entry::Variable(name) => match bound.iter().position(|other| other == name) {
+                    Some(index) => single(values[index]),
+                    None => variables[name].iter().fold([0; 4], |mut set, &state| {
+                        set[state as usize / 64] |= 1 << (state % 64);
+                        set
+                    }),
+                },
+            };
+
+            let center = set(&parsed[0]);
+            let neighbors: Vec<StateSet> = parsed[1..parsed.len() - 1].iter().map(set).collect();
+            let output = match &parsed[parsed.len() - 1] {
+                Entry::State(state) => *state,
+                Entry::Variable(name) => {
+                    values[bound.iter().position(|other| other == name).unwrap()]
+                }
+            };
+
+            for arrangement in symmetries.arrangements(&neighbors) {
+                let mut inputs = [ANY_STATE; 9];
+                inputs[8] = center;
+                for (&index, &set) in clockwise.iter().zip(arrangement.iter()) {
+                    inputs[index] = set;
+                }
+
+                // a later transition matching the same cells never applies
+                if seen.insert(inputs) {
+                    transitions.push((inputs, output));
+                }
+            }
+        }
+    }
+
+    let num_states = num_states.ok_or(RuleTableError::MissingSection)?;
+
+    Ok((
+        num_states,
+        neighborhood,
+        Engine::Table(Table::new(num_states, &transitions)),
+    ))
+}
+
+/// Get every combination of values of the bound variables of a transition.
+fn bindings(bound: &[&str], variables: &HashMap<&str, Vec<u8>>) -> Vec<Vec<u8>> {
+    let mut combinations: Vec<Vec<u8>> = vec![Vec::new()];
+
+    for name in bound {
+        combinations = combinations
+            .iter()
+            .flat_map(|values| {
+                variables[name].iter().map(move |&value| {
+                    let mut values = values.clone();
+                    values.push(value);
+                    values
+                })
+            })
+            .collect();
+    }
+
+    combinations
+}
+
+/// Parse the lines of a `@TREE` section into its number of states,
+/// [Neighborhood], and [Tree].
+fn parse_tree(lines: &[(usize, &str)]) -> Result<(usize, Neighborhood, Engine), RuleTableError> {
+    let mut num_states: Option<usize> = None;
+    let mut neighborhood: Option<(Neighborhood, &'static [usize])> = None;
+    let mut num_nodes: Option<usize> = None;
+    let mut nodes: Vec<Vec<u32>> = Vec::new();
+    let mut levels: Vec<usize> = Vec::new();
+    let mut last_line = 0;
+
+    for &(number, line) in lines {
+        let invalid = || RuleTableError::InvalidLine { line: number };
+        last_line = number;
+
+        if let Some((key, value)) = line.split_once('=') {
+            let value: usize = value.trim().parse().map_err(|_| invalid())?;
+
+            match key.trim() {
+                "num_states" if (1..=256).contains(&value) => num_states = Some(value),
+                "num_neighbors" => {
+                    neighborhood = match value {
+                        8 => Some((Neighborhood::Moore, &MOORE_TREE_ORDER)),
+                        4 => Some((Neighborhood::VonNeumann, &VON_NEUMANN_TREE_ORDER)),
+                        _ => return Err(RuleTableError::Unsupported { line: number }),
+                    }
+                }
+                "num_nodes" => num_nodes = Some(value),
+                _ => return Err(invalid()),
+            }
+            continue;
+        }
+
+        let num_states = num_states.ok_or_else(invalid)?;
+        let values: Vec<usize> = line
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid())?;
+
+        let (level, children) = match values.split_first() {
+            Some((&level, children)) if level >= 1 && children.len() == num_states => {
+                (level, children)
+            }
+            _ => return Err(invalid()),
+        };
+
+        // leaves are states, and other children are nodes one level down
+        let valid = children.iter().all(|&child| {
+            if level == 1 {
+                child < num_states
+            } else {
+                child < nodes.len() && levels[child] == level - 1
+            }
+        });
+        if !valid {
+            return Err(invalid());
+        }
+
+        nodes.push(children.iter().map(|&child| child as u32).collect());
+        levels.push(level);
+    }
+
+    let invalid = || RuleTableError::InvalidLine { line: last_line };
+    let (num_states, (neighborhood, order)) = match (num_states, neighborhood) {
+        (Some(num_states), Some(neighborhood)) => (num_states, neighborhood),
+        _ => return Err(invalid()),
+    };
+
+    // the root is indexed by every neighbor and the cell itself
+    if levels.last() != Some(&(order.len() + 1))
+        || num_nodes.is_some_and(|count| count != nodes.len())
+    {
+        return Err(invalid());
+    }
+
+    Ok((
+        num_states,
+        neighborhood,
+        Engine::Tree(Tree {
+            num_states,
+            order,
+            nodes,
+        }),
+    ))
+}
@@ -0,0 +1,131 @@
+//! Asynchronous stepping of simulations, for Tokio-based servers and GUIs.
+//!
+//! [ConwaySim::run] turns a simulation into a [Runner], a [Stream] which
+//! steps the simulation on a Tokio timer and yields each new generation
+//! number, without blocking a thread between generations. The simulation
+//! can be inspected or edited between generations through the [Runner]:
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use futures_util::StreamExt;
+//! use gameoflife_rs::ConwaySim;
+//!
+//! async fn animate(sim: ConwaySim) {
+//!     let mut runner = sim.run(Duration::from_millis(100));
+//!
+//!     while let Some(generation) = runner.next().await {
+//!         println!("generation {}:\n{}", generation, runner.get_sim().get_grid());
+//!
+//!         if generation == 10 {
+//!             break;
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+use tokio::time::{self, Instant, Interval, MissedTickBehavior};
+
+use crate::game::ConwaySim;
+use crate::world::World;
+
+/// Create the timer of a [Runner], first firing one interval from now.
+fn timer(interval: Duration) -> Interval {
+    let mut timer = time::interval_at(Instant::now() + interval, interval);
+
+    // a runner left unpolled resumes at its pace rather than catching up
+    timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    timer
+}
+
+/// A [Stream] stepping a simulation at a fixed interval, yielding the number
+/// of each new generation.
+///
+/// The stream never ends on its own; stop polling it, or use a combinator
+/// such as `take_while`, to stop the simulation.
+pub struct Runner<W: World = crate::Grid> {
+    /// The simulation being stepped.
+    sim: ConwaySim<W>,
+
+    /// The timer of the generations.
+    timer: Interval,
+}
+
+impl<W: World> Runner<W> {
+    /// Get the simulation being stepped.
+    pub fn get_sim(&self) -> &ConwaySim<W> {
+        &self.sim
+    }
+
+    /// Get the simulation being stepped for editing between generations.
+    pub fn get_sim_mut(&mut self) -> &mut ConwaySim<W> {
+        &mut self.sim
+    }
+
+    /// Set the interval between generations, the next one due one interval
+    /// from now.
+    ///
+    /// # Arguments
+    /// * `interval` - The time between generations.
+    ///
+    /// # Panics
+    /// Panics if the interval is zero or if called outside of a Tokio
+    /// runtime.
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.timer = timer(interval);
+    }
+
+    /// Get the interval between generations.
+    pub fn get_interval(&self) -> Duration {
+        self.timer.period()
+    }
+
+    /// Stop stepping the simulation, returning it.
+    pub fn into_sim(self) -> ConwaySim<W> {
+        self.sim
+    }
+}
+
+impl<W: World + Unpin> Stream for Runner<W> {
+    type Item = u32;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u32>> {
+        let runner = self.get_mut();
+
+        match runner.timer.poll_tick(cx) {
+            Poll::Ready(_) => {
+                runner.sim.step();
+                Poll::Ready(Some(runner.sim.get_generation()))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<W: World> ConwaySim<W> {
+    /// Step the simulation asynchronously at a fixed interval, as a [Runner]
+    /// streaming the number of each new generation.
+    ///
+    /// The first generation is due one interval from now. A runner which is
+    /// not polled for a while resumes one interval after it is polled again
+    /// rather than stepping repeatedly to catch up.
+    ///
+    /// # Arguments
+    /// * `interval` - The time between generations.
+    ///
+    /// # Panics
+    /// Panics if the interval is zero or if called outside of a Tokio
+    /// runtime.
+    pub fn run(self, interval: Duration) -> Runner<W> {
+        Runner {
+            sim: self,
+            timer: timer(interval),
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! A pool of long-lived worker threads spreading independent tasks between
+//! them with work stealing.
+//!
+//! The tasks of each call are split into one contiguous queue per thread.
+//! Each thread takes batches from the front of its own queue and, once it
+//! runs dry, steals batches from the other queues, so threads given cheaper
+//! tasks help those given more expensive ones instead of idling.
+//!
+//! The workers are spawned the first time a call asks for them and then
+//! wait for the tasks of later calls, so a simulation stepped on several
+//! threads pays for spawning them once rather than every generation. The
+//! calling thread takes a queue of its own, so a call on `n` threads keeps
+//! `n - 1` workers busy.
+
+use std::fmt;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+/// Number of tasks taken from a queue at a time, trading contention on the
+/// queues for balance between the threads.
+const BATCH_SIZE: usize = 4;
+
+/// The share of a call's tasks run by one worker.
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A contiguous queue of task indices, taken from the front by any thread.
+struct Queue {
+    /// Index of the next task to take, which may run past the end.
+    next: AtomicUsize,
+
+    /// Index past the last task of the queue.
+    end: usize,
+}
+
+impl Queue {
+    /// Take the next batch of task indices, if any remain.
+    fn take(&self) -> Option<Range<usize>> {
+        let start = self.next.fetch_add(BATCH_SIZE, Ordering::Relaxed);
+
+        if start < self.end {
+            Some(start..(start + BATCH_SIZE).min(self.end))
+        } else {
+            None
+        }
+    }
+}
+
+/// Run the tasks of a thread's own queue, then steal from the others.
+///
+/// # Arguments
+/// * `items` - The items to run the task for.
+/// * `queues` - The queues of item indices, one per thread.
+/// * `index` - The index of the thread's own queue.
+/// * `task` - The task run for each item, producing a result or [None].
+fn drain<T, R>(
+    items: &[T],
+    queues: &[Queue],
+    index: usize,
+    task: &(dyn Fn(&T) -> Option<R> + Sync),
+) -> Vec<R> {
+    let mut results: Vec<R> = Vec::new();
+
+    for queue in queues[index..].iter().chain(&queues[..index]) {
+        while let Some(batch) = queue.take() {
+            results.extend(items[batch].iter().filter_map(task));
+        }
+    }
+
+    results
+}
+
+/// A long-lived thread running the [Job]s sent to it.
+struct Worker {
+    /// Sender of the worker's [Job]s, which stops the worker once dropped.
+    jobs: Sender<Job>,
+
+    /// The worker's thread.
+    handle: JoinHandle<()>,
+}
+
+impl Worker {
+    /// Spawn a new worker, waiting for [Job]s.
+    fn spawn() -> Worker {
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        let handle = thread::spawn(move || {
+            for job in receiver {
+                job();
+            }
+        });
+
+        Worker { jobs, handle }
+    }
+}
+
+/// A pool of worker threads, spawned as calls to [Pool::run] ask for them
+/// and joined once the pool is dropped.
+pub(crate) struct Pool {
+    /// The workers spawned so far.
+    workers: Mutex<Vec<Worker>>,
+}
+
+impl Pool {
+    /// Create a new pool, without spawning any worker until one is needed.
+    pub(crate) fn new() -> Pool {
+        Pool {
+            workers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Run a task for every item on up to `num_threads` threads, the calling
+    /// thread and the pool's workers, returning the results the task
+    /// produced in no particular order.
+    ///
+    /// With a single thread, the tasks run on the calling thread without
+    /// waking any worker.
+    ///
+    /// # Arguments
+    /// * `items` - The items to run the task for.
+    /// * `num_threads` - The number of threads to share the items between.
+    /// * `task` - The task run for each item, producing a result or [None].
+    ///
+    /// # Panics
+    /// Panics if the task panics on any thread.
+    pub(crate) fn run<T, R, F>(&self, items: Vec<T>, num_threads: usize, task: F) -> Vec<R>
+    where
+        T: Send + Sync + 'static,
+        R: Send + 'static,
+        F: Fn(&T) -> Option<R> + Send + Sync + 'static,
+    {
+        let num_threads = num_threads.clamp(1, items.len().max(1));
+        if num_threads == 1 {
+            return items.iter().filter_map(&task).collect();
+        }
+
+        let queue_len = items.len().div_ceil(num_threads);
+        let queues: Arc<[Queue]> = (0..num_threads)
+            .map(|index| Queue {
+                next: AtomicUsize::new(index * queue_len),
+                end: ((index + 1) * queue_len).min(items.len()),
+            })
+            .collect();
+        let items = Arc::new(items);
+        let task = Arc::new(task);
+
+        let (sender, receiver) = mpsc::channel::<Vec<R>>();
+        {
+            let mut workers = self.workers.lock().expect("scheduler pool poisoned");
+            while workers.len() < num_threads - 1 {
+                workers.push(Worker::spawn());
+            }
+
+            for (index, worker) in workers[..num_threads - 1].iter_mut().enumerate() {
+                let (items, queues, task) = (items.clone(), queues.clone(), task.clone());
+                let sender = sender.clone();
+
+                let job: Job = Box::new(move || {
+                    let results = drain(&items, &queues, index + 1, &*task);
+
+                    // release the items before reporting, so the caller holds
+                    // the last reference once every result is in
+                    drop((items, queues, task));
+                    sender.send(results).ok();
+                });
+
+                // a worker whose task panicked has stopped, so replace it
+                if let Err(mpsc::SendError(job)) = worker.jobs.send(job) {
+                    *worker = Worker::spawn();
+                    worker.jobs.send(job).expect("scheduler thread stopped");
+                }
+            }
+        }
+        drop(sender);
+
+        let mut results = drain(&items, &queues, 0, &*task);
+        for _ in 1..num_threads {
+            results.extend(receiver.recv().expect("scheduler thread panicked"));
+        }
+
+        results
+    }
+}
+
+impl Default for Pool {
+    fn default() -> Pool {
+        Pool::new()
+    }
+}
+
+impl fmt::Debug for Pool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let num_workers = self.workers.lock().map_or(0, |workers| workers.len());
+
+        f.debug_struct("Pool")
+            .field("num_workers", &num_workers)
+            .finish()
+    }
+}
+
+impl Drop for Pool {
+    /// Stop the workers once they finish their [Job]s, and join them.
+    fn drop(&mut self) {
+        let workers = match self.workers.get_mut() {
+            Ok(workers) => std::mem::take(workers),
+            Err(poisoned) => std::mem::take(poisoned.into_inner()),
+        };
+
+        for Worker { jobs, handle } in workers {
+            drop(jobs);
+            handle.join().ok();
+        }
+    }
+}
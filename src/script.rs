@@ -0,0 +1,358 @@
+//! Lua scripting of a simulation, in the spirit of Golly's scripts.
+//!
+//! Scripts drive a [ConwaySim] through the functions of the global `life`
+//! table:
+//!
+//! | Function                     | Effect                                               |
+//! |------------------------------|------------------------------------------------------|
+//! | `life.rows()`, `life.cols()` | size of the grid                                     |
+//! | `life.resize(rows, cols)`    | grow or shrink the grid, keeping its cells centered  |
+//! | `life.get(row, col)`         | whether a cell is alive                              |
+//! | `life.set(row, col [, on])`  | set a cell alive, or dead if `on` is false           |
+//! | `life.toggle(row, col)`      | toggle a cell                                        |
+//! | `life.clear()`               | set every cell dead                                  |
+//! | `life.cells()`               | list of `{row, col}` pairs of the live cells         |
+//! | `life.load(name, row, col)`  | place a built-in pattern or pattern file             |
+//! | `life.rle(text, row, col)`   | place a pattern given as RLE text                    |
+//! | `life.rule([rulestring])`    | get, or set and get, the rule                        |
+//! | `life.step([n])`             | advance `n` generations (default 1)                  |
+//! | `life.generation()`          | current generation                                   |
+//! | `life.population()`          | number of live cells                                 |
+//! | `life.status()`              | extinct, stable, oscillating, or running             |
+//! | `life.show()`                | the grid drawn as text                               |
+//! | `life.on_generation(f)`      | call `f(generation)` after each generation stepped   |
+//!
+//! Patterns are placed with their top left cell at (row, col), keeping the
+//! cells already alive. Callbacks registered with `life.on_generation` are
+//! called by `life.step`, and may themselves query and edit the grid.
+//!
+//! ```
+//! use gameoflife_rs::script::Script;
+//! use gameoflife_rs::ConwaySim;
+//!
+//! let script = Script::new(ConwaySim::new(10, 10)).unwrap();
+//! script
+//!     .run("life.load('glider', 1, 1); life.step(4); assert(life.population() == 5)")
+//!     .unwrap();
+//!
+//! assert_eq!(script.into_sim().get_generation(), 4);
+//! ```
+
+use std::cell::RefCell;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+use mlua::{Function, Lua, Table};
+
+use crate::game::{Cell, ConwaySim, Grid};
+use crate::pattern::{Overlap, Pattern};
+use crate::patterns;
+use crate::rule::Rule;
+use crate::transform::Anchor;
+use crate::world::World;
+
+/// Name of the registry table holding the `life.on_generation` callbacks.
+const CALLBACKS: &str = "gameoflife_on_generation";
+
+/// Error raised while loading or running a script.
+#[derive(Clone, Debug)]
+pub struct ScriptError {
+    /// Description of the error.
+    message: String,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "script error: {}", self.message)
+    }
+}
+
+impl error::Error for ScriptError {}
+
+impl From<mlua::Error> for ScriptError {
+    fn from(err: mlua::Error) -> ScriptError {
+        ScriptError {
+            message: err.to_string(),
+        }
+    }
+}
+
+/// The simulation shared between the [Script] and the functions it exposes.
+type SharedSim = Rc<RefCell<ConwaySim>>;
+
+/// Convert any error into an error raised in Lua.
+fn lua_error(err: impl fmt::Display) -> mlua::Error {
+    mlua::Error::RuntimeError(err.to_string())
+}
+
+/// Check that (row, col) lies within the simulation's [Grid].
+fn check_bounds(sim: &ConwaySim, row: u32, col: u32) -> mlua::Result<()> {
+    sim.get_grid()
+        .try_get(row, col)
+        .map(|_| ())
+        .map_err(lua_error)
+}
+
+/// Place the live cells of a [Grid] with its top left cell at (row, col).
+fn place(sim: &SharedSim, grid: Grid, row: u32, col: u32) -> mlua::Result<()> {
+    sim.borrow_mut()
+        .stamp(&Pattern::new(grid), row, col, Overlap::Or)
+        .map_err(lua_error)
+}
+
+/// A Lua interpreter driving a [ConwaySim].
+pub struct Script {
+    /// The Lua interpreter.
+    lua: Lua,
+
+    /// The simulation the script drives.
+    sim: SharedSim,
+}
+
+impl Script {
+    /// Create a new [Script] environment driving a simulation, with the
+    /// `life` table of functions installed.
+    ///
+    /// # Arguments
+    /// * `sim` - The simulation scripts drive.
+    pub fn new(sim: ConwaySim) -> Result<Script, ScriptError> {
+        let script = Script {
+            lua: Lua::new(),
+            sim: Rc::new(RefCell::new(sim)),
+        };
+
+        script
+            .lua
+            .set_named_registry_value(CALLBACKS, script.lua.create_table()?)?;
+        script.install()?;
+
+        Ok(script)
+    }
+
+    /// Install the `life` table of functions.
+    fn install(&self) -> mlua::Result<()> {
+        let lua = &self.lua;
+        let life = lua.create_table()?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "rows",
+            lua.create_function(move |_, ()| Ok(sim.borrow().get_grid().get_num_rows()))?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "cols",
+            lua.create_function(move |_, ()| Ok(sim.borrow().get_grid().get_num_cols()))?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "resize",
+            lua.create_function(move |_, (num_rows, num_cols): (u32, u32)| {
+                sim.borrow_mut().resize(num_rows, num_cols, Anchor::Center);
+                Ok(())
+            })?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "get",
+            lua.create_function(move |_, (row, col): (u32, u32)| {
+                let sim = sim.borrow();
+                check_bounds(&sim, row, col)?;
+                Ok(sim.is_cell_alive(row, col))
+            })?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "set",
+            lua.create_function(move |_, (row, col, alive): (u32, u32, Option<bool>)| {
+                let mut sim = sim.borrow_mut();
+                check_bounds(&sim, row, col)?;
+
+                let state = if alive.unwrap_or(true) {
+                    Cell::Alive
+                } else {
+                    Cell::Dead
+                };
+                sim.set_cell(row, col, state);
+                Ok(())
+            })?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "toggle",
+            lua.create_function(move |_, (row, col): (u32, u32)| {
+                let mut sim = sim.borrow_mut();
+                check_bounds(&sim, row, col)?;
+                sim.toggle_cell(row, col);
+                Ok(())
+            })?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "clear",
+            lua.create_function(move |_, ()| {
+                sim.borrow_mut().clear();
+                Ok(())
+            })?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "cells",
+            lua.create_function(move |lua, ()| {
+                let cells = lua.create_table()?;
+
+                for (row, col) in sim.borrow().get_grid().live_cells() {
+                    cells.push(lua.create_sequence_from([row, col])?)?;
+                }
+
+                Ok(cells)
+            })?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "load",
+            lua.create_function(move |_, (name, row, col): (String, u32, u32)| {
+                let grid = patterns::load(&name)
+                    .map(|sim| sim.get_grid().clone())
+                    .map_err(|err| lua_error(format!("{}: {}", name, err)))?;
+                place(&sim, grid, row, col)
+            })?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "rle",
+            lua.create_function(move |_, (rle, row, col): (String, u32, u32)| {
+                let grid = Grid::from_rle(&rle).map_err(lua_error)?;
+                place(&sim, grid, row, col)
+            })?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "rule",
+            lua.create_function(move |_, rulestring: Option<String>| {
+                if let Some(rulestring) = rulestring {
+                    let rule: Rule = rulestring.parse().map_err(lua_error)?;
+                    sim.borrow_mut().set_rule(rule);
+                }
+
+                Ok(sim.borrow().get_rule().to_string())
+            })?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "step",
+            lua.create_function(move |lua, n: Option<u32>| {
+                let callbacks: Table = lua.named_registry_value(CALLBACKS)?;
+
+                for _ in 0..n.unwrap_or(1) {
+                    // release the simulation before calling back into Lua
+                    let generation = {
+                        let mut sim = sim.borrow_mut();
+                        sim.step();
+                        sim.get_generation()
+                    };
+
+                    for callback in callbacks.clone().sequence_values::<Function>() {
+                        callback?.call::<_, ()>(generation)?;
+                    }
+                }
+
+                Ok(())
+            })?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "generation",
+            lua.create_function(move |_, ()| Ok(sim.borrow().get_generation()))?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "population",
+            lua.create_function(move |_, ()| Ok(sim.borrow().get_population()))?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "status",
+            lua.create_function(move |_, ()| Ok(sim.borrow().get_status().to_string()))?,
+        )?;
+
+        let sim = self.sim.clone();
+        life.set(
+            "show",
+            lua.create_function(move |_, ()| Ok(sim.borrow().to_string()))?,
+        )?;
+
+        life.set(
+            "on_generation",
+            lua.create_function(|lua, callback: Function| {
+                let callbacks: Table = lua.named_registry_value(CALLBACKS)?;
+                callbacks.push(callback)
+            })?,
+        )?;
+
+        lua.globals().set("life", life)
+    }
+
+    /// Run a script.
+    ///
+    /// # Arguments
+    /// * `source` - The Lua source of the script.
+    pub fn run(&self, source: &str) -> Result<(), ScriptError> {
+        self.lua.load(source).exec()?;
+
+        Ok(())
+    }
+
+    /// Run a script file.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the Lua script.
+    pub fn run_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ScriptError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path).map_err(|err| ScriptError {
+            message: format!("unable to read {}: {}", path.display(), err),
+        })?;
+
+        self.lua
+            .load(&source)
+            .set_name(path.display().to_string())
+            .exec()?;
+
+        Ok(())
+    }
+
+    /// Get the number of the current generation of the simulation.
+    pub fn get_generation(&self) -> u32 {
+        self.sim.borrow().get_generation()
+    }
+
+    /// End the script, returning the simulation as the script left it.
+    pub fn into_sim(self) -> ConwaySim {
+        let Script { lua, sim } = self;
+
+        // the functions installed in Lua hold the other references
+        drop(lua);
+
+        match Rc::try_unwrap(sim) {
+            Ok(sim) => sim.into_inner(),
+            Err(_) => unreachable!("the simulation is only shared with the Lua interpreter"),
+        }
+    }
+}
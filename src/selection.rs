@@ -0,0 +1,154 @@
+//! Rectangular selections of the interactive user interfaces, and the
+//! clipboard [Pattern] they are copied to and pasted from.
+
+use gameoflife_rs::{ConwaySim, Grid, Overlap, Pattern, Transform};
+
+/// A rectangle of cells selected between two opposite corners.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Selection {
+    /// The (row, col) of the corner the selection started from.
+    anchor: (u32, u32),
+
+    /// The (row, col) of the opposite corner, following the cursor or mouse.
+    corner: (u32, u32),
+}
+
+impl Selection {
+    /// Create a new [Selection] of a single cell.
+    pub fn new(row: u32, col: u32) -> Selection {
+        Selection {
+            anchor: (row, col),
+            corner: (row, col),
+        }
+    }
+
+    /// Create a new [Selection] of a rectangle of cells, clipped to a grid.
+    ///
+    /// # Arguments
+    /// * `grid` - The grid the selection is in.
+    /// * `row` - Row of the rectangle's top left cell.
+    /// * `col` - Column of the rectangle's top left cell.
+    /// * `num_rows` - The number of rows (height) of the rectangle, at least 1.
+    /// * `num_cols` - The number of columns (width) of the rectangle, at
+    ///   least 1.
+    pub fn with_size(grid: &Grid, row: u32, col: u32, num_rows: u32, num_cols: u32) -> Selection {
+        let last_row = row.saturating_add(num_rows.max(1) - 1);
+        let last_col = col.saturating_add(num_cols.max(1) - 1);
+
+        Selection {
+            anchor: (row, col),
+            corner: (
+                last_row.min(grid.get_num_rows().saturating_sub(1)),
+                last_col.min(grid.get_num_cols().saturating_sub(1)),
+            ),
+        }
+    }
+
+    /// Move the corner opposite the anchor to a cell.
+    pub fn extend_to(&mut self, row: u32, col: u32) {
+        self.corner = (row, col);
+    }
+
+    /// Get the (row, col) of the top left cell.
+    pub fn get_origin(&self) -> (u32, u32) {
+        (
+            self.anchor.0.min(self.corner.0),
+            self.anchor.1.min(self.corner.1),
+        )
+    }
+
+    /// Get the number of rows (height) of the selection.
+    pub fn get_num_rows(&self) -> u32 {
+        self.anchor.0.max(self.corner.0) - self.anchor.0.min(self.corner.0) + 1
+    }
+
+    /// Get the number of columns (width) of the selection.
+    pub fn get_num_cols(&self) -> u32 {
+        self.anchor.1.max(self.corner.1) - self.anchor.1.min(self.corner.1) + 1
+    }
+
+    /// Check whether a cell is within the selection.
+    #[cfg_attr(not(feature = "tui"), allow(dead_code))]
+    pub fn contains(&self, row: u32, col: u32) -> bool {
+        let (first_row, first_col) = self.get_origin();
+
+        row >= first_row
+            && col >= first_col
+            && row - first_row < self.get_num_rows()
+            && col - first_col < self.get_num_cols()
+    }
+}
+
+/// Copy the selected cells into a [Pattern].
+pub fn copy(sim: &ConwaySim, selection: &Selection) -> Pattern {
+    let (row, col) = selection.get_origin();
+
+    Pattern::new(
+        sim.get_grid()
+            .region(row, col, selection.get_num_rows(), selection.get_num_cols())
+            .to_grid(),
+    )
+}
+
+/// Copy the selected cells into a [Pattern], and set them dead.
+pub fn cut(sim: &mut ConwaySim, selection: &Selection) -> Pattern {
+    let pattern = copy(sim, selection);
+    let (row, col) = selection.get_origin();
+    let empty = Pattern::new(Grid::new(pattern.get_num_rows(), pattern.get_num_cols()));
+
+    sim.stamp(&empty, row, col, Overlap::Overwrite)
+        .expect("a copy of the selection fits the grid");
+
+    pattern
+}
+
+/// Paste a [Pattern] over the cells from (row, col), clipping it to the
+/// grid, and get the selection of the pasted cells.
+///
+/// # Arguments
+/// * `sim` - The simulation to paste into.
+/// * `pattern` - The [Pattern] to paste, replacing the cells under it.
+/// * `row` - Row of the pattern's top left cell.
+/// * `col` - Column of the pattern's top left cell.
+pub fn paste(sim: &mut ConwaySim, pattern: &Pattern, row: u32, col: u32) -> Selection {
+    let grid = sim.get_grid();
+    let clipped = Pattern::new(
+        pattern
+            .get_grid()
+            .region(
+                0,
+                0,
+                grid.get_num_rows().saturating_sub(row),
+                grid.get_num_cols().saturating_sub(col),
+            )
+            .to_grid(),
+    );
+
+    let selection = Selection::with_size(
+        grid,
+        row,
+        col,
+        clipped.get_num_rows(),
+        clipped.get_num_cols(),
+    );
+
+    sim.stamp(&clipped, row, col, Overlap::Overwrite)
+        .expect("a pattern clipped to the grid fits it");
+
+    selection
+}
+
+/// Transform the selected cells in place, keeping their top left corner,
+/// and get the selection of the transformed cells.
+///
+/// # Arguments
+/// * `sim` - The simulation to transform the cells of.
+/// * `selection` - The cells to transform.
+/// * `transform` - The rotation or reflection to apply.
+pub fn transform(sim: &mut ConwaySim, selection: &Selection, transform: Transform) -> Selection {
+    let pattern = cut(sim, selection);
+    let transformed = Pattern::new(pattern.get_grid().transformed(transform));
+    let (row, col) = selection.get_origin();
+
+    paste(sim, &transformed, row, col)
+}
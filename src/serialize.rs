@@ -0,0 +1,176 @@
+//! Serde serialization of grids, worlds, and simulations.
+//!
+//! A [ConwaySim] is serialized as its generation, [Rule], and [World]. The
+//! recorded [History](crate::History) and [Stats](crate::Stats) are not part
+//! of the serialized state.
+
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use crate::chunked::ChunkedWorld;
+use crate::game::{Cell, ConwaySim, Grid};
+use crate::rule::Rule;
+use crate::sparse::SparseWorld;
+use crate::topology::Topology;
+use crate::world::World;
+
+/// Serialized form of a [Grid], its cells listed row by row.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Grid")]
+struct GridData {
+    num_rows: u32,
+    num_cols: u32,
+    cells: Vec<Cell>,
+    #[serde(default)]
+    topology: Topology,
+}
+
+impl Serialize for Grid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut cells: Vec<Cell> = Vec::new();
+
+        for row in 0..self.get_num_rows() {
+            for col in 0..self.get_num_cols() {
+                cells.push(self.get(row, col));
+            }
+        }
+
+        GridData {
+            num_rows: self.get_num_rows(),
+            num_cols: self.get_num_cols(),
+            cells,
+            topology: self.get_topology(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Grid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Grid, D::Error> {
+        let data = GridData::deserialize(deserializer)?;
+
+        let expected = data.num_rows as u64 * data.num_cols as u64;
+        if data.cells.len() as u64 != expected {
+            return Err(de::Error::invalid_length(
+                data.cells.len(),
+                &format!("{} cells", expected).as_str(),
+            ));
+        }
+
+        let mut grid = Grid::new(data.num_rows, data.num_cols);
+        grid.set_topology(data.topology);
+
+        for (index, &cell) in data.cells.iter().enumerate() {
+            let row = (index / data.num_cols as usize) as u32;
+            let col = (index % data.num_cols as usize) as u32;
+            grid.set(row, col, cell);
+        }
+
+        Ok(grid)
+    }
+}
+
+/// Serialized form of a [SparseWorld], its live cells listed in order.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "SparseWorld")]
+struct SparseWorldData {
+    cells: Vec<(i64, i64)>,
+}
+
+impl Serialize for SparseWorld {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SparseWorldData {
+            cells: self.live_cells(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SparseWorld {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<SparseWorld, D::Error> {
+        let data = SparseWorldData::deserialize(deserializer)?;
+
+        let mut world = SparseWorld::new();
+        world.set_cells(&data.cells);
+
+        Ok(world)
+    }
+}
+
+/// Serialized form of a [ChunkedWorld], its live cells listed in order.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "ChunkedWorld")]
+struct ChunkedWorldData {
+    cells: Vec<(i64, i64)>,
+}
+
+impl Serialize for ChunkedWorld {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ChunkedWorldData {
+            cells: self.live_cells(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ChunkedWorld {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ChunkedWorld, D::Error> {
+        let data = ChunkedWorldData::deserialize(deserializer)?;
+
+        let mut world = ChunkedWorld::new();
+        world.set_cells(&data.cells);
+
+        Ok(world)
+    }
+}
+
+impl Serialize for Rule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Rule, D::Error> {
+        let rulestring = String::deserialize(deserializer)?;
+
+        rulestring.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Serialized form of a [ConwaySim] borrowing its state.
+#[derive(serde::Serialize)]
+#[serde(rename = "ConwaySim")]
+struct SimRef<'a, W> {
+    generation: u32,
+    rule: Rule,
+    world: &'a W,
+}
+
+/// Serialized form of a [ConwaySim] owning its state.
+#[derive(serde::Deserialize)]
+#[serde(rename = "ConwaySim")]
+struct SimData<W> {
+    generation: u32,
+    rule: Rule,
+    world: W,
+}
+
+impl<W: World + Serialize> Serialize for ConwaySim<W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SimRef {
+            generation: self.get_generation(),
+            rule: self.get_rule(),
+            world: self.get_world(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, W: World + Deserialize<'de>> Deserialize<'de> for ConwaySim<W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ConwaySim<W>, D::Error> {
+        let data = SimData::<W>::deserialize(deserializer)?;
+
+        Ok(ConwaySim::restore(data.world, data.generation, data.rule))
+    }
+}
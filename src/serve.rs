@@ -0,0 +1,486 @@
+//! Streaming of a running simulation to WebSocket clients.
+//!
+//! The server also hosts the [REST API](crate::api) of simulations.
+//!
+//! The simulation is stepped at a fixed interval and each generation's
+//! births and deaths are broadcast to every client connected to `/ws`. A
+//! client first receives a snapshot of the whole grid and then one diff per
+//! generation; a client which falls too far behind is sent a new snapshot.
+//!
+//! JSON text frames look like:
+//!
+//! ```text
+//! {"type":"snapshot","generation":0,"rows":64,"cols":64,"rule":"B3/S23","cells":[[1,2],[3,4]]}
+//! {"type":"diff","generation":1,"births":[[2,2]],"deaths":[[1,2]]}
+//! ```
+//!
+//! Binary frames hold little-endian `u32`s after a one byte tag. A snapshot
+//! (tag 0) holds the generation, rows, columns, and number of live cells,
+//! followed by the (row, col) of each live cell. A diff (tag 1) holds the
+//! generation and the numbers of births and deaths, followed by the
+//! (row, col) of each birth and then of each death.
+//!
+//! With `--shared` the world is a [SharedWorld] which every client may place
+//! cells into, streamed as JSON whose cells carry their color as a third
+//! element. A client is first sent a snapshot, which also lists the palette
+//! of colors, and a welcome with its player id and color:
+//!
+//! ```text
+//! {"type":"welcome","player":1,"color":1}
+//! ```
+//!
+//! A player places cells by sending `{"type":"place","cells":[[1,2]]}`, and
+//! is answered with the cells placed and those rejected and why. The cells
+//! placed are broadcast to every client as a diff of the same generation.
+//!
+//! ```text
+//! {"type":"placed","cells":[[1,2]],"rejected":[{"cell":[3,4],"reason":"occupied"}]}
+//! ```
+
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::{Html, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use gameoflife_rs::colors::palette;
+use gameoflife_rs::render::Rgb;
+use gameoflife_rs::{patterns, Anchor, ColorSim, ConwaySim, Diff, Grid, World};
+use serde::Deserialize;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::api;
+use crate::cli::{FrameFormat, ServeArgs};
+use crate::multiplayer::{ColorDiff, SharedWorld};
+use crate::simulate::json_string;
+
+/// Number of generations buffered for each client before it falls behind.
+const BUFFERED_GENERATIONS: usize = 64;
+
+/// Tag of a binary snapshot frame.
+const SNAPSHOT_TAG: u8 = 0;
+
+/// Tag of a binary diff frame.
+const DIFF_TAG: u8 = 1;
+
+/// The world streamed to the clients.
+enum Game {
+    /// A simulation stepped on its own.
+    Solo(Box<ConwaySim>),
+
+    /// A multi-color world shared by players placing cells into it.
+    Shared(SharedWorld),
+}
+
+/// Message sent by a client of a shared world.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    /// Place cells of the player's color.
+    Place { cells: Vec<(i64, i64)> },
+}
+
+/// The world and the channel its generations are broadcast on, shared by the
+/// stepping task and every client.
+struct Shared {
+    /// The running world.
+    game: Mutex<Game>,
+
+    /// Sender of the encoded diff of each generation.
+    diffs: broadcast::Sender<Message>,
+
+    /// Encoding of the messages.
+    format: FrameFormat,
+}
+
+impl Shared {
+    /// Encode a snapshot of the current generation.
+    fn snapshot(&self, game: &Game) -> Message {
+        let sim = match game {
+            Game::Solo(sim) => sim,
+            Game::Shared(world) => return color_snapshot(world),
+        };
+
+        let grid = sim.get_grid();
+        let cells = grid.live_cells();
+
+        match self.format {
+            FrameFormat::Json => Message::Text(
+                format!(
+                    "{{\"type\":\"snapshot\",\"generation\":{},\"rows\":{},\"cols\":{},\"rule\":{},\"cells\":{}}}",
+                    sim.get_generation(),
+                    grid.get_num_rows(),
+                    grid.get_num_cols(),
+                    json_string(&sim.get_rule().to_string()),
+                    json_cells(&cells)
+                )
+                .into(),
+            ),
+            FrameFormat::Binary => {
+                let mut frame = vec![SNAPSHOT_TAG];
+                for value in [
+                    sim.get_generation(),
+                    grid.get_num_rows(),
+                    grid.get_num_cols(),
+                    cells.len() as u32,
+                ] {
+                    frame.extend_from_slice(&value.to_le_bytes());
+                }
+                push_cells(&mut frame, &cells);
+
+                Message::Binary(frame.into())
+            }
+        }
+    }
+
+    /// Encode the births and deaths of a generation.
+    fn diff(&self, generation: u32, diff: &Diff) -> Message {
+        match self.format {
+            FrameFormat::Json => Message::Text(
+                format!(
+                    "{{\"type\":\"diff\",\"generation\":{},\"births\":{},\"deaths\":{}}}",
+                    generation,
+                    json_cells(&diff.births),
+                    json_cells(&diff.deaths)
+                )
+                .into(),
+            ),
+            FrameFormat::Binary => {
+                let mut frame = vec![DIFF_TAG];
+                for value in [
+                    generation,
+                    diff.births.len() as u32,
+                    diff.deaths.len() as u32,
+                ] {
+                    frame.extend_from_slice(&value.to_le_bytes());
+                }
+                push_cells(&mut frame, &diff.births);
+                push_cells(&mut frame, &diff.deaths);
+
+                Message::Binary(frame.into())
+            }
+        }
+    }
+
+    /// Advance the world by one generation and broadcast its changes.
+    fn step(&self) {
+        let mut game = self.game.lock().expect("world lock poisoned");
+
+        let message = match &mut *game {
+            Game::Solo(sim) => {
+                let previous = sim.get_grid().clone();
+                sim.step();
+
+                let diff = World::diff(sim.get_grid(), &previous);
+                self.diff(sim.get_generation(), &diff)
+            }
+            Game::Shared(world) => {
+                let diff = world.step();
+                color_diff(world.get_sim().get_generation(), &diff)
+            }
+        };
+
+        // sending fails only while no client is connected
+        let _ = self.diffs.send(message);
+    }
+
+    /// Take a snapshot of the current generation and subscribe to the diffs
+    /// of the following generations, without missing any in between.
+    fn subscribe(&self) -> (Message, broadcast::Receiver<Message>) {
+        let game = self.game.lock().expect("world lock poisoned");
+
+        (self.snapshot(&game), self.diffs.subscribe())
+    }
+
+    /// Add a player to a shared world, returning the welcome message sent
+    /// to them, or [None] if the world is not shared.
+    fn join(&self) -> Option<(u32, Message)> {
+        let mut game = self.game.lock().expect("world lock poisoned");
+
+        match &mut *game {
+            Game::Solo(_) => None,
+            Game::Shared(world) => {
+                let (player, color) = world.join();
+                let welcome = format!(
+                    "{{\"type\":\"welcome\",\"player\":{},\"color\":{}}}",
+                    player, color
+                );
+
+                Some((player, Message::Text(welcome.into())))
+            }
+        }
+    }
+
+    /// Remove a player from a shared world.
+    fn leave(&self, player: u32) {
+        if let Game::Shared(world) = &mut *self.game.lock().expect("world lock poisoned") {
+            world.leave(player);
+        }
+    }
+
+    /// Handle a message from a player of a shared world, broadcasting the
+    /// cells placed and returning the reply to the player.
+    fn receive(&self, player: u32, text: &str) -> Message {
+        let message = match Json::<ClientMessage>::from_bytes(text.as_bytes()) {
+            Ok(Json(message)) => message,
+            Err(err) => return error_message(&err.body_text()),
+        };
+
+        let mut game = self.game.lock().expect("world lock poisoned");
+        let world = match &mut *game {
+            Game::Shared(world) => world,
+            Game::Solo(_) => return error_message("the world is not shared"),
+        };
+
+        match message {
+            ClientMessage::Place { cells } => {
+                let placement = world.place(player, &cells);
+
+                if !placement.placed.is_empty() {
+                    let sim = world.get_sim();
+                    let diff = ColorDiff {
+                        births: placement
+                            .placed
+                            .iter()
+                            .map(|&(row, col)| (row, col, sim.get_grid().get(row, col)))
+                            .collect(),
+                        deaths: Vec::new(),
+                    };
+
+                    let _ = self.diffs.send(color_diff(sim.get_generation(), &diff));
+                }
+
+                let placed: Vec<(i64, i64)> = placement
+                    .placed
+                    .iter()
+                    .map(|&(row, col)| (row as i64, col as i64))
+                    .collect();
+                let rejected: Vec<String> = placement
+                    .rejected
+                    .iter()
+                    .map(|&((row, col), reason)| {
+                        format!(
+                            "{{\"cell\":[{},{}],\"reason\":{}}}",
+                            row,
+                            col,
+                            json_string(&reason.to_string())
+                        )
+                    })
+                    .collect();
+
+                Message::Text(
+                    format!(
+                        "{{\"type\":\"placed\",\"cells\":{},\"rejected\":[{}]}}",
+                        json_cells(&placed),
+                        rejected.join(",")
+                    )
+                    .into(),
+                )
+            }
+        }
+    }
+}
+
+/// Encode an error reply to a client.
+fn error_message(error: &str) -> Message {
+    Message::Text(format!("{{\"type\":\"error\",\"error\":{}}}", json_string(error)).into())
+}
+
+/// Encode cells as a JSON array of [row, col] pairs.
+fn json_cells(cells: &[(i64, i64)]) -> String {
+    let pairs: Vec<String> = cells
+        .iter()
+        .map(|(row, col)| format!("[{},{}]", row, col))
+        .collect();
+
+    format!("[{}]", pairs.join(","))
+}
+
+/// Encode colored cells as a JSON array of [row, col, color] triples.
+fn json_color_cells(cells: &[(u32, u32, u8)]) -> String {
+    let triples: Vec<String> = cells
+        .iter()
+        .map(|(row, col, color)| format!("[{},{},{}]", row, col, color))
+        .collect();
+
+    format!("[{}]", triples.join(","))
+}
+
+/// Encode a snapshot of a shared world, with its palette of colors indexed
+/// by state.
+fn color_snapshot(world: &SharedWorld) -> Message {
+    let sim: &ColorSim = world.get_sim();
+    let colors: Vec<String> = palette(world.get_variant())
+        .iter()
+        .map(|&Rgb(red, green, blue)| format!("\"#{:02x}{:02x}{:02x}\"", red, green, blue))
+        .collect();
+
+    Message::Text(
+        format!(
+            "{{\"type\":\"snapshot\",\"generation\":{},\"rows\":{},\"cols\":{},\"rule\":{},\"palette\":[{}],\"cells\":{}}}",
+            sim.get_generation(),
+            sim.get_grid().get_num_rows(),
+            sim.get_grid().get_num_cols(),
+            json_string(&sim.get_rule().to_string()),
+            colors.join(","),
+            json_color_cells(&world.live_cells())
+        )
+        .into(),
+    )
+}
+
+/// Encode the changes of a generation of a shared world.
+fn color_diff(generation: u32, diff: &ColorDiff) -> Message {
+    let deaths: Vec<(i64, i64)> = diff
+        .deaths
+        .iter()
+        .map(|&(row, col)| (row as i64, col as i64))
+        .collect();
+
+    Message::Text(
+        format!(
+            "{{\"type\":\"diff\",\"generation\":{},\"births\":{},\"deaths\":{}}}",
+            generation,
+            json_color_cells(&diff.births),
+            json_cells(&deaths)
+        )
+        .into(),
+    )
+}
+
+/// Append cells to a binary frame as pairs of little-endian `u32`s.
+fn push_cells(frame: &mut Vec<u8>, cells: &[(i64, i64)]) {
+    for &(row, col) in cells {
+        frame.extend_from_slice(&(row as u32).to_le_bytes());
+        frame.extend_from_slice(&(col as u32).to_le_bytes());
+    }
+}
+
+/// Step the world forever, broadcasting the diff of each generation.
+async fn run_sim(shared: Arc<Shared>, interval: Duration) {
+    let mut ticks = tokio::time::interval(interval);
+
+    loop {
+        ticks.tick().await;
+        shared.step();
+    }
+}
+
+/// Stream the world to a connected client until it disconnects.
+async fn stream(mut socket: WebSocket, shared: Arc<Shared>) {
+    let (snapshot, diffs) = shared.subscribe();
+    if socket.send(snapshot).await.is_err() {
+        return;
+    }
+
+    match shared.join() {
+        Some((player, welcome)) => {
+            if socket.send(welcome).await.is_ok() {
+                relay(&mut socket, &shared, diffs, Some(player)).await;
+            }
+            shared.leave(player);
+        }
+        None => relay(&mut socket, &shared, diffs, None).await,
+    }
+}
+
+/// Relay the diffs to a client, and the messages of a player to a shared
+/// world, until the client disconnects.
+async fn relay(
+    socket: &mut WebSocket,
+    shared: &Shared,
+    mut diffs: broadcast::Receiver<Message>,
+    player: Option<u32>,
+) {
+    loop {
+        tokio::select! {
+            diff = diffs.recv() => {
+                let message = match diff {
+                    Ok(message) => message,
+                    Err(RecvError::Lagged(_)) => {
+                        let (snapshot, resubscribed) = shared.subscribe();
+                        diffs = resubscribed;
+                        snapshot
+                    }
+                    Err(RecvError::Closed) => return,
+                };
+
+                if socket.send(message).await.is_err() {
+                    return;
+                }
+            }
+            received = socket.recv() => match (received, player) {
+                (Some(Ok(Message::Text(text))), Some(player)) => {
+                    let reply = shared.receive(player, text.as_str());
+                    if socket.send(reply).await.is_err() {
+                        return;
+                    }
+                }
+                (Some(Ok(Message::Close(_))), _) | (Some(Err(_)), _) | (None, _) => return,
+                (Some(Ok(_)), _) => {}
+            },
+        }
+    }
+}
+
+/// Upgrade a request to `/ws` to a WebSocket streaming the world.
+async fn upgrade(ws: WebSocketUpgrade, State(shared): State<Arc<Shared>>) -> Response {
+    ws.on_upgrade(move |socket| stream(socket, shared))
+}
+
+/// Serve the live viewer page.
+async fn viewer() -> Html<&'static str> {
+    Html(include_str!("../www/stream.html"))
+}
+
+/// Run the world and stream it to WebSocket clients until interrupted.
+pub fn run(args: &ServeArgs) -> Result<(), Box<dyn Error>> {
+    let loaded = patterns::load(&args.pattern)?;
+    let rule = args.rule.unwrap_or_else(|| loaded.get_rule());
+    Grid::check_size(args.rows, args.cols)?;
+
+    let grid = loaded
+        .get_grid()
+        .resized(args.rows, args.cols, Anchor::Center);
+
+    let game = if args.shared {
+        let mut sim = ColorSim::from_grid(&grid, args.variant.into(), crate::random_seed());
+        sim.set_rule(rule);
+
+        Game::Shared(SharedWorld::new(sim, args.rate_limit))
+    } else {
+        let mut sim = ConwaySim::new_with_grid(grid);
+        sim.set_rule(rule);
+        sim.set_topology(args.topology);
+
+        Game::Solo(Box::new(sim))
+    };
+
+    let (diffs, _) = broadcast::channel(BUFFERED_GENERATIONS);
+    let shared = Arc::new(Shared {
+        game: Mutex::new(game),
+        diffs,
+        format: args.format,
+    });
+
+    let app = Router::new()
+        .route("/", get(viewer))
+        .route("/ws", get(upgrade))
+        .with_state(shared.clone())
+        .merge(api::router());
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(args.bind).await?;
+        eprintln!("streaming on ws://{}/ws", listener.local_addr()?);
+
+        tokio::spawn(run_sim(shared, Duration::from_millis(args.interval.max(1))));
+        axum::serve(listener, app).await?;
+
+        Ok(())
+    })
+}
@@ -0,0 +1,173 @@
+//! Named simulation sessions, autosaved as checkpoints so that long runs
+//! survive crashes and can be resumed with `resume <NAME>`.
+//!
+//! Each session is a directory under `$XDG_DATA_HOME/gameoflife/sessions`
+//! (by default `~/.local/share/gameoflife/sessions`) holding the latest
+//! checkpoint of the simulation.
+
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use gameoflife_rs::{CheckpointError, ConwaySim};
+
+/// Name of the checkpoint file of a session.
+const CHECKPOINT_FILE: &str = "checkpoint.golc";
+
+/// Error raised while saving or resuming a session.
+#[derive(Debug)]
+pub enum SessionError {
+    /// Neither `XDG_DATA_HOME` nor `HOME` is set, so there is nowhere to keep
+    /// sessions.
+    NoDataDir,
+
+    /// The session name is empty or is not a plain file name.
+    InvalidName(String),
+
+    /// No session of the name was saved.
+    NotFound(String),
+
+    /// A file of the session could not be read or written.
+    Io { path: PathBuf, err: io::Error },
+
+    /// The checkpoint of the session could not be loaded.
+    Checkpoint { path: PathBuf, err: CheckpointError },
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SessionError::NoDataDir => write!(f, "neither XDG_DATA_HOME nor HOME is set"),
+            SessionError::InvalidName(name) => write!(f, "invalid session name '{}'", name),
+            SessionError::NotFound(name) => write!(f, "no session named '{}'", name),
+            SessionError::Io { path, err } => write!(f, "{}: {}", path.display(), err),
+            SessionError::Checkpoint { path, err } => write!(f, "{}: {}", path.display(), err),
+        }
+    }
+}
+
+impl error::Error for SessionError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            SessionError::Io { err, .. } => Some(err),
+            SessionError::Checkpoint { err, .. } => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Write a file by writing a temporary file next to it and renaming it over
+/// the file, so that a crash never leaves a partly written file behind.
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), SessionError> {
+    let temporary = path.with_extension("tmp");
+
+    fs::write(&temporary, contents)
+        .and_then(|()| fs::rename(&temporary, path))
+        .map_err(|err| SessionError::Io {
+            path: path.to_path_buf(),
+            err,
+        })
+}
+
+/// A named session autosaving a simulation.
+#[derive(Clone, Debug)]
+pub struct Session {
+    /// Directory the session is saved in.
+    dir: PathBuf,
+
+    /// Number of generations between autosaves.
+    interval: u32,
+
+    /// Generation of the simulation when it was last saved or resumed.
+    saved_generation: u32,
+}
+
+impl Session {
+    /// Get the directory of a named session, without creating it.
+    fn dir(name: &str) -> Result<PathBuf, SessionError> {
+        let is_plain = Path::new(name).file_name().and_then(|file| file.to_str()) == Some(name);
+        if name.is_empty() || !is_plain || name == "." || name == ".." {
+            return Err(SessionError::InvalidName(name.to_string()));
+        }
+
+        let data_dir = env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+            .ok_or(SessionError::NoDataDir)?;
+
+        Ok(data_dir.join("gameoflife").join("sessions").join(name))
+    }
+
+    /// Create a session, or take over an existing one of the same name.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the session.
+    /// * `interval` - Number of generations between autosaves (at least 1).
+    pub fn create(name: &str, interval: u32) -> Result<Session, SessionError> {
+        let dir = Session::dir(name)?;
+        fs::create_dir_all(&dir).map_err(|err| SessionError::Io {
+            path: dir.clone(),
+            err,
+        })?;
+
+        Ok(Session {
+            dir,
+            interval: interval.max(1),
+            saved_generation: 0,
+        })
+    }
+
+    /// Resume a saved session, restoring its simulation as last saved.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the session.
+    /// * `interval` - Number of generations between autosaves (at least 1).
+    pub fn resume(name: &str, interval: u32) -> Result<(Session, ConwaySim), SessionError> {
+        let dir = Session::dir(name)?;
+        let path = dir.join(CHECKPOINT_FILE);
+        if !path.exists() {
+            return Err(SessionError::NotFound(name.to_string()));
+        }
+
+        let sim = ConwaySim::load_checkpoint(&path)
+            .map_err(|err| SessionError::Checkpoint { path, err })?;
+
+        let session = Session {
+            dir,
+            interval: interval.max(1),
+            saved_generation: sim.get_generation(),
+        };
+
+        Ok((session, sim))
+    }
+
+    /// Save the simulation, replacing the previous save.
+    ///
+    /// # Arguments
+    /// * `sim` - The simulation to save.
+    pub fn save(&mut self, sim: &ConwaySim) -> Result<(), SessionError> {
+        self.saved_generation = sim.get_generation();
+
+        write_atomically(&self.dir.join(CHECKPOINT_FILE), &sim.to_checkpoint())
+    }
+
+    /// Save the simulation if it has moved the autosave interval or more
+    /// generations, forward or back, since it was last saved.
+    ///
+    /// Called as often as wanted, e.g. once per frame of an interactive
+    /// loop, it saves no more than once per interval, and a failed save is
+    /// not retried until the next interval.
+    ///
+    /// # Arguments
+    /// * `sim` - The simulation to save.
+    pub fn autosave(&mut self, sim: &ConwaySim) -> Result<(), SessionError> {
+        if sim.get_generation().abs_diff(self.saved_generation) >= self.interval {
+            self.save(sim)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+//! A handle sharing a simulation between threads.
+//!
+//! A [SharedSim] lets a compute thread step a simulation while render
+//! threads draw it concurrently. Each generation is published as a [Frame],
+//! an immutable copy of the world which readers hold for as long as they
+//! like; the lock over the latest frame is only ever held to swap or clone a
+//! pointer, so readers never wait for a generation to be computed and the
+//! stepping never waits for a frame to be drawn.
+//!
+//! ```
+//! use std::thread;
+//!
+//! use gameoflife_rs::{ConwaySim, SharedSim};
+//!
+//! let mut sim = ConwaySim::new(16, 16);
+//! sim.set_cells(&[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+//! let shared = SharedSim::new(sim);
+//!
+//! thread::scope(|scope| {
+//!     scope.spawn(|| shared.step_n(20));
+//!
+//!     // the glider is whole in every frame, whichever generation it shows
+//!     let frame = shared.snapshot();
+//!     assert_eq!(frame.get_grid().get_population(), 5);
+//! });
+//!
+//! assert_eq!(shared.get_generation(), 20);
+//! ```
+
+use std::mem;
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::game::{ConwaySim, Grid};
+use crate::world::World;
+
+/// An immutable copy of a simulation's world at one generation.
+#[derive(Clone, Debug)]
+pub struct Frame<W: World = Grid> {
+    /// The generation of the world.
+    generation: u32,
+
+    /// The world at the generation.
+    world: W,
+}
+
+impl<W: World> Frame<W> {
+    /// Create a new [Frame] of a simulation's current generation.
+    fn new(sim: &ConwaySim<W>) -> Frame<W> {
+        Frame {
+            generation: sim.get_generation(),
+            world: sim.get_world().clone(),
+        }
+    }
+
+    /// Get the generation of the frame.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Get the world at the frame's generation.
+    pub fn get_world(&self) -> &W {
+        &self.world
+    }
+}
+
+impl Frame<Grid> {
+    /// Get the [Grid] at the frame's generation.
+    pub fn get_grid(&self) -> &Grid {
+        &self.world
+    }
+}
+
+/// The simulation of a [SharedSim] and the frame it last replaced.
+struct State<W: World> {
+    /// The simulation being shared.
+    sim: ConwaySim<W>,
+
+    /// The frame replaced by the latest one, reused for the next frame once
+    /// no reader holds it.
+    spare: Option<Arc<Frame<W>>>,
+}
+
+/// The state shared by the handles of a [SharedSim].
+struct Inner<W: World> {
+    /// The simulation, locked while stepping or editing it.
+    state: Mutex<State<W>>,
+
+    /// The frame of the latest generation.
+    latest: RwLock<Arc<Frame<W>>>,
+}
+
+/// A cloneable handle to a simulation shared between threads.
+///
+/// Stepping or editing the simulation locks it, then publishes the result
+/// as the latest [Frame], which [SharedSim::snapshot] returns without
+/// waiting on the simulation. Frames are double buffered: the storage of a
+/// replaced frame is reused for the next one once no reader holds it.
+pub struct SharedSim<W: World = Grid> {
+    /// The state shared by every handle.
+    inner: Arc<Inner<W>>,
+}
+
+impl<W: World> Clone for SharedSim<W> {
+    /// Create another handle to the same simulation.
+    fn clone(&self) -> SharedSim<W> {
+        SharedSim {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<W: World> SharedSim<W> {
+    /// Create a new [SharedSim] of a simulation, publishing its current
+    /// generation as the first [Frame].
+    ///
+    /// # Arguments
+    /// * `sim` - The simulation to share.
+    pub fn new(sim: ConwaySim<W>) -> SharedSim<W> {
+        let latest = Arc::new(Frame::new(&sim));
+
+        SharedSim {
+            inner: Arc::new(Inner {
+                state: Mutex::new(State { sim, spare: None }),
+                latest: RwLock::new(latest),
+            }),
+        }
+    }
+
+    /// Get the [Frame] of the latest generation, which stays valid however
+    /// far the simulation advances while it is held.
+    pub fn snapshot(&self) -> Arc<Frame<W>> {
+        let latest = self.inner.latest.read().expect("frame lock poisoned");
+
+        Arc::clone(&latest)
+    }
+
+    /// Get the latest published generation.
+    pub fn get_generation(&self) -> u32 {
+        self.snapshot().get_generation()
+    }
+
+    /// Advance the simulation by one generation and publish it.
+    pub fn step(&self) {
+        self.edit(|sim| sim.step());
+    }
+
+    /// Advance the simulation by `n` generations, publishing only the last.
+    ///
+    /// # Arguments
+    /// * `n` - The number of generations to advance the simulation.
+    pub fn step_n(&self, n: u32) {
+        self.edit(|sim| sim.step_n(n));
+    }
+
+    /// Modify the simulation, such as to set cells or change its rule, then
+    /// publish the result.
+    ///
+    /// Other handles wait to step or edit the simulation until the
+    /// modification finishes, but readers of frames do not.
+    ///
+    /// # Arguments
+    /// * `edit` - Closure modifying the simulation.
+    pub fn edit<R>(&self, edit: impl FnOnce(&mut ConwaySim<W>) -> R) -> R {
+        let mut state = self.inner.state.lock().expect("simulation lock poisoned");
+        let result = edit(&mut state.sim);
+        self.publish(&mut state);
+
+        result
+    }
+
+    /// Publish the simulation's current generation as the latest [Frame],
+    /// copied into the spare frame if no reader still holds it.
+    fn publish(&self, state: &mut State<W>) {
+        let frame = match state.spare.take() {
+            Some(mut spare) => match Arc::get_mut(&mut spare) {
+                Some(frame) => {
+                    frame.generation = state.sim.get_generation();
+                    frame.world.clone_from(state.sim.get_world());
+                    spare
+                }
+                None => Arc::new(Frame::new(&state.sim)),
+            },
+            None => Arc::new(Frame::new(&state.sim)),
+        };
+
+        let mut latest = self.inner.latest.write().expect("frame lock poisoned");
+        state.spare = Some(mem::replace(&mut *latest, frame));
+    }
+
+    /// Take back the simulation if this is its only handle, or get the
+    /// handle back otherwise.
+    pub fn try_into_sim(self) -> Result<ConwaySim<W>, SharedSim<W>> {
+        match Arc::try_unwrap(self.inner) {
+            Ok(inner) => {
+                let state = inner.state.into_inner().expect("simulation lock poisoned");
+
+                Ok(state.sim)
+            }
+            Err(inner) => Err(SharedSim { inner }),
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Headless batch runs of patterns with machine-readable results.
+
+use std::error::Error;
+use std::io::{self, Write};
+use std::path::Path;
+
+use gameoflife_rs::{patterns, Anchor, Census, ConwaySim, Cycle, Grid, SparseWorld, World};
+
+use crate::cli::{OutputFormat, SimulateArgs};
+
+/// Results of simulating a single pattern.
+struct RunResult {
+    /// The pattern file or built-in pattern name.
+    pattern: String,
+
+    /// The rulestring the pattern was run under.
+    rule: String,
+
+    /// Number of generations run.
+    generations: u32,
+
+    /// Population of the final generation.
+    population: u64,
+
+    /// Status of the final generation.
+    status: String,
+
+    /// Generation at which the pattern was first detected as extinct,
+    /// stable, or oscillating, if it settled.
+    stabilized_at: Option<u32>,
+
+    /// The cycle the pattern entered, if it settled.
+    cycle: Option<Cycle>,
+
+    /// Census of the objects in the final generation.
+    census: Census,
+}
+
+impl RunResult {
+    /// Get the CSV header row.
+    fn csv_header() -> &'static str {
+        "pattern,rule,generations,population,status,stabilized_at,cycle_start,cycle_length,census"
+    }
+
+    /// Format the result as a CSV row, with the census as `name=count` pairs
+    /// separated by semicolons.
+    fn to_csv(&self) -> String {
+        let census: Vec<String> = self
+            .census
+            .get_counts()
+            .iter()
+            .map(|(name, count)| format!("{}={}", name, count))
+            .collect();
+
+        format!(
+            "{},{},{},{},{},{},{},{},{}",
+            csv_field(&self.pattern),
+            csv_field(&self.rule),
+            self.generations,
+            self.population,
+            csv_field(&self.status),
+            self.stabilized_at
+                .map_or(String::new(), |generation| generation.to_string()),
+            self.cycle
+                .map_or(String::new(), |cycle| cycle.get_start().to_string()),
+            self.cycle
+                .map_or(String::new(), |cycle| cycle.get_length().to_string()),
+            csv_field(&census.join(";"))
+        )
+    }
+
+    /// Format the result as a single-line JSON object.
+    fn to_json(&self) -> String {
+        let census: Vec<String> = self
+            .census
+            .get_counts()
+            .iter()
+            .map(|(name, count)| format!("{}:{}", json_string(name), count))
+            .collect();
+
+        format!(
+            "{{\"pattern\":{},\"rule\":{},\"generations\":{},\"population\":{},\"status\":{},\"stabilized_at\":{},\"cycle_start\":{},\"cycle_length\":{},\"census\":{{{}}}}}",
+            json_string(&self.pattern),
+            json_string(&self.rule),
+            self.generations,
+            self.population,
+            json_string(&self.status),
+            self.stabilized_at
+                .map_or("null".to_string(), |generation| generation.to_string()),
+            self.cycle
+                .map_or("null".to_string(), |cycle| cycle.get_start().to_string()),
+            self.cycle
+                .map_or("null".to_string(), |cycle| cycle.get_length().to_string()),
+            census.join(",")
+        )
+    }
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Encode a string as a JSON string literal.
+pub(crate) fn json_string(string: &str) -> String {
+    let mut json = String::from("\"");
+
+    for c in string.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+    json
+}
+
+/// Run a simulation for a number of generations, noting when it settled.
+fn run_sim<W: World>(mut sim: ConwaySim<W>, pattern: &str, generations: u32) -> RunResult {
+    let mut stabilized_at = None;
+
+    for _ in 0..generations {
+        if stabilized_at.is_none() && sim.get_status().is_settled() {
+            stabilized_at = Some(sim.get_generation());
+        }
+
+        sim.step();
+    }
+
+    if stabilized_at.is_none() && sim.get_status().is_settled() {
+        stabilized_at = Some(sim.get_generation());
+    }
+
+    RunResult {
+        pattern: pattern.to_string(),
+        rule: sim.get_rule().to_string(),
+        generations: sim.get_generation(),
+        population: sim.get_population(),
+        status: sim.get_status().to_string(),
+        stabilized_at,
+        cycle: sim.get_cycle(),
+        census: sim.census(),
+    }
+}
+
+/// Simulate a single pattern, on a bounded grid if a size was given and on
+/// an unbounded plane otherwise.
+fn simulate(args: &SimulateArgs, path: &Path) -> Result<RunResult, Box<dyn Error>> {
+    let loaded = patterns::load(path)?;
+    let rule = args.rule.unwrap_or_else(|| loaded.get_rule());
+    let name = path.display().to_string();
+    let pattern = loaded.get_grid();
+
+    if args.rows.is_some() || args.cols.is_some() {
+        let num_rows = args.rows.unwrap_or_else(|| pattern.get_num_rows());
+        let num_cols = args.cols.unwrap_or_else(|| pattern.get_num_cols());
+        Grid::check_size(num_rows, num_cols)?;
+
+        let mut sim = ConwaySim::new_with_grid(pattern.resized(num_rows, num_cols, Anchor::Center));
+        sim.set_rule(rule);
+        sim.set_topology(args.topology);
+
+        Ok(run_sim(sim, &name, args.generations))
+    } else {
+        let mut sim = ConwaySim::new_with_world(SparseWorld::from_grid(pattern));
+        sim.set_rule(rule);
+
+        Ok(run_sim(sim, &name, args.generations))
+    }
+}
+
+/// Simulate each pattern without rendering and print one result per line,
+/// returning the first error after simulating the rest.
+pub fn run(args: &SimulateArgs) -> Result<(), Box<dyn Error>> {
+    let mut output = io::stdout().lock();
+    let mut first_error: Option<Box<dyn Error>> = None;
+
+    if args.format == OutputFormat::Csv {
+        writeln!(output, "{}", RunResult::csv_header())?;
+    }
+
+    for path in args.patterns.iter() {
+        match simulate(args, path) {
+            Ok(result) => match args.format {
+                OutputFormat::Csv => writeln!(output, "{}", result.to_csv())?,
+                OutputFormat::Json => writeln!(output, "{}", result.to_json())?,
+            },
+            Err(err) => {
+                eprintln!("error: {}: {}", path.display(), err);
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+
+    match first_error {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
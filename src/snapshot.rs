@@ -0,0 +1,276 @@
+//! Immutable snapshots of grids which share unchanged regions.
+//!
+//! A [GridSnapshot] stores its cells as tiles of [CHUNK_SIZE] cells square,
+//! each behind an [Arc]. Taking a snapshot with [GridSnapshot::update] reuses
+//! every tile of the previous snapshot which did not change, so keeping the
+//! snapshot of every generation only costs memory for the regions where
+//! something happened, and cloning a snapshot costs a reference count.
+//!
+//! ```
+//! use std::sync::{Arc, Mutex};
+//!
+//! use gameoflife_rs::snapshot::GridSnapshot;
+//! use gameoflife_rs::ConwaySim;
+//!
+//! let mut sim = ConwaySim::new(256, 256);
+//! sim.set_cells(&[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+//!
+//! let snapshots: Arc<Mutex<Vec<GridSnapshot>>> = Arc::default();
+//! let kept = Arc::clone(&snapshots);
+//! sim.on_snapshot(move |_, snapshot| kept.lock().unwrap().push(snapshot.clone()));
+//! for _ in 0..4 {
+//!     sim.step();
+//! }
+//!
+//! // the glider only changes the top left tile of the 8x8 tiles
+//! let snapshots = snapshots.lock().unwrap();
+//! assert_eq!(snapshots[3].count_shared_tiles(&snapshots[2]), 63);
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::game::{Cell, ConwaySim, Grid, CHUNK_SIZE};
+use crate::topology::Topology;
+
+/// An immutable, cheaply cloned snapshot of a [Grid], sharing the tiles
+/// which did not change with the snapshot it was updated from.
+#[derive(Clone, Debug)]
+pub struct GridSnapshot {
+    /// Number of rows (height) of the snapshot.
+    num_rows: u32,
+
+    /// Number of columns (width) of the snapshot.
+    num_cols: u32,
+
+    /// How the cells on the edges of the snapshot find their neighbors.
+    topology: Topology,
+
+    /// The tiles of cells in row-major order, each with its cells in
+    /// row-major order. Tiles on the bottom and right edges may be smaller.
+    tiles: Arc<Vec<Arc<[Cell]>>>,
+}
+
+impl GridSnapshot {
+    /// Create a new [GridSnapshot] of a [Grid], sharing nothing.
+    ///
+    /// # Arguments
+    /// * `grid` - The grid to take a snapshot of.
+    pub fn new(grid: &Grid) -> GridSnapshot {
+        GridSnapshot::build(grid, None)
+    }
+
+    /// Take a new [GridSnapshot] of a [Grid], sharing the tiles of this
+    /// snapshot whose cells are unchanged.
+    ///
+    /// Nothing is shared if the grid's size or topology differ from this
+    /// snapshot's.
+    ///
+    /// # Arguments
+    /// * `grid` - The grid to take a snapshot of, usually a later generation
+    ///   of this snapshot's.
+    pub fn update(&self, grid: &Grid) -> GridSnapshot {
+        let same_shape = (
+            grid.get_num_rows(),
+            grid.get_num_cols(),
+            grid.get_topology(),
+        ) == (self.num_rows, self.num_cols, self.topology);
+
+        GridSnapshot::build(grid, Some(self).filter(|_| same_shape))
+    }
+
+    /// Build a [GridSnapshot] of a [Grid], reusing the unchanged tiles of a
+    /// previous snapshot of the same shape, if any.
+    fn build(grid: &Grid, previous: Option<&GridSnapshot>) -> GridSnapshot {
+        let mut snapshot = GridSnapshot {
+            num_rows: grid.get_num_rows(),
+            num_cols: grid.get_num_cols(),
+            topology: grid.get_topology(),
+            tiles: Arc::default(),
+        };
+
+        let cells = grid.get_cells();
+        let width = snapshot.num_cols as usize;
+
+        let tiles: Vec<Arc<[Cell]>> = (0..snapshot.num_tiles())
+            .map(|index| {
+                let (row, col, num_rows, num_cols) = snapshot.tile_bounds(index);
+                let rows = (row..row + num_rows).map(|row| {
+                    let start = row as usize * width + col as usize;
+                    &cells[start..start + num_cols as usize]
+                });
+
+                if let Some(tile) = previous.map(|previous| &previous.tiles[index]) {
+                    let unchanged = tile
+                        .chunks(num_cols as usize)
+                        .zip(rows.clone())
+                        .all(|(before, after)| before == after);
+
+                    if unchanged {
+                        return Arc::clone(tile);
+                    }
+                }
+
+                rows.flatten().copied().collect()
+            })
+            .collect();
+
+        snapshot.tiles = Arc::new(tiles);
+
+        snapshot
+    }
+
+    /// Get the number of tiles along each row of the snapshot.
+    fn num_tile_cols(&self) -> u32 {
+        self.num_cols.div_ceil(CHUNK_SIZE)
+    }
+
+    /// Get the number of tiles of the snapshot.
+    fn num_tiles(&self) -> usize {
+        (self.num_rows.div_ceil(CHUNK_SIZE) * self.num_tile_cols()) as usize
+    }
+
+    /// Get the (row, col, num_rows, num_cols) of the cells covered by a tile.
+    fn tile_bounds(&self, index: usize) -> (u32, u32, u32, u32) {
+        let num_tile_cols = self.num_tile_cols() as usize;
+        let row = (index / num_tile_cols) as u32 * CHUNK_SIZE;
+        let col = (index % num_tile_cols) as u32 * CHUNK_SIZE;
+
+        (
+            row,
+            col,
+            CHUNK_SIZE.min(self.num_rows - row),
+            CHUNK_SIZE.min(self.num_cols - col),
+        )
+    }
+
+    /// Get the number of rows (height) of the snapshot.
+    pub fn get_num_rows(&self) -> u32 {
+        self.num_rows
+    }
+
+    /// Get the number of columns (width) of the snapshot.
+    pub fn get_num_cols(&self) -> u32 {
+        self.num_cols
+    }
+
+    /// Get the [Topology] of the grid the snapshot was taken of.
+    pub fn get_topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Get a specified [Cell] of the snapshot.
+    ///
+    /// # Panics
+    /// Panics if the (row, col) position is outside of the snapshot.
+    pub fn get(&self, row: u32, col: u32) -> Cell {
+        assert!(
+            row < self.num_rows && col < self.num_cols,
+            "cell ({}, {}) is outside of the {}x{} snapshot",
+            row,
+            col,
+            self.num_rows,
+            self.num_cols
+        );
+
+        let index = (row / CHUNK_SIZE * self.num_tile_cols() + col / CHUNK_SIZE) as usize;
+        let (_, _, _, num_cols) = self.tile_bounds(index);
+
+        self.tiles[index][((row % CHUNK_SIZE) * num_cols + col % CHUNK_SIZE) as usize]
+    }
+
+    /// Get the number of live cells.
+    pub fn get_population(&self) -> u64 {
+        self.tiles
+            .iter()
+            .flat_map(|tile| tile.iter())
+            .filter(|cell| cell.is_alive())
+            .count() as u64
+    }
+
+    /// Count the tiles this snapshot shares with another rather than storing
+    /// its own copy of.
+    pub fn count_shared_tiles(&self, other: &GridSnapshot) -> usize {
+        self.tiles
+            .iter()
+            .zip(other.tiles.iter())
+            .filter(|(tile, other)| Arc::ptr_eq(tile, other))
+            .count()
+    }
+
+    /// Copy the cells of the snapshot into a new [Grid].
+    pub fn to_grid(&self) -> Grid {
+        let mut grid = Grid::new(self.num_rows, self.num_cols);
+        grid.set_topology(self.topology);
+
+        for (index, tile) in self.tiles.iter().enumerate() {
+            let (row, col, _, num_cols) = self.tile_bounds(index);
+
+            for (offset, cell) in tile.iter().enumerate() {
+                if cell.is_alive() {
+                    let offset = offset as u32;
+                    grid.set(
+                        row + offset / num_cols,
+                        col + offset % num_cols,
+                        Cell::Alive,
+                    );
+                }
+            }
+        }
+
+        grid
+    }
+}
+
+impl PartialEq for GridSnapshot {
+    /// Compare the cells and topology of two snapshots, skipping the tiles
+    /// they share.
+    fn eq(&self, other: &GridSnapshot) -> bool {
+        (self.num_rows, self.num_cols, self.topology)
+            == (other.num_rows, other.num_cols, other.topology)
+            && self
+                .tiles
+                .iter()
+                .zip(other.tiles.iter())
+                .all(|(tile, other)| Arc::ptr_eq(tile, other) || tile == other)
+    }
+}
+
+impl Eq for GridSnapshot {}
+
+impl fmt::Display for GridSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.to_grid().fmt(f)
+    }
+}
+
+impl From<&Grid> for GridSnapshot {
+    fn from(grid: &Grid) -> GridSnapshot {
+        GridSnapshot::new(grid)
+    }
+}
+
+impl ConwaySim<Grid> {
+    /// Register a callback notified after each step with the new generation
+    /// and a [GridSnapshot] of its [Grid], which shares the unchanged tiles
+    /// of the previous generation's snapshot so that it can be kept cheaply.
+    ///
+    /// Like [ConwaySim::on_generation], edits and moving backwards through
+    /// the [History](crate::History) do not notify the callback.
+    pub fn on_snapshot<F>(&mut self, mut observer: F)
+    where
+        F: FnMut(u32, &GridSnapshot) + Send + 'static,
+    {
+        let mut previous: Option<GridSnapshot> = None;
+
+        self.on_generation(move |generation, grid, _| {
+            let snapshot = match &previous {
+                Some(previous) => previous.update(grid),
+                None => GridSnapshot::new(grid),
+            };
+
+            observer(generation, &snapshot);
+            previous = Some(snapshot);
+        });
+    }
+}
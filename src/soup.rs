@@ -0,0 +1,239 @@
+//! Random soup generation.
+//!
+//! Soups are generated with a seeded ChaCha RNG, so the same seed always
+//! produces the same soup regardless of platform.
+//!
+//! A soup can also be made symmetric, as in apgsearch's symmetric search
+//! modes: each [Symmetry] copies the random cells of part of the grid onto
+//! the rest by its rotations and reflections. Life preserves the symmetry
+//! of a soup on a grid whose topology shares it, such as bounded edges, so
+//! every generation stays symmetric, and oscillators and spaceships which
+//! are rare from random soups turn up more often.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error;
+use core::fmt;
+use core::str::FromStr;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::game::Grid;
+use crate::transform::Transform;
+
+/// Error raised while parsing a [Symmetry] name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SymmetryError {
+    /// The name that failed to parse.
+    name: String,
+}
+
+impl fmt::Display for SymmetryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid symmetry: '{}'", self.name)
+    }
+}
+
+impl error::Error for SymmetryError {}
+
+/// The symmetry of a random soup, named as in apgsearch.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Symmetry {
+    /// No symmetry.
+    #[default]
+    C1,
+
+    /// Unchanged by a half turn.
+    C2,
+
+    /// Unchanged by a quarter turn. Requires a square grid.
+    C4,
+
+    /// Mirrored left to right.
+    D2,
+
+    /// Mirrored left to right and top to bottom.
+    D4,
+
+    /// Unchanged by every rotation and reflection. Requires a square grid.
+    D8,
+}
+
+impl Symmetry {
+    /// Every [Symmetry], from the least to the most symmetric.
+    pub const ALL: [Symmetry; 6] = [
+        Symmetry::C1,
+        Symmetry::C2,
+        Symmetry::C4,
+        Symmetry::D2,
+        Symmetry::D4,
+        Symmetry::D8,
+    ];
+
+    /// Get the [Transform]s which leave a soup of this symmetry unchanged.
+    pub fn get_transforms(self) -> &'static [Transform] {
+        match self {
+            Symmetry::C1 => &[Transform::Identity],
+            Symmetry::C2 => &[Transform::Identity, Transform::Rotate180],
+            Symmetry::C4 => &[
+                Transform::Identity,
+                Transform::Rotate90,
+                Transform::Rotate180,
+                Transform::Rotate270,
+            ],
+            Symmetry::D2 => &[Transform::Identity, Transform::FlipHorizontal],
+            Symmetry::D4 => &[
+                Transform::Identity,
+                Transform::FlipHorizontal,
+                Transform::FlipVertical,
+                Transform::Rotate180,
+            ],
+            Symmetry::D8 => &Transform::ALL,
+        }
+    }
+
+    /// Determine if the symmetry turns rows into columns, so that it only
+    /// fits a square grid.
+    pub fn is_square_only(self) -> bool {
+        matches!(self, Symmetry::C4 | Symmetry::D8)
+    }
+}
+
+impl FromStr for Symmetry {
+    type Err = SymmetryError;
+
+    /// Parse a symmetry name: C1, C2, C4, D2, D4, or D8.
+    fn from_str(name: &str) -> Result<Symmetry, SymmetryError> {
+        Symmetry::ALL
+            .iter()
+            .copied()
+            .find(|symmetry| symmetry.to_string().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| SymmetryError {
+                name: name.to_string(),
+            })
+    }
+}
+
+impl fmt::Display for Symmetry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Symmetry::C1 => "C1",
+            Symmetry::C2 => "C2",
+            Symmetry::C4 => "C4",
+            Symmetry::D2 => "D2",
+            Symmetry::D4 => "D4",
+            Symmetry::D8 => "D8",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// Get the position a [Transform] moves a cell of a grid to.
+///
+/// # Arguments
+/// * `transform` - The rotation or reflection, which must keep the grid's
+///   size.
+/// * `row` - Row of the cell.
+/// * `col` - Column of the cell.
+/// * `last_row` - The last row of the grid.
+/// * `last_col` - The last column of the grid.
+fn transform_cell(
+    transform: Transform,
+    row: u32,
+    col: u32,
+    last_row: u32,
+    last_col: u32,
+) -> (u32, u32) {
+    match transform {
+        Transform::Identity => (row, col),
+        Transform::Rotate90 => (col, last_row - row),
+        Transform::Rotate180 => (last_row - row, last_col - col),
+        Transform::Rotate270 => (last_col - col, row),
+        Transform::FlipHorizontal => (row, last_col - col),
+        Transform::FlipVertical => (last_row - row, col),
+        Transform::Transpose => (col, row),
+        Transform::AntiTranspose => (last_col - col, last_row - row),
+    }
+}
+
+impl Grid {
+    /// Create a new [Grid] filled with a random soup of live cells.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the [Grid].
+    /// * `num_cols` - The number of columns (width) of the [Grid].
+    /// * `density` - Probability of each cell being alive, from 0.0 to 1.0.
+    /// * `seed` - Seed of the random number generator.
+    ///
+    /// # Panics
+    /// Panics if `density` is not within 0.0 to 1.0.
+    pub fn random(num_rows: u32, num_cols: u32, density: f64, seed: u64) -> Grid {
+        Grid::random_symmetric(num_rows, num_cols, density, seed, Symmetry::C1)
+    }
+
+    /// Create a new [Grid] filled with a random soup of live cells with a
+    /// [Symmetry].
+    ///
+    /// Each cell takes the state of the first of the cells its symmetry
+    /// moves it to, so whether a mirror axis runs along a row or column of
+    /// cells or between two depends on whether the grid is odd or even
+    /// along that axis. A soup of [Symmetry::C1] is the same as
+    /// [Grid::random] with the same seed.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the [Grid].
+    /// * `num_cols` - The number of columns (width) of the [Grid].
+    /// * `density` - Probability of each cell being alive, from 0.0 to 1.0.
+    /// * `seed` - Seed of the random number generator.
+    /// * `symmetry` - The [Symmetry] of the soup.
+    ///
+    /// # Panics
+    /// Panics if `density` is not within 0.0 to 1.0, or if the symmetry
+    /// [only fits a square grid](Symmetry::is_square_only) and the [Grid]
+    /// is not square.
+    pub fn random_symmetric(
+        num_rows: u32,
+        num_cols: u32,
+        density: f64,
+        seed: u64,
+        symmetry: Symmetry,
+    ) -> Grid {
+        assert!(
+            !symmetry.is_square_only() || num_rows == num_cols,
+            "{} symmetry requires a square grid",
+            symmetry
+        );
+
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut grid = Grid::new(num_rows, num_cols);
+
+        let mut random: Vec<bool> = Vec::new();
+        for _ in 0..num_rows as u64 * num_cols as u64 {
+            random.push(rng.random_bool(density));
+        }
+
+        let (last_row, last_col) = (num_rows.saturating_sub(1), num_cols.saturating_sub(1));
+        let mut cells: Vec<(u32, u32)> = Vec::new();
+
+        for row in 0..num_rows {
+            for col in 0..num_cols {
+                let (source_row, source_col) = symmetry
+                    .get_transforms()
+                    .iter()
+                    .map(|&transform| transform_cell(transform, row, col, last_row, last_col))
+                    .min()
+                    .unwrap_or((row, col));
+
+                if random[source_row as usize * num_cols as usize + source_col as usize] {
+                    cells.push((row, col));
+                }
+            }
+        }
+
+        grid.set_cells(&cells);
+
+        grid
+    }
+}
@@ -0,0 +1,202 @@
+//! An unbounded [World] storing only its live cells.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::game::{ConwaySim, Grid};
+use crate::rule::Rule;
+use crate::world::World;
+
+/// An unbounded [World] backed by the set of its live cells, allowing patterns
+/// such as glider guns to expand indefinitely.
+///
+/// Since only live cells and their neighbors are ever considered, rules with
+/// births on zero neighbors (`B0`) do not cause empty space to be born.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SparseWorld {
+    /// The (row, col) of each live cell.
+    cells: HashSet<(i64, i64)>,
+}
+
+impl SparseWorld {
+    /// Create a new, empty [SparseWorld].
+    pub fn new() -> SparseWorld {
+        SparseWorld {
+            cells: HashSet::new(),
+        }
+    }
+
+    /// Create a new [SparseWorld] from a [Grid], with the [Grid]'s (0, 0) cell
+    /// at the world's (0, 0) cell.
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cells of the world.
+    pub fn from_grid(grid: &Grid) -> SparseWorld {
+        let mut world = SparseWorld::new();
+        world.set_cells(&grid.live_cells());
+
+        world
+    }
+
+    /// Set the cells of the [SparseWorld] as alive.
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (row, col) to set as alive.
+    pub fn set_cells(&mut self, cells: &[(i64, i64)]) {
+        self.cells.extend(cells.iter().cloned());
+    }
+
+    /// Set the specified cell as alive or dead.
+    pub fn set(&mut self, row: i64, col: i64, alive: bool) {
+        if alive {
+            self.cells.insert((row, col));
+        } else {
+            self.cells.remove(&(row, col));
+        }
+    }
+
+    /// Get the (min_row, min_col, max_row, max_col) bounding box of the live
+    /// cells, or [None] if there are none.
+    pub fn bounding_box(&self) -> Option<(i64, i64, i64, i64)> {
+        let min_row = self.cells.iter().map(|&(row, _)| row).min()?;
+        let min_col = self.cells.iter().map(|&(_, col)| col).min()?;
+        let max_row = self.cells.iter().map(|&(row, _)| row).max()?;
+        let max_col = self.cells.iter().map(|&(_, col)| col).max()?;
+
+        Some((min_row, min_col, max_row, max_col))
+    }
+
+    /// Copy a rectangular region of the world into a new [Grid].
+    ///
+    /// # Arguments
+    /// * `row` - Row of the region's top left cell.
+    /// * `col` - Column of the region's top left cell.
+    /// * `num_rows` - The number of rows (height) of the region.
+    /// * `num_cols` - The number of columns (width) of the region.
+    pub fn to_grid(&self, row: i64, col: i64, num_rows: u32, num_cols: u32) -> Grid {
+        let mut grid = Grid::new(num_rows, num_cols);
+
+        let cells: Vec<(u32, u32)> = self
+            .cells
+            .iter()
+            .filter(|&&(r, c)| {
+                r >= row && c >= col && r < row + num_rows as i64 && c < col + num_cols as i64
+            })
+            .map(|&(r, c)| ((r - row) as u32, (c - col) as u32))
+            .collect();
+
+        grid.set_cells(&cells);
+
+        grid
+    }
+}
+
+impl World for SparseWorld {
+    fn is_alive(&self, row: i64, col: i64) -> bool {
+        self.cells.contains(&(row, col))
+    }
+
+    fn get_population(&self) -> u64 {
+        self.cells.len() as u64
+    }
+
+    fn live_cells(&self) -> Vec<(i64, i64)> {
+        let mut cells: Vec<(i64, i64)> = self.cells.iter().cloned().collect();
+        cells.sort_unstable();
+
+        cells
+    }
+
+    fn get_area(&self) -> u64 {
+        match self.bounding_box() {
+            Some((min_row, min_col, max_row, max_col)) => {
+                (max_row - min_row + 1) as u64 * (max_col - min_col + 1) as u64
+            }
+            None => 0,
+        }
+    }
+
+    fn count_changes(&self, previous: &SparseWorld) -> (u64, u64) {
+        (
+            self.cells.difference(&previous.cells).count() as u64,
+            previous.cells.difference(&self.cells).count() as u64,
+        )
+    }
+
+    fn step_into(&self, rule: &Rule, next: &mut SparseWorld) {
+        // count the live neighbors of every cell adjacent to a live cell
+        let mut neighbor_counts: HashMap<(i64, i64), u8> =
+            HashMap::with_capacity(self.cells.len() * 8);
+
+        for &(row, col) in self.cells.iter() {
+            for row_offset in -1..=1 {
+                for col_offset in -1..=1 {
+                    if (row_offset, col_offset) != (0, 0) {
+                        *neighbor_counts
+                            .entry((row + row_offset, col + col_offset))
+                            .or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+
+        next.cells.clear();
+
+        // live cells without any live neighbors are not counted above
+        if rule.is_survival(0) {
+            next.cells.extend(
+                self.cells
+                    .iter()
+                    .filter(|cell| !neighbor_counts.contains_key(cell)),
+            );
+        }
+
+        for (cell, count) in neighbor_counts {
+            let alive = if self.cells.contains(&cell) {
+                rule.is_survival(count)
+            } else {
+                rule.is_birth(count)
+            };
+
+            if alive {
+                next.cells.insert(cell);
+            }
+        }
+    }
+}
+
+impl fmt::Display for SparseWorld {
+    /// Display the bounding box of the live cells.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.bounding_box() {
+            Some((min_row, min_col, max_row, max_col)) => {
+                let num_rows = (max_row - min_row + 1) as u32;
+                let num_cols = (max_col - min_col + 1) as u32;
+
+                self.to_grid(min_row, min_col, num_rows, num_cols).fmt(f)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl ConwaySim<SparseWorld> {
+    /// Create a new simulation of an empty, unbounded [SparseWorld].
+    pub fn new_sparse() -> ConwaySim<SparseWorld> {
+        ConwaySim::new_with_world(SparseWorld::new())
+    }
+
+    /// Determine if the specified cell is alive.
+    pub fn is_cell_alive(&self, row: i64, col: i64) -> bool {
+        self.get_world().is_alive(row, col)
+    }
+
+    /// Set the cells of the simulation's [SparseWorld] as alive.
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (row, col) to set as alive.
+    pub fn set_cells(&mut self, cells: &[(i64, i64)]) {
+        self.get_world_mut().set_cells(cells);
+        self.record_edit();
+    }
+}
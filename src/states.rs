@@ -0,0 +1,177 @@
+//! A bounded grid of multi-state cells, shared by automata with more than
+//! two cell states.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::game::{Grid, GridError};
+
+/// A bounded grid of cells, each holding a state index from 0 to 255.
+///
+/// State 0 is the empty (dead) state; the meaning of the other states is up
+/// to the automaton using the grid.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct StateGrid {
+    /// Number of rows (height) of the [StateGrid].
+    num_rows: u32,
+
+    /// Number of columns (width) of the [StateGrid].
+    num_cols: u32,
+
+    /// The cell states in row-major order.
+    states: Vec<u8>,
+}
+
+impl StateGrid {
+    /// Create a new [StateGrid] of empty cells.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the [StateGrid].
+    /// * `num_cols` - The number of columns (width) of the [StateGrid].
+    pub fn new(num_rows: u32, num_cols: u32) -> StateGrid {
+        StateGrid {
+            num_rows,
+            num_cols,
+            states: vec![0; num_rows as usize * num_cols as usize],
+        }
+    }
+
+    /// Create a new [StateGrid] from a [Grid], with live cells in state 1.
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cells.
+    pub fn from_grid(grid: &Grid) -> StateGrid {
+        StateGrid {
+            num_rows: grid.get_num_rows(),
+            num_cols: grid.get_num_cols(),
+            states: grid.get_cells().iter().map(|&cell| cell as u8).collect(),
+        }
+    }
+
+    /// Get the number of rows (height) of the [StateGrid].
+    pub fn get_num_rows(&self) -> u32 {
+        self.num_rows
+    }
+
+    /// Get the number of columns (width) of the [StateGrid].
+    pub fn get_num_cols(&self) -> u32 {
+        self.num_cols
+    }
+
+    /// Get all cell states in row-major order.
+    pub fn get_states(&self) -> &[u8] {
+        &self.states
+    }
+
+    /// Convert a (row, col) position to a row-major index.
+    fn cell_to_index(&self, row: u32, col: u32) -> usize {
+        row as usize * self.num_cols as usize + col as usize
+    }
+
+    /// Get the state of a specified cell.
+    pub fn get(&self, row: u32, col: u32) -> u8 {
+        self.states[self.cell_to_index(row, col)]
+    }
+
+    /// Set the state of a specified cell.
+    pub fn set(&mut self, row: u32, col: u32, state: u8) {
+        let index = self.cell_to_index(row, col);
+        self.states[index] = state;
+    }
+
+    /// Set the state of a specified cell, or return a [GridError] if it is
+    /// out of bounds.
+    pub fn try_set(&mut self, row: u32, col: u32, state: u8) -> Result<(), GridError> {
+        if row < self.num_rows && col < self.num_cols {
+            self.set(row, col, state);
+            Ok(())
+        } else {
+            Err(GridError::OutOfBounds {
+                row,
+                col,
+                num_rows: self.num_rows,
+                num_cols: self.num_cols,
+            })
+        }
+    }
+
+    /// Set the specified cells to a state.
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (row, col) to set.
+    /// * `state` - The state to set the cells to.
+    pub fn set_cells(&mut self, cells: &[(u32, u32)], state: u8) {
+        for &(row, col) in cells {
+            self.set(row, col, state);
+        }
+    }
+
+    /// Get the number of cells in a state.
+    pub fn count(&self, state: u8) -> u64 {
+        self.states.iter().filter(|&&other| other == state).count() as u64
+    }
+
+    /// Get the number of the eight neighbors of a cell for which `matches`
+    /// returns true. Neighbors outside of the [StateGrid] are not counted.
+    ///
+    /// # Arguments
+    /// * `row` - Row of the cell.
+    /// * `col` - Column of the cell.
+    /// * `matches` - Predicate on the neighbor states to count.
+    pub fn count_neighbors(&self, row: u32, col: u32, matches: impl Fn(u8) -> bool) -> u8 {
+        let mut count = 0;
+
+        for neighbor_row in row.saturating_sub(1)..=(row + 1).min(self.num_rows - 1) {
+            for neighbor_col in col.saturating_sub(1)..=(col + 1).min(self.num_cols - 1) {
+                if (neighbor_row, neighbor_col) != (row, col)
+                    && matches(self.get(neighbor_row, neighbor_col))
+                {
+                    count += 1;
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Compute the next states of all cells into `next`, resizing it if
+    /// needed.
+    ///
+    /// # Arguments
+    /// * `next` - The grid the next states are written into.
+    /// * `transition` - Function of a cell's (row, col) and state returning
+    ///   its next state.
+    pub fn map_into(&self, next: &mut StateGrid, transition: impl Fn(u32, u32, u8) -> u8) {
+        if next.num_rows != self.num_rows || next.num_cols != self.num_cols {
+            *next = StateGrid::new(self.num_rows, self.num_cols);
+        }
+
+        for row in 0..self.num_rows {
+            for col in 0..self.num_cols {
+                let index = self.cell_to_index(row, col);
+                next.states[index] = transition(row, col, self.states[index]);
+            }
+        }
+    }
+}
+
+impl fmt::Display for StateGrid {
+    /// Display empty cells as '◻', state 1 as '◼', and other states as
+    /// their digit (or letter above 9).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in self.states.chunks(self.num_cols.max(1) as usize) {
+            for &state in line {
+                let symbol = match state {
+                    0 => '◻',
+                    1 => '◼',
+                    _ => core::char::from_digit(state as u32, 36).unwrap_or('+'),
+                };
+                write!(f, "{}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+//! Per-generation population statistics.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+#[cfg(feature = "std")]
+use std::fs;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+/// Statistics of a single generation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GenerationStats {
+    /// The generation the statistics describe.
+    pub generation: u32,
+
+    /// Number of live cells.
+    pub population: u64,
+
+    /// Number of cells born since the previous generation.
+    pub births: u64,
+
+    /// Number of cells which died since the previous generation.
+    pub deaths: u64,
+
+    /// Fraction of the world's area which is alive, from 0.0 to 1.0.
+    pub density: f64,
+}
+
+/// Time series of [GenerationStats] recorded by a simulation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    /// The recorded statistics, oldest first.
+    samples: Vec<GenerationStats>,
+}
+
+impl Stats {
+    /// Create a new, empty [Stats].
+    pub fn new() -> Stats {
+        Stats {
+            samples: Vec::new(),
+        }
+    }
+
+    /// Get the recorded statistics, oldest first.
+    pub fn samples(&self) -> &[GenerationStats] {
+        &self.samples
+    }
+
+    /// Get the statistics of the latest recorded generation.
+    pub fn latest(&self) -> Option<&GenerationStats> {
+        self.samples.last()
+    }
+
+    /// Get the highest population recorded.
+    pub fn peak_population(&self) -> u64 {
+        self.samples
+            .iter()
+            .map(|sample| sample.population)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Get the populations of the most recent `count` generations recorded,
+    /// oldest first, e.g. to plot them.
+    pub fn recent_populations(&self, count: usize) -> Vec<u64> {
+        let start = self.samples.len().saturating_sub(count);
+
+        self.samples[start..]
+            .iter()
+            .map(|sample| sample.population)
+            .collect()
+    }
+
+    /// Record the statistics of a generation, replacing any later ones.
+    pub(crate) fn record(&mut self, sample: GenerationStats) {
+        self.samples
+            .retain(|recorded| recorded.generation < sample.generation);
+        self.samples.push(sample);
+    }
+
+    /// Record an edit of the world: the population and density of the
+    /// current generation are updated and any later statistics discarded.
+    pub(crate) fn record_edit(&mut self, generation: u32, population: u64, density: f64) {
+        self.truncate_after(generation);
+
+        match self.samples.last_mut() {
+            Some(sample) if sample.generation == generation => {
+                sample.population = population;
+                sample.density = density;
+            }
+            _ => self.samples.push(GenerationStats {
+                generation,
+                population,
+                births: 0,
+                deaths: 0,
+                density,
+            }),
+        }
+    }
+
+    /// Discard the statistics of generations after `generation`.
+    pub(crate) fn truncate_after(&mut self, generation: u32) {
+        self.samples
+            .retain(|sample| sample.generation <= generation);
+    }
+
+    /// Write the statistics as CSV with a header row.
+    ///
+    /// # Arguments
+    /// * `writer` - Destination of the CSV.
+    #[cfg(feature = "std")]
+    pub fn write_csv<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.to_csv().as_bytes())
+    }
+
+    /// Get the statistics as CSV with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        csv.push_str("generation,population,births,deaths,density\n");
+
+        for sample in self.samples.iter() {
+            writeln!(
+                csv,
+                "{},{},{},{},{}",
+                sample.generation, sample.population, sample.births, sample.deaths, sample.density
+            )
+            .expect("writing to a String cannot fail");
+        }
+
+        csv
+    }
+
+    /// Save the statistics to a CSV file.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the CSV file.
+    #[cfg(feature = "std")]
+    pub fn save_csv_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_csv())
+    }
+}
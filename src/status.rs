@@ -0,0 +1,175 @@
+//! Detection of extinct, stable, and oscillating simulations.
+//!
+//! After every generation the simulation's world is hashed and looked up in a
+//! rolling map from the hashes of recent generations to their generation
+//! numbers. The first repeated hash means the simulation has entered a
+//! [Cycle]: it starts at the generation the hash was first seen, and its
+//! length is the distance between the repeats.
+
+use alloc::collections::{BTreeMap, VecDeque};
+use core::fmt;
+
+/// Number of recent generations remembered, and so the longest cycle which
+/// can be detected.
+const MAX_PERIOD: usize = 4096;
+
+/// Status of a simulation.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SimStatus {
+    /// The simulation is still changing.
+    Running,
+
+    /// No cells are alive.
+    Extinct,
+
+    /// The world no longer changes from one generation to the next.
+    Stable,
+
+    /// The world repeats every `period` generations.
+    Oscillating { period: u32 },
+}
+
+impl SimStatus {
+    /// Determine if the simulation has settled: it is extinct, stable, or
+    /// oscillating, so advancing it further will not produce anything new.
+    pub fn is_settled(self) -> bool {
+        self != SimStatus::Running
+    }
+}
+
+impl fmt::Display for SimStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SimStatus::Running => write!(f, "running"),
+            SimStatus::Extinct => write!(f, "extinct"),
+            SimStatus::Stable => write!(f, "stable"),
+            SimStatus::Oscillating { period } => write!(f, "oscillating (period {})", period),
+        }
+    }
+}
+
+/// The generations a simulation repeats forever once it reaches them.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Cycle {
+    /// The first generation of the cycle.
+    start: u32,
+
+    /// The number of generations after which the world repeats.
+    length: u32,
+}
+
+impl Cycle {
+    /// Get the first generation of the cycle.
+    pub fn get_start(&self) -> u32 {
+        self.start
+    }
+
+    /// Get the number of generations after which the world repeats, 1 for a
+    /// world that no longer changes.
+    pub fn get_length(&self) -> u32 {
+        self.length
+    }
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "entered a cycle of length {} starting at generation {}",
+            self.length, self.start
+        )
+    }
+}
+
+/// Tracks the hashes of recent generations to determine the [SimStatus].
+#[derive(Clone, Debug)]
+pub(crate) struct StatusTracker {
+    /// (generation, hash) of the most recent generations, oldest first.
+    recent: VecDeque<(u32, u64)>,
+
+    /// The generation each hash of `recent` was first seen at.
+    generations: BTreeMap<u64, u32>,
+
+    /// The cycle the simulation entered, once found.
+    cycle: Option<Cycle>,
+
+    /// The status as of the latest generation.
+    status: SimStatus,
+}
+
+impl StatusTracker {
+    /// Create a new [StatusTracker] for a simulation that is running.
+    pub(crate) fn new() -> StatusTracker {
+        StatusTracker {
+            recent: VecDeque::new(),
+            generations: BTreeMap::new(),
+            cycle: None,
+            status: SimStatus::Running,
+        }
+    }
+
+    /// Get the status as of the latest generation.
+    pub(crate) fn get_status(&self) -> SimStatus {
+        self.status
+    }
+
+    /// Get the cycle the simulation entered, if found.
+    pub(crate) fn get_cycle(&self) -> Option<Cycle> {
+        self.cycle
+    }
+
+    /// Determine if no generation has been seen since the tracker was
+    /// created or reset.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.recent.is_empty()
+    }
+
+    /// Forget all recent generations, e.g. after the world is edited.
+    ///
+    /// # Arguments
+    /// * `population` - The population of the world.
+    pub(crate) fn reset(&mut self, population: u64) {
+        self.recent.clear();
+        self.generations.clear();
+        self.cycle = None;
+        self.status = if population == 0 {
+            SimStatus::Extinct
+        } else {
+            SimStatus::Running
+        };
+    }
+
+    /// Update the status with a new generation.
+    ///
+    /// # Arguments
+    /// * `generation` - The generation of the world.
+    /// * `hash` - The hash of the world.
+    /// * `population` - The population of the world.
+    pub(crate) fn update(&mut self, generation: u32, hash: u64, population: u64) {
+        // the first repeat is the start of the cycle, which later repeats
+        // only confirm
+        let first_seen = *self.generations.entry(hash).or_insert(generation);
+        if self.cycle.is_none() && first_seen < generation {
+            self.cycle = Some(Cycle {
+                start: first_seen,
+                length: generation - first_seen,
+            });
+        }
+
+        self.recent.push_back((generation, hash));
+        if self.recent.len() > MAX_PERIOD {
+            if let Some((oldest, hash)) = self.recent.pop_front() {
+                if self.generations.get(&hash) == Some(&oldest) {
+                    self.generations.remove(&hash);
+                }
+            }
+        }
+
+        self.status = match self.cycle {
+            _ if population == 0 => SimStatus::Extinct,
+            Some(Cycle { length: 1, .. }) => SimStatus::Stable,
+            Some(Cycle { length, .. }) => SimStatus::Oscillating { period: length },
+            None => SimStatus::Running,
+        };
+    }
+}
@@ -0,0 +1,90 @@
+//! Conditions ending a run of a simulation.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::game::{ConwaySim, Grid};
+use crate::world::World;
+
+/// Closure returning true for a simulation once it should stop.
+pub type StopPredicate<W> = Box<dyn Fn(&ConwaySim<W>) -> bool + Send>;
+
+/// A condition on which [ConwaySim::run_until] stops stepping.
+pub enum StopCondition<W: World = Grid> {
+    /// Stop after a number of generations have been run.
+    MaxGenerations(u32),
+
+    /// Stop once no cell is alive.
+    Extinct,
+
+    /// Stop once the simulation is extinct, stable, or oscillating.
+    Settled,
+
+    /// Stop once the population is at least this high.
+    PopulationAtLeast(u64),
+
+    /// Stop once the population is at most this low.
+    PopulationAtMost(u64),
+
+    /// Stop once a closure returns true for the simulation.
+    Custom(StopPredicate<W>),
+
+    /// Stop once any of the conditions is met.
+    Any(Vec<StopCondition<W>>),
+}
+
+impl<W: World> StopCondition<W> {
+    /// Create a [StopCondition::Custom] from a closure.
+    ///
+    /// # Arguments
+    /// * `condition` - Returns true for the simulation once it should stop.
+    pub fn custom<F>(condition: F) -> StopCondition<W>
+    where
+        F: Fn(&ConwaySim<W>) -> bool + Send + 'static,
+    {
+        StopCondition::Custom(Box::new(condition))
+    }
+
+    /// Determine if the condition is met, for callers driving their own
+    /// loop.
+    ///
+    /// # Arguments
+    /// * `sim` - The simulation being run.
+    /// * `generations_run` - The number of generations run so far.
+    pub fn is_met(&self, sim: &ConwaySim<W>, generations_run: u32) -> bool {
+        match self {
+            StopCondition::MaxGenerations(max) => generations_run >= *max,
+            StopCondition::Extinct => !sim.is_any_cell_alive(),
+            StopCondition::Settled => sim.get_status().is_settled(),
+            StopCondition::PopulationAtLeast(population) => sim.get_population() >= *population,
+            StopCondition::PopulationAtMost(population) => sim.get_population() <= *population,
+            StopCondition::Custom(condition) => condition(sim),
+            StopCondition::Any(conditions) => conditions
+                .iter()
+                .any(|condition| condition.is_met(sim, generations_run)),
+        }
+    }
+}
+
+impl<W: World> ConwaySim<W> {
+    /// Step the simulation until a [StopCondition] is met, checking it before
+    /// each step, and return the number of generations run.
+    ///
+    /// A condition which is never met, such as [StopCondition::Extinct] for a
+    /// glider gun, runs forever; combine it with
+    /// [StopCondition::MaxGenerations] using [StopCondition::Any] to bound the
+    /// run.
+    ///
+    /// # Arguments
+    /// * `condition` - The condition on which to stop.
+    pub fn run_until(&mut self, condition: &StopCondition<W>) -> u32 {
+        let mut generations_run = 0;
+
+        while !condition.is_met(self, generations_run) {
+            self.step();
+            generations_run += 1;
+        }
+
+        generations_run
+    }
+}
@@ -0,0 +1,93 @@
+//! Density sweeps of random soups with tabular or machine-readable results.
+
+use std::error::Error;
+use std::io::{self, Write};
+
+use gameoflife_rs::analysis::DensityResult;
+use gameoflife_rs::{DensitySweep, Grid};
+
+use crate::cli::{OutputFormat, SweepArgs};
+
+/// Get the CSV header row.
+fn csv_header() -> &'static str {
+    "density,trials,mean_population,stabilized,mean_stabilization,extinct"
+}
+
+/// Format the result of one density as a CSV row.
+fn to_csv(result: &DensityResult) -> String {
+    format!(
+        "{},{},{},{},{},{}",
+        result.get_density(),
+        result.get_num_trials(),
+        result.get_mean_population(),
+        result.get_num_stabilized(),
+        result
+            .get_mean_stabilization()
+            .map_or(String::new(), |generations| generations.to_string()),
+        result.get_num_extinct()
+    )
+}
+
+/// Format the result of one density as a single-line JSON object.
+fn to_json(result: &DensityResult) -> String {
+    format!(
+        "{{\"density\":{},\"trials\":{},\"mean_population\":{},\"stabilized\":{},\"mean_stabilization\":{},\"extinct\":{}}}",
+        result.get_density(),
+        result.get_num_trials(),
+        result.get_mean_population(),
+        result.get_num_stabilized(),
+        result
+            .get_mean_stabilization()
+            .map_or("null".to_string(), |generations| generations.to_string()),
+        result.get_num_extinct()
+    )
+}
+
+/// Sweep the densities of random soups and print the results of each
+/// density.
+pub fn run(args: &SweepArgs) -> Result<(), Box<dyn Error>> {
+    if !(0.0..=1.0).contains(&args.from) || !(0.0..=1.0).contains(&args.to) {
+        return Err("densities must be within 0.0 to 1.0".into());
+    }
+
+    Grid::check_size(args.rows, args.cols)?;
+
+    let mut sweep = DensitySweep::new();
+    sweep.set_size(args.rows, args.cols);
+    sweep.set_density_range(args.from, args.to, args.steps);
+    sweep.set_num_trials(args.trials);
+    sweep.set_max_generations(args.generations);
+    sweep.set_topology(args.topology);
+
+    if let Some(rule) = args.rule {
+        sweep.set_rule(rule);
+    }
+
+    if let Some(num_threads) = args.threads {
+        sweep.set_num_threads(num_threads);
+    }
+
+    let seed = args.seed.unwrap_or_else(crate::random_seed);
+    let report = sweep.run(seed);
+    let mut output = io::stdout().lock();
+
+    match args.format {
+        None => {
+            writeln!(output, "Seed: {}", seed)?;
+            write!(output, "{}", report)?;
+        }
+        Some(OutputFormat::Csv) => {
+            writeln!(output, "{}", csv_header())?;
+            for result in report.get_results() {
+                writeln!(output, "{}", to_csv(result))?;
+            }
+        }
+        Some(OutputFormat::Json) => {
+            for result in report.get_results() {
+                writeln!(output, "{}", to_json(result))?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+//! Timeline of the generations a simulation can return to, with bookmarks,
+//! shared by the interactive user interfaces.
+//!
+//! The timeline spans from the oldest generation the simulation's [History]
+//! can restore to the furthest generation reached. Seeking to an earlier
+//! generation restores the latest snapshot before it and re-simulates, and
+//! seeking to a later one steps forward again.
+//!
+//! [History]: gameoflife_rs::History
+
+use std::ops::RangeInclusive;
+
+use gameoflife_rs::{ConwaySim, HistoryError};
+
+use crate::keymap::Key;
+
+/// Number of moves of [Action::ScrubBack](crate::keymap::Action::ScrubBack)
+/// or [Action::ScrubForward](crate::keymap::Action::ScrubForward) across the
+/// whole timeline.
+const SCRUB_STEPS: u32 = 50;
+
+/// Outcome of a key typed into the generation entry of a [Timeline].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Entry {
+    /// The generation is still being typed.
+    Typing,
+
+    /// The entry was closed with escape, or with enter before typing a
+    /// generation.
+    Cancelled,
+
+    /// A generation was entered with enter.
+    Submitted(u32),
+}
+
+/// Timeline of the generations of a [ConwaySim].
+#[derive(Clone, Debug)]
+pub struct Timeline {
+    /// The furthest generation reached.
+    latest: u32,
+
+    /// The bookmarked generations, in order.
+    bookmarks: Vec<u32>,
+
+    /// The digits of the generation being entered, if the entry is open.
+    entry: Option<String>,
+}
+
+impl Timeline {
+    /// Create a new [Timeline] ending at the simulation's current generation.
+    pub fn new(sim: &ConwaySim) -> Timeline {
+        Timeline {
+            latest: sim.get_generation(),
+            bookmarks: Vec::new(),
+            entry: None,
+        }
+    }
+
+    /// Extend the timeline to the simulation's current generation, if it is
+    /// the furthest reached.
+    pub fn observe(&mut self, sim: &ConwaySim) {
+        self.latest = self.latest.max(sim.get_generation());
+    }
+
+    /// Get the generations of the timeline, from the oldest which can be
+    /// restored to the furthest reached.
+    pub fn get_range(&self, sim: &ConwaySim) -> RangeInclusive<u32> {
+        let generation = sim.get_generation();
+        let earliest = sim
+            .get_history()
+            .and_then(|history| history.earliest_generation())
+            .unwrap_or(generation);
+
+        earliest.min(generation)..=self.latest.max(generation)
+    }
+
+    /// Get the position of a generation along the timeline, from 0 at its
+    /// start to 1 at its end.
+    pub fn fraction_of(&self, sim: &ConwaySim, generation: u32) -> f32 {
+        let range = self.get_range(sim);
+        let span = range.end() - range.start();
+
+        if span == 0 {
+            return 1.0;
+        }
+
+        generation.saturating_sub(*range.start()).min(span) as f32 / span as f32
+    }
+
+    /// Get the generation at a position along the timeline.
+    ///
+    /// # Arguments
+    /// * `fraction` - The position, from 0 at the start of the timeline to 1
+    ///   at its end.
+    pub fn generation_at(&self, sim: &ConwaySim, fraction: f32) -> u32 {
+        let range = self.get_range(sim);
+        let span = range.end() - range.start();
+
+        range.start() + (fraction.clamp(0.0, 1.0) * span as f32).round() as u32
+    }
+
+    /// Move the simulation to a generation.
+    ///
+    /// Generations after the furthest reached are simulated, so a distant
+    /// one takes a while to reach.
+    ///
+    /// # Arguments
+    /// * `sim` - The simulation to move.
+    /// * `generation` - The generation to move to.
+    pub fn seek(&mut self, sim: &mut ConwaySim, generation: u32) -> Result<(), HistoryError> {
+        if generation != sim.get_generation() {
+            sim.seek(generation)?;
+            self.observe(sim);
+        }
+
+        Ok(())
+    }
+
+    /// Move the simulation along the timeline by a fiftieth of its length,
+    /// and at least one generation, staying within it.
+    ///
+    /// # Arguments
+    /// * `sim` - The simulation to move.
+    /// * `forward` - Whether to move towards the end of the timeline.
+    pub fn scrub(&mut self, sim: &mut ConwaySim, forward: bool) -> Result<(), HistoryError> {
+        let range = self.get_range(sim);
+        let distance = ((range.end() - range.start()) / SCRUB_STEPS).max(1);
+        let generation = sim.get_generation();
+
+        let target = if forward {
+            generation.saturating_add(distance).min(*range.end())
+        } else {
+            generation.saturating_sub(distance).max(*range.start())
+        };
+
+        self.seek(sim, target)
+    }
+
+    /// Get the bookmarked generations, in order.
+    pub fn get_bookmarks(&self) -> &[u32] {
+        &self.bookmarks
+    }
+
+    /// Bookmark a generation, or remove its bookmark.
+    ///
+    /// Returns whether the generation is now bookmarked.
+    pub fn toggle_bookmark(&mut self, generation: u32) -> bool {
+        match self.bookmarks.binary_search(&generation) {
+            Ok(index) => {
+                self.bookmarks.remove(index);
+                false
+            }
+            Err(index) => {
+                self.bookmarks.insert(index, generation);
+                true
+            }
+        }
+    }
+
+    /// Get the latest bookmark before a generation, if any.
+    pub fn previous_bookmark(&self, generation: u32) -> Option<u32> {
+        self.bookmarks
+            .iter()
+            .rev()
+            .copied()
+            .find(|&bookmark| bookmark < generation)
+    }
+
+    /// Get the earliest bookmark after a generation, if any.
+    pub fn next_bookmark(&self, generation: u32) -> Option<u32> {
+        self.bookmarks
+            .iter()
+            .copied()
+            .find(|&bookmark| bookmark > generation)
+    }
+
+    /// Open the entry of a generation to move to.
+    pub fn start_entry(&mut self) {
+        self.entry = Some(String::new());
+    }
+
+    /// Get the digits typed into the generation entry, if it is open.
+    pub fn get_entry(&self) -> Option<&str> {
+        self.entry.as_deref()
+    }
+
+    /// Type a key into the open generation entry: digits are added,
+    /// backspace removes the last digit, enter submits the generation, and
+    /// escape closes the entry. Other keys are ignored.
+    pub fn type_key(&mut self, key: Key) -> Entry {
+        let digits = match self.entry.as_mut() {
+            Some(digits) => digits,
+            None => return Entry::Cancelled,
+        };
+
+        match key {
+            Key::Char(c) if c.is_ascii_digit() => {
+                // ignore digits beyond the last generation
+                digits.push(c);
+                if digits.parse::<u32>().is_err() {
+                    digits.pop();
+                }
+            }
+            Key::Backspace => {
+                digits.pop();
+            }
+            Key::Enter => {
+                return match self.entry.take().and_then(|digits| digits.parse().ok()) {
+                    Some(generation) => Entry::Submitted(generation),
+                    None => Entry::Cancelled,
+                };
+            }
+            Key::Esc => {
+                self.entry = None;
+                return Entry::Cancelled;
+            }
+            _ => {}
+        }
+
+        Entry::Typing
+    }
+}
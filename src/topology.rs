@@ -0,0 +1,224 @@
+//! Boundary conditions of a [Grid](crate::Grid), deciding which cells the
+//! cells on its edges see as neighbors.
+//!
+//! Each pair of opposite edges is joined by an [Edge] rule: bounded edges
+//! see only dead cells beyond them, wrapping edges see the opposite edge,
+//! mirrored edges see themselves reflected, and twisted edges see the
+//! opposite edge flipped end to end. A [Topology] combines the rules of the
+//! top/bottom and left/right edges.
+
+use alloc::string::{String, ToString};
+use core::error;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::game::ConwaySim;
+
+/// Error raised while parsing an [Edge] or [Topology] name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TopologyError {
+    /// The name that failed to parse.
+    name: String,
+}
+
+impl fmt::Display for TopologyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid topology: '{}'", self.name)
+    }
+}
+
+impl error::Error for TopologyError {}
+
+/// How the neighbors beyond one pair of opposite edges are found.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Edge {
+    /// Cells beyond the edge are dead.
+    Bounded,
+
+    /// The edge is joined to the opposite edge.
+    Wrap,
+
+    /// Cells beyond the edge mirror the cells on it.
+    Mirror,
+
+    /// The edge is joined to the opposite edge flipped end to end, as in a
+    /// Möbius strip.
+    Twist,
+}
+
+impl Edge {
+    /// Map a coordinate along an axis of `len` cells to one within it,
+    /// returning whether the other coordinate is flipped, or [None] if the
+    /// cell beyond the edge is dead.
+    fn resolve(self, position: i64, len: i64) -> Option<(i64, bool)> {
+        if (0..len).contains(&position) {
+            return Some((position, false));
+        }
+
+        match self {
+            Edge::Bounded => None,
+            Edge::Wrap => Some((position.rem_euclid(len), false)),
+            Edge::Mirror => {
+                let reflected = if position < 0 {
+                    -1 - position
+                } else {
+                    2 * len - 1 - position
+                };
+                Some((reflected.clamp(0, len - 1), false))
+            }
+            Edge::Twist => Some((position.rem_euclid(len), true)),
+        }
+    }
+}
+
+impl FromStr for Edge {
+    type Err = TopologyError;
+
+    /// Parse an edge name: bounded, wrap, mirror, or twist.
+    fn from_str(name: &str) -> Result<Edge, TopologyError> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "bounded" => Ok(Edge::Bounded),
+            "wrap" => Ok(Edge::Wrap),
+            "mirror" => Ok(Edge::Mirror),
+            "twist" => Ok(Edge::Twist),
+            _ => Err(TopologyError {
+                name: name.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Edge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Edge::Bounded => write!(f, "bounded"),
+            Edge::Wrap => write!(f, "wrap"),
+            Edge::Mirror => write!(f, "mirror"),
+            Edge::Twist => write!(f, "twist"),
+        }
+    }
+}
+
+/// The topology of a [Grid](crate::Grid), joining its edges.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Topology {
+    /// All cells beyond the edges are dead.
+    #[default]
+    Bounded,
+
+    /// Both pairs of edges wrap around.
+    Torus,
+
+    /// Both pairs of edges mirror the cells on them.
+    Mirror,
+
+    /// The top and bottom edges wrap around, and the left and right edges
+    /// are twisted.
+    Klein,
+
+    /// Both pairs of edges are twisted.
+    CrossSurface,
+
+    /// An [Edge] rule chosen for each pair of edges.
+    PerAxis {
+        /// Rule of the top and bottom edges.
+        rows: Edge,
+
+        /// Rule of the left and right edges.
+        cols: Edge,
+    },
+}
+
+impl Topology {
+    /// Get the [Edge] rules of the top/bottom and left/right edges.
+    pub fn get_edges(self) -> (Edge, Edge) {
+        match self {
+            Topology::Bounded => (Edge::Bounded, Edge::Bounded),
+            Topology::Torus => (Edge::Wrap, Edge::Wrap),
+            Topology::Mirror => (Edge::Mirror, Edge::Mirror),
+            Topology::Klein => (Edge::Wrap, Edge::Twist),
+            Topology::CrossSurface => (Edge::Twist, Edge::Twist),
+            Topology::PerAxis { rows, cols } => (rows, cols),
+        }
+    }
+
+    /// Map the position of a neighbor, which may be beyond the edges of a
+    /// grid, to the (row, col) of the cell it refers to, or [None] if it is
+    /// dead.
+    ///
+    /// # Arguments
+    /// * `row` - Row of the neighbor.
+    /// * `col` - Column of the neighbor.
+    /// * `num_rows` - The number of rows (height) of the grid.
+    /// * `num_cols` - The number of columns (width) of the grid.
+    pub fn resolve(self, row: i64, col: i64, num_rows: u32, num_cols: u32) -> Option<(u32, u32)> {
+        let (rows, cols) = self.get_edges();
+        let (num_rows, num_cols) = (num_rows as i64, num_cols as i64);
+
+        let (row, flip_col) = rows.resolve(row, num_rows)?;
+        let col = if flip_col { num_cols - 1 - col } else { col };
+
+        let (col, flip_row) = cols.resolve(col, num_cols)?;
+        let row = if flip_row { num_rows - 1 - row } else { row };
+
+        Some((row as u32, col as u32))
+    }
+}
+
+impl FromStr for Topology {
+    type Err = TopologyError;
+
+    /// Parse a topology name (bounded, torus, mirror, klein, or
+    /// cross-surface), or a pair of [Edge] rules for the top/bottom and
+    /// left/right edges separated by a comma, e.g. `wrap,mirror`.
+    fn from_str(name: &str) -> Result<Topology, TopologyError> {
+        let error = || TopologyError {
+            name: name.to_string(),
+        };
+
+        if let Some((rows, cols)) = name.split_once(',') {
+            return match (rows.parse(), cols.parse()) {
+                (Ok(rows), Ok(cols)) => Ok(Topology::PerAxis { rows, cols }),
+                _ => Err(error()),
+            };
+        }
+
+        match name.trim().to_ascii_lowercase().as_str() {
+            "bounded" => Ok(Topology::Bounded),
+            "torus" => Ok(Topology::Torus),
+            "mirror" => Ok(Topology::Mirror),
+            "klein" => Ok(Topology::Klein),
+            "cross-surface" => Ok(Topology::CrossSurface),
+            _ => Err(error()),
+        }
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Topology::Bounded => write!(f, "bounded"),
+            Topology::Torus => write!(f, "torus"),
+            Topology::Mirror => write!(f, "mirror"),
+            Topology::Klein => write!(f, "klein"),
+            Topology::CrossSurface => write!(f, "cross-surface"),
+            Topology::PerAxis { rows, cols } => write!(f, "{},{}", rows, cols),
+        }
+    }
+}
+
+impl ConwaySim {
+    /// Set the [Topology] of the simulation's [Grid](crate::Grid), deciding
+    /// the neighbors of cells on its edges.
+    ///
+    /// # Arguments
+    /// * `topology` - The new [Topology] of the [Grid](crate::Grid).
+    pub fn set_topology(&mut self, topology: Topology) {
+        if self.get_grid().get_topology() != topology {
+            self.get_world_mut().set_topology(topology);
+            self.record_edit();
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! Geometric transformations of a [Grid].
+
+use crate::game::{ConwaySim, Grid};
+use crate::topology::Topology;
+
+/// Where the existing cells are placed when a [Grid] is resized.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Anchor {
+    /// Keep the cells centered, growing or shrinking evenly on all sides.
+    Center,
+
+    /// Keep the cells in the top left corner.
+    TopLeft,
+
+    /// Keep the cells in the top right corner.
+    TopRight,
+
+    /// Keep the cells in the bottom left corner.
+    BottomLeft,
+
+    /// Keep the cells in the bottom right corner.
+    BottomRight,
+}
+
+impl Anchor {
+    /// Get the offset added to a position along one axis when its length
+    /// changes from `old` to `new`.
+    ///
+    /// # Arguments
+    /// * `old` - The length before resizing.
+    /// * `new` - The length after resizing.
+    /// * `at_end` - Whether the anchor is at the bottom or right end of the
+    ///   axis rather than the top or left.
+    fn offset(self, old: u32, new: u32, at_end: bool) -> i64 {
+        let difference = new as i64 - old as i64;
+
+        match self {
+            Anchor::Center => difference / 2,
+            _ if at_end => difference,
+            _ => 0,
+        }
+    }
+}
+
+/// One of the eight rotations and reflections of a [Grid].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Transform {
+    /// Unchanged.
+    Identity,
+
+    /// Rotated a quarter turn clockwise.
+    Rotate90,
+
+    /// Rotated a half turn.
+    Rotate180,
+
+    /// Rotated a quarter turn counterclockwise.
+    Rotate270,
+
+    /// Mirrored left to right.
+    FlipHorizontal,
+
+    /// Mirrored top to bottom.
+    FlipVertical,
+
+    /// Mirrored across the main diagonal, so that rows become columns.
+    Transpose,
+
+    /// Mirrored across the anti-diagonal.
+    AntiTranspose,
+}
+
+impl Transform {
+    /// Every [Transform], starting with [Transform::Identity].
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::Transpose,
+        Transform::AntiTranspose,
+    ];
+}
+
+/// Get the [Topology] of a [Grid] whose rows have become its columns, with
+/// the [Edge](crate::Edge) rules of its two pairs of edges swapped.
+fn transposed(topology: Topology) -> Topology {
+    match topology.get_edges() {
+        (rows, cols) if rows == cols => topology,
+        (rows, cols) => Topology::PerAxis {
+            rows: cols,
+            cols: rows,
+        },
+    }
+}
+
+impl Grid {
+    /// Build a new [Grid] of the given size and this [Grid]'s [Topology],
+    /// copying each cell from the position of this [Grid] given by `source`.
+    fn remapped(
+        &self,
+        num_rows: u32,
+        num_cols: u32,
+        source: impl Fn(u32, u32) -> (u32, u32),
+    ) -> Grid {
+        let mut grid = Grid::new(num_rows, num_cols);
+        grid.set_topology(self.get_topology());
+
+        for row in 0..num_rows {
+            for col in 0..num_cols {
+                let (source_row, source_col) = source(row, col);
+                grid.set(row, col, self.get(source_row, source_col));
+            }
+        }
+
+        grid
+    }
+
+    /// Create a copy of the [Grid] rotated a quarter turn clockwise. The
+    /// rules of the two pairs of edges of its [Topology] are swapped with
+    /// them.
+    pub fn rotate90(&self) -> Grid {
+        let last_row = self.get_num_rows().saturating_sub(1);
+
+        let mut grid = self.remapped(self.get_num_cols(), self.get_num_rows(), |row, col| {
+            (last_row - col, row)
+        });
+        grid.set_topology(transposed(self.get_topology()));
+
+        grid
+    }
+
+    /// Create a copy of the [Grid] rotated a half turn.
+    pub fn rotate180(&self) -> Grid {
+        let last_row = self.get_num_rows().saturating_sub(1);
+        let last_col = self.get_num_cols().saturating_sub(1);
+
+        self.remapped(self.get_num_rows(), self.get_num_cols(), |row, col| {
+            (last_row - row, last_col - col)
+        })
+    }
+
+    /// Create a copy of the [Grid] mirrored left to right.
+    pub fn flip_horizontal(&self) -> Grid {
+        let last_col = self.get_num_cols().saturating_sub(1);
+
+        self.remapped(self.get_num_rows(), self.get_num_cols(), |row, col| {
+            (row, last_col - col)
+        })
+    }
+
+    /// Create a copy of the [Grid] mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Grid {
+        let last_row = self.get_num_rows().saturating_sub(1);
+
+        self.remapped(self.get_num_rows(), self.get_num_cols(), |row, col| {
+            (last_row - row, col)
+        })
+    }
+
+    /// Create a copy of the [Grid] mirrored across its main diagonal, so that
+    /// rows become columns. The rules of the two pairs of edges of its
+    /// [Topology] are swapped with them.
+    pub fn transpose(&self) -> Grid {
+        let mut grid = self.remapped(self.get_num_cols(), self.get_num_rows(), |row, col| {
+            (col, row)
+        });
+        grid.set_topology(transposed(self.get_topology()));
+
+        grid
+    }
+
+    /// Create a copy of the [Grid] with a [Transform] applied.
+    pub fn transformed(&self, transform: Transform) -> Grid {
+        match transform {
+            Transform::Identity => self.clone(),
+            Transform::Rotate90 => self.rotate90(),
+            Transform::Rotate180 => self.rotate180(),
+            Transform::Rotate270 => self.rotate180().rotate90(),
+            Transform::FlipHorizontal => self.flip_horizontal(),
+            Transform::FlipVertical => self.flip_vertical(),
+            Transform::Transpose => self.transpose(),
+            Transform::AntiTranspose => self.transpose().rotate180(),
+        }
+    }
+
+    /// Get the (min_row, min_col, max_row, max_col) bounding box of the live
+    /// cells, or [None] if there are none.
+    pub fn bounding_box(&self) -> Option<(u32, u32, u32, u32)> {
+        let mut bounds: Option<(u32, u32, u32, u32)> = None;
+
+        for row in 0..self.get_num_rows() {
+            for col in 0..self.get_num_cols() {
+                if !self.get(row, col).is_alive() {
+                    continue;
+                }
+
+                bounds = Some(match bounds {
+                    Some((min_row, min_col, max_row, max_col)) => (
+                        min_row.min(row),
+                        min_col.min(col),
+                        max_row.max(row),
+                        max_col.max(col),
+                    ),
+                    None => (row, col, row, col),
+                });
+            }
+        }
+
+        bounds
+    }
+
+    /// Create a copy of the [Grid] trimmed to the bounding box of its live
+    /// cells, or an empty 0x0 [Grid] if there are none, keeping its
+    /// [Topology].
+    pub fn cropped(&self) -> Grid {
+        match self.bounding_box() {
+            Some((min_row, min_col, max_row, max_col)) => {
+                self.remapped(max_row - min_row + 1, max_col - min_col + 1, |row, col| {
+                    (min_row + row, min_col + col)
+                })
+            }
+            None => {
+                let mut grid = Grid::new(0, 0);
+                grid.set_topology(self.get_topology());
+                grid
+            }
+        }
+    }
+
+    /// Create a copy of the [Grid] with a new size, keeping its cells where
+    /// the [Anchor] places them and its [Topology](crate::Topology). Growing
+    /// adds dead cells, while shrinking drops the cells which no longer fit.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the new [Grid].
+    /// * `num_cols` - The number of columns (width) of the new [Grid].
+    /// * `anchor` - Where the existing cells are placed.
+    pub fn resized(&self, num_rows: u32, num_cols: u32, anchor: Anchor) -> Grid {
+        let at_bottom = anchor == Anchor::BottomLeft || anchor == Anchor::BottomRight;
+        let at_right = anchor == Anchor::TopRight || anchor == Anchor::BottomRight;
+
+        let row_offset = anchor.offset(self.get_num_rows(), num_rows, at_bottom);
+        let col_offset = anchor.offset(self.get_num_cols(), num_cols, at_right);
+
+        let mut grid = Grid::new(num_rows, num_cols);
+        grid.set_topology(self.get_topology());
+
+        for row in 0..self.get_num_rows() {
+            for col in 0..self.get_num_cols() {
+                let (new_row, new_col) = (row as i64 + row_offset, col as i64 + col_offset);
+
+                if self.get(row, col).is_alive()
+                    && (0..num_rows as i64).contains(&new_row)
+                    && (0..num_cols as i64).contains(&new_col)
+                {
+                    grid.set(new_row as u32, new_col as u32, self.get(row, col));
+                }
+            }
+        }
+
+        grid
+    }
+}
+
+impl ConwaySim {
+    /// Grow or shrink the simulation's [Grid], as with [Grid::resized], for
+    /// example to give a pattern reaching the boundary more room mid-run.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The new number of rows (height) of the [Grid].
+    /// * `num_cols` - The new number of columns (width) of the [Grid].
+    /// * `anchor` - Where the existing cells are placed.
+    pub fn resize(&mut self, num_rows: u32, num_cols: u32, anchor: Anchor) {
+        let resized = self.get_grid().resized(num_rows, num_cols, anchor);
+        *self.get_world_mut() = resized;
+        self.record_edit();
+    }
+}
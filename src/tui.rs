@@ -0,0 +1,756 @@
+//! Interactive terminal user interface.
+
+use std::io::{self, Write};
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use gameoflife_rs::{ConwaySim, Pattern, Transform};
+use ratatui::crossterm::event::{
+    self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::crossterm::execute;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, Paragraph, Sparkline};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::clock::Clock;
+use crate::keymap::{self, Action, Key, Keymap};
+use crate::selection::{self, Selection};
+use crate::session::Session;
+use crate::timeline::{Entry, Timeline};
+
+/// Longest wait for input while paused, before drawing again.
+const PAUSED_POLL: Duration = Duration::from_millis(2000);
+
+/// Symbol of a live cell, two characters wide so cells are roughly square.
+const ALIVE_SYMBOL: &str = "██";
+
+/// Symbol of a dead cell.
+const DEAD_SYMBOL: &str = "  ";
+
+/// Height of the population graph, including its border.
+const GRAPH_HEIGHT: u16 = 7;
+
+/// Keys and descriptions of the cursor, which cannot be rebound, for the
+/// help overlay.
+const CURSOR_HELP: [(&str, &str); 2] = [
+    ("arrows/hjkl", "move the cursor"),
+    ("enter/t", "toggle the cell"),
+];
+
+/// Symbol of the timeline's track.
+const TRACK_SYMBOL: &str = "─";
+
+/// Symbol of a bookmark on the timeline.
+const BOOKMARK_SYMBOL: &str = "◆";
+
+/// Symbol of the timeline's scrubber, at the current generation.
+const SCRUBBER_SYMBOL: &str = "█";
+
+/// Copy a pattern to the system clipboard as RLE, through the OSC 52 escape
+/// sequence understood by most terminal emulators.
+fn copy_to_system(pattern: &Pattern) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(
+        stdout,
+        "\x1b]52;c;{}\x07",
+        STANDARD.encode(pattern.to_rle())
+    )?;
+
+    stdout.flush()
+}
+
+/// The visible rows and columns, cursor, and selection some lines of cells
+/// were built for.
+type LineView = (Range<u32>, Range<u32>, (u32, u32), Option<Selection>);
+
+/// The lines of cells last drawn, rebuilt only where the grid changed while
+/// the view stays the same.
+struct LineCache {
+    /// The view the lines were built for.
+    view: LineView,
+
+    /// The lines of cells, one per visible row.
+    lines: Vec<Line<'static>>,
+}
+
+/// State of the terminal user interface.
+struct App {
+    /// The simulation being explored.
+    sim: ConwaySim,
+
+    /// The session autosaving the simulation, if any.
+    session: Option<Session>,
+
+    /// Whether the simulation is paused.
+    paused: bool,
+
+    /// Schedule of the generations while playing.
+    clock: Clock,
+
+    /// The (row, col) of the editing cursor.
+    cursor: (u32, u32),
+
+    /// The selected cells, if any.
+    selection: Option<Selection>,
+
+    /// Whether moving the cursor extends the selection.
+    selecting: bool,
+
+    /// The cells last copied or cut.
+    clipboard: Option<Pattern>,
+
+    /// Whether the population graph is shown below the grid.
+    show_graph: bool,
+
+    /// The generations which can be returned to, and their bookmarks.
+    timeline: Timeline,
+
+    /// Area of the timeline's track last drawn, clicked to move along it.
+    track: Rect,
+
+    /// Whether the left mouse button was pressed on the timeline and is
+    /// still down, moving the scrubber along.
+    scrubbing: bool,
+
+    /// The keys bound to each action.
+    keymap: Keymap,
+
+    /// Whether the key bindings are shown over the grid.
+    show_help: bool,
+
+    /// Outcome of the last action to report in the status bar, if any.
+    message: Option<String>,
+
+    /// Whether the user has requested to quit.
+    quit: bool,
+
+    /// The lines of cells last drawn, if any.
+    lines: Option<LineCache>,
+}
+
+impl App {
+    /// Create a new, paused [App], recording the simulation's statistics for
+    /// the population graph.
+    fn new(mut sim: ConwaySim, session: Option<Session>, clock: Clock, keymap: Keymap) -> App {
+        if sim.get_stats().is_none() {
+            sim.enable_stats();
+        }
+        sim.enable_undo(keymap::UNDO_CAPACITY);
+        if sim.get_history().is_none() {
+            sim.enable_history(keymap::HISTORY_INTERVAL, keymap::HISTORY_CAPACITY);
+        }
+
+        let grid = sim.get_grid();
+        let cursor = (grid.get_num_rows() / 2, grid.get_num_cols() / 2);
+        let timeline = Timeline::new(&sim);
+
+        App {
+            sim,
+            session,
+            paused: true,
+            clock,
+            cursor,
+            selection: None,
+            selecting: false,
+            clipboard: None,
+            show_graph: true,
+            timeline,
+            track: Rect::default(),
+            scrubbing: false,
+            keymap,
+            show_help: false,
+            message: None,
+            quit: false,
+            lines: None,
+        }
+    }
+
+    /// Run the event loop until the user quits.
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        let mut last_tick = Instant::now();
+
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            // wait for input until the next generation is due
+            let timeout = if self.paused {
+                PAUSED_POLL
+            } else {
+                self.clock.until_next()
+            };
+
+            if event::poll(timeout)? {
+                match event::read()? {
+                    Event::Key(key) if key.kind == KeyEventKind::Press => {
+                        self.handle_key(key.code, key.modifiers);
+                    }
+                    Event::Paste(text) => self.paste_text(&text),
+                    Event::Mouse(mouse) => self.handle_mouse(mouse),
+                    _ => {}
+                }
+            }
+
+            let elapsed = last_tick.elapsed();
+            last_tick = Instant::now();
+
+            if self.paused {
+                self.clock.reset();
+            } else {
+                for _ in 0..self.clock.advance(elapsed) {
+                    self.sim.step();
+                }
+            }
+
+            self.timeline.observe(&self.sim);
+
+            if let Some(session) = &mut self.session {
+                if let Err(err) = session.autosave(&self.sim) {
+                    self.message = Some(format!("Unable to autosave: {}", err));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Perform an action bound to a key.
+    fn perform(&mut self, action: Action) {
+        match action {
+            Action::Pause => self.paused = !self.paused,
+            Action::Step => {
+                self.paused = true;
+                self.sim.step();
+            }
+            Action::StepBack => {
+                self.paused = true;
+
+                if self.sim.get_generation() == 0 {
+                    self.message = Some("At the first generation".to_string());
+                } else if let Err(err) = self.sim.step_backward() {
+                    self.message = Some(format!("Unable to step back: {}", err));
+                }
+            }
+            Action::ScrubBack | Action::ScrubForward => {
+                self.paused = true;
+
+                let forward = action == Action::ScrubForward;
+                if let Err(err) = self.timeline.scrub(&mut self.sim, forward) {
+                    self.message = Some(format!("Unable to scrub: {}", err));
+                }
+            }
+            Action::GoTo => self.timeline.start_entry(),
+            Action::Bookmark => {
+                let generation = self.sim.get_generation();
+
+                self.message = Some(if self.timeline.toggle_bookmark(generation) {
+                    format!("Bookmarked generation {}", generation)
+                } else {
+                    format!("Removed the bookmark of generation {}", generation)
+                });
+            }
+            Action::PreviousBookmark => {
+                match self.timeline.previous_bookmark(self.sim.get_generation()) {
+                    Some(generation) => self.go_to(generation),
+                    None => self.message = Some("No earlier bookmark".to_string()),
+                }
+            }
+            Action::NextBookmark => match self.timeline.next_bookmark(self.sim.get_generation()) {
+                Some(generation) => self.go_to(generation),
+                None => self.message = Some("No later bookmark".to_string()),
+            },
+            Action::Faster => self.clock.faster(),
+            Action::Slower => self.clock.slower(),
+            Action::Clear => {
+                self.sim.begin_edit();
+                self.sim.clear();
+            }
+            Action::Randomize => {
+                self.sim.begin_edit();
+                keymap::randomize(&mut self.sim);
+            }
+            Action::Undo => {
+                if !self.sim.undo() {
+                    self.message = Some("Nothing to undo".to_string());
+                }
+            }
+            Action::Redo => {
+                if !self.sim.redo() {
+                    self.message = Some("Nothing to redo".to_string());
+                }
+            }
+            Action::Select => {
+                self.selection = match self.selection {
+                    Some(_) => None,
+                    None => Some(Selection::new(self.cursor.0, self.cursor.1)),
+                };
+                self.selecting = self.selection.is_some();
+            }
+            Action::Copy | Action::Cut | Action::Rotate | Action::Flip => {
+                self.selecting = false;
+
+                match self.selection {
+                    Some(selected) => self.edit_selection(action, &selected),
+                    None => self.message = Some("Nothing selected".to_string()),
+                }
+            }
+            Action::Paste => match &self.clipboard {
+                Some(pattern) => {
+                    let (row, col) = self.cursor;
+                    self.sim.begin_edit();
+                    self.selection = Some(selection::paste(&mut self.sim, pattern, row, col));
+                    self.selecting = false;
+                }
+                None => self.message = Some("Nothing to paste".to_string()),
+            },
+            Action::Save => {
+                self.message = Some(match keymap::save(&self.sim) {
+                    Ok(path) => format!("Saved {}", path.display()),
+                    Err(err) => format!("Unable to save: {}", err),
+                });
+            }
+            Action::Graph => self.show_graph = !self.show_graph,
+            Action::Fit => {
+                // the grid scrolls to the cursor, so move it to the middle
+                // of the live cells
+                if let Some((min_row, min_col, max_row, max_col)) =
+                    self.sim.get_grid().bounding_box()
+                {
+                    self.cursor = ((min_row + max_row) / 2, (min_col + max_col) / 2);
+                }
+            }
+            // the whole grid does not fit the terminal, so there is no minimap
+            Action::Minimap => {}
+            Action::Help => self.show_help = !self.show_help,
+            Action::Quit => self.quit = true,
+        }
+    }
+
+    /// Pause and move to a generation of the timeline, reporting if it
+    /// cannot be reached.
+    fn go_to(&mut self, generation: u32) {
+        self.paused = true;
+
+        if let Err(err) = self.timeline.seek(&mut self.sim, generation) {
+            self.message = Some(format!(
+                "Unable to go to generation {}: {}",
+                generation, err
+            ));
+        }
+    }
+
+    /// Copy, cut, rotate, or flip the selected cells.
+    fn edit_selection(&mut self, action: Action, selected: &Selection) {
+        match action {
+            Action::Copy | Action::Cut => {
+                let pattern = if action == Action::Cut {
+                    self.sim.begin_edit();
+                    selection::cut(&mut self.sim, selected)
+                } else {
+                    selection::copy(&self.sim, selected)
+                };
+
+                self.message = Some(match copy_to_system(&pattern) {
+                    Ok(()) => format!(
+                        "Copied {}x{} as RLE",
+                        pattern.get_num_cols(),
+                        pattern.get_num_rows()
+                    ),
+                    Err(err) => format!("Unable to copy to the clipboard: {}", err),
+                });
+                self.clipboard = Some(pattern);
+            }
+            Action::Rotate => {
+                self.sim.begin_edit();
+                self.selection = Some(selection::transform(
+                    &mut self.sim,
+                    selected,
+                    Transform::Rotate90,
+                ));
+            }
+            Action::Flip => {
+                self.sim.begin_edit();
+                self.selection = Some(selection::transform(
+                    &mut self.sim,
+                    selected,
+                    Transform::FlipHorizontal,
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    /// Paste RLE text pasted into the terminal at the cursor, keeping it as
+    /// the clipboard.
+    fn paste_text(&mut self, text: &str) {
+        self.message = None;
+
+        match Pattern::from_rle(text) {
+            Ok(pattern) => {
+                let (row, col) = self.cursor;
+                self.sim.begin_edit();
+                self.selection = Some(selection::paste(&mut self.sim, &pattern, row, col));
+                self.selecting = false;
+                self.clipboard = Some(pattern);
+            }
+            Err(err) => self.message = Some(format!("Unable to paste: {}", err)),
+        }
+    }
+
+    /// Handle a key press, performing its action or otherwise moving the
+    /// cursor or toggling the cell under it.
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        self.message = None;
+
+        let key = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Key::Ctrl(c.to_ascii_lowercase()))
+            }
+            KeyCode::Char(c) => Some(Key::from(c)),
+            KeyCode::Enter => Some(Key::Enter),
+            KeyCode::Esc => Some(Key::Esc),
+            KeyCode::Tab => Some(Key::Tab),
+            KeyCode::Backspace => Some(Key::Backspace),
+            KeyCode::Up => Some(Key::Up),
+            KeyCode::Down => Some(Key::Down),
+            KeyCode::Left => Some(Key::Left),
+            KeyCode::Right => Some(Key::Right),
+            _ => None,
+        };
+
+        // keys are typed into the generation entry while it is open
+        if self.timeline.get_entry().is_some() {
+            if let Some(Entry::Submitted(generation)) = key.map(|key| self.timeline.type_key(key)) {
+                self.go_to(generation);
+            }
+            return;
+        }
+
+        if let Some(action) = key.and_then(|key| self.keymap.get_action(key)) {
+            self.perform(action);
+            return;
+        }
+
+        let grid = self.sim.get_grid();
+        let (max_row, max_col) = (
+            grid.get_num_rows().saturating_sub(1),
+            grid.get_num_cols().saturating_sub(1),
+        );
+        let (row, col) = self.cursor;
+
+        match code {
+            // cursor movement
+            KeyCode::Up | KeyCode::Char('k') => self.cursor = (row.saturating_sub(1), col),
+            KeyCode::Down | KeyCode::Char('j') => self.cursor = ((row + 1).min(max_row), col),
+            KeyCode::Left | KeyCode::Char('h') => self.cursor = (row, col.saturating_sub(1)),
+            KeyCode::Right | KeyCode::Char('l') => self.cursor = (row, (col + 1).min(max_col)),
+
+            // editing
+            KeyCode::Enter | KeyCode::Char('t') => {
+                self.sim.begin_edit();
+                self.sim.toggle_cell(row, col);
+            }
+
+            _ => {}
+        }
+
+        if let (Some(selection), true) = (self.selection.as_mut(), self.selecting) {
+            selection.extend_to(self.cursor.0, self.cursor.1);
+        }
+    }
+
+    /// Handle a mouse event, moving to the generation clicked on the
+    /// timeline and following the mouse while dragging.
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let track = self.track;
+        let on_track =
+            mouse.row == track.y && mouse.column >= track.x && mouse.column < track.x + track.width;
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.scrubbing = on_track,
+            MouseEventKind::Drag(MouseButton::Left) => {}
+            _ => {
+                self.scrubbing = false;
+                return;
+            }
+        }
+
+        if self.scrubbing {
+            let column = mouse.column.clamp(track.x, track.x + track.width - 1) - track.x;
+            let fraction = column as f32 / track.width.saturating_sub(1).max(1) as f32;
+
+            self.message = None;
+            self.go_to(self.timeline.generation_at(&self.sim, fraction));
+        }
+    }
+
+    /// Draw the grid, population graph, timeline, and status bar.
+    fn draw(&mut self, frame: &mut Frame) {
+        let graph_height = if self.show_graph { GRAPH_HEIGHT } else { 0 };
+        let [grid_area, graph_area, timeline_area, status_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(graph_height),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let block = Block::bordered().title(format!(" Generation {} ", self.sim.get_generation()));
+        let inner = block.inner(grid_area);
+
+        frame.render_widget(
+            Paragraph::new(self.grid_lines(inner)).block(block),
+            grid_area,
+        );
+
+        if self.show_graph {
+            self.draw_graph(frame, graph_area);
+        }
+
+        self.draw_timeline(frame, timeline_area);
+
+        let state = if self.paused { "paused" } else { "playing" };
+        let hint = match (self.timeline.get_entry(), &self.message) {
+            (Some(digits), _) => format!("Go to generation: {}_", digits),
+            (None, Some(message)) => message.clone(),
+            (None, None) => format!("[{}] keys", self.keymap.describe_keys(Action::Help)),
+        };
+        let selected = match &self.selection {
+            Some(selection) => format!(
+                " | selected {}x{}",
+                selection.get_num_cols(),
+                selection.get_num_rows()
+            ),
+            None => String::new(),
+        };
+        let status = format!(
+            " {} | population {} | {}{} | {}",
+            state,
+            self.sim.get_grid().get_population(),
+            self.clock,
+            selected,
+            hint
+        );
+
+        frame.render_widget(Paragraph::new(status), status_area);
+
+        if self.show_help {
+            self.draw_help(frame);
+        }
+    }
+
+    /// Draw the key bound to each action in a box over the grid.
+    fn draw_help(&self, frame: &mut Frame) {
+        let entries = self.keymap.help_entries();
+        let entries = entries
+            .iter()
+            .map(|(keys, description)| (keys.as_str(), *description))
+            .chain(CURSOR_HELP.iter().copied());
+
+        let lines: Vec<Line> = entries
+            .map(|(keys, description)| Line::from(format!(" {:>12}  {} ", keys, description)))
+            .collect();
+
+        let area = frame.area();
+        let width = (lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 2).min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+
+        frame.render_widget(Clear, popup);
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(" Keys ")),
+            popup,
+        );
+    }
+
+    /// Draw a sparkline of the population of the most recent generations
+    /// which fit within `area`.
+    fn draw_graph(&self, frame: &mut Frame, area: Rect) {
+        let stats = match self.sim.get_stats() {
+            Some(stats) => stats,
+            None => return,
+        };
+
+        let block =
+            Block::bordered().title(format!(" Population (peak {}) ", stats.peak_population()));
+        let populations = stats.recent_populations(block.inner(area).width as usize);
+
+        frame.render_widget(Sparkline::default().block(block).data(&populations), area);
+    }
+
+    /// Draw the timeline of the generations which can be returned to within
+    /// the single row of `area`, between its first and last generations,
+    /// with its bookmarks and a scrubber at the current generation.
+    fn draw_timeline(&mut self, frame: &mut Frame, area: Rect) {
+        let range = self.timeline.get_range(&self.sim);
+        let start = format!(" {} ", range.start());
+        let end = format!(" {} ", range.end());
+
+        let width = area.width.saturating_sub((start.len() + end.len()) as u16);
+        self.track = Rect::new(area.x + start.len() as u16, area.y, width, area.height);
+        if width == 0 || area.height == 0 {
+            return;
+        }
+
+        let column = |generation: u32| {
+            let fraction = self.timeline.fraction_of(&self.sim, generation);
+            (fraction * (width - 1) as f32).round() as usize
+        };
+
+        let mut symbols =
+            vec![(TRACK_SYMBOL, Style::default().fg(Color::DarkGray)); width as usize];
+        for &bookmark in self.timeline.get_bookmarks() {
+            if range.contains(&bookmark) {
+                symbols[column(bookmark)] = (BOOKMARK_SYMBOL, Style::default().fg(Color::Yellow));
+            }
+        }
+        symbols[column(self.sim.get_generation())].0 = SCRUBBER_SYMBOL;
+
+        let spans: Vec<Span> = std::iter::once(Span::raw(start))
+            .chain(
+                symbols
+                    .into_iter()
+                    .map(|(symbol, style)| Span::styled(symbol, style)),
+            )
+            .chain(std::iter::once(Span::raw(end)))
+            .collect();
+
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    /// Build the lines of cells visible within `area`, scrolled to keep the
+    /// cursor in view.
+    ///
+    /// While the view is unchanged, only the rows within the grid's dirty
+    /// regions are rebuilt.
+    fn grid_lines(&mut self, area: Rect) -> Vec<Line<'static>> {
+        let grid = self.sim.get_grid();
+
+        let visible_rows = (area.height as u32).max(1);
+        let visible_cols = (area.width as u32 / 2).max(1);
+
+        let (cursor_row, cursor_col) = self.cursor;
+        let row_offset = (cursor_row + 1).saturating_sub(visible_rows);
+        let col_offset = (cursor_col + 1).saturating_sub(visible_cols);
+
+        let last_row = grid.get_num_rows().min(row_offset + visible_rows);
+        let last_col = grid.get_num_cols().min(col_offset + visible_cols);
+
+        let view = (
+            row_offset..last_row,
+            col_offset..last_col,
+            self.cursor,
+            self.selection,
+        );
+
+        match self.lines.take() {
+            Some(mut cache) if cache.view == view => {
+                for region in grid.dirty_regions() {
+                    let (row, col) = region.get_origin();
+                    let end_col = col + region.get_num_cols();
+                    if end_col <= col_offset || col >= last_col {
+                        continue;
+                    }
+
+                    let start_row = row.max(row_offset);
+                    let end_row = (row + region.get_num_rows()).min(last_row);
+                    for row in start_row..end_row {
+                        cache.lines[(row - row_offset) as usize] =
+                            self.grid_line(row, col_offset..last_col);
+                    }
+                }
+
+                self.lines = Some(cache);
+            }
+            _ => {
+                let lines = (row_offset..last_row)
+                    .map(|row| self.grid_line(row, col_offset..last_col))
+                    .collect();
+
+                self.lines = Some(LineCache { view, lines });
+            }
+        }
+
+        self.sim.clear_dirty();
+
+        self.lines
+            .as_ref()
+            .map_or_else(Vec::new, |cache| cache.lines.clone())
+    }
+
+    /// Build the line of cells of a row within a range of columns.
+    fn grid_line(&self, row: u32, cols: Range<u32>) -> Line<'static> {
+        let grid = self.sim.get_grid();
+
+        let spans: Vec<Span> = cols
+            .map(|col| {
+                let symbol = if grid.get(row, col).is_alive() {
+                    ALIVE_SYMBOL
+                } else {
+                    DEAD_SYMBOL
+                };
+
+                let selected = self
+                    .selection
+                    .is_some_and(|selection| selection.contains(row, col));
+
+                if (row, col) == self.cursor {
+                    Span::styled(symbol, Style::default().add_modifier(Modifier::REVERSED))
+                } else if selected {
+                    Span::styled(
+                        symbol,
+                        Style::default().fg(Color::Yellow).bg(Color::DarkGray),
+                    )
+                } else {
+                    Span::raw(symbol)
+                }
+            })
+            .collect();
+
+        Line::from(spans)
+    }
+}
+
+/// Explore a simulation interactively in the terminal until the user quits.
+///
+/// # Arguments
+/// * `sim` - The simulation to explore, initially paused.
+/// * `session` - The session autosaving the simulation as it runs and
+///   saving it when the user quits, if any.
+/// * `clock` - Schedule of the generations while playing.
+/// * `keymap` - The keys bound to each action.
+pub fn run(
+    sim: ConwaySim,
+    session: Option<Session>,
+    clock: Clock,
+    keymap: Keymap,
+) -> io::Result<()> {
+    let mut app = App::new(sim, session, clock, keymap);
+
+    // pasting into the terminal sends the text at once rather than as keys,
+    // and the mouse moves along the timeline
+    let mut terminal = ratatui::init();
+    let result = execute!(io::stdout(), EnableBracketedPaste, EnableMouseCapture)
+        .and_then(|()| app.run(&mut terminal))
+        .and(execute!(
+            io::stdout(),
+            DisableMouseCapture,
+            DisableBracketedPaste
+        ));
+    ratatui::restore();
+
+    match &mut app.session {
+        Some(session) => result.and(session.save(&app.sim).map_err(io::Error::other)),
+        None => result,
+    }
+}
@@ -0,0 +1,87 @@
+//! Undo and redo of edits made to a simulation, separate from rewinding its
+//! generations with a [History](crate::History).
+//!
+//! Before each edit, such as toggling a cell, stamping a pattern, or a whole
+//! stroke of painted cells, [ConwaySim::begin_edit] saves the state of the
+//! simulation. [ConwaySim::undo] restores the state saved before the latest
+//! edit, and [ConwaySim::redo] returns to the state undone.
+//!
+//! [ConwaySim::begin_edit]: crate::ConwaySim::begin_edit
+//! [ConwaySim::undo]: crate::ConwaySim::undo
+//! [ConwaySim::redo]: crate::ConwaySim::redo
+
+use alloc::vec::Vec;
+
+use crate::history::Snapshot;
+
+/// The states saved before recent edits of a simulation, and the states
+/// undone since the latest edit.
+#[derive(Clone, Debug)]
+pub struct UndoStack<W> {
+    /// Maximum number of edits which can be undone, the oldest are
+    /// discarded first.
+    capacity: usize,
+
+    /// The states to undo back to, the latest last.
+    undo: Vec<Snapshot<W>>,
+
+    /// The undone states to redo, the latest undone last.
+    redo: Vec<Snapshot<W>>,
+}
+
+impl<W> UndoStack<W> {
+    /// Create a new, empty [UndoStack].
+    ///
+    /// # Arguments
+    /// * `capacity` - Maximum number of edits which can be undone (at least
+    ///   1).
+    pub(crate) fn new(capacity: usize) -> UndoStack<W> {
+        UndoStack {
+            capacity: capacity.max(1),
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    /// Get the maximum number of edits which can be undone.
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get the number of edits which can be undone.
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// Get the number of undone edits which can be redone.
+    pub fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+
+    /// Save the state before an edit, discarding the undone states as the
+    /// edit starts a new line of edits.
+    pub(crate) fn push(&mut self, snapshot: Snapshot<W>) {
+        self.redo.clear();
+        self.undo.push(snapshot);
+
+        if self.undo.len() > self.capacity {
+            self.undo.remove(0);
+        }
+    }
+
+    /// Take the state to undo back to, saving the current state to redo.
+    pub(crate) fn undo(&mut self, current: Snapshot<W>) -> Option<Snapshot<W>> {
+        let snapshot = self.undo.pop()?;
+        self.redo.push(current);
+
+        Some(snapshot)
+    }
+
+    /// Take the latest undone state, saving the current state to undo.
+    pub(crate) fn redo(&mut self, current: Snapshot<W>) -> Option<Snapshot<W>> {
+        let snapshot = self.redo.pop()?;
+        self.undo.push(current);
+
+        Some(snapshot)
+    }
+}
@@ -0,0 +1,174 @@
+//! Borrowed rectangular windows into a [Grid].
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::game::{Cell, Grid, GridError};
+
+/// A borrowed rectangular region of a [Grid], addressed relative to its top
+/// left cell, for rendering or analyzing part of a board without copying it.
+#[derive(Copy, Clone, Debug)]
+pub struct GridView<'a> {
+    /// The viewed [Grid].
+    grid: &'a Grid,
+
+    /// Row of the [Grid] at the top of the view.
+    row: u32,
+
+    /// Column of the [Grid] at the left of the view.
+    col: u32,
+
+    /// Number of rows (height) of the view.
+    num_rows: u32,
+
+    /// Number of columns (width) of the view.
+    num_cols: u32,
+}
+
+impl Grid {
+    /// Get a view of a rectangular region of the [Grid].
+    ///
+    /// The region is clipped to the [Grid], so it may be smaller than
+    /// requested, or empty if it starts outside of the [Grid].
+    ///
+    /// # Arguments
+    /// * `row` - Row of the region's top left cell.
+    /// * `col` - Column of the region's top left cell.
+    /// * `num_rows` - The number of rows (height) of the region.
+    /// * `num_cols` - The number of columns (width) of the region.
+    pub fn region(&self, row: u32, col: u32, num_rows: u32, num_cols: u32) -> GridView<'_> {
+        let row = row.min(self.get_num_rows());
+        let col = col.min(self.get_num_cols());
+
+        GridView {
+            grid: self,
+            row,
+            col,
+            num_rows: num_rows.min(self.get_num_rows() - row),
+            num_cols: num_cols.min(self.get_num_cols() - col),
+        }
+    }
+}
+
+impl<'a> GridView<'a> {
+    /// Get the (row, col) of the view's top left cell within the [Grid].
+    pub fn get_origin(&self) -> (u32, u32) {
+        (self.row, self.col)
+    }
+
+    /// Get the number of rows (height) of the view.
+    pub fn get_num_rows(&self) -> u32 {
+        self.num_rows
+    }
+
+    /// Get the number of columns (width) of the view.
+    pub fn get_num_cols(&self) -> u32 {
+        self.num_cols
+    }
+
+    /// Get a specified [Cell] of the view, relative to its top left cell.
+    ///
+    /// # Panics
+    /// Panics if the (row, col) position is outside of the view.
+    pub fn get(&self, row: u32, col: u32) -> Cell {
+        match self.try_get(row, col) {
+            Ok(cell) => cell,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Get a specified [Cell] of the view, relative to its top left cell, or
+    /// a [GridError] if it is outside of the view.
+    pub fn try_get(&self, row: u32, col: u32) -> Result<Cell, GridError> {
+        if row < self.num_rows && col < self.num_cols {
+            Ok(self.grid.get(self.row + row, self.col + col))
+        } else {
+            Err(GridError::OutOfBounds {
+                row,
+                col,
+                num_rows: self.num_rows,
+                num_cols: self.num_cols,
+            })
+        }
+    }
+
+    /// Iterate over the rows of the view, each as a slice of its cells.
+    pub fn rows(&self) -> impl Iterator<Item = &'a [Cell]> + '_ {
+        let width = self.grid.get_num_cols() as usize;
+        let (start, end) = (self.col as usize, (self.col + self.num_cols) as usize);
+        let cells = self.grid.get_cells();
+
+        (self.row..self.row + self.num_rows).map(move |row| {
+            let offset = row as usize * width;
+            &cells[offset + start..offset + end]
+        })
+    }
+
+    /// Iterate over the cells of the view in row-major order, as tuples
+    /// (row, col, state) relative to its top left cell.
+    pub fn cells(&self) -> impl Iterator<Item = (u32, u32, Cell)> + '_ {
+        self.rows().enumerate().flat_map(|(row, line)| {
+            line.iter()
+                .enumerate()
+                .map(move |(col, &state)| (row as u32, col as u32, state))
+        })
+    }
+
+    /// Get the (row, col) of each live cell of the view, relative to its top
+    /// left cell.
+    pub fn live_cells(&self) -> Vec<(u32, u32)> {
+        self.cells()
+            .filter(|&(_, _, state)| state.is_alive())
+            .map(|(row, col, _)| (row, col))
+            .collect()
+    }
+
+    /// Get the number of [Cell::Alive] cells of the view.
+    pub fn get_population(&self) -> u64 {
+        self.rows()
+            .map(|line| line.iter().filter(|cell| cell.is_alive()).count() as u64)
+            .sum()
+    }
+
+    /// Get a view of a rectangular region of this view, clipped to it.
+    ///
+    /// # Arguments
+    /// * `row` - Row of the region's top left cell, relative to this view.
+    /// * `col` - Column of the region's top left cell, relative to this view.
+    /// * `num_rows` - The number of rows (height) of the region.
+    /// * `num_cols` - The number of columns (width) of the region.
+    pub fn region(&self, row: u32, col: u32, num_rows: u32, num_cols: u32) -> GridView<'a> {
+        let row = row.min(self.num_rows);
+        let col = col.min(self.num_cols);
+
+        GridView {
+            grid: self.grid,
+            row: self.row + row,
+            col: self.col + col,
+            num_rows: num_rows.min(self.num_rows - row),
+            num_cols: num_cols.min(self.num_cols - col),
+        }
+    }
+
+    /// Copy the cells of the view into a new [Grid].
+    pub fn to_grid(&self) -> Grid {
+        let mut grid = Grid::new(self.num_rows, self.num_cols);
+        grid.set_cells(&self.live_cells());
+
+        grid
+    }
+}
+
+impl fmt::Display for GridView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in self.rows() {
+            for &cell in line {
+                let symbol = if cell == Cell::Dead { '◻' } else { '◼' };
+                write!(f, "{}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
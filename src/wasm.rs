@@ -0,0 +1,94 @@
+//! WebAssembly bindings for driving a simulation from JavaScript.
+//!
+//! The cells of the [Universe] live in the WebAssembly linear memory and can
+//! be read without copying through [Universe::cells_ptr], one byte per cell
+//! in row-major order (0 when dead, 1 when alive). See `www/` for an HTML
+//! canvas frontend.
+
+use wasm_bindgen::prelude::*;
+
+use crate::game::{Cell, ConwaySim, Grid};
+use crate::rule::{Rule, RuleError};
+
+/// A simulation exposed to JavaScript.
+#[wasm_bindgen]
+pub struct Universe {
+    /// The simulation being driven.
+    sim: ConwaySim,
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Create a new [Universe] of dead cells.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the grid.
+    /// * `num_cols` - The number of columns (width) of the grid.
+    #[wasm_bindgen(constructor)]
+    pub fn new(num_rows: u32, num_cols: u32) -> Universe {
+        Universe {
+            sim: ConwaySim::new(num_rows, num_cols),
+        }
+    }
+
+    /// Create a new [Universe] filled with a random soup.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the grid.
+    /// * `num_cols` - The number of columns (width) of the grid.
+    /// * `density` - Probability of each cell being alive, from 0.0 to 1.0.
+    /// * `seed` - Seed of the random number generator.
+    pub fn random(num_rows: u32, num_cols: u32, density: f64, seed: u64) -> Universe {
+        Universe {
+            sim: ConwaySim::new_with_grid(Grid::random(num_rows, num_cols, density, seed)),
+        }
+    }
+
+    /// Get the number of columns of the grid.
+    pub fn width(&self) -> u32 {
+        self.sim.get_grid().get_num_cols()
+    }
+
+    /// Get the number of rows of the grid.
+    pub fn height(&self) -> u32 {
+        self.sim.get_grid().get_num_rows()
+    }
+
+    /// Get a pointer to the cells in the WebAssembly memory.
+    ///
+    /// The cells move every generation, so the pointer must be fetched again
+    /// after each [Universe::tick].
+    pub fn cells_ptr(&self) -> *const Cell {
+        self.sim.get_grid().get_cells().as_ptr()
+    }
+
+    /// Get the simulation's current generation.
+    pub fn generation(&self) -> u32 {
+        self.sim.get_generation()
+    }
+
+    /// Get the number of live cells.
+    pub fn population(&self) -> u64 {
+        self.sim.get_population()
+    }
+
+    /// Advance the simulation by one generation.
+    pub fn tick(&mut self) {
+        self.sim.step();
+    }
+
+    /// Toggle a cell between dead and alive.
+    pub fn toggle_cell(&mut self, row: u32, col: u32) {
+        self.sim.toggle_cell(row, col);
+    }
+
+    /// Set the rule from a rulestring in B/S notation, e.g. `"B36/S23"`.
+    pub fn set_rule(&mut self, rulestring: &str) -> Result<(), JsValue> {
+        let rule: Rule = rulestring
+            .parse()
+            .map_err(|err: RuleError| JsValue::from_str(&err.to_string()))?;
+        self.sim.set_rule(rule);
+
+        Ok(())
+    }
+}
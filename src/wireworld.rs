@@ -0,0 +1,202 @@
+//! Wireworld, a cellular automaton for simulating digital logic circuits.
+//!
+//! Each cell is empty, a conductor, an electron head, or an electron tail.
+//! Every generation an electron head becomes a tail, a tail becomes a
+//! conductor, and a conductor becomes a head if exactly one or two of its
+//! neighbors are heads, so electrons travel along wires of conductors.
+//!
+//! States are numbered as in Golly, so Wireworld RLE files saved by Golly
+//! load directly.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::render::Rgb;
+use crate::rle::{self, RleError};
+use crate::states::StateGrid;
+
+/// State of an empty cell.
+pub const EMPTY: u8 = 0;
+
+/// State of an electron head.
+pub const HEAD: u8 = 1;
+
+/// State of an electron tail.
+pub const TAIL: u8 = 2;
+
+/// State of a conductor.
+pub const CONDUCTOR: u8 = 3;
+
+/// Get the next state of a cell.
+///
+/// # Arguments
+/// * `state` - The current state of the cell.
+/// * `head_count` - The number of neighbors which are electron heads.
+pub fn next_state(state: u8, head_count: u8) -> u8 {
+    match state {
+        HEAD => TAIL,
+        TAIL => CONDUCTOR,
+        CONDUCTOR if head_count == 1 || head_count == 2 => HEAD,
+        CONDUCTOR => CONDUCTOR,
+        _ => EMPTY,
+    }
+}
+
+/// Build the palette of colors indexed by Wireworld state, for
+/// [AnsiRenderer::render_states](crate::render::ansi::AnsiRenderer::render_states).
+pub fn palette() -> Vec<Rgb> {
+    vec![
+        Rgb::DARK_GREY,
+        Rgb(40, 90, 255),
+        Rgb(255, 60, 20),
+        Rgb(230, 180, 40),
+    ]
+}
+
+/// Simulation of Wireworld on a bounded [StateGrid].
+#[derive(Clone, Debug)]
+pub struct WireworldSim {
+    /// The cell states.
+    grid: StateGrid,
+
+    /// Buffer the next generation is computed into.
+    next: StateGrid,
+
+    /// The simulation's current generation.
+    generation: u32,
+}
+
+impl WireworldSim {
+    /// Create a new simulation of empty cells.
+    ///
+    /// # Arguments
+    /// * `num_rows` - The number of rows (height) of the grid.
+    /// * `num_cols` - The number of columns (width) of the grid.
+    pub fn new(num_rows: u32, num_cols: u32) -> WireworldSim {
+        WireworldSim::new_with_grid(StateGrid::new(num_rows, num_cols))
+    }
+
+    /// Create a new simulation from existing cell states. States other than
+    /// the four Wireworld states are emptied.
+    ///
+    /// # Arguments
+    /// * `grid` - The initial cell states.
+    pub fn new_with_grid(mut grid: StateGrid) -> WireworldSim {
+        for row in 0..grid.get_num_rows() {
+            for col in 0..grid.get_num_cols() {
+                if grid.get(row, col) > CONDUCTOR {
+                    grid.set(row, col, EMPTY);
+                }
+            }
+        }
+
+        WireworldSim {
+            next: grid.clone(),
+            grid,
+            generation: 0,
+        }
+    }
+
+    /// Create a new simulation from a circuit in the multi-state RLE format.
+    ///
+    /// # Arguments
+    /// * `rle` - The RLE encoded circuit.
+    pub fn from_rle(rle: &str) -> Result<WireworldSim, RleError> {
+        StateGrid::from_rle(rle).map(WireworldSim::new_with_grid)
+    }
+
+    /// Create a new simulation from an RLE circuit file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the RLE circuit file.
+    pub fn load_rle_file<P: AsRef<Path>>(path: P) -> Result<WireworldSim, RleError> {
+        let rle = fs::read_to_string(path)?;
+        let (grid, _rule) = rle::parse_states(&rle)?;
+
+        Ok(WireworldSim::new_with_grid(grid))
+    }
+
+    /// Get the simulation's cell states.
+    pub fn get_grid(&self) -> &StateGrid {
+        &self.grid
+    }
+
+    /// Get the simulation's current generation.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Get the number of electron heads.
+    pub fn get_num_electrons(&self) -> u64 {
+        self.grid.count(HEAD)
+    }
+
+    /// Set the state of a specified cell. States other than the four
+    /// Wireworld states empty the cell.
+    pub fn set_cell(&mut self, row: u32, col: u32, state: u8) {
+        let state = if state > CONDUCTOR { EMPTY } else { state };
+        self.grid.set(row, col, state);
+    }
+
+    /// Set the specified cells to a state, e.g. to lay a wire of
+    /// [CONDUCTOR] cells.
+    ///
+    /// # Arguments
+    /// * `cells` - Array slice of tuples (row, col) to set.
+    /// * `state` - The state to set the cells to.
+    pub fn set_cells(&mut self, cells: &[(u32, u32)], state: u8) {
+        for &(row, col) in cells {
+            self.set_cell(row, col, state);
+        }
+    }
+
+    /// Advance the simulation by one generation.
+    pub fn step(&mut self) {
+        self.generation += 1;
+
+        let grid = &self.grid;
+        grid.map_into(&mut self.next, |row, col, state| {
+            let head_count = if state == CONDUCTOR {
+                grid.count_neighbors(row, col, |neighbor| neighbor == HEAD)
+            } else {
+                // only conductors depend on their neighbors
+                0
+            };
+
+            next_state(state, head_count)
+        });
+
+        std::mem::swap(&mut self.grid, &mut self.next);
+    }
+
+    /// Advance the simulation by `n` generations.
+    pub fn step_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+}
+
+impl fmt::Display for WireworldSim {
+    /// Display empty cells as ' ', conductors as '#', electron heads as '@',
+    /// and electron tails as '~'.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let num_cols = self.grid.get_num_cols().max(1) as usize;
+
+        for line in self.grid.get_states().chunks(num_cols) {
+            for &state in line {
+                let symbol = match state {
+                    HEAD => '@',
+                    TAIL => '~',
+                    CONDUCTOR => '#',
+                    _ => ' ',
+                };
+                write!(f, "{}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
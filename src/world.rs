@@ -0,0 +1,118 @@
+//! The [World] abstraction shared by bounded and unbounded simulations.
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
+
+use crate::observer::Diff;
+use crate::rule::Rule;
+
+/// Hasher of [World::state_hash].
+#[cfg(feature = "std")]
+pub(crate) type StateHasher = std::collections::hash_map::DefaultHasher;
+
+/// Hasher of [World::state_hash] without the standard library: 64-bit
+/// FNV-1a, which is fast on small inputs and needs no random state.
+#[cfg(not(feature = "std"))]
+pub(crate) struct StateHasher(u64);
+
+#[cfg(not(feature = "std"))]
+impl Default for StateHasher {
+    fn default() -> StateHasher {
+        StateHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl Hasher for StateHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// A [World] of cells which a [ConwaySim](crate::ConwaySim) advances one
+/// generation at a time.
+///
+/// Cells are addressed by signed (row, col) coordinates so that both bounded
+/// worlds, such as a [Grid](crate::Grid), and unbounded worlds, such as a
+/// [SparseWorld](crate::SparseWorld), can be represented.
+pub trait World: Clone {
+    /// Determine if the cell at (row, col) is alive. Cells outside of a
+    /// bounded world are always dead.
+    fn is_alive(&self, row: i64, col: i64) -> bool;
+
+    /// Get the number of live cells.
+    fn get_population(&self) -> u64;
+
+    /// Get the live cells as (row, col) tuples.
+    fn live_cells(&self) -> Vec<(i64, i64)>;
+
+    /// Get the number of cells the world covers. For unbounded worlds this is
+    /// the area of the bounding box of the live cells.
+    fn get_area(&self) -> u64;
+
+    /// Count the cells born and the cells which died since a previous
+    /// generation, returned as (births, deaths).
+    ///
+    /// # Arguments
+    /// * `previous` - The world at the previous generation.
+    fn count_changes(&self, previous: &Self) -> (u64, u64) {
+        let before: BTreeSet<(i64, i64)> = previous.live_cells().into_iter().collect();
+        let after: BTreeSet<(i64, i64)> = self.live_cells().into_iter().collect();
+
+        (
+            after.difference(&before).count() as u64,
+            before.difference(&after).count() as u64,
+        )
+    }
+
+    /// Get the cells born and the cells which died since a previous
+    /// generation.
+    ///
+    /// # Arguments
+    /// * `previous` - The world at the previous generation.
+    fn diff(&self, previous: &Self) -> Diff {
+        let before: BTreeSet<(i64, i64)> = previous.live_cells().into_iter().collect();
+        let after: BTreeSet<(i64, i64)> = self.live_cells().into_iter().collect();
+
+        let mut births: Vec<(i64, i64)> = after.difference(&before).cloned().collect();
+        let mut deaths: Vec<(i64, i64)> = before.difference(&after).cloned().collect();
+        births.sort_unstable();
+        deaths.sort_unstable();
+
+        Diff { births, deaths }
+    }
+
+    /// Get the number of (rows, cols) after which the world wraps around
+    /// along each axis, as on a torus, or [None] for axes which do not wrap.
+    fn get_wrap(&self) -> (Option<u32>, Option<u32>) {
+        (None, None)
+    }
+
+    /// Mark the whole world as changed, for worlds tracking the regions
+    /// which changed for incremental rendering, such as when the world was
+    /// restored from a snapshot.
+    fn mark_dirty(&mut self) {}
+
+    /// Get a hash of the live cells, used to detect repeating generations.
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::default();
+        self.live_cells().hash(&mut hasher);
+
+        hasher.finish()
+    }
+
+    /// Compute the next generation of the world into `next`, reusing its
+    /// storage where possible.
+    ///
+    /// # Arguments
+    /// * `rule` - The [Rule] applied to each cell.
+    /// * `next` - The world the next generation is written into.
+    fn step_into(&self, rule: &Rule, next: &mut Self);
+}
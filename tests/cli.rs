@@ -0,0 +1,17 @@
+//! Tests of the command-line interface of the gameoflife-rs binary.
+
+use std::process::Command;
+
+#[test]
+fn grid_too_large_is_an_error() {
+    let output = Command::new(env!("CARGO_BIN_EXE_gameoflife-rs"))
+        .args(["--rows", "100000", "--cols", "100000", "--generations", "1"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "error: a grid cannot be 100000x100000\n"
+    );
+}
@@ -0,0 +1,138 @@
+//! Regression tests of loading patterns and checkpoints from malformed or
+//! oversized input.
+
+use gameoflife_rs::{
+    CheckpointError, ConwaySim, GolError, Grid, HashLifeSim, Life106Error, MacrocellError, RleError,
+};
+
+#[test]
+fn rle_header_too_large_for_a_grid_is_rejected() {
+    match Grid::from_rle("x = 100000, y = 100000\no!") {
+        Err(RleError::TooLarge { num_rows, num_cols }) => {
+            assert_eq!((num_rows, num_cols), (100000, 100000))
+        }
+        other => panic!(
+            "unexpected result: {:?}",
+            other.map(|grid| grid.get_population())
+        ),
+    }
+}
+
+#[test]
+fn rle_header_without_cells_is_rejected() {
+    for (rle, size) in [
+        ("x = 0, y = 0\n!", (0, 0)),
+        ("x = 0, y = 3\n!", (3, 0)),
+        ("x = 3, y = 0\n!", (0, 3)),
+    ] {
+        match Grid::from_rle(rle) {
+            Err(err @ RleError::Empty { .. }) => {
+                let expected = Grid::try_new(size.0, size.1).unwrap_err();
+                assert_eq!(GolError::from(err).to_string(), expected.to_string());
+            }
+            other => panic!(
+                "unexpected result: {:?}",
+                other.map(|grid| grid.get_population())
+            ),
+        }
+    }
+}
+
+#[test]
+fn rle_rule_with_a_bounded_grid_suffix_is_read() {
+    for (rle, rule) in [
+        ("x = 3, y = 1, rule = B36/S23:T64,64\n3o!", "B36/S23"),
+        ("x = 3, y = 1, rule = B3/S23:P20,30\n3o!", "B3/S23"),
+    ] {
+        let sim = ConwaySim::from_rle(rle).unwrap();
+
+        assert_eq!(sim.get_rule().to_string(), rule);
+        assert_eq!(sim.get_population(), 3);
+    }
+}
+
+#[test]
+fn life106_too_large_for_a_grid_is_rejected() {
+    for (life106, size) in [
+        ("#Life 1.06\n0 0\n70000 70000\n", (70001, 70001)),
+        ("#Life 1.06\n0 0\n0 5000000000\n", (u32::MAX, 1)),
+    ] {
+        match Grid::from_life106(life106) {
+            Err(Life106Error::TooLarge { num_rows, num_cols }) => {
+                assert_eq!((num_rows, num_cols), size)
+            }
+            other => panic!(
+                "unexpected result: {:?}",
+                other.map(|grid| grid.get_population())
+            ),
+        }
+    }
+}
+
+#[test]
+fn checkpoint_too_large_for_a_grid_is_rejected() {
+    // overwrite the rows and columns of the header
+    let mut checkpoint = ConwaySim::new(2, 2).to_checkpoint();
+    checkpoint[13..17].copy_from_slice(&100000u32.to_le_bytes());
+    checkpoint[17..21].copy_from_slice(&100000u32.to_le_bytes());
+
+    match ConwaySim::from_checkpoint(&checkpoint) {
+        Err(CheckpointError::TooLarge { num_rows, num_cols }) => {
+            assert_eq!((num_rows, num_cols), (100000, 100000))
+        }
+        other => panic!(
+            "unexpected result: {:?}",
+            other.map(|sim| sim.get_population())
+        ),
+    }
+}
+
+#[test]
+fn macrocell_too_large_for_a_grid_is_rejected() {
+    let mut hashlife = HashLifeSim::new();
+    hashlife.set_cells(&[(0, 0), (1 << 39, 0)]).unwrap();
+
+    match ConwaySim::from_macrocell(&hashlife.to_macrocell()) {
+        Err(MacrocellError::TooLarge { num_rows, num_cols }) => {
+            assert_eq!((num_rows, num_cols), (u32::MAX, 1))
+        }
+        other => panic!(
+            "unexpected result: {:?}",
+            other.map(|sim| sim.get_population())
+        ),
+    }
+}
+
+#[test]
+fn macrocell_keeps_its_generation() {
+    let sim = ConwaySim::from_macrocell("[M2]\n#R B3/S23\n#G 42\n.*$..*$***$\n").unwrap();
+
+    assert_eq!(sim.get_generation(), 42);
+    assert_eq!(sim.get_population(), 5);
+    assert_eq!(sim.get_grid().get_num_rows(), 3);
+}
+
+#[test]
+fn hashlife_rejects_b0_rle_rules() {
+    let path = std::env::temp_dir().join(format!("gameoflife-rs-b0-{}.rle", std::process::id()));
+    std::fs::write(&path, "x = 1, y = 1, rule = B0/S8\no!\n").unwrap();
+
+    let result = HashLifeSim::load_rle_file(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    match result {
+        Err(RleError::UnsupportedRule { rule }) => assert_eq!(rule.to_string(), "B0/S8"),
+        other => panic!(
+            "unexpected result: {:?}",
+            other.map(|sim| sim.get_population())
+        ),
+    }
+}
+
+#[test]
+fn hashlife_displays_patterns_too_large_for_a_grid() {
+    let mut hashlife = HashLifeSim::new();
+    hashlife.set_cells(&[(0, 0), (1 << 39, 0)]).unwrap();
+
+    assert!(hashlife.to_string().contains("too large to display"));
+}
@@ -0,0 +1,533 @@
+//! Property-based tests of invariants every world backend must uphold.
+
+use gameoflife_rs::isotropic::IsotropicRule;
+use gameoflife_rs::ltl::{LtlRule, LtlSim};
+use gameoflife_rs::margolus::{BlockRule, MargolusSim};
+use gameoflife_rs::reversible::SecondOrderSim;
+use gameoflife_rs::ruletable::RuleTable;
+use gameoflife_rs::topology::{Edge, Topology};
+use gameoflife_rs::{
+    ConwaySim, Grid, HashLifeSim, Pattern, Rule, Sim, SparseWorld, Symmetry, Transform, World,
+};
+use proptest::prelude::*;
+
+/// Still lifes of Conway's rule, in RLE.
+const STILL_LIFES: &[&str] = &[
+    "x = 2, y = 2\n2o$2o!",
+    "x = 4, y = 3\nb2o$o2bo$b2o!",
+    "x = 4, y = 4\nb2o$o2bo$bobo$2bo!",
+    "x = 3, y = 3\n2o$obo$bo!",
+    "x = 3, y = 3\nbo$obo$bo!",
+];
+
+/// Conway's rule as a Golly rule table.
+const LIFE_TABLE: &str = "@RULE Life
+@TABLE
+n_states:2
+neighborhood:Moore
+symmetries:permute
+var a={0,1}
+var b={0,1}
+var c={0,1}
+var d={0,1}
+var e={0,1}
+var f={0,1}
+var g={0,1}
+var h={0,1}
+0,1,1,1,0,0,0,0,0,1
+1,1,1,0,0,0,0,0,0,1
+1,1,1,1,0,0,0,0,0,1
+1,a,b,c,d,e,f,g,h,0
+";
+
+/// Strategy generating arbitrary rules, excluding births on zero neighbors
+/// which unbounded worlds cannot represent.
+fn rule() -> impl Strategy<Value = Rule> {
+    (any::<[bool; 8]>(), any::<[bool; 9]>()).prop_map(|(birth, survival)| {
+        let birth: Vec<u8> = (1..=8).filter(|&n| birth[n as usize - 1]).collect();
+        let survival: Vec<u8> = (0..=8).filter(|&n| survival[n as usize]).collect();
+
+        Rule::new(&birth, &survival)
+    })
+}
+
+/// Strategy generating random soups of up to 24x24 cells.
+fn soup() -> impl Strategy<Value = Grid> {
+    (1u32..24, 1u32..24, 0.0..=1.0, any::<u64>())
+        .prop_map(|(num_rows, num_cols, density, seed)| {
+            Grid::random(num_rows, num_cols, density, seed)
+        })
+}
+
+/// Copy `pattern` into a new [Grid] at (row, col).
+fn place(pattern: &Grid, num_rows: u32, num_cols: u32, row: u32, col: u32) -> Grid {
+    let mut grid = Grid::new(num_rows, num_cols);
+
+    for (pattern_row, pattern_col) in pattern.live_cells() {
+        grid.set(
+            row + pattern_row as u32,
+            col + pattern_col as u32,
+            gameoflife_rs::Cell::Alive,
+        );
+    }
+
+    grid
+}
+
+proptest! {
+    #[test]
+    fn still_lifes_remain_still(
+        index in 0..STILL_LIFES.len(),
+        row in 0u32..12,
+        col in 0u32..12,
+    ) {
+        let pattern = Grid::from_rle(STILL_LIFES[index]).unwrap();
+        let grid = place(&pattern, 16, 16, row, col);
+
+        let mut sim = ConwaySim::new_with_grid(grid.clone());
+        for _ in 0..4 {
+            sim.step();
+            prop_assert_eq!(sim.get_grid(), &grid);
+        }
+    }
+
+    #[test]
+    fn blinker_has_period_two(row in 1u32..12, col in 1u32..12, vertical in any::<bool>()) {
+        // keep the blinker at least a cell away from the edges in both phases
+        let pattern = if vertical {
+            Grid::from_rle("x = 1, y = 3\no$o$o!").unwrap()
+        } else {
+            Grid::from_rle("x = 3, y = 1\n3o!").unwrap()
+        };
+        let grid = place(&pattern, 16, 16, row, col);
+
+        let mut sim = ConwaySim::new_with_grid(grid.clone());
+        sim.step();
+        prop_assert_ne!(sim.get_grid(), &grid);
+        sim.step();
+        prop_assert_eq!(sim.get_grid(), &grid);
+    }
+
+    #[test]
+    fn empty_grid_stays_empty(
+        num_rows in 0u32..32,
+        num_cols in 0u32..32,
+        rule in rule(),
+        generations in 1u32..8,
+    ) {
+        let mut sim = ConwaySim::new(num_rows, num_cols);
+        sim.set_rule(rule);
+
+        for _ in 0..generations {
+            sim.step();
+            prop_assert!(!sim.is_any_cell_alive());
+        }
+    }
+
+    #[test]
+    fn bounded_and_sparse_agree_on_interior(
+        grid in soup(),
+        rule in rule(),
+        generations in 1u32..6,
+    ) {
+        let mut bounded = ConwaySim::new_with_grid(grid.clone());
+        let mut sparse = ConwaySim::new_with_world(SparseWorld::from_grid(&grid));
+        bounded.set_rule(rule);
+        sparse.set_rule(rule);
+
+        for _ in 0..generations {
+            bounded.step();
+            sparse.step();
+        }
+
+        // the edges of the bounded grid disturb the cells at most one cell
+        // further in each generation
+        let margin = generations as i64;
+        for row in margin..grid.get_num_rows() as i64 - margin {
+            for col in margin..grid.get_num_cols() as i64 - margin {
+                prop_assert_eq!(
+                    bounded.get_world().is_alive(row, col),
+                    sparse.get_world().is_alive(row, col),
+                    "cell ({}, {}) differs", row, col
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn life_and_generic_sim_agree(
+        grid in soup(),
+        rule in rule(),
+        topology in prop::sample::select(vec![
+            Topology::Bounded,
+            Topology::Torus,
+            Topology::Mirror,
+            Topology::Klein,
+            Topology::CrossSurface,
+        ]),
+        generations in 1u32..6,
+    ) {
+        let mut grid = grid;
+        grid.set_topology(topology);
+
+        let mut life = ConwaySim::new_with_grid(grid.clone());
+        let mut generic = Sim::new(grid, rule);
+        life.set_rule(rule);
+
+        for _ in 0..generations {
+            life.step();
+            generic.step();
+            prop_assert_eq!(life.get_grid(), generic.get_grid());
+        }
+    }
+
+    #[test]
+    fn life_like_and_isotropic_rules_agree(grid in soup(), rule in rule(), generations in 1u32..6) {
+        let isotropic = IsotropicRule::from(rule);
+        prop_assert_eq!(isotropic.to_rule(), Some(rule));
+        prop_assert_eq!(isotropic.to_string().parse::<IsotropicRule>().unwrap(), isotropic);
+
+        let mut life = ConwaySim::new_with_grid(grid.clone());
+        let mut custom = Sim::new(grid, isotropic);
+        life.set_rule(rule);
+
+        for _ in 0..generations {
+            life.step();
+            custom.step();
+            prop_assert_eq!(life.get_grid(), custom.get_grid());
+        }
+    }
+
+    #[test]
+    fn life_and_rule_table_agree(grid in soup(), generations in 1u32..6) {
+        let table: RuleTable = LIFE_TABLE.parse().unwrap();
+        let mut states = Grid::filled(grid.get_num_rows(), grid.get_num_cols(), 0u8);
+        for (row, col) in grid.live_cells() {
+            states.set(row as u32, col as u32, 1);
+        }
+
+        let mut life = ConwaySim::new_with_grid(grid);
+        let mut custom = Sim::new(states, table);
+
+        for _ in 0..generations {
+            life.step();
+            custom.step();
+            prop_assert_eq!(life.get_grid(), &custom.get_grid().to_grid());
+        }
+    }
+
+    #[test]
+    fn life_and_radius_one_ltl_agree(
+        grid in soup(),
+        rule in rule(),
+        topology in prop::sample::select(vec![
+            Topology::Bounded,
+            Topology::Torus,
+            Topology::Klein,
+        ]),
+        generations in 1u32..6,
+    ) {
+        let counts = |alive: &dyn Fn(u8) -> bool| -> Vec<(u32, u32)> {
+            (0..=8).filter(|&n| alive(n)).map(|n| (n as u32, n as u32)).collect()
+        };
+        let ltl = LtlRule::new(1, &counts(&|n| rule.is_birth(n)), &counts(&|n| rule.is_survival(n)));
+
+        let mut grid = grid;
+        grid.set_topology(topology);
+
+        let mut life = ConwaySim::new_with_grid(grid.clone());
+        let mut larger = LtlSim::from_grid(&grid, ltl);
+        life.set_rule(rule);
+
+        for _ in 0..generations {
+            life.step();
+            larger.step();
+            prop_assert_eq!(life.get_grid(), &larger.get_grid().to_grid());
+        }
+    }
+
+    #[test]
+    fn reversible_block_rules_step_backward_exactly(
+        grid in soup(),
+        rule in prop::sample::select(vec![
+            BlockRule::critters(),
+            BlockRule::tron(),
+            BlockRule::billiard_ball(),
+        ]),
+        topology in prop::sample::select(vec![Topology::Bounded, Topology::Torus, Topology::Klein]),
+        generations in 1u32..20,
+    ) {
+        let mut grid = grid;
+        grid.set_topology(topology);
+
+        let mut sim = MargolusSim::new_with_grid(grid.clone(), rule);
+        sim.step_n(generations);
+
+        for _ in 0..generations {
+            prop_assert!(sim.step_backward());
+        }
+        prop_assert!(!sim.step_backward());
+        prop_assert_eq!(sim.get_grid(), &grid);
+    }
+
+    #[test]
+    fn second_order_rules_step_backward_exactly(
+        grid in soup(),
+        rule in rule(),
+        topology in prop::sample::select(vec![Topology::Bounded, Topology::Torus, Topology::Mirror]),
+        generations in 1u32..20,
+    ) {
+        let mut grid = grid;
+        grid.set_topology(topology);
+
+        let mut sim = SecondOrderSim::new_with_grid(grid.clone(), rule);
+        sim.step_n(generations);
+
+        for _ in 0..generations {
+            prop_assert!(sim.step_backward());
+        }
+        prop_assert_eq!(sim.get_grid(), &grid);
+        prop_assert_eq!(sim.get_previous().get_population(), 0);
+    }
+
+    #[test]
+    fn symmetric_soups_stay_symmetric(
+        size in 1u32..20,
+        extra_cols in 0u32..4,
+        density in 0.0..=1.0,
+        seed in any::<u64>(),
+        symmetry in prop::sample::select(Symmetry::ALL.to_vec()),
+        rule in rule(),
+    ) {
+        let num_cols = if symmetry.is_square_only() { size } else { size + extra_cols };
+        let mut sim = ConwaySim::new_with_grid(
+            Grid::random_symmetric(size, num_cols, density, seed, symmetry),
+        );
+        sim.set_rule(rule);
+
+        for _ in 0..4 {
+            for &transform in symmetry.get_transforms() {
+                prop_assert_eq!(&sim.get_grid().transformed(transform), sim.get_grid());
+            }
+            sim.step();
+        }
+    }
+
+    #[test]
+    fn transforms_commute_with_stepping(
+        grid in soup(),
+        rule in rule(),
+        transform in prop::sample::select(Transform::ALL.to_vec()),
+        topology in prop::sample::select(vec![
+            Topology::Bounded,
+            Topology::Torus,
+            Topology::Mirror,
+            Topology::Klein,
+            Topology::PerAxis { rows: Edge::Wrap, cols: Edge::Mirror },
+        ]),
+    ) {
+        let mut sim = ConwaySim::new_with_grid(grid);
+        sim.set_rule(rule);
+        sim.set_topology(topology);
+
+        let mut transformed = ConwaySim::new_with_grid(sim.get_grid().transformed(transform));
+        transformed.set_rule(rule);
+
+        sim.step();
+        transformed.step();
+        prop_assert_eq!(transformed.get_grid(), &sim.get_grid().transformed(transform));
+    }
+
+    #[test]
+    fn grid_diffs_count_the_differing_cells(
+        grid in soup(),
+        density in 0.0..=1.0,
+        seed in any::<u64>(),
+    ) {
+        let other = Grid::random(grid.get_num_rows(), grid.get_num_cols(), density, seed);
+        let forward = grid.diff(&other);
+        let backward = other.diff(&grid);
+
+        let num_differing = grid
+            .get_cells()
+            .iter()
+            .zip(other.get_cells().iter())
+            .filter(|(cell, other_cell)| cell != other_cell)
+            .count() as u64;
+
+        prop_assert_eq!(forward.get_hamming_distance(), num_differing);
+        prop_assert_eq!(forward.get_added(), backward.get_removed());
+        prop_assert_eq!(forward.get_jaccard_similarity(), backward.get_jaccard_similarity());
+        prop_assert_eq!(forward.is_empty(), grid == other);
+        prop_assert!(grid.diff(&grid).is_empty());
+        prop_assert_eq!(grid.diff(&grid).get_jaccard_similarity(), 1.0);
+    }
+
+    #[test]
+    fn canonical_hashes_ignore_position_and_orientation(
+        grid in soup(),
+        transform in prop::sample::select(Transform::ALL.to_vec()),
+        offset in (0u32..8, 0u32..8),
+    ) {
+        let moved = grid.transformed(transform);
+        let moved = place(&moved, moved.get_num_rows() + 8, moved.get_num_cols() + 8, offset.0, offset.1);
+
+        prop_assert_eq!(
+            Pattern::new(grid).canonical_hash(),
+            Pattern::new(moved).canonical_hash()
+        );
+    }
+
+    #[test]
+    fn cycles_start_at_the_first_repeated_generation(seed in any::<u64>(), rule in rule()) {
+        let mut grid = Grid::random(6, 6, 0.4, seed);
+        grid.set_topology(Topology::Torus);
+
+        let mut sim = ConwaySim::new_with_grid(grid.clone());
+        sim.set_rule(rule);
+
+        let mut seen = vec![grid];
+        while sim.get_cycle().is_none() && seen.len() < 2000 {
+            sim.step();
+            seen.push(sim.get_grid().clone());
+        }
+
+        if let Some(cycle) = sim.get_cycle() {
+            let (start, length) = (cycle.get_start() as usize, cycle.get_length() as usize);
+            let latest = seen.len() - 1;
+
+            // found as soon as the start repeats, and no sooner
+            prop_assert_eq!(latest, start + length);
+            prop_assert_eq!(&seen[start], &seen[latest]);
+            prop_assert!(seen[start + 1..latest].iter().all(|grid| grid != &seen[latest]));
+            if start > 0 {
+                prop_assert_ne!(&seen[start - 1], &seen[latest - 1]);
+            }
+        }
+    }
+
+    #[test]
+    fn checkpoints_resume_identically(
+        grid in soup(),
+        rule in rule(),
+        topology in prop::sample::select(vec![
+            Topology::Bounded,
+            Topology::Torus,
+            Topology::Klein,
+            Topology::PerAxis { rows: Edge::Mirror, cols: Edge::Twist },
+        ]),
+        generations in 0u32..6,
+    ) {
+        let mut sim = ConwaySim::new_with_grid(grid);
+        sim.set_rule(rule);
+        sim.set_topology(topology);
+        sim.step_n(generations);
+
+        let mut resumed = ConwaySim::from_checkpoint(&sim.to_checkpoint()).unwrap();
+        prop_assert_eq!(resumed.get_generation(), sim.get_generation());
+        prop_assert_eq!(resumed.get_rule(), sim.get_rule());
+        prop_assert_eq!(resumed.get_grid(), sim.get_grid());
+
+        sim.step();
+        resumed.step();
+        prop_assert_eq!(resumed.get_grid(), sim.get_grid());
+    }
+
+    #[test]
+    fn sparse_and_chunked_agree(
+        grid in soup(),
+        rules in (rule(), rule()),
+        origin in (-80i64..80, -80i64..80),
+        generations in 1u32..12,
+    ) {
+        // place the soup anywhere, straddling chunks on either side of zero
+        let (row, col) = origin;
+        let cells: Vec<(i64, i64)> = grid
+            .live_cells()
+            .into_iter()
+            .map(|(r, c)| (r + row, c + col))
+            .collect();
+
+        let mut sparse = ConwaySim::new_sparse();
+        let mut chunked = ConwaySim::new_chunked();
+        sparse.set_cells(&cells);
+        chunked.set_cells(&cells);
+
+        // switch rules halfway, which must wake sleeping chunks
+        for generation in 0..generations {
+            let rule = if generation < generations / 2 { rules.0 } else { rules.1 };
+            sparse.set_rule(rule);
+            chunked.set_rule(rule);
+
+            sparse.step();
+            chunked.step();
+            prop_assert_eq!(sparse.get_world().live_cells(), chunked.get_world().live_cells());
+        }
+    }
+
+    #[test]
+    fn sparse_and_hashlife_agree(grid in soup(), rule in rule(), generations in 1u64..16) {
+        let mut sparse = ConwaySim::new_with_world(SparseWorld::from_grid(&grid));
+        let mut hashlife = HashLifeSim::new_with_rule(rule);
+        hashlife.set_cells(&grid.live_cells()).unwrap();
+        sparse.set_rule(rule);
+
+        for _ in 0..generations {
+            sparse.step();
+        }
+        hashlife.step_n(generations).unwrap();
+
+        let mut cells = hashlife.live_cells();
+        cells.sort_unstable();
+
+        prop_assert_eq!(sparse.get_world().live_cells(), cells);
+    }
+}
+
+#[cfg(feature = "simd")]
+proptest! {
+    #[test]
+    fn grid_and_bit_grid_agree(grid in soup(), rule in any::<([bool; 9], [bool; 9])>(), generations in 1u32..8) {
+        let (birth, survival) = rule;
+        let birth: Vec<u8> = (0..=8).filter(|&n| birth[n as usize]).collect();
+        let survival: Vec<u8> = (0..=8).filter(|&n| survival[n as usize]).collect();
+        let rule = Rule::new(&birth, &survival);
+
+        let mut naive = ConwaySim::new_with_grid(grid.clone());
+        let mut packed = ConwaySim::new_bit_packed(&grid);
+        naive.set_rule(rule);
+        packed.set_rule(rule);
+
+        for _ in 0..generations {
+            naive.step();
+            packed.step();
+            prop_assert_eq!(naive.get_grid(), &packed.get_world().to_grid());
+        }
+    }
+}
+
+proptest! {
+    // soups large enough to be shared between four threads are slow to step
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn chunked_agrees_across_threads(seed in any::<u64>(), rule in rule(), generations in 1u32..6) {
+        let soup = Grid::random(768, 768, 0.4, seed);
+
+        let mut single = ConwaySim::new_chunked();
+        let mut shared = ConwaySim::new_chunked();
+        single.set_num_threads(1);
+        shared.set_num_threads(4);
+
+        for sim in [&mut single, &mut shared] {
+            sim.set_cells(&soup.live_cells());
+            sim.set_rule(rule);
+        }
+
+        for _ in 0..generations {
+            single.step();
+            shared.step();
+            prop_assert_eq!(single.get_world(), shared.get_world());
+        }
+    }
+}
@@ -0,0 +1,103 @@
+// Canvas frontend for the WebAssembly build, see www/README.md.
+import init, { Universe } from "./pkg/gameoflife_rs.js";
+
+const CELL_SIZE = 6;
+const GRID_COLOR = "#404040";
+const DEAD_COLOR = "#181818";
+const ALIVE_COLOR = "#ffffff";
+
+const NUM_ROWS = 96;
+const NUM_COLS = 128;
+
+const wasm = await init();
+
+const universe = Universe.random(NUM_ROWS, NUM_COLS, 0.3, BigInt(Date.now()));
+const width = universe.width();
+const height = universe.height();
+
+const canvas = document.getElementById("game-of-life-canvas");
+canvas.width = (CELL_SIZE + 1) * width + 1;
+canvas.height = (CELL_SIZE + 1) * height + 1;
+
+const ctx = canvas.getContext("2d");
+const status = document.getElementById("status");
+
+const drawGrid = () => {
+  ctx.beginPath();
+  ctx.strokeStyle = GRID_COLOR;
+
+  for (let col = 0; col <= width; col++) {
+    ctx.moveTo(col * (CELL_SIZE + 1) + 1, 0);
+    ctx.lineTo(col * (CELL_SIZE + 1) + 1, (CELL_SIZE + 1) * height + 1);
+  }
+
+  for (let row = 0; row <= height; row++) {
+    ctx.moveTo(0, row * (CELL_SIZE + 1) + 1);
+    ctx.lineTo((CELL_SIZE + 1) * width + 1, row * (CELL_SIZE + 1) + 1);
+  }
+
+  ctx.stroke();
+};
+
+const drawCells = () => {
+  // the cells move every generation, so fetch the pointer again each frame
+  const cells = new Uint8Array(wasm.memory.buffer, universe.cells_ptr(), width * height);
+
+  ctx.beginPath();
+
+  for (let row = 0; row < height; row++) {
+    for (let col = 0; col < width; col++) {
+      ctx.fillStyle = cells[row * width + col] === 1 ? ALIVE_COLOR : DEAD_COLOR;
+      ctx.fillRect(col * (CELL_SIZE + 1) + 1, row * (CELL_SIZE + 1) + 1, CELL_SIZE, CELL_SIZE);
+    }
+  }
+
+  ctx.stroke();
+
+  status.textContent = `Generation: ${universe.generation()} Population: ${universe.population()}`;
+};
+
+let animationId = null;
+
+const renderLoop = () => {
+  universe.tick();
+  drawCells();
+  animationId = requestAnimationFrame(renderLoop);
+};
+
+const playPauseButton = document.getElementById("play-pause");
+
+const play = () => {
+  playPauseButton.textContent = "Pause";
+  renderLoop();
+};
+
+const pause = () => {
+  playPauseButton.textContent = "Play";
+  cancelAnimationFrame(animationId);
+  animationId = null;
+};
+
+playPauseButton.addEventListener("click", () => (animationId === null ? play() : pause()));
+
+document.getElementById("step").addEventListener("click", () => {
+  pause();
+  universe.tick();
+  drawCells();
+});
+
+canvas.addEventListener("click", (event) => {
+  const bounds = canvas.getBoundingClientRect();
+  const scaleX = canvas.width / bounds.width;
+  const scaleY = canvas.height / bounds.height;
+
+  const col = Math.min(Math.floor(((event.clientX - bounds.left) * scaleX) / (CELL_SIZE + 1)), width - 1);
+  const row = Math.min(Math.floor(((event.clientY - bounds.top) * scaleY) / (CELL_SIZE + 1)), height - 1);
+
+  universe.toggle_cell(row, col);
+  drawCells();
+});
+
+drawGrid();
+drawCells();
+play();